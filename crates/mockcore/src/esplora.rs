@@ -0,0 +1,231 @@
+use {
+  super::*,
+  std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::atomic::{self, AtomicBool},
+  },
+};
+
+/// Minimal Esplora REST API, serving the same chain state as the RPC server
+pub(crate) struct Esplora {
+  port: u16,
+  shutdown: Arc<AtomicBool>,
+}
+
+impl Esplora {
+  pub(crate) fn spawn(state: Arc<Mutex<State>>) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+
+    let port = listener.local_addr().unwrap().port();
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+
+    {
+      let shutdown = shutdown.clone();
+      thread::spawn(move || {
+        for stream in listener.incoming() {
+          if shutdown.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          let Ok(stream) = stream else {
+            continue;
+          };
+
+          let state = state.clone();
+
+          thread::spawn(move || Self::handle(&state, stream));
+        }
+      });
+    }
+
+    Self { port, shutdown }
+  }
+
+  pub(crate) fn url(&self) -> String {
+    format!("http://127.0.0.1:{}", self.port)
+  }
+
+  fn handle(state: &Mutex<State>, mut stream: TcpStream) {
+    let mut reader = BufReader::new(&stream);
+
+    let mut request = String::new();
+
+    if reader.read_line(&mut request).is_err() {
+      return;
+    }
+
+    loop {
+      let mut header = String::new();
+      match reader.read_line(&mut header) {
+        Ok(0) => break,
+        Ok(_) if header == "\r\n" => break,
+        Ok(_) => {}
+        Err(_) => return,
+      }
+    }
+
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+    let (status, body) = {
+      let mut state = state.lock().unwrap();
+
+      if state.esplora_failures > 0 {
+        state.esplora_failures -= 1;
+        ("429 Too Many Requests", b"too many requests".to_vec())
+      } else {
+        match Self::route(&state, path) {
+          Some(body) => ("200 OK", body),
+          None => ("404 Not Found", b"not found".to_vec()),
+        }
+      }
+    };
+
+    write!(
+      stream,
+      "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+      body.len()
+    )
+    .ok();
+
+    stream.write_all(&body).ok();
+  }
+
+  fn route(state: &State, path: &str) -> Option<Vec<u8>> {
+    let components = path
+      .trim_start_matches('/')
+      .split('/')
+      .collect::<Vec<&str>>();
+
+    match components.as_slice() {
+      ["blocks", "tip", "height"] => Some((state.hashes.len() - 1).to_string().into_bytes()),
+      ["block-height", height] => state
+        .hashes
+        .get(height.parse::<usize>().ok()?)
+        .map(|hash| hash.to_string().into_bytes()),
+      ["block", hash] => {
+        let hash = hash.parse::<BlockHash>().ok()?;
+
+        let block = state.blocks.get(&hash)?;
+
+        let height = state.hashes.iter().position(|best| *best == hash)?;
+
+        Some(
+          serde_json::to_vec(&serde_json::json!({
+            "id": hash,
+            "height": height,
+            "version": block.header.version.to_consensus(),
+            "timestamp": block.header.time,
+            "tx_count": block.txdata.len(),
+            "size": block.total_size(),
+            "weight": block.weight().to_wu(),
+            "merkle_root": block.header.merkle_root,
+            "previousblockhash": (height > 0).then_some(block.header.prev_blockhash),
+            "mediantime": block.header.time,
+            "nonce": block.header.nonce,
+            "bits": block.header.bits.to_consensus(),
+            "difficulty": 0.0,
+          }))
+          .unwrap(),
+        )
+      }
+      ["block", hash, "status"] => {
+        let hash = hash.parse::<BlockHash>().ok()?;
+
+        state.blocks.get(&hash)?;
+
+        let height = state.hashes.iter().position(|best| *best == hash);
+
+        Some(
+          serde_json::to_vec(&serde_json::json!({
+            "in_best_chain": height.is_some(),
+            "height": height,
+            "next_best": height.and_then(|height| state.hashes.get(height + 1)),
+          }))
+          .unwrap(),
+        )
+      }
+      ["block", hash, "raw"] => state
+        .blocks
+        .get(&hash.parse::<BlockHash>().ok()?)
+        .map(serialize),
+      ["block", hash, "header"] => state
+        .blocks
+        .get(&hash.parse::<BlockHash>().ok()?)
+        .map(|block| hex::encode(serialize(&block.header)).into_bytes()),
+      ["mempool", "txids"] => Some(
+        serde_json::to_vec(
+          &state
+            .mempool()
+            .iter()
+            .map(Transaction::compute_txid)
+            .collect::<Vec<Txid>>(),
+        )
+        .unwrap(),
+      ),
+      ["tx", txid, "raw"] => state
+        .transactions
+        .get(&txid.parse::<Txid>().ok()?)
+        .map(serialize),
+      ["tx", txid] => {
+        let txid = txid.parse::<Txid>().ok()?;
+
+        let tx = state.transactions.get(&txid)?;
+
+        let block_height = state.txid_to_block_height.get(&txid);
+
+        Some(
+          serde_json::to_vec(&serde_json::json!({
+            "txid": txid,
+            "version": tx.version.0,
+            "locktime": tx.lock_time.to_consensus_u32(),
+            "vin": tx.input.iter().map(|input| serde_json::json!({
+              "txid": input.previous_output.txid,
+              "vout": input.previous_output.vout,
+              "scriptsig": input.script_sig,
+              "witness": input.witness,
+              "sequence": input.sequence.0,
+            })).collect::<Vec<serde_json::Value>>(),
+            "vout": tx.output.iter().map(|output| serde_json::json!({
+              "scriptpubkey": output.script_pubkey,
+              "value": output.value.to_sat(),
+            })).collect::<Vec<serde_json::Value>>(),
+            "status": {
+              "confirmed": block_height.is_some(),
+              "block_height": block_height,
+            },
+          }))
+          .unwrap(),
+        )
+      }
+      ["tx", txid, "outspend", vout] => {
+        let outpoint = OutPoint {
+          txid: txid.parse().ok()?,
+          vout: vout.parse().ok()?,
+        };
+
+        state
+          .transactions
+          .get(&outpoint.txid)?
+          .output
+          .get(usize::try_from(outpoint.vout).unwrap())?;
+
+        Some(
+          serde_json::to_vec(&serde_json::json!({
+            "spent": !state.utxos.contains_key(&outpoint),
+          }))
+          .unwrap(),
+        )
+      }
+      _ => None,
+    }
+  }
+}
+
+impl Drop for Esplora {
+  fn drop(&mut self) {
+    self.shutdown.store(true, atomic::Ordering::Relaxed);
+    TcpStream::connect(("127.0.0.1", self.port)).ok();
+  }
+}
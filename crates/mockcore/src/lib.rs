@@ -22,7 +22,7 @@ use {
     secp256k1::{self, rand},
     sighash::{self, SighashCache, TapSighashType},
     Amount, Block, Network, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid,
-    WPubkeyHash, Witness, Wtxid,
+    WPubkeyHash, Witness,
   },
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, EstimateMode, FeeRatePercentiles,
@@ -35,6 +35,7 @@ use {
     LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult, StringOrStringArray,
     Timestamp, WalletProcessPsbtResult, WalletTxInfo,
   },
+  esplora::Esplora,
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
   ord::{SimulateRawTransactionOptions, SimulateRawTransactionResult},
@@ -45,7 +46,7 @@ use {
     collections::{BTreeMap, BTreeSet, HashMap},
     fs, mem,
     path::PathBuf,
-    sync::{Arc, Mutex, MutexGuard},
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
     thread,
     time::Duration,
   },
@@ -56,6 +57,7 @@ use {
 const COIN_VALUE: u64 = 100_000_000;
 
 mod api;
+mod esplora;
 mod server;
 mod state;
 mod wallet;
@@ -161,6 +163,7 @@ impl Builder {
 
     Handle {
       close_handle: Some(close_handle),
+      esplora: OnceLock::new(),
       tempdir,
       port,
       state,
@@ -238,6 +241,7 @@ impl Default for TransactionTemplate<'_> {
 
 pub struct Handle {
   close_handle: Option<CloseHandle>,
+  esplora: OnceLock<Esplora>,
   port: u16,
   state: Arc<Mutex<State>>,
   tempdir: TempDir,
@@ -248,6 +252,13 @@ impl Handle {
     format!("http://127.0.0.1:{}", self.port)
   }
 
+  pub fn esplora_url(&self) -> String {
+    self
+      .esplora
+      .get_or_init(|| Esplora::spawn(self.state.clone()))
+      .url()
+  }
+
   pub fn address(&self, output: OutPoint) -> Address {
     let state = self.state();
 
@@ -258,7 +269,7 @@ impl Handle {
     .unwrap()
   }
 
-  pub fn state(&self) -> MutexGuard<State> {
+  pub fn state(&self) -> MutexGuard<'_, State> {
     self.state.lock().unwrap()
   }
 
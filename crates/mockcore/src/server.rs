@@ -11,7 +11,7 @@ impl Server {
     Self { network, state }
   }
 
-  fn state(&self) -> MutexGuard<State> {
+  fn state(&self) -> MutexGuard<'_, State> {
     self.state.lock().unwrap()
   }
 
@@ -44,10 +44,12 @@ impl Api for Server {
   }
 
   fn get_blockchain_info(&self) -> Result<GetBlockchainInfoResult, jsonrpc_core::Error> {
+    let height = u64::try_from(self.state().hashes.len() - 1).unwrap();
+
     Ok(GetBlockchainInfoResult {
       chain: self.network,
-      blocks: 0,
-      headers: 0,
+      blocks: height,
+      headers: height,
       best_block_hash: self.state().hashes[0],
       difficulty: 0.0,
       median_time: 0,
@@ -218,7 +220,9 @@ impl Api for Server {
     verbosity: u64,
   ) -> Result<String, jsonrpc_core::Error> {
     assert_eq!(verbosity, 0, "Verbosity level {verbosity} is unsupported");
-    match self.state().blocks.get(&block_hash) {
+    let mut state = self.state();
+    state.get_block_calls += 1;
+    match state.blocks.get(&block_hash) {
       Some(block) => Ok(hex::encode(serialize(block))),
       None => Err(Self::not_found()),
     }
@@ -665,6 +669,17 @@ impl Api for Server {
     )
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(
+      self
+        .state()
+        .mempool()
+        .iter()
+        .map(|tx| tx.compute_txid())
+        .collect(),
+    )
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -688,9 +703,9 @@ impl Api for Server {
         Some(transaction) => Ok(
           serde_json::to_value(GetRawTransactionResult {
             in_active_chain: Some(true),
-            hex: Vec::new(),
-            txid: Txid::all_zeros(),
-            hash: Wtxid::all_zeros(),
+            hex: serialize(transaction),
+            txid,
+            hash: transaction.compute_wtxid(),
             size: 0,
             vsize: 0,
             version: 2,
@@ -723,7 +738,11 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match state.transactions.get(&txid) {
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool().iter().find(|tx| tx.compute_txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -5,7 +5,9 @@ pub struct State {
   pub blocks: BTreeMap<BlockHash, Block>,
   pub change_addresses: BTreeSet<Address>,
   pub descriptors: Vec<(String, bitcoincore_rpc::json::Timestamp)>,
+  pub esplora_failures: usize,
   pub fail_lock_unspent: bool,
+  pub get_block_calls: usize,
   pub hashes: Vec<BlockHash>,
   pub loaded_wallets: BTreeSet<String>,
   pub locked: BTreeSet<OutPoint>,
@@ -35,7 +37,9 @@ impl State {
       blocks,
       change_addresses: BTreeSet::new(),
       descriptors: Vec::new(),
+      esplora_failures: 0,
       fail_lock_unspent,
+      get_block_calls: 0,
       hashes,
       loaded_wallets: BTreeSet::new(),
       locked: BTreeSet::new(),
@@ -1,16 +1,19 @@
 use {
   super::*,
+  crate::index::event::Event,
   serde_hex::{SerHex, Strict},
 };
 
 pub use crate::{
   subcommand::decode::RawOutput as Decode,
   templates::{
-    BlocksHtml as Blocks, RuneHtml as Rune, RunesHtml as Runes, StatusHtml as Status,
-    TransactionHtml as Transaction,
+    BlocksHtml as Blocks, MempoolHtml as Mempool, RuneHoldersHtml as RuneHolders, RuneHtml as Rune,
+    RunesHtml as Runes, StatusHtml as Status, TransactionHtml as Transaction,
   },
 };
 
+pub mod v1;
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Block {
   pub best_height: u32,
@@ -79,7 +82,9 @@ pub struct BlockInfo {
 pub struct Children {
   pub ids: Vec<InscriptionId>,
   pub more: bool,
+  pub next_cursor: Option<String>,
   pub page: usize,
+  pub prev_cursor: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -89,6 +94,57 @@ pub struct ChildInscriptions {
   pub page: usize,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Delegators {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Events {
+  pub events: Vec<LoggedEvent>,
+  pub more: bool,
+  pub next: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoggedEvent {
+  pub event: Event,
+  pub height: u32,
+  pub sequence: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Health {
+  pub healthy: bool,
+  pub height: Option<u32>,
+  pub reason: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Readiness {
+  pub ready: bool,
+  pub height: Option<u32>,
+  pub lag: Option<u64>,
+  pub reason: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionHistory {
+  pub id: InscriptionId,
+  pub locations: Vec<InscriptionLocation>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionLocation {
+  pub address: Option<String>,
+  pub height: u32,
+  pub new_satpoint: SatPoint,
+  pub old_satpoint: Option<SatPoint>,
+  pub txid: Txid,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ParentInscriptions {
   pub parents: Vec<RelativeInscriptionRecursive>,
@@ -104,6 +160,7 @@ pub struct Inscription {
   pub children: Vec<InscriptionId>,
   pub content_length: Option<usize>,
   pub content_type: Option<String>,
+  pub delegator_count: Option<u64>,
   pub effective_content_type: Option<String>,
   pub fee: u64,
   pub height: u32,
@@ -111,6 +168,7 @@ pub struct Inscription {
   pub next: Option<InscriptionId>,
   pub number: i32,
   pub parents: Vec<InscriptionId>,
+  pub pending: Option<Txid>,
   pub previous: Option<InscriptionId>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<ordinals::Sat>,
@@ -120,6 +178,12 @@ pub struct Inscription {
   pub metaprotocol: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionMetadata {
+  pub id: InscriptionId,
+  pub metadata: Option<serde_json::Value>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InscriptionRecursive {
   pub charms: Vec<Charm>,
@@ -151,11 +215,16 @@ pub struct RelativeInscriptionRecursive {
   pub timestamp: i64,
 }
 
+/// Page of inscriptions. Only listings of all inscriptions, at
+/// `/inscriptions`, return cursors, which may be passed as `?cursor=<CURSOR>`
+/// to fetch the previous or next page.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Inscriptions {
   pub ids: Vec<InscriptionId>,
   pub more: bool,
+  pub next_cursor: Option<String>,
   pub page_index: u32,
+  pub prev_cursor: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
@@ -166,12 +235,23 @@ pub struct UtxoRecursive {
   pub value: u64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct MempoolTransaction {
+  pub etching: Option<SpacedRune>,
+  pub inscriptions: Vec<InscriptionId>,
+  pub mint: Option<RuneId>,
+  pub runes: Vec<SpacedRune>,
+  pub transferred_inscriptions: Vec<InscriptionId>,
+  pub txid: Txid,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct Output {
   pub address: Option<Address<NetworkUnchecked>>,
   pub indexed: bool,
   pub inscriptions: Option<Vec<InscriptionId>>,
   pub outpoint: OutPoint,
+  pub pending: Option<Txid>,
   pub runes: Option<BTreeMap<SpacedRune, Pile>>,
   pub sat_ranges: Option<Vec<(u64, u64)>>,
   pub script_pubkey: ScriptBuf,
@@ -185,6 +265,7 @@ impl Output {
     chain: Chain,
     inscriptions: Option<Vec<InscriptionId>>,
     outpoint: OutPoint,
+    pending: Option<Txid>,
     tx_out: TxOut,
     indexed: bool,
     runes: Option<BTreeMap<SpacedRune, Pile>>,
@@ -199,6 +280,7 @@ impl Output {
       indexed,
       inscriptions,
       outpoint,
+      pending,
       runes,
       sat_ranges,
       script_pubkey: tx_out.script_pubkey,
@@ -229,6 +311,35 @@ pub struct Sat {
   pub timestamp: i64,
 }
 
+impl Sat {
+  pub(crate) fn new(
+    sat: ordinals::Sat,
+    address: Option<bitcoin::Address>,
+    blocktime: Blocktime,
+    inscriptions: Vec<InscriptionId>,
+    satpoint: Option<SatPoint>,
+  ) -> Self {
+    Self {
+      address: address.map(|address| address.to_string()),
+      block: sat.height().0,
+      charms: Charm::charms(sat.charms()),
+      cycle: sat.cycle(),
+      decimal: sat.decimal().to_string(),
+      degree: sat.degree().to_string(),
+      epoch: sat.epoch().0,
+      inscriptions,
+      name: sat.name(),
+      number: sat.0,
+      offset: sat.third(),
+      percentile: sat.percentile(),
+      period: sat.period(),
+      rarity: sat.rarity(),
+      satpoint,
+      timestamp: blocktime.timestamp().timestamp(),
+    }
+  }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SatInscription {
   pub id: Option<InscriptionId>,
@@ -248,3 +359,17 @@ pub struct AddressInfo {
   pub sat_balance: u64,
   pub runes_balances: Option<Vec<(SpacedRune, Decimal, Option<char>)>>,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressRuneBalances {
+  pub height: u32,
+  pub outputs: Vec<OutPoint>,
+  pub runes_balances: Vec<(SpacedRune, Decimal, Option<char>)>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolder {
+  pub address: Option<String>,
+  pub amount: u128,
+  pub script_pubkey: ScriptBuf,
+}
@@ -0,0 +1,388 @@
+//! Response types of the versioned JSON API served under `/api/v1`.
+//!
+//! Unlike the other types in [`api`](super), which mirror the HTML pages and
+//! change along with them, these types are a stable contract. Fields must not
+//! be removed, renamed, or change type. Breaking changes require a new API
+//! version. Conversions from the unversioned types destructure them without
+//! `..`, so that new fields are only exposed deliberately.
+
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+  BadRequest,
+  Internal,
+  NotAcceptable,
+  NotFound,
+}
+
+/// Body of all error responses
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Error {
+  pub code: ErrorCode,
+  pub message: String,
+}
+
+/// Page of a listing. `next` is an opaque cursor which, if present, may be
+/// passed as `?cursor=<NEXT>` to fetch the following page.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  pub next: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Address {
+  pub address: String,
+  pub inscriptions: Option<Vec<InscriptionId>>,
+  pub outputs: Vec<OutPoint>,
+  pub runes: Option<Vec<RuneBalance>>,
+  pub sat_balance: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Block {
+  pub hash: BlockHash,
+  pub height: u32,
+  pub inscriptions: Vec<InscriptionId>,
+  pub previous_block: Option<BlockHash>,
+  pub runes: Vec<SpacedRune>,
+  pub timestamp: u64,
+  pub transaction_count: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Inscription {
+  pub address: Option<String>,
+  pub charms: Vec<Charm>,
+  pub child_count: u64,
+  pub content_length: Option<u64>,
+  pub content_type: Option<String>,
+  pub fee: u64,
+  pub height: u32,
+  pub id: InscriptionId,
+  pub metaprotocol: Option<String>,
+  pub number: i32,
+  pub parents: Vec<InscriptionId>,
+  pub rune: Option<SpacedRune>,
+  pub sat: Option<ordinals::Sat>,
+  pub satpoint: SatPoint,
+  pub timestamp: i64,
+  pub value: Option<u64>,
+}
+
+impl From<super::Inscription> for Inscription {
+  fn from(inscription: super::Inscription) -> Self {
+    let super::Inscription {
+      address,
+      charms,
+      child_count,
+      children: _,
+      content_length,
+      content_type,
+      delegator_count: _,
+      effective_content_type: _,
+      fee,
+      height,
+      id,
+      metaprotocol,
+      next: _,
+      number,
+      parents,
+      pending: _,
+      previous: _,
+      rune,
+      sat,
+      satpoint,
+      timestamp,
+      value,
+    } = inscription;
+
+    Self {
+      address,
+      charms,
+      child_count,
+      content_length: content_length.map(|length| length.try_into().unwrap()),
+      content_type,
+      fee,
+      height,
+      id,
+      metaprotocol,
+      number,
+      parents,
+      rune,
+      sat,
+      satpoint,
+      timestamp,
+      value,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub address: Option<String>,
+  pub inscriptions: Option<Vec<InscriptionId>>,
+  pub outpoint: OutPoint,
+  pub runes: Option<Vec<RuneBalance>>,
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+  pub script_pubkey: ScriptBuf,
+  pub spent: bool,
+  pub value: u64,
+}
+
+impl From<super::Output> for Output {
+  fn from(output: super::Output) -> Self {
+    let super::Output {
+      address,
+      indexed: _,
+      inscriptions,
+      outpoint,
+      pending: _,
+      runes,
+      sat_ranges,
+      script_pubkey,
+      spent,
+      transaction: _,
+      value,
+    } = output;
+
+    Self {
+      address: address.map(|address| address.assume_checked().to_string()),
+      inscriptions,
+      outpoint,
+      runes: runes.map(|runes| {
+        runes
+          .into_iter()
+          .map(|(rune, pile)| RuneBalance {
+            amount: pile.amount,
+            divisibility: pile.divisibility,
+            rune,
+            symbol: pile.symbol,
+          })
+          .collect()
+      }),
+      sat_ranges,
+      script_pubkey,
+      spent,
+      value,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rune {
+  pub block: u64,
+  pub burned: u128,
+  pub divisibility: u8,
+  pub etching: Txid,
+  pub holders: Option<u64>,
+  pub id: RuneId,
+  pub mintable: bool,
+  pub mints: u128,
+  pub name: SpacedRune,
+  pub number: u64,
+  pub parent: Option<InscriptionId>,
+  pub premine: u128,
+  pub symbol: Option<char>,
+  pub terms: Option<RuneTerms>,
+  pub timestamp: u64,
+  pub turbo: bool,
+}
+
+impl From<super::Rune> for Rune {
+  fn from(rune: super::Rune) -> Self {
+    let super::Rune {
+      entry:
+        RuneEntry {
+          block,
+          burned,
+          divisibility,
+          etching,
+          mints,
+          number,
+          premine,
+          spaced_rune,
+          symbol,
+          terms,
+          timestamp,
+          turbo,
+        },
+      holders,
+      id,
+      mintable,
+      parent,
+    } = rune;
+
+    Self {
+      block,
+      burned,
+      divisibility,
+      etching,
+      holders,
+      id,
+      mintable,
+      mints,
+      name: spaced_rune,
+      number,
+      parent,
+      premine,
+      symbol,
+      terms: terms.map(|terms| RuneTerms {
+        amount: terms.amount,
+        cap: terms.cap,
+        height_end: terms.height.1,
+        height_start: terms.height.0,
+        offset_end: terms.offset.1,
+        offset_start: terms.offset.0,
+      }),
+      timestamp,
+      turbo,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneBalance {
+  pub amount: u128,
+  pub divisibility: u8,
+  pub rune: SpacedRune,
+  pub symbol: Option<char>,
+}
+
+impl From<(SpacedRune, Decimal, Option<char>)> for RuneBalance {
+  fn from((rune, amount, symbol): (SpacedRune, Decimal, Option<char>)) -> Self {
+    Self {
+      amount: amount.value,
+      divisibility: amount.scale,
+      rune,
+      symbol,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneTerms {
+  pub amount: Option<u128>,
+  pub cap: Option<u128>,
+  pub height_end: Option<u64>,
+  pub height_start: Option<u64>,
+  pub offset_end: Option<u64>,
+  pub offset_start: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Sat {
+  pub address: Option<String>,
+  pub block: u32,
+  pub charms: Vec<Charm>,
+  pub cycle: u32,
+  pub decimal: String,
+  pub degree: String,
+  pub epoch: u32,
+  pub inscriptions: Vec<InscriptionId>,
+  pub name: String,
+  pub number: u64,
+  pub offset: u64,
+  pub percentile: String,
+  pub period: u32,
+  pub rarity: Rarity,
+  pub satpoint: Option<SatPoint>,
+  pub timestamp: i64,
+}
+
+impl From<super::Sat> for Sat {
+  fn from(sat: super::Sat) -> Self {
+    let super::Sat {
+      address,
+      block,
+      charms,
+      cycle,
+      decimal,
+      degree,
+      epoch,
+      inscriptions,
+      name,
+      number,
+      offset,
+      percentile,
+      period,
+      rarity,
+      satpoint,
+      timestamp,
+    } = sat;
+
+    Self {
+      address,
+      block,
+      charms,
+      cycle,
+      decimal,
+      degree,
+      epoch,
+      inscriptions,
+      name,
+      number,
+      offset,
+      percentile,
+      period,
+      rarity,
+      satpoint,
+      timestamp,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Status {
+  pub address_index: bool,
+  pub chain: Chain,
+  pub height: Option<u32>,
+  pub inscription_index: bool,
+  pub inscriptions: u64,
+  pub rune_index: bool,
+  pub runes: u64,
+  pub sat_index: bool,
+  pub transaction_index: bool,
+  pub version: String,
+}
+
+impl From<super::Status> for Status {
+  fn from(status: super::Status) -> Self {
+    let super::Status {
+      address_index,
+      blessed_inscriptions: _,
+      chain,
+      cursed_inscriptions: _,
+      height,
+      initial_sync_time: _,
+      inscription_index,
+      inscriptions,
+      json_api: _,
+      lost_sats: _,
+      minimum_rune_for_next_block: _,
+      replica_lag: _,
+      rune_index,
+      runes,
+      sat_index,
+      started: _,
+      transaction_index,
+      unrecoverably_reorged: _,
+      uptime: _,
+    } = status;
+
+    Self {
+      address_index,
+      chain,
+      height,
+      inscription_index,
+      inscriptions,
+      rune_index,
+      runes,
+      sat_index,
+      transaction_index,
+      version: env!("CARGO_PKG_VERSION").into(),
+    }
+  }
+}
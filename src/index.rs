@@ -1,12 +1,19 @@
 use {
   self::{
+    block_files::BlockFiles,
+    chain_source::ChainSource,
     entry::{
-      Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionHistoryEntry,
+      InscriptionHistoryEntryValue, InscriptionIdValue, OutPointValue, RuneEntryValue, RuneIdValue,
+      SatPointValue, SatRange, TxidValue,
     },
     event::Event,
     lot::Lot,
+    mempool::Mempool,
+    metrics::Metrics,
     reorg::Reorg,
+    replica::{DatabaseHandle, Replica},
+    undo::UndoLog,
     updater::Updater,
     utxo_entry::{ParsedUtxoEntry, UtxoEntry, UtxoEntryBuf},
   },
@@ -25,54 +32,251 @@ use {
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
   redb::{
-    Database, DatabaseError, MultimapTable, MultimapTableDefinition, MultimapTableHandle,
-    ReadOnlyTable, ReadableMultimapTable, ReadableTable, ReadableTableMetadata, RepairSession,
-    StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
+    AccessGuard, Database, DatabaseError, MultimapTable, MultimapTableDefinition,
+    MultimapTableHandle, MultimapValue, ReadOnlyTable, ReadableMultimapTable, ReadableTable,
+    ReadableTableMetadata, RepairSession, StorageError, Table, TableDefinition, TableHandle,
+    TableStats, WriteTransaction,
   },
   std::{
     collections::HashMap,
     io::{BufWriter, Write},
+    iter,
+    ops::Bound,
     sync::Once,
   },
 };
 
 pub use self::entry::RuneEntry;
 
+mod block_files;
+mod chain_source;
+pub mod check;
 pub(crate) mod entry;
+mod esplora;
 pub mod event;
 mod fetcher;
 mod lot;
+mod mempool;
+mod metrics;
+mod migration;
 mod reorg;
+mod replica;
 mod rtx;
+pub mod snapshot;
+mod undo;
 mod updater;
 mod utxo_entry;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 30;
+const SCHEMA_VERSION: u64 = 31;
 
+/// Maximum number of inscriptions examined in each direction when listing a
+/// page of filtered inscriptions
+const MAX_SCANNED_INSCRIPTIONS: usize = 10_000;
+
+define_multimap_table! { CONTENT_HASH_TO_SEQUENCE_NUMBER, &[u8; 32], u32 }
+define_multimap_table! { DELEGATE_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
+define_multimap_table! { RUNE_BALANCE_TO_HOLDER, (RuneIdValue, u128), &[u8] }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
+define_multimap_table! { SCRIPT_PUBKEY_TO_RUNIC_OUTPOINT, &[u8], OutPointValue }
+define_table! { CONTENT_TYPE_TO_SEQUENCE_NUMBER, (&str, u32), () }
+define_table! { EVENT_SEQUENCE_TO_EVENT, u64, (u32, &[u8]) }
+define_table! { FEE_TO_SEQUENCE_NUMBER, (u64, u32), () }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
+define_table! { HEIGHT_TO_FIRST_EVENT_SEQUENCE, u32, u64 }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
+define_table! { HEIGHT_TO_UNDO, u32, &[u8] }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { MEDIA_TO_SEQUENCE_NUMBER, (&str, u32), () }
+define_table! { METADATA_TO_SEQUENCE_NUMBER, (&str, &str, u32), () }
+define_table! { METAPROTOCOL_TO_SEQUENCE_NUMBER, (&str, u32), () }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_UTXO_ENTRY, &OutPointValue, &UtxoEntry }
+define_table! { RUNE_HOLDER_TO_BALANCE, (RuneIdValue, &[u8]), u128 }
+define_table! { RUNE_ID_TO_HOLDER_COUNT, RuneIdValue, u64 }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
+define_table! { RUNIC_OUTPOINT_TO_HEIGHT, &OutPointValue, u32 }
+define_table! { RUNIC_OUTPOINT_TO_SCRIPT_PUBKEY, &OutPointValue, &[u8] }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
+define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_HISTORY, (u32, u32), InscriptionHistoryEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
+define_table! { SPENT_RUNIC_OUTPOINT_TO_BALANCES, &OutPointValue, (u32, &[u8]) }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 
+#[derive(Clone, Debug)]
+pub enum AddressOrOutPoint {
+  Address(Address),
+  OutPoint(OutPoint),
+}
+
+/// Filter for listings of inscriptions. Filtering by content type or
+/// metaprotocol requires an index created with `--index-inscription-attributes`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, clap::Args)]
+pub struct InscriptionFilter {
+  #[arg(long, help = "Only list inscriptions with <CHARM>.")]
+  pub charm: Option<Charm>,
+  #[arg(
+    long,
+    help = "Only list inscriptions with <CONTENT_TYPE>, either a MIME type, like `image/png`, or a media category, like `image`."
+  )]
+  pub content_type: Option<String>,
+  #[arg(
+    long,
+    help = "Only list inscriptions created at or after <FROM_HEIGHT>."
+  )]
+  pub from_height: Option<u32>,
+  #[arg(long, help = "Only list inscriptions with <METAPROTOCOL>.")]
+  pub metaprotocol: Option<String>,
+}
+
+impl InscriptionFilter {
+  pub fn is_empty(&self) -> bool {
+    *self == Self::default()
+  }
+
+  pub fn requires_attribute_index(&self) -> bool {
+    self.content_type.is_some() || self.metaprotocol.is_some()
+  }
+}
+
+/// Order of listings. Listings of children default to oldest first, and all
+/// other listings to newest first.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sort {
+  /// Highest fee first. Only inscriptions can be sorted by fee, and only if
+  /// the index was created with `--index-inscription-attributes`.
+  Fee,
+  Newest,
+  Oldest,
+}
+
+impl Sort {
+  pub const ALL: [Self; 3] = [Self::Fee, Self::Newest, Self::Oldest];
+}
+
+impl Display for Sort {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Fee => "fee",
+        Self::Newest => "newest",
+        Self::Oldest => "oldest",
+      }
+    )
+  }
+}
+
+/// Page of a listing, along with the keys of the first items of the previous
+/// and next pages, if there are any. Keys remain valid while new items are
+/// added, so they can be used as stable cursors.
+#[derive(Debug, PartialEq)]
+pub struct Listing<T, K> {
+  pub items: Vec<T>,
+  pub prev: Option<K>,
+  pub next: Option<K>,
+}
+
+impl<T, K> Listing<T, K> {
+  /// `forward` yields keys and items in listing order, starting with the
+  /// first item of the page, and `backward` yields the keys of the items
+  /// before the page, in reverse listing order.
+  fn new(
+    forward: impl Iterator<Item = Result<(K, T)>>,
+    backward: impl Iterator<Item = Result<K>>,
+    page_size: usize,
+  ) -> Result<Self> {
+    Self::scan(
+      forward.map(|result| result.map(|(key, item)| (key, Some(item)))),
+      backward.map(|result| result.map(|key| (key, true))),
+      page_size,
+      usize::MAX,
+    )
+  }
+
+  /// Like `new`, but `forward` yields every candidate along with its item, if
+  /// it matches, `backward` yields every candidate along with whether it
+  /// matches, and at most `limit` candidates are examined in each direction.
+  /// If the limit is reached first, the page is cut short, and its cursors
+  /// point at where examination stopped, so sparse listings are walked a
+  /// bounded number of candidates at a time.
+  fn scan(
+    forward: impl Iterator<Item = Result<(K, Option<T>)>>,
+    backward: impl Iterator<Item = Result<(K, bool)>>,
+    page_size: usize,
+    limit: usize,
+  ) -> Result<Self> {
+    let mut items = Vec::new();
+    let mut next = None;
+
+    for (scanned, result) in forward.enumerate() {
+      let (key, item) = result?;
+
+      if scanned == limit {
+        next = Some(key);
+        break;
+      }
+
+      let Some(item) = item else {
+        continue;
+      };
+
+      if items.len() == page_size {
+        next = Some(key);
+        break;
+      }
+
+      items.push(item);
+    }
+
+    let mut prev = None;
+    let mut matches = 0;
+
+    for (scanned, result) in backward.enumerate() {
+      if matches == page_size || scanned == limit {
+        break;
+      }
+
+      let (key, matched) = result?;
+
+      if matched {
+        matches += 1;
+      }
+
+      if matched || scanned + 1 == limit {
+        prev = Some(key);
+      }
+    }
+
+    Ok(Self { items, prev, next })
+  }
+}
+
+/// Content types are indexed without parameters, so that, for example,
+/// `text/plain;charset=utf-8` is found by `text/plain`.
+fn normalize_content_type(content_type: &str) -> String {
+  content_type
+    .split(';')
+    .next()
+    .unwrap_or_default()
+    .trim()
+    .to_ascii_lowercase()
+}
+
 #[derive(Copy, Clone)]
 pub(crate) enum Statistic {
   Schema = 0,
@@ -92,6 +296,14 @@ pub(crate) enum Statistic {
   SatRanges = 14,
   UnboundInscriptions = 16,
   LastSavepointHeight = 17,
+  IndexEvents = 18,
+  IndexInscriptionHistory = 19,
+  IndexRuneHistory = 20,
+  IndexRuneHolders = 21,
+  IndexContentHashes = 22,
+  IndexInscriptionAttributes = 23,
+  IndexMetadata = 24,
+  IndexDelegates = 25,
 }
 
 impl Statistic {
@@ -192,19 +404,31 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub struct Index {
-  pub(crate) client: Client,
-  database: Database,
+  block_files: Arc<Mutex<Option<BlockFiles>>>,
+  pub(crate) client: Box<dyn ChainSource>,
+  database: DatabaseHandle,
   durability: redb::Durability,
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
   index_addresses: bool,
+  index_content_hashes: bool,
+  index_delegates: bool,
+  index_events: bool,
+  index_inscription_attributes: bool,
+  index_inscription_history: bool,
   index_inscriptions: bool,
+  index_metadata: bool,
+  index_rune_history: bool,
+  index_rune_holders: bool,
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
+  mempool: Mutex<Mempool>,
+  metrics: Arc<Metrics>,
   path: PathBuf,
+  replica: Mutex<Replica>,
   settings: Settings,
   started: DateTime<Utc>,
   first_index_height: u32,
@@ -220,7 +444,17 @@ impl Index {
     settings: &Settings,
     event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   ) -> Result<Self> {
-    let client = settings.bitcoin_rpc_client(None)?;
+    for (flag, set) in [
+      ("--index-rune-history", settings.index_rune_history_raw()),
+      ("--index-rune-holders", settings.index_rune_holders_raw()),
+    ] {
+      ensure!(
+        !set || settings.index_runes_raw(),
+        "{flag} requires --index-runes"
+      );
+    }
+
+    let client = chain_source::open(settings)?;
 
     let path = settings.index().to_owned();
 
@@ -262,6 +496,26 @@ impl Index {
       }
     };
 
+    // a read-only replica never writes to the primary's index, but keeps its
+    // own copy, which is created here from the snapshot if it does not exist,
+    // and which is replaced by `refresh_replica` when the snapshot changes
+    let loaded = if settings.read_only() && !path.try_exists()? {
+      let Some(snapshot) = settings.replica_snapshot() else {
+        bail!(
+          "index `{}` does not exist, and cannot be created in read-only mode",
+          path.display()
+        );
+      };
+
+      let modified = fs::metadata(snapshot)?.modified()?;
+
+      Self::restore(settings, snapshot)?;
+
+      Some(modified)
+    } else {
+      None
+    };
+
     let database = match Database::builder()
       .set_cache_size(index_cache_size)
       .set_repair_callback(repair_callback)
@@ -277,11 +531,16 @@ impl Index {
             .unwrap_or(0);
 
           match schema_version.cmp(&SCHEMA_VERSION) {
-            cmp::Ordering::Less =>
-              bail!(
-                "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
+            cmp::Ordering::Less if settings.read_only() => bail!(
+              "index at `{}` has schema {schema_version} but ord schema is {SCHEMA_VERSION}, and cannot be migrated in read-only mode",
+              path.display()
+            ),
+            cmp::Ordering::Less => Self::migrate(
+              &database,
+              schema_version,
+              &path,
+              !(cfg!(test) || log_enabled!(log::Level::Info) || integration_test),
+            )?,
             cmp::Ordering::Greater =>
               bail!(
                 "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
@@ -306,22 +565,40 @@ impl Index {
         tx.set_durability(durability);
         tx.set_quick_repair(true);
 
+        tx.open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(RUNE_BALANCE_TO_HOLDER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNIC_OUTPOINT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_table(CONTENT_TYPE_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(EVENT_SEQUENCE_TO_EVENT)?;
+        tx.open_table(FEE_TO_SEQUENCE_NUMBER)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+        tx.open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+        tx.open_table(HEIGHT_TO_UNDO)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(MEDIA_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(METADATA_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(METAPROTOCOL_TO_SEQUENCE_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
         tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+        tx.open_table(RUNE_HOLDER_TO_BALANCE)?;
+        tx.open_table(RUNE_ID_TO_HOLDER_COUNT)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
+        tx.open_table(RUNIC_OUTPOINT_TO_HEIGHT)?;
+        tx.open_table(RUNIC_OUTPOINT_TO_SCRIPT_PUBKEY)?;
         tx.open_table(SAT_TO_SATPOINT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+        tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_HISTORY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        tx.open_table(SPENT_RUNIC_OUTPOINT_TO_BALANCES)?;
         tx.open_table(TRANSACTION_ID_TO_RUNE)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
@@ -334,6 +611,42 @@ impl Index {
             u64::from(settings.index_addresses_raw()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexContentHashes,
+            u64::from(settings.index_content_hashes_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexDelegates,
+            u64::from(settings.index_delegates_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexEvents,
+            u64::from(settings.index_events_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexInscriptionAttributes,
+            u64::from(settings.index_inscription_attributes_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexInscriptionHistory,
+            u64::from(settings.index_inscription_history_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexMetadata,
+            u64::from(settings.index_metadata_raw()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexInscriptions,
@@ -346,6 +659,18 @@ impl Index {
             u64::from(settings.index_runes_raw()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexRuneHistory,
+            u64::from(settings.index_rune_history_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexRuneHolders,
+            u64::from(settings.index_rune_holders_raw()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexSats,
@@ -413,6 +738,14 @@ impl Index {
     };
 
     let index_addresses;
+    let index_content_hashes;
+    let index_delegates;
+    let index_events;
+    let index_inscription_attributes;
+    let index_inscription_history;
+    let index_metadata;
+    let index_rune_history;
+    let index_rune_holders;
     let index_runes;
     let index_sats;
     let index_transactions;
@@ -422,12 +755,70 @@ impl Index {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
       index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+      index_content_hashes = Self::is_statistic_set(&statistics, Statistic::IndexContentHashes)?;
+      index_delegates = Self::is_statistic_set(&statistics, Statistic::IndexDelegates)?;
+      index_events = Self::is_statistic_set(&statistics, Statistic::IndexEvents)?;
+      index_inscription_attributes =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionAttributes)?;
+      index_inscription_history =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionHistory)?;
       index_inscriptions = Self::is_statistic_set(&statistics, Statistic::IndexInscriptions)?;
+      index_metadata = Self::is_statistic_set(&statistics, Statistic::IndexMetadata)?;
+      index_rune_history = Self::is_statistic_set(&statistics, Statistic::IndexRuneHistory)?;
+      index_rune_holders = Self::is_statistic_set(&statistics, Statistic::IndexRuneHolders)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
     }
 
+    // these indexes cannot be built for blocks which have already been
+    // indexed, so an index, for example one migrated from an older schema,
+    // cannot be opened with them unless it was created with them
+    for (flag, requested, built) in [
+      (
+        "--index-content-hashes",
+        settings.index_content_hashes_raw(),
+        index_content_hashes,
+      ),
+      (
+        "--index-delegates",
+        settings.index_delegates_raw(),
+        index_delegates,
+      ),
+      ("--index-events", settings.index_events_raw(), index_events),
+      (
+        "--index-inscription-attributes",
+        settings.index_inscription_attributes_raw(),
+        index_inscription_attributes,
+      ),
+      (
+        "--index-inscription-history",
+        settings.index_inscription_history_raw(),
+        index_inscription_history,
+      ),
+      (
+        "--index-metadata",
+        settings.index_metadata_raw(),
+        index_metadata,
+      ),
+      (
+        "--index-rune-history",
+        settings.index_rune_history_raw(),
+        index_rune_history,
+      ),
+      (
+        "--index-rune-holders",
+        settings.index_rune_holders_raw(),
+        index_rune_holders,
+      ),
+    ] {
+      ensure!(
+        !requested || built,
+        "index at `{}` was not built with `{flag}`, consider deleting and rebuilding the index with `{flag}`, or running without it",
+        path.display(),
+      );
+    }
+
     let genesis_block_coinbase_transaction =
       settings.chain().genesis_block().coinbase().unwrap().clone();
 
@@ -443,20 +834,32 @@ impl Index {
 
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.compute_txid(),
+      block_files: Arc::new(Mutex::new(None)),
       client,
-      database,
+      database: DatabaseHandle::new(database),
       durability,
       event_sender,
       first_index_height,
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
       index_addresses,
+      index_content_hashes,
+      index_delegates,
+      index_events,
+      index_inscription_attributes,
+      index_inscription_history,
+      index_metadata,
+      index_rune_history,
+      index_rune_holders,
       index_runes,
       index_sats,
       index_transactions,
       index_inscriptions,
+      mempool: Mutex::new(Mempool::default()),
+      metrics: Arc::default(),
       settings: settings.clone(),
       path,
+      replica: Mutex::new(Replica::new(loaded)),
       started: Utc::now(),
       unrecoverably_reorged: AtomicBool::new(false),
     })
@@ -500,10 +903,42 @@ impl Index {
     self.index_addresses
   }
 
+  pub fn has_content_hash_index(&self) -> bool {
+    self.index_content_hashes
+  }
+
+  pub fn has_delegate_index(&self) -> bool {
+    self.index_delegates
+  }
+
+  pub fn has_event_index(&self) -> bool {
+    self.index_events
+  }
+
+  pub fn has_inscription_attribute_index(&self) -> bool {
+    self.index_inscription_attributes
+  }
+
+  pub fn has_inscription_history_index(&self) -> bool {
+    self.index_inscription_history
+  }
+
   pub fn has_inscription_index(&self) -> bool {
     self.index_inscriptions
   }
 
+  pub fn has_metadata_index(&self) -> bool {
+    self.index_metadata
+  }
+
+  pub fn has_rune_history_index(&self) -> bool {
+    self.index_rune_history
+  }
+
+  pub fn has_rune_holder_index(&self) -> bool {
+    self.index_rune_holders
+  }
+
   pub fn has_rune_index(&self) -> bool {
     self.index_runes
   }
@@ -554,6 +989,7 @@ impl Index {
         self.settings.chain().network(),
         Height(next_height),
       ),
+      replica_lag: self.replica_lag()?,
       rune_index: self.has_rune_index(),
       runes: statistic(Statistic::Runes)?,
       sat_index: self.has_sat_index(),
@@ -564,11 +1000,7 @@ impl Index {
     })
   }
 
-  pub fn info(&self) -> Result<Info> {
-    let stats = self.database.begin_write()?.stats()?;
-
-    let rtx = self.database.begin_read()?;
-
+  fn table_info(rtx: &redb::ReadTransaction) -> Result<BTreeMap<String, TableInfo>> {
     let mut tables: BTreeMap<String, TableInfo> = BTreeMap::new();
 
     for handle in rtx.list_tables()? {
@@ -591,6 +1023,56 @@ impl Index {
       assert!(tables.contains_key(table.name()));
     }
 
+    Ok(tables)
+  }
+
+  /// Whether the index is ready to serve requests, which it is not if it has
+  /// been unrecoverably reorged, or is more than `max_lag` blocks behind
+  /// Bitcoin Core's headers
+  pub(crate) fn readiness(&self, max_lag: u64) -> Result<api::Readiness> {
+    let height = self.block_height()?.map(|height| height.n());
+
+    let blocks_indexed = height.map(|height| u64::from(height) + 1).unwrap_or(0);
+
+    let (lag, reason) = match self.client.header_count() {
+      Ok(headers) => {
+        let lag = (headers + 1).saturating_sub(blocks_indexed);
+        (
+          Some(lag),
+          (lag > max_lag).then(|| {
+            format!("index is {lag} blocks behind Bitcoin Core, maximum lag is {max_lag}")
+          }),
+        )
+      }
+      Err(err) => (
+        None,
+        Some(format!(
+          "failed to fetch header count from Bitcoin Core: {err}"
+        )),
+      ),
+    };
+
+    let reason = if self.unrecoverably_reorged.load(atomic::Ordering::Relaxed) {
+      Some("index has been unrecoverably reorged".into())
+    } else {
+      reason
+    };
+
+    Ok(api::Readiness {
+      ready: reason.is_none(),
+      height,
+      lag,
+      reason,
+    })
+  }
+
+  pub fn info(&self) -> Result<Info> {
+    let stats = self.database.begin_write()?.stats()?;
+
+    let rtx = self.database.begin_read()?;
+
+    let mut tables = Self::table_info(&rtx)?;
+
     let total_bytes = tables
       .values()
       .map(|table_info| table_info.total_bytes)
@@ -651,6 +1133,10 @@ impl Index {
   }
 
   pub fn update(&self) -> Result {
+    if self.settings.read_only() {
+      return self.refresh_replica();
+    }
+
     loop {
       let wtx = self.begin_write()?;
 
@@ -669,13 +1155,18 @@ impl Index {
       };
 
       match updater.update_index(wtx) {
-        Ok(ok) => return Ok(ok),
+        Ok(()) => {
+          let tip = self.block_hash(None)?;
+          self.mempool.lock().unwrap().discard_if_stale(tip);
+          return Ok(());
+        }
         Err(err) => {
           log::info!("{}", err.to_string());
 
           match err.downcast_ref() {
             Some(&reorg::Error::Recoverable { height, depth }) => {
               Reorg::handle_reorg(self, height, depth)?;
+              self.metrics.record_reorg();
             }
             Some(&reorg::Error::Unrecoverable) => {
               self
@@ -690,6 +1181,18 @@ impl Index {
     }
   }
 
+  pub fn update_mempool(&self) -> Result {
+    Mempool::update(self)
+  }
+
+  pub(crate) fn mempool(&self) -> api::Mempool {
+    self.mempool.lock().unwrap().summary()
+  }
+
+  pub(crate) fn mempool_spender(&self, outpoint: OutPoint) -> Option<Txid> {
+    self.mempool.lock().unwrap().spender(outpoint)
+  }
+
   pub fn export(&self, filename: &String, include_addresses: bool) -> Result {
     let mut writer = BufWriter::new(File::create(filename)?);
     let rtx = self.database.begin_read()?;
@@ -784,14 +1287,58 @@ impl Index {
     Ok(tx)
   }
 
-  fn increment_statistic(wtx: &WriteTransaction, statistic: Statistic, n: u64) -> Result {
+  fn increment_statistic(
+    wtx: &WriteTransaction,
+    statistic: Statistic,
+    n: u64,
+    undo: &mut UndoLog,
+  ) -> Result {
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
     let value = statistic_to_count
       .get(&(statistic.key()))?
       .map(|x| x.value())
       .unwrap_or_default()
       + n;
-    statistic_to_count.insert(&statistic.key(), &value)?;
+    undo.insert(&mut statistic_to_count, &statistic.key(), &value)?;
+    Ok(())
+  }
+
+  /// Append events to the event log. Events are never removed from the log,
+  /// so they are not recorded in undo logs, and sequence numbers only ever
+  /// increase.
+  pub(crate) fn log_events(wtx: &WriteTransaction, events: &[Event]) -> Result {
+    let mut event_sequence_to_event = wtx.open_table(EVENT_SEQUENCE_TO_EVENT)?;
+    let mut height_to_first_event_sequence = wtx.open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE)?;
+
+    let next = event_sequence_to_event
+      .last()?
+      .map(|(sequence, _)| sequence.value() + 1)
+      .unwrap_or(0);
+
+    for (sequence, event) in (next..).zip(events) {
+      let height = event.block_height();
+
+      event_sequence_to_event.insert(sequence, (height, serde_json::to_vec(event)?.as_slice()))?;
+
+      Self::log_first_event_sequence(&mut height_to_first_event_sequence, height, sequence)?;
+    }
+
+    Ok(())
+  }
+
+  /// Record `sequence` as the first event logged for a block at `height`,
+  /// unless an earlier event was logged for a block at that height. Blocks are
+  /// first indexed in order of height, so the first events logged for higher
+  /// blocks have higher sequence numbers.
+  pub(crate) fn log_first_event_sequence(
+    height_to_first_event_sequence: &mut Table<u32, u64>,
+    height: u32,
+    sequence: u64,
+  ) -> Result {
+    if height_to_first_event_sequence.get(height)?.is_none() {
+      height_to_first_event_sequence.insert(height, sequence)?;
+    }
+
     Ok(())
   }
 
@@ -1001,74 +1548,401 @@ impl Index {
     Ok((entries, more))
   }
 
-  pub fn encode_rune_balance(id: RuneId, balance: u128, buffer: &mut Vec<u8>) {
-    varint::encode_to_vec(id.block.into(), buffer);
-    varint::encode_to_vec(id.tx.into(), buffer);
-    varint::encode_to_vec(balance, buffer);
-  }
-
-  pub fn decode_rune_balance(buffer: &[u8]) -> Result<((RuneId, u128), usize)> {
-    let mut len = 0;
-    let (block, block_len) = varint::decode(&buffer[len..])?;
-    len += block_len;
-    let (tx, tx_len) = varint::decode(&buffer[len..])?;
-    len += tx_len;
-    let id = RuneId {
-      block: block.try_into()?,
-      tx: tx.try_into()?,
-    };
-    let (balance, balance_len) = varint::decode(&buffer[len..])?;
-    len += balance_len;
-    Ok(((id, balance), len))
-  }
-
-  pub fn get_rune_balances_for_output(
+  /// Page `page_index` of runes, newest first, for routes which paginate by
+  /// page number. Only the keys of earlier pages are skipped, and the page
+  /// itself is read starting at the computed cursor.
+  pub fn runes_page(
     &self,
-    outpoint: OutPoint,
-  ) -> Result<Option<BTreeMap<SpacedRune, Pile>>> {
-    if !self.index_runes {
-      return Ok(None);
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<Listing<(RuneId, RuneEntry), RuneId>> {
+    let start = self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+      .iter()?
+      .rev()
+      .nth(page_size.saturating_mul(page_index))
+      .transpose()?
+      .map(|(id, _entry)| RuneId::load(id.value()));
+
+    match start {
+      Some(start) => self.runes_from(Sort::Newest, Some(start), page_size),
+      None => Ok(Listing {
+        items: Vec::new(),
+        prev: None,
+        next: None,
+      }),
     }
+  }
 
+  /// Runes in `sort` order, starting at rune `start`, or the first rune if
+  /// `None`
+  pub fn runes_from(
+    &self,
+    sort: Sort,
+    start: Option<RuneId>,
+    page_size: usize,
+  ) -> Result<Listing<(RuneId, RuneEntry), RuneId>> {
     let rtx = self.database.begin_read()?;
 
-    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 
-    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let start = start.map(|id| id.store());
 
-    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
-      return Ok(Some(BTreeMap::new()));
+    let newest = match sort {
+      Sort::Fee => bail!("runes cannot be sorted by fee"),
+      Sort::Newest => true,
+      Sort::Oldest => false,
     };
 
-    let balances_buffer = balances.value();
-
-    let mut balances = BTreeMap::new();
-    let mut i = 0;
-    while i < balances_buffer.len() {
-      let ((id, amount), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
-      i += length;
+    let at_or_after = start.map_or(Bound::Unbounded, Bound::Included);
 
-      let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
+    let forward = if newest {
+      Self::rune_range(
+        &rune_id_to_rune_entry,
+        (Bound::Unbounded, at_or_after),
+        true,
+      )?
+    } else {
+      Self::rune_range(
+        &rune_id_to_rune_entry,
+        (at_or_after, Bound::Unbounded),
+        false,
+      )?
+    };
 
-      balances.insert(
-        entry.spaced_rune,
-        Pile {
-          amount,
-          divisibility: entry.divisibility,
-          symbol: entry.symbol,
-        },
-      );
-    }
+    let backward = match start {
+      Some(start) if newest => Self::rune_range(
+        &rune_id_to_rune_entry,
+        (Bound::Excluded(start), Bound::Unbounded),
+        false,
+      )?,
+      Some(start) => Self::rune_range(
+        &rune_id_to_rune_entry,
+        (Bound::Unbounded, Bound::Excluded(start)),
+        true,
+      )?,
+      None => Box::new(iter::empty()),
+    };
 
-    Ok(Some(balances))
+    Listing::new(
+      forward.map(|result| result.map(|(id, entry)| (id, (id, entry)))),
+      backward.map(|result| result.map(|(id, _)| id)),
+      page_size,
+    )
   }
 
-  pub fn get_rune_balance_map(&self) -> Result<BTreeMap<SpacedRune, BTreeMap<OutPoint, Pile>>> {
-    let outpoint_balances = self.get_rune_balances()?;
-
-    let rtx = self.database.begin_read()?;
+  fn rune_range<'a>(
+    rune_id_to_rune_entry: &'a ReadOnlyTable<RuneIdValue, RuneEntryValue>,
+    range: (Bound<RuneIdValue>, Bound<RuneIdValue>),
+    reverse: bool,
+  ) -> Result<Box<dyn Iterator<Item = Result<(RuneId, RuneEntry)>> + 'a>> {
+    let entries = rune_id_to_rune_entry.range(range)?.map(|result| {
+      let (id, entry) = result?;
+      Ok((RuneId::load(id.value()), RuneEntry::load(entry.value())))
+    });
 
-    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    Ok(if reverse {
+      Box::new(entries.rev())
+    } else {
+      Box::new(entries)
+    })
+  }
+
+  pub fn get_rune_holder_count(&self, id: RuneId) -> Result<Option<u64>> {
+    if !self.index_rune_holders {
+      return Ok(None);
+    }
+
+    Ok(Some(
+      self
+        .database
+        .begin_read()?
+        .open_table(RUNE_ID_TO_HOLDER_COUNT)?
+        .get(id.store())?
+        .map(|count| count.value())
+        .unwrap_or_default(),
+    ))
+  }
+
+  /// Script pubkeys holding rune `id` and their balances, largest balance
+  /// first, starting at the holder `start`, or the largest holder if `None`.
+  /// Returns `None` if the index was not created with `--index-rune-holders`.
+  pub fn get_rune_holders(
+    &self,
+    id: RuneId,
+    start: Option<(u128, ScriptBuf)>,
+    page_size: usize,
+  ) -> Result<Option<Listing<(ScriptBuf, u128), (u128, ScriptBuf)>>> {
+    if !self.index_rune_holders {
+      return Ok(None);
+    }
+
+    let rune_balance_to_holder = self
+      .database
+      .begin_read()?
+      .open_multimap_table(RUNE_BALANCE_TO_HOLDER)?;
+
+    let id = id.store();
+
+    // holders with equal balances are listed in script pubkey order, so a page
+    // starts partway through the holders of the balance of `start`
+    let Some((balance, script_pubkey)) = start else {
+      let forward = Self::rune_holder_range(
+        rune_balance_to_holder
+          .range((id, 0)..=(id, u128::MAX))?
+          .rev(),
+        false,
+      );
+
+      return Ok(Some(Listing::new(
+        forward.map(|result| result.map(|holder| (holder.clone(), (holder.1, holder.0)))),
+        iter::empty(),
+        page_size,
+      )?));
+    };
+
+    let at_or_after =
+      Self::rune_holder_values(balance, rune_balance_to_holder.get((id, balance))?, false)
+        .skip_while(|result| {
+          result
+            .as_ref()
+            .is_ok_and(|(_balance, holder)| *holder < script_pubkey)
+        });
+
+    let before =
+      Self::rune_holder_values(balance, rune_balance_to_holder.get((id, balance))?, true)
+        .skip_while(|result| {
+          result
+            .as_ref()
+            .is_ok_and(|(_balance, holder)| *holder >= script_pubkey)
+        });
+
+    let forward = at_or_after.chain(Self::rune_holder_range(
+      rune_balance_to_holder.range((id, 0)..(id, balance))?.rev(),
+      false,
+    ));
+
+    let backward = before.chain(Self::rune_holder_range(
+      rune_balance_to_holder.range((
+        Bound::Excluded((id, balance)),
+        Bound::Included((id, u128::MAX)),
+      ))?,
+      true,
+    ));
+
+    Ok(Some(Listing::new(
+      forward.map(|result| result.map(|holder| (holder.clone(), (holder.1, holder.0)))),
+      backward,
+      page_size,
+    )?))
+  }
+
+  fn rune_holder_range<'a>(
+    range: impl Iterator<
+        Item = Result<
+          (
+            AccessGuard<'static, (RuneIdValue, u128)>,
+            MultimapValue<'static, &'static [u8]>,
+          ),
+          StorageError,
+        >,
+      > + 'a,
+    reverse: bool,
+  ) -> impl Iterator<Item = Result<(u128, ScriptBuf)>> + 'a {
+    range.flat_map(
+      move |result| -> Box<dyn Iterator<Item = Result<(u128, ScriptBuf)>>> {
+        match result {
+          Ok((key, values)) => {
+            let (_id, balance) = key.value();
+            Box::new(Self::rune_holder_values(balance, values, reverse))
+          }
+          Err(err) => Box::new(iter::once(Err(err.into()))),
+        }
+      },
+    )
+  }
+
+  fn rune_holder_values(
+    balance: u128,
+    values: MultimapValue<'static, &'static [u8]>,
+    reverse: bool,
+  ) -> Box<dyn Iterator<Item = Result<(u128, ScriptBuf)>>> {
+    let values = values.map(move |script_pubkey| {
+      Ok((
+        balance,
+        ScriptBuf::from_bytes(script_pubkey?.value().to_vec()),
+      ))
+    });
+
+    if reverse {
+      Box::new(values.rev())
+    } else {
+      Box::new(values)
+    }
+  }
+
+  pub fn encode_rune_balance(id: RuneId, balance: u128, buffer: &mut Vec<u8>) {
+    varint::encode_to_vec(id.block.into(), buffer);
+    varint::encode_to_vec(id.tx.into(), buffer);
+    varint::encode_to_vec(balance, buffer);
+  }
+
+  pub fn decode_rune_balance(buffer: &[u8]) -> Result<((RuneId, u128), usize)> {
+    let mut len = 0;
+    let (block, block_len) = varint::decode(&buffer[len..])?;
+    len += block_len;
+    let (tx, tx_len) = varint::decode(&buffer[len..])?;
+    len += tx_len;
+    let id = RuneId {
+      block: block.try_into()?,
+      tx: tx.try_into()?,
+    };
+    let (balance, balance_len) = varint::decode(&buffer[len..])?;
+    len += balance_len;
+    Ok(((id, balance), len))
+  }
+
+  pub fn get_rune_balances_for_output(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Option<BTreeMap<SpacedRune, Pile>>> {
+    if !self.index_runes {
+      return Ok(None);
+    }
+
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+      return Ok(Some(BTreeMap::new()));
+    };
+
+    let balances_buffer = balances.value();
+
+    let mut balances = BTreeMap::new();
+    let mut i = 0;
+    while i < balances_buffer.len() {
+      let ((id, amount), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
+      i += length;
+
+      let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
+
+      balances.insert(
+        entry.spaced_rune,
+        Pile {
+          amount,
+          divisibility: entry.divisibility,
+          symbol: entry.symbol,
+        },
+      );
+    }
+
+    Ok(Some(balances))
+  }
+
+  /// Rune balances of the outputs of an address, or of a single outpoint, as of
+  /// the end of the block at `height`. Returns `None` if the index was not
+  /// created with `--index-rune-history`.
+  pub fn get_rune_balances_at_height(
+    &self,
+    target: &AddressOrOutPoint,
+    height: u32,
+  ) -> Result<Option<BTreeMap<OutPoint, BTreeMap<SpacedRune, Pile>>>> {
+    if !self.index_rune_history {
+      return Ok(None);
+    }
+
+    let rtx = self.database.begin_read()?;
+
+    let outpoints = match target {
+      AddressOrOutPoint::Address(address) => rtx
+        .open_multimap_table(SCRIPT_PUBKEY_TO_RUNIC_OUTPOINT)?
+        .get(address.script_pubkey().as_bytes())?
+        .map(|result| {
+          result
+            .map(|outpoint| OutPoint::load(outpoint.value()))
+            .map_err(|err| err.into())
+        })
+        .collect::<Result<Vec<OutPoint>>>()?,
+      AddressOrOutPoint::OutPoint(outpoint) => vec![*outpoint],
+    };
+
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let runic_outpoint_to_height = rtx.open_table(RUNIC_OUTPOINT_TO_HEIGHT)?;
+    let spent_runic_outpoint_to_balances = rtx.open_table(SPENT_RUNIC_OUTPOINT_TO_BALANCES)?;
+
+    let mut result = BTreeMap::new();
+
+    for outpoint in outpoints {
+      let Some(created) = runic_outpoint_to_height.get(&outpoint.store())? else {
+        continue;
+      };
+
+      if created.value() > height {
+        continue;
+      }
+
+      let spent = spent_runic_outpoint_to_balances.get(&outpoint.store())?;
+
+      let unspent;
+
+      let balances_buffer = match &spent {
+        Some(spent) => {
+          let (spent_height, balances_buffer) = spent.value();
+
+          if spent_height <= height {
+            continue;
+          }
+
+          balances_buffer
+        }
+        None => {
+          unspent = outpoint_to_balances.get(&outpoint.store())?;
+
+          let Some(unspent) = &unspent else {
+            continue;
+          };
+
+          unspent.value()
+        }
+      };
+
+      let mut balances = BTreeMap::new();
+      let mut i = 0;
+      while i < balances_buffer.len() {
+        let ((id, amount), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
+        i += length;
+
+        let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
+
+        balances.insert(
+          entry.spaced_rune,
+          Pile {
+            amount,
+            divisibility: entry.divisibility,
+            symbol: entry.symbol,
+          },
+        );
+      }
+
+      result.insert(outpoint, balances);
+    }
+
+    Ok(Some(result))
+  }
+
+  pub fn get_rune_balance_map(&self) -> Result<BTreeMap<SpacedRune, BTreeMap<OutPoint, Pile>>> {
+    let outpoint_balances = self.get_rune_balances()?;
+
+    let rtx = self.database.begin_read()?;
+
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 
     let mut rune_balances_by_id: BTreeMap<RuneId, BTreeMap<OutPoint, u128>> = BTreeMap::new();
 
@@ -1146,30 +2020,28 @@ impl Index {
   }
 
   pub fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
-    self.client.get_block_header(&hash).into_option()
+    self.client.block_header(hash)
   }
 
   pub fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
-    self.client.get_block_header_info(&hash).into_option()
+    self.client.block_header_info(hash)
   }
 
   pub fn block_stats(&self, height: u64) -> Result<Option<GetBlockStatsResult>> {
-    self.client.get_block_stats(height).into_option()
+    self.client.block_stats(height)
   }
 
   pub fn get_block_by_height(&self, height: u32) -> Result<Option<Block>> {
-    Ok(
-      self
-        .client
-        .get_block_hash(height.into())
-        .into_option()?
-        .map(|hash| self.client.get_block(&hash))
-        .transpose()?,
-    )
+    self
+      .client
+      .block_hash(height.into())?
+      .map(|hash| self.client.block(hash))
+      .transpose()
+      .map(Option::flatten)
   }
 
   pub fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
-    self.client.get_block(&hash).into_option()
+    self.client.block(hash)
   }
 
   pub fn get_collections_paginated(
@@ -1212,6 +2084,17 @@ impl Index {
     &self,
     inscription_id: InscriptionId,
   ) -> Result<Vec<InscriptionId>> {
+    self
+      .get_children_by_inscription_id_paginated(inscription_id, usize::MAX, 0)
+      .map(|(children, _more)| children)
+  }
+
+  pub(crate) fn get_children_by_inscription_id_paginated(
+    &self,
+    inscription_id: InscriptionId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
     let Some(sequence_number) = rtx
@@ -1219,12 +2102,10 @@ impl Index {
       .get(&inscription_id.store())?
       .map(|sequence_number| sequence_number.value())
     else {
-      return Ok(Vec::new());
+      return Ok((Vec::new(), false));
     };
 
-    self
-      .get_children_by_sequence_number_paginated(sequence_number, usize::MAX, 0)
-      .map(|(children, _more)| children)
+    self.get_children_by_sequence_number_paginated(sequence_number, page_size, page_index)
   }
 
   #[cfg(test)]
@@ -1305,100 +2186,270 @@ impl Index {
     Ok((children, more))
   }
 
-  pub fn get_parents_by_sequence_number_paginated(
+  /// Children in `sort` order, starting at the child with sequence number
+  /// `start`, or the first child if `None`
+  pub fn get_children_by_sequence_number_from(
     &self,
-    parent_sequence_numbers: Vec<u32>,
+    sequence_number: u32,
+    sort: Sort,
+    start: Option<u32>,
     page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<InscriptionId>, bool)> {
+  ) -> Result<Listing<InscriptionId, u32>> {
+    let oldest = match sort {
+      Sort::Fee => bail!("children cannot be sorted by fee"),
+      Sort::Newest => false,
+      Sort::Oldest => true,
+    };
+
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut parents = parent_sequence_numbers
-      .iter()
-      .skip(page_index * page_size)
-      .take(page_size.saturating_add(1))
-      .map(|sequence_number| {
-        sequence_number_to_entry
-          .get(sequence_number)
-          .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          .map_err(|err| err.into())
-      })
-      .collect::<Result<Vec<InscriptionId>>>()?;
-
-    let more_parents = parents.len() > page_size;
+    let mut children = rtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+      .get(sequence_number)?
+      .map(|result| result.map(|child| child.value()))
+      .collect::<Result<Vec<u32>, StorageError>>()?;
 
-    if more_parents {
-      parents.pop();
+    if !oldest {
+      children.reverse();
     }
 
-    Ok((parents, more_parents))
-  }
-
-  pub fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
-    let rtx = self.database.begin_read()?;
-
-    let transaction_id_to_rune = rtx.open_table(TRANSACTION_ID_TO_RUNE)?;
-    let Some(rune) = transaction_id_to_rune.get(&txid.store())? else {
-      return Ok(None);
-    };
-
-    let rune_to_rune_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
-    let id = rune_to_rune_id.get(rune.value())?.unwrap();
-
-    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-    let entry = rune_id_to_rune_entry.get(&id.value())?.unwrap();
+    let position = start
+      .map(|start| {
+        children.partition_point(|&child| if oldest { child < start } else { child > start })
+      })
+      .unwrap_or_default();
 
-    Ok(Some(RuneEntry::load(entry.value()).spaced_rune))
+    let (before, page) = children.split_at(position);
+
+    Listing::new(
+      page.iter().map(|&child| {
+        Ok((
+          child,
+          InscriptionEntry::load(sequence_number_to_entry.get(child)?.unwrap().value()).id,
+        ))
+      }),
+      before.iter().rev().map(|&child| Ok(child)),
+      page_size,
+    )
   }
 
-  pub fn get_inscription_ids_by_sat(&self, sat: Sat) -> Result<Vec<InscriptionId>> {
+  pub fn get_delegators_paginated(
+    &self,
+    delegate: InscriptionId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let ids = rtx
-      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
-      .get(&sat.n())?
+    let mut delegators = rtx
+      .open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBER)?
+      .get(&delegate.store())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
       .map(|result| {
         result
           .and_then(|sequence_number| {
-            let sequence_number = sequence_number.value();
-            sequence_number_to_inscription_entry
-              .get(sequence_number)
+            sequence_number_to_entry
+              .get(sequence_number.value())
               .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
           })
           .map_err(|err| err.into())
       })
       .collect::<Result<Vec<InscriptionId>>>()?;
 
-    Ok(ids)
+    let more = delegators.len() > page_size;
+
+    if more {
+      delegators.pop();
+    }
+
+    Ok((delegators, more))
   }
 
-  pub fn get_inscription_ids_by_sat_paginated(
+  pub fn get_inscriptions_by_content_hash_paginated(
     &self,
-    sat: Sat,
-    page_size: u64,
-    page_index: u64,
+    hash: [u8; 32],
+    page_size: usize,
+    page_index: usize,
   ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut ids = rtx
-      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
-      .get(&sat.n())?
-      .skip(page_index.saturating_mul(page_size).try_into().unwrap())
-      .take(page_size.saturating_add(1).try_into().unwrap())
+    let mut inscriptions = rtx
+      .open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?
+      .get(&hash)?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
       .map(|result| {
         result
           .and_then(|sequence_number| {
-            let sequence_number = sequence_number.value();
-            sequence_number_to_inscription_entry
-              .get(sequence_number)
+            sequence_number_to_entry
+              .get(sequence_number.value())
+              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  pub fn get_inscriptions_by_metadata_paginated(
+    &self,
+    key: &str,
+    value: Option<&str>,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let metadata_to_sequence_number = rtx.open_table(METADATA_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    // entries are ordered by key, so entries with other keys end the range
+    let range = match value {
+      Some(value) => metadata_to_sequence_number.range((key, value, 0)..=(key, value, u32::MAX))?,
+      None => metadata_to_sequence_number.range((key, "", 0)..)?,
+    };
+
+    let mut inscriptions = Vec::new();
+
+    for result in range
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+    {
+      let (entry, _) = result?;
+      let (entry_key, _, sequence_number) = entry.value();
+
+      if entry_key != key {
+        break;
+      }
+
+      inscriptions.push(
+        InscriptionEntry::load(
+          sequence_number_to_entry
+            .get(sequence_number)?
+            .unwrap()
+            .value(),
+        )
+        .id,
+      );
+    }
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
+  pub fn get_parents_by_sequence_number_paginated(
+    &self,
+    parent_sequence_numbers: Vec<u32>,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut parents = parent_sequence_numbers
+      .iter()
+      .skip(page_index * page_size)
+      .take(page_size.saturating_add(1))
+      .map(|sequence_number| {
+        sequence_number_to_entry
+          .get(sequence_number)
+          .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more_parents = parents.len() > page_size;
+
+    if more_parents {
+      parents.pop();
+    }
+
+    Ok((parents, more_parents))
+  }
+
+  pub fn get_etching(&self, txid: Txid) -> Result<Option<SpacedRune>> {
+    let rtx = self.database.begin_read()?;
+
+    let transaction_id_to_rune = rtx.open_table(TRANSACTION_ID_TO_RUNE)?;
+    let Some(rune) = transaction_id_to_rune.get(&txid.store())? else {
+      return Ok(None);
+    };
+
+    let rune_to_rune_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
+    let id = rune_to_rune_id.get(rune.value())?.unwrap();
+
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let entry = rune_id_to_rune_entry.get(&id.value())?.unwrap();
+
+    Ok(Some(RuneEntry::load(entry.value()).spaced_rune))
+  }
+
+  pub fn get_inscription_ids_by_sat(&self, sat: Sat) -> Result<Vec<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let ids = rtx
+      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+      .get(&sat.n())?
+      .map(|result| {
+        result
+          .and_then(|sequence_number| {
+            let sequence_number = sequence_number.value();
+            sequence_number_to_inscription_entry
+              .get(sequence_number)
+              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    Ok(ids)
+  }
+
+  pub fn get_inscription_ids_by_sat_paginated(
+    &self,
+    sat: Sat,
+    page_size: u64,
+    page_index: u64,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut ids = rtx
+      .open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?
+      .get(&sat.n())?
+      .skip(page_index.saturating_mul(page_size).try_into().unwrap())
+      .take(page_size.saturating_add(1).try_into().unwrap())
+      .map(|result| {
+        result
+          .and_then(|sequence_number| {
+            let sequence_number = sequence_number.value();
+            sequence_number_to_inscription_entry
+              .get(sequence_number)
               .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
           })
           .map_err(|err| err.into())
@@ -1448,7 +2499,6 @@ impl Index {
     .transpose()
   }
 
-  #[cfg(test)]
   pub(crate) fn get_inscription_id_by_inscription_number(
     &self,
     inscription_number: i32,
@@ -1493,6 +2543,43 @@ impl Index {
     Ok(satpoint)
   }
 
+  pub fn get_inscription_history(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Vec<api::InscriptionLocation>>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let chain = self.settings.chain();
+
+    rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_HISTORY)?
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .map(|result| {
+        let entry = InscriptionHistoryEntry::load(result?.1.value());
+
+        Ok(api::InscriptionLocation {
+          address: entry
+            .script_pubkey
+            .and_then(|script_pubkey| chain.address_from_script(&script_pubkey).ok())
+            .map(|address| address.to_string()),
+          height: entry.height,
+          new_satpoint: entry.new_satpoint,
+          old_satpoint: entry.old_satpoint,
+          txid: entry.txid,
+        })
+      })
+      .collect::<Result<Vec<api::InscriptionLocation>>>()
+      .map(Some)
+  }
+
   pub fn get_inscription_by_id(
     &self,
     inscription_id: InscriptionId,
@@ -1612,7 +2699,7 @@ impl Index {
       }
     }
 
-    self.client.get_raw_transaction(&txid, None).into_option()
+    self.client.transaction(txid)
   }
 
   pub fn get_transaction_hex_recursive(&self, txid: Txid) -> Result<Option<String>> {
@@ -1622,10 +2709,12 @@ impl Index {
       )));
     }
 
-    self
-      .client
-      .get_raw_transaction_hex(&txid, None)
-      .into_option()
+    Ok(
+      self
+        .client
+        .transaction(txid)?
+        .map(|transaction| consensus::encode::serialize_hex(&transaction)),
+    )
   }
 
   pub fn find(&self, sat: Sat) -> Result<Option<SatPoint>> {
@@ -1747,10 +2836,7 @@ impl Index {
             .get(&outpoint.store())?
             .is_none()
         } else {
-          self
-            .client
-            .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
-            .is_none()
+          !self.client.output_unspent(outpoint)?
         },
     )
   }
@@ -1764,19 +2850,12 @@ impl Index {
       return Ok(true);
     }
 
-    let Some(info) = self
-      .client
-      .get_raw_transaction_info(&outpoint.txid, None)
-      .into_option()?
+    let Some((transaction, Some(_height))) = self.client.transaction_with_height(outpoint.txid)?
     else {
       return Ok(false);
     };
 
-    if info.blockhash.is_none() {
-      return Ok(false);
-    }
-
-    if outpoint.vout.into_usize() >= info.vout.len() {
+    if outpoint.vout.into_usize() >= transaction.output.len() {
       return Ok(false);
     }
 
@@ -1857,85 +2936,429 @@ impl Index {
     Ok((inscriptions, more))
   }
 
-  pub fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
-    let rtx = self.database.begin_read()?;
-
-    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-
-    let Some(newest_sequence_number) = height_to_last_sequence_number
-      .get(&block_height)?
-      .map(|ag| ag.value())
-    else {
-      return Ok(Vec::new());
+  /// Page `page_index` of inscriptions matching `filter`, newest first, for
+  /// routes which paginate by page number. Sequence numbers are dense, so
+  /// without a filter the page starts at a computed sequence number.
+  /// Otherwise, the page is found by following `next` from the first page.
+  pub fn get_inscriptions_page(
+    &self,
+    filter: &InscriptionFilter,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<Listing<InscriptionId, u32>> {
+    let empty = Listing {
+      items: Vec::new(),
+      prev: None,
+      next: None,
     };
 
-    let oldest_sequence_number = height_to_last_sequence_number
-      .get(block_height.saturating_sub(1))?
-      .map(|ag| ag.value())
-      .unwrap_or(0);
+    let start = if filter.is_empty() {
+      let last = self
+        .database
+        .begin_read()?
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .last()?
+        .map(|(number, _entry)| number.value());
 
-    (oldest_sequence_number..newest_sequence_number)
-      .map(|num| match sequence_number_to_inscription_entry.get(&num) {
-        Ok(Some(inscription_id)) => Ok(InscriptionEntry::load(inscription_id.value()).id),
-        Ok(None) => Err(anyhow!(
-          "could not find inscription for inscription number {num}"
-        )),
-        Err(err) => Err(anyhow!(err)),
-      })
-      .collect::<Result<Vec<InscriptionId>>>()
-  }
+      let start = last.and_then(|last| {
+        let offset = u32::try_from(page_size.checked_mul(page_index)?).ok()?;
+        last.checked_sub(offset)
+      });
 
-  pub fn get_runes_in_block(&self, block_height: u64) -> Result<Vec<SpacedRune>> {
-    let rtx = self.database.begin_read()?;
+      let Some(start) = start else {
+        return Ok(empty);
+      };
 
-    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+      Some(start)
+    } else {
+      let mut start = None;
 
-    let min_id = RuneId {
-      block: block_height,
-      tx: 0,
-    };
+      for _ in 0..page_index {
+        match self
+          .get_inscriptions_from(filter, Sort::Newest, start, page_size)?
+          .next
+        {
+          Some(next) => start = Some(next),
+          None => return Ok(empty),
+        }
+      }
 
-    let max_id = RuneId {
-      block: block_height,
-      tx: u32::MAX,
+      start
     };
 
-    let runes = rune_id_to_rune_entry
-      .range(min_id.store()..=max_id.store())?
-      .map(|result| result.map(|(_, entry)| RuneEntry::load(entry.value()).spaced_rune))
-      .collect::<Result<Vec<SpacedRune>, StorageError>>()?;
-
-    Ok(runes)
+    self.get_inscriptions_from(filter, Sort::Newest, start, page_size)
   }
 
-  pub fn get_highest_paying_inscriptions_in_block(
+  /// Inscriptions matching `filter` in `sort` order, starting at the
+  /// inscription with sequence number `start`, or the first matching
+  /// inscription if `None`. Filters which are not backed by an attribute
+  /// table, like charms, may match few of the inscriptions examined, so pages
+  /// may be cut short, in which case they are still followed by `next`.
+  pub fn get_inscriptions_from(
     &self,
-    block_height: u32,
-    n: usize,
-  ) -> Result<(Vec<InscriptionId>, usize)> {
-    let inscription_ids = self.get_inscriptions_in_block(block_height)?;
+    filter: &InscriptionFilter,
+    sort: Sort,
+    start: Option<u32>,
+    page_size: usize,
+  ) -> Result<Listing<InscriptionId, u32>> {
+    ensure!(
+      sort != Sort::Fee || self.index_inscription_attributes,
+      "sorting by fee requires index created with `--index-inscription-attributes`"
+    );
 
-    let mut inscription_to_fee: Vec<(InscriptionId, u64)> = Vec::new();
-    for id in &inscription_ids {
-      inscription_to_fee.push((
-        *id,
-        self
-          .get_inscription_entry(*id)?
-          .ok_or_else(|| anyhow!("could not get entry for inscription {id}"))?
-          .fee,
-      ));
-    }
+    let rtx = self.database.begin_read()?;
 
-    inscription_to_fee.sort_by_key(|(_, fee)| *fee);
+    let content_type_to_sequence_number = rtx.open_table(CONTENT_TYPE_TO_SEQUENCE_NUMBER)?;
+    let fee_to_sequence_number = rtx.open_table(FEE_TO_SEQUENCE_NUMBER)?;
+    let media_to_sequence_number = rtx.open_table(MEDIA_TO_SEQUENCE_NUMBER)?;
+    let metaprotocol_to_sequence_number = rtx.open_table(METAPROTOCOL_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    Ok((
-      inscription_to_fee
-        .iter()
-        .map(|(id, _)| *id)
-        .rev()
-        .take(n)
+    let content_type = filter.content_type.as_deref().map(normalize_content_type);
+
+    // MIME types contain a slash, media categories do not
+    let content_type = content_type.as_deref().map(|content_type| {
+      if content_type.contains('/') {
+        (&content_type_to_sequence_number, content_type)
+      } else {
+        (&media_to_sequence_number, content_type)
+      }
+    });
+
+    let mut attributes = filter
+      .metaprotocol
+      .as_deref()
+      .map(|metaprotocol| (&metaprotocol_to_sequence_number, metaprotocol))
+      .into_iter()
+      .chain(content_type)
+      .collect::<Vec<_>>();
+
+    // when sorting by sequence number, iterate over the first attribute table,
+    // and look up the remaining attributes
+    let attribute = if sort == Sort::Fee || attributes.is_empty() {
+      None
+    } else {
+      Some(attributes.remove(0))
+    };
+
+    // sequence numbers increase with height, so inscriptions before
+    // `from_height` are excluded by starting at the first one after it
+    let lowest = match filter.from_height {
+      Some(from_height) => rtx
+        .open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?
+        .range(..from_height)?
+        .next_back()
+        .transpose()?
+        .map(|(_height, next)| next.value())
+        .unwrap_or_default(),
+      None => 0,
+    };
+
+    let (forward, backward) = match sort {
+      Sort::Fee => {
+        let start = start.map(|start| {
+          sequence_number_to_entry.get(start).map(|entry| {
+            (
+              entry.map_or(u64::MAX, |entry| InscriptionEntry::load(entry.value()).fee),
+              start,
+            )
+          })
+        });
+
+        let start = start.transpose()?;
+
+        (
+          Self::fee_range(
+            &fee_to_sequence_number,
+            (
+              Bound::Unbounded,
+              start.map_or(Bound::Unbounded, Bound::Included),
+            ),
+            true,
+          )?,
+          match start {
+            Some(start) => Self::fee_range(
+              &fee_to_sequence_number,
+              (Bound::Excluded(start), Bound::Unbounded),
+              false,
+            )?,
+            None => Box::new(iter::empty()),
+          },
+        )
+      }
+      Sort::Newest => (
+        Self::sequence_number_range(
+          attribute,
+          &sequence_number_to_entry,
+          lowest,
+          start.unwrap_or(u32::MAX),
+          true,
+        )?,
+        match start.and_then(|start| start.checked_add(1)) {
+          Some(after) => Self::sequence_number_range(
+            attribute,
+            &sequence_number_to_entry,
+            after.max(lowest),
+            u32::MAX,
+            false,
+          )?,
+          None => Box::new(iter::empty()),
+        },
+      ),
+      Sort::Oldest => (
+        Self::sequence_number_range(
+          attribute,
+          &sequence_number_to_entry,
+          start.unwrap_or_default().max(lowest),
+          u32::MAX,
+          false,
+        )?,
+        match start.and_then(|start| start.checked_sub(1)) {
+          Some(before) => {
+            Self::sequence_number_range(attribute, &sequence_number_to_entry, lowest, before, true)?
+          }
+          None => Box::new(iter::empty()),
+        },
+      ),
+    };
+
+    let matching = |sequence_number: u32| -> Result<Option<InscriptionEntry>> {
+      for (table, value) in &attributes {
+        if table.get((*value, sequence_number))?.is_none() {
+          return Ok(None);
+        }
+      }
+
+      let entry = InscriptionEntry::load(
+        sequence_number_to_entry
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      if filter
+        .from_height
+        .is_some_and(|from_height| entry.height < from_height)
+      {
+        return Ok(None);
+      }
+
+      if filter
+        .charm
+        .is_some_and(|charm| !charm.is_set(entry.charms))
+      {
+        return Ok(None);
+      }
+
+      Ok(Some(entry))
+    };
+
+    Listing::scan(
+      forward.map(|result| {
+        let sequence_number = result?;
+        Ok((
+          sequence_number,
+          matching(sequence_number)?.map(|entry| entry.id),
+        ))
+      }),
+      backward.map(|result| {
+        let sequence_number = result?;
+        Ok((sequence_number, matching(sequence_number)?.is_some()))
+      }),
+      page_size,
+      MAX_SCANNED_INSCRIPTIONS,
+    )
+  }
+
+  /// Sequence numbers from `lowest` to `highest`, inclusive, of inscriptions
+  /// with `attribute`, if given, or of all inscriptions otherwise
+  fn sequence_number_range<'a>(
+    attribute: Option<(&'a ReadOnlyTable<(&'static str, u32), ()>, &'a str)>,
+    sequence_number_to_entry: &'a ReadOnlyTable<u32, InscriptionEntryValue>,
+    lowest: u32,
+    highest: u32,
+    reverse: bool,
+  ) -> Result<Box<dyn Iterator<Item = Result<u32>> + 'a>> {
+    if lowest > highest {
+      return Ok(Box::new(iter::empty()));
+    }
+
+    let sequence_numbers: Box<dyn DoubleEndedIterator<Item = Result<u32>> + 'a> = match attribute {
+      Some((table, value)) => Box::new(
+        table
+          .range((value, lowest)..=(value, highest))?
+          .map(|result| Ok(result?.0.value().1)),
+      ),
+      None => Box::new(
+        sequence_number_to_entry
+          .range(lowest..=highest)?
+          .map(|result| Ok(result?.0.value())),
+      ),
+    };
+
+    Ok(if reverse {
+      Box::new(sequence_numbers.rev())
+    } else {
+      sequence_numbers
+    })
+  }
+
+  fn fee_range<'a>(
+    fee_to_sequence_number: &'a ReadOnlyTable<(u64, u32), ()>,
+    range: (Bound<(u64, u32)>, Bound<(u64, u32)>),
+    reverse: bool,
+  ) -> Result<Box<dyn Iterator<Item = Result<u32>> + 'a>> {
+    let sequence_numbers = fee_to_sequence_number
+      .range(range)?
+      .map(|result| Ok(result?.0.value().1));
+
+    Ok(if reverse {
+      Box::new(sequence_numbers.rev())
+    } else {
+      Box::new(sequence_numbers)
+    })
+  }
+
+  pub fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let Some(newest_sequence_number) = height_to_last_sequence_number
+      .get(&block_height)?
+      .map(|ag| ag.value())
+    else {
+      return Ok(Vec::new());
+    };
+
+    let oldest_sequence_number = height_to_last_sequence_number
+      .get(block_height.saturating_sub(1))?
+      .map(|ag| ag.value())
+      .unwrap_or(0);
+
+    (oldest_sequence_number..newest_sequence_number)
+      .map(|num| match sequence_number_to_inscription_entry.get(&num) {
+        Ok(Some(inscription_id)) => Ok(InscriptionEntry::load(inscription_id.value()).id),
+        Ok(None) => Err(anyhow!(
+          "could not find inscription for inscription number {num}"
+        )),
+        Err(err) => Err(anyhow!(err)),
+      })
+      .collect::<Result<Vec<InscriptionId>>>()
+  }
+
+  pub fn get_runes_in_block(&self, block_height: u64) -> Result<Vec<SpacedRune>> {
+    let rtx = self.database.begin_read()?;
+
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let min_id = RuneId {
+      block: block_height,
+      tx: 0,
+    };
+
+    let max_id = RuneId {
+      block: block_height,
+      tx: u32::MAX,
+    };
+
+    let runes = rune_id_to_rune_entry
+      .range(min_id.store()..=max_id.store())?
+      .map(|result| result.map(|(_, entry)| RuneEntry::load(entry.value()).spaced_rune))
+      .collect::<Result<Vec<SpacedRune>, StorageError>>()?;
+
+    Ok(runes)
+  }
+
+  /// Returns up to `limit` logged events, starting at event `sequence`, or, if
+  /// `from_height` is given and later, at the first event logged for a block at
+  /// or above `from_height`
+  pub fn get_events(
+    &self,
+    sequence: u64,
+    from_height: Option<u32>,
+    limit: usize,
+  ) -> Result<api::Events> {
+    let rtx = self.database.begin_read()?;
+
+    let event_sequence_to_event = rtx.open_table(EVENT_SEQUENCE_TO_EVENT)?;
+
+    let sequence = match from_height {
+      Some(height) => {
+        let first = match rtx
+          .open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE)?
+          .range(height..)?
+          .next()
+          .transpose()?
+        {
+          Some((_height, first)) => first.value(),
+          None => event_sequence_to_event
+            .last()?
+            .map(|(sequence, _)| sequence.value() + 1)
+            .unwrap_or(0),
+        };
+
+        sequence.max(first)
+      }
+      None => sequence,
+    };
+
+    let mut events = event_sequence_to_event
+      .range(sequence..)?
+      .take(limit.saturating_add(1))
+      .map(|result| {
+        let (sequence, value) = result?;
+        let (height, event) = value.value();
+        Ok(api::LoggedEvent {
+          event: serde_json::from_slice(event)?,
+          height,
+          sequence: sequence.value(),
+        })
+      })
+      .collect::<Result<Vec<api::LoggedEvent>>>()?;
+
+    let more = events.len() > limit;
+
+    if more {
+      events.pop();
+    }
+
+    let next = events
+      .last()
+      .map(|logged| logged.sequence + 1)
+      .unwrap_or(sequence);
+
+    Ok(api::Events { events, more, next })
+  }
+
+  pub fn get_highest_paying_inscriptions_in_block(
+    &self,
+    block_height: u32,
+    n: usize,
+  ) -> Result<(Vec<InscriptionId>, usize)> {
+    let inscription_ids = self.get_inscriptions_in_block(block_height)?;
+
+    let mut inscription_to_fee: Vec<(InscriptionId, u64)> = Vec::new();
+    for id in &inscription_ids {
+      inscription_to_fee.push((
+        *id,
+        self
+          .get_inscription_entry(*id)?
+          .ok_or_else(|| anyhow!("could not get entry for inscription {id}"))?
+          .fee,
+      ));
+    }
+
+    inscription_to_fee.sort_by_key(|(_, fee)| *fee);
+
+    Ok((
+      inscription_to_fee
+        .iter()
+        .map(|(id, _)| *id)
+        .rev()
+        .take(n)
         .collect(),
       inscription_ids.len(),
     ))
@@ -2095,6 +3518,17 @@ impl Index {
       })
       .collect::<Result<Vec<InscriptionId>>>()?;
 
+    let delegator_count = if self.index_delegates {
+      Some(
+        rtx
+          .open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBER)?
+          .get(&entry.id.store())?
+          .len(),
+      )
+    } else {
+      None
+    };
+
     let rune = if let Some(rune_id) = rtx
       .open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?
       .get(sequence_number)?
@@ -2157,6 +3591,7 @@ impl Index {
         children,
         content_length: inscription.content_length(),
         content_type: inscription.content_type().map(|s| s.to_string()),
+        delegator_count,
         effective_content_type: effective_mime_type,
         fee: entry.fee,
         height: entry.height,
@@ -2164,6 +3599,7 @@ impl Index {
         next,
         number: entry.inscription_number,
         parents,
+        pending: self.mempool_spender(satpoint.outpoint),
         previous,
         rune,
         sat: entry.sat,
@@ -2332,13 +3768,25 @@ impl Index {
     &self,
     outputs: &Vec<OutPoint>,
   ) -> Result<Option<Vec<(SpacedRune, Decimal, Option<char>)>>> {
-    let mut runes = BTreeMap::new();
+    let mut balances = Vec::new();
 
     for output in outputs {
       let Some(rune_balances) = self.get_rune_balances_for_output(*output)? else {
         return Ok(None);
       };
 
+      balances.push(rune_balances);
+    }
+
+    Ok(Some(Self::aggregate_rune_balances(balances)))
+  }
+
+  pub(crate) fn aggregate_rune_balances(
+    balances: impl IntoIterator<Item = BTreeMap<SpacedRune, Pile>>,
+  ) -> Vec<(SpacedRune, Decimal, Option<char>)> {
+    let mut runes = BTreeMap::new();
+
+    for rune_balances in balances {
       for (spaced_rune, pile) in rune_balances {
         runes
           .entry(spaced_rune)
@@ -2356,12 +3804,10 @@ impl Index {
       }
     }
 
-    Ok(Some(
-      runes
-        .into_iter()
-        .map(|(spaced_rune, (decimal, symbol))| (spaced_rune, decimal, symbol))
-        .collect(),
-    ))
+    runes
+      .into_iter()
+      .map(|(spaced_rune, (decimal, symbol))| (spaced_rune, decimal, symbol))
+      .collect()
   }
 
   pub(crate) fn get_sat_balances_for_outputs(&self, outputs: &Vec<OutPoint>) -> Result<u64> {
@@ -2446,6 +3892,7 @@ impl Index {
         self.settings.chain(),
         inscriptions,
         outpoint,
+        self.mempool_spender(outpoint),
         txout.clone(),
         indexed,
         runes,
@@ -2459,7 +3906,7 @@ impl Index {
 
 #[cfg(test)]
 mod tests {
-  use {super::*, crate::index::testing::Context};
+  use {super::*, crate::index::testing::Context, bitcoin::hashes::sha256};
 
   #[test]
   fn height_limit() {
@@ -3499,7 +4946,131 @@ mod tests {
   }
 
   #[test]
-  fn new_schema_gives_correct_error() {
+  fn schema_30_index_is_migrated() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::create_dir(tempdir.path().join("regtest")).unwrap();
+
+    // created by ord 0.22.2 with `--index-addresses --index-runes
+    // --index-sats`, after mining one block, and one block containing an
+    // inscription
+    let mut index = Vec::new();
+    brotli::Decompressor::new(
+      include_bytes!("index/testdata/schema-30.redb.br").as_slice(),
+      4096,
+    )
+    .read_to_end(&mut index)
+    .unwrap();
+
+    fs::write(tempdir.path().join("regtest").join("index.redb"), index).unwrap();
+
+    let context = Context::builder().tempdir(tempdir).build();
+
+    let rtx = context.index.database.begin_read().unwrap();
+
+    assert_eq!(
+      rtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .get(&Statistic::Schema.key())
+        .unwrap()
+        .unwrap()
+        .value(),
+      SCHEMA_VERSION,
+    );
+
+    rtx
+      .open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)
+      .unwrap();
+    rtx
+      .open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBER)
+      .unwrap();
+    rtx.open_multimap_table(RUNE_BALANCE_TO_HOLDER).unwrap();
+    rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_RUNIC_OUTPOINT)
+      .unwrap();
+    rtx.open_table(CONTENT_TYPE_TO_SEQUENCE_NUMBER).unwrap();
+    rtx.open_table(EVENT_SEQUENCE_TO_EVENT).unwrap();
+    rtx.open_table(FEE_TO_SEQUENCE_NUMBER).unwrap();
+    rtx.open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE).unwrap();
+    rtx.open_table(HEIGHT_TO_UNDO).unwrap();
+    rtx.open_table(MEDIA_TO_SEQUENCE_NUMBER).unwrap();
+    rtx.open_table(METADATA_TO_SEQUENCE_NUMBER).unwrap();
+    rtx.open_table(METAPROTOCOL_TO_SEQUENCE_NUMBER).unwrap();
+    rtx.open_table(RUNE_HOLDER_TO_BALANCE).unwrap();
+    rtx.open_table(RUNE_ID_TO_HOLDER_COUNT).unwrap();
+    rtx.open_table(RUNIC_OUTPOINT_TO_HEIGHT).unwrap();
+    rtx.open_table(RUNIC_OUTPOINT_TO_SCRIPT_PUBKEY).unwrap();
+    rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_HISTORY)
+      .unwrap();
+    rtx.open_table(SPENT_RUNIC_OUTPOINT_TO_BALANCES).unwrap();
+
+    assert!(context.index.has_address_index());
+    assert!(context.index.has_rune_index());
+    assert!(context.index.has_sat_index());
+    assert!(!context.index.has_content_hash_index());
+    assert!(!context.index.has_delegate_index());
+    assert!(!context.index.has_event_index());
+    assert!(!context.index.has_inscription_attribute_index());
+    assert!(!context.index.has_inscription_history_index());
+
+    assert_eq!(context.index.block_count().unwrap(), 3);
+
+    let id = context
+      .index
+      .get_inscription_id_by_inscription_number(0)
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_entry(id)
+        .unwrap()
+        .unwrap()
+        .height,
+      2,
+    );
+  }
+
+  #[test]
+  fn migrated_index_cannot_be_opened_with_new_index_flags() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::create_dir(tempdir.path().join("regtest")).unwrap();
+
+    let mut index = Vec::new();
+    brotli::Decompressor::new(
+      include_bytes!("index/testdata/schema-30.redb.br").as_slice(),
+      4096,
+    )
+    .read_to_end(&mut index)
+    .unwrap();
+
+    fs::write(tempdir.path().join("regtest").join("index.redb"), index).unwrap();
+
+    let path = tempdir.path().to_owned();
+
+    let delimiter = if cfg!(windows) { '\\' } else { '/' };
+
+    assert_eq!(
+      Context::builder()
+        .arg("--index-delegates")
+        .tempdir(tempdir)
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "index at `{}{delimiter}regtest{delimiter}index.redb` was not built with `--index-delegates`, consider deleting and rebuilding the index with `--index-delegates`, or running without it",
+        path.display()
+      ),
+    );
+  }
+
+  #[test]
+  fn schema_requiring_reindex_gives_correct_error() {
     let tempdir = {
       let context = Context::builder().build();
 
@@ -3508,7 +5079,7 @@ mod tests {
       wtx
         .open_table(STATISTIC_TO_COUNT)
         .unwrap()
-        .insert(&Statistic::Schema.key(), &u64::MAX)
+        .insert(&Statistic::Schema.key(), &29)
         .unwrap();
 
       wtx.commit().unwrap();
@@ -3522,22 +5093,49 @@ mod tests {
 
     assert_eq!(
       Context::builder().tempdir(tempdir).try_build().err().unwrap().to_string(),
-      format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {}, ord schema {SCHEMA_VERSION}", path.display(), u64::MAX));
+      format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema 29, ord schema {SCHEMA_VERSION}", path.display()));
   }
 
   #[test]
-  fn inscriptions_on_output() {
-    for context in Context::configurations() {
-      context.mine_blocks(1);
-
-      let txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-        ..default()
-      });
+  fn new_schema_gives_correct_error() {
+    let tempdir = {
+      let context = Context::builder().build();
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+      let wtx = context.index.database.begin_write().unwrap();
 
-      assert_eq!(
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &u64::MAX)
+        .unwrap();
+
+      wtx.commit().unwrap();
+
+      context.tempdir
+    };
+
+    let path = tempdir.path().to_owned();
+
+    let delimiter = if cfg!(windows) { '\\' } else { '/' };
+
+    assert_eq!(
+      Context::builder().tempdir(tempdir).try_build().err().unwrap().to_string(),
+      format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {}, ord schema {SCHEMA_VERSION}", path.display(), u64::MAX));
+  }
+
+  #[test]
+  fn inscriptions_on_output() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      assert_eq!(
         context
           .index
           .get_inscriptions_for_output(OutPoint { txid, vout: 0 })
@@ -4795,44 +6393,33 @@ mod tests {
   }
 
   #[test]
-  fn inscription_without_parent_tag_has_no_parent_entry() {
-    for context in Context::configurations() {
-      context.mine_blocks(1);
-
-      let txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-        ..default()
-      });
-
-      context.mine_blocks(1);
-
-      let inscription_id = InscriptionId { txid, index: 0 };
-
-      assert!(context
-        .index
-        .get_inscription_entry(inscription_id)
-        .unwrap()
-        .unwrap()
-        .parents
-        .is_empty());
-    }
-  }
+  fn recover_from_reorg_deeper_than_savepoints_with_undo_records() {
+    for mut context in [
+      Context::builder().args(["--undo-depth", "30"]).build(),
+      Context::builder()
+        .args(["--index-sats", "--undo-depth", "30"])
+        .build(),
+    ] {
+      context.index.set_durability(redb::Durability::Immediate);
 
-  #[test]
-  fn inscription_with_parent_tag_without_parent_has_no_parent_entry() {
-    for context in Context::configurations() {
       context.mine_blocks(1);
 
-      let parent_txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        )],
         ..default()
       });
 
-      context.mine_blocks(1);
+      context.mine_blocks(11);
 
-      let parent_inscription_id = InscriptionId {
-        txid: parent_txid,
-        index: 0,
+      let first_id = InscriptionId { txid, index: 0 };
+      let first_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
       };
 
       let txid = context.core.broadcast_tx(TransactionTemplate {
@@ -4840,180 +6427,478 @@ mod tests {
           2,
           0,
           0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parents: vec![parent_inscription_id.value()],
-            ..default()
-          }
-          .to_witness(),
+          inscription("text/plain;charset=utf-8", "hello").to_witness(),
         )],
         ..default()
       });
 
-      context.mine_blocks(1);
+      let second_id = InscriptionId { txid, index: 0 };
+      let second_location = SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      };
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+      context.mine_blocks(25);
 
-      assert!(context
+      context
         .index
-        .get_inscription_entry(inscription_id)
-        .unwrap()
-        .unwrap()
-        .parents
-        .is_empty());
+        .assert_inscription_location(second_id, second_location, Some(100 * COIN_VALUE));
+
+      for _ in 0..25 {
+        context.core.invalidate_tip();
+      }
+
+      context.mine_blocks(27);
+
+      assert!(!context.index.inscription_exists(second_id).unwrap());
+
+      context
+        .index
+        .assert_inscription_location(first_id, first_location, Some(50 * COIN_VALUE));
     }
   }
 
   #[test]
-  fn inscription_with_parent_tag_and_parent_has_parent_entry() {
-    for context in Context::configurations() {
-      context.mine_blocks(1);
+  fn rollback_restores_index_to_earlier_height() {
+    const RUNE: u128 = 99246114928149462;
 
-      let parent_txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-        ..default()
-      });
+    fn dump(index: &Index) -> Vec<(String, Vec<u8>)> {
+      let rtx = index.database.begin_read().unwrap();
 
-      context.mine_blocks(1);
+      let mut tables = snapshot::TABLES
+        .iter()
+        .filter(|table| table.name() != STATISTIC_TO_COUNT.name())
+        .map(|table| {
+          let mut entries = Vec::new();
+          table.dump(&rtx, &mut entries).unwrap();
+          (table.name().into(), entries)
+        })
+        .collect::<Vec<(String, Vec<u8>)>>();
+
+      for statistic in [
+        Statistic::BlessedInscriptions,
+        Statistic::CursedInscriptions,
+        Statistic::InitialSyncTime,
+        Statistic::LostSats,
+        Statistic::OutputsTraversed,
+        Statistic::ReservedRunes,
+        Statistic::Runes,
+        Statistic::SatRanges,
+        Statistic::UnboundInscriptions,
+      ] {
+        tables.push((
+          format!("statistic {}", statistic.key()),
+          index.statistic(statistic).to_le_bytes().into(),
+        ));
+      }
 
-      let parent_inscription_id = InscriptionId {
-        txid: parent_txid,
-        index: 0,
-      };
+      tables
+    }
 
-      let txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(
-          2,
-          1,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parents: vec![parent_inscription_id.value()],
-            ..default()
-          }
-          .to_witness(),
-        )],
-        ..default()
-      });
+    let context = Context::builder()
+      .args([
+        "--index-addresses",
+        "--index-inscription-history",
+        "--index-rune-history",
+        "--index-rune-holders",
+        "--index-runes",
+        "--index-sats",
+        "--undo-depth",
+        "100",
+      ])
+      .build();
 
-      context.mine_blocks(1);
+    context.mine_blocks(1);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
 
-      assert_eq!(
-        context.index.get_parents_by_inscription_id(inscription_id),
-        vec![parent_inscription_id]
-      );
+    context.mine_blocks(1);
 
-      assert_eq!(
-        context
-          .index
-          .get_children_by_inscription_id(parent_inscription_id)
-          .unwrap(),
-        vec![inscription_id]
-      );
-    }
-  }
+    let height = context.index.block_height().unwrap().unwrap().n();
 
-  #[test]
-  fn inscription_with_two_parent_tags_and_parents_has_parent_entries() {
-    for context in Context::configurations() {
-      context.mine_blocks(2);
+    let before = dump(&context.index);
 
-      let parent_txid_a = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-        ..default()
-      });
-      let parent_txid_b = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
-        ..default()
-      });
+    let commits = context.index.statistic(Statistic::Commits);
 
-      context.mine_blocks(1);
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 1, 0, Witness::new()),
+        (2, 0, 0, inscription("text/plain", "bar").to_witness()),
+      ],
+      ..default()
+    });
 
-      let parent_inscription_id_a = InscriptionId {
-        txid: parent_txid_a,
-        index: 0,
-      };
-      let parent_inscription_id_b = InscriptionId {
-        txid: parent_txid_b,
-        index: 0,
-      };
+    context.mine_blocks_with_subsidy(1, 0);
 
-      let multi_parent_inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
-        parents: vec![
-          parent_inscription_id_a.value(),
-          parent_inscription_id_b.value(),
-        ],
+    context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
         ..default()
-      };
-      let multi_parent_witness = multi_parent_inscription.to_witness();
-
-      let revelation_input = (3, 1, 0, multi_parent_witness);
+      },
+      1,
+    );
 
-      let parent_b_input = (3, 2, 0, Witness::new());
+    assert!(context.index.block_height().unwrap().unwrap().n() > height + 5);
 
-      let txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[revelation_input, parent_b_input],
-        ..default()
-      });
+    context.index.rollback(height).unwrap();
 
-      context.mine_blocks(1);
+    assert_eq!(context.index.block_height().unwrap().unwrap().n(), height);
 
-      let inscription_id = InscriptionId { txid, index: 0 };
+    pretty_assert_eq!(dump(&context.index), before);
 
-      assert_eq!(
-        context.index.get_parents_by_inscription_id(inscription_id),
-        vec![parent_inscription_id_a, parent_inscription_id_b]
-      );
+    assert_eq!(context.index.statistic(Statistic::Commits), commits + 1);
 
-      assert_eq!(
-        context
-          .index
-          .get_children_by_inscription_id(parent_inscription_id_a)
-          .unwrap(),
-        vec![inscription_id]
-      );
-      assert_eq!(
-        context
-          .index
-          .get_children_by_inscription_id(parent_inscription_id_b)
-          .unwrap(),
-        vec![inscription_id]
-      );
-    }
+    assert_eq!(context.index.check(Some(10)).unwrap(), Vec::new());
   }
 
   #[test]
-  fn inscription_with_repeated_parent_tags_and_parents_has_singular_parent_entry() {
-    for context in Context::configurations() {
-      context.mine_blocks(1);
+  fn rollback_only_deletes_savepoints_above_new_tip() {
+    let mut context = Context::builder().args(["--undo-depth", "30"]).build();
 
-      let parent_txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-        ..default()
-      });
+    context.index.set_durability(redb::Durability::Immediate);
 
-      context.mine_blocks(1);
+    context.mine_blocks(25);
 
-      let parent_inscription_id = InscriptionId {
-        txid: parent_txid,
-        index: 0,
-      };
+    let savepoints = |index: &Index| {
+      index
+        .database
+        .begin_write()
+        .unwrap()
+        .list_persistent_savepoints()
+        .unwrap()
+        .collect::<Vec<u64>>()
+    };
 
-      let txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(
-          2,
-          1,
-          0,
+    let before = savepoints(&context.index);
+
+    assert!(!before.is_empty());
+
+    let last = u32::try_from(context.index.statistic(Statistic::LastSavepointHeight)).unwrap();
+
+    context.index.rollback(last).unwrap();
+
+    assert_eq!(savepoints(&context.index), before);
+
+    context.index.rollback(last - 2).unwrap();
+
+    assert_eq!(
+      context.index.statistic(Statistic::LastSavepointHeight),
+      u64::from(last - 1),
+    );
+
+    let after = savepoints(&context.index);
+
+    assert_eq!(after.len(), 1);
+    assert!(!before.contains(&after[0]));
+  }
+
+  #[test]
+  fn undo_records_are_only_kept_within_undo_depth() {
+    let context = Context::builder().args(["--undo-depth", "3"]).build();
+
+    context.mine_blocks_with_update(10, false);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(2);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    assert!(context.index.inscription_exists(id).unwrap());
+
+    assert_eq!(
+      context.index.rollback(9).unwrap_err().to_string(),
+      "cannot roll back to height 9, undo records are only kept for blocks within `--undo-depth` of the tip",
+    );
+
+    context.index.rollback(12).unwrap();
+
+    assert!(!context.index.inscription_exists(id).unwrap());
+
+    assert_eq!(context.index.check(Some(10)).unwrap(), Vec::new());
+
+    assert_eq!(
+      context.index.rollback(13).unwrap_err().to_string(),
+      "cannot roll back to height 13, index tip is at height 12",
+    );
+  }
+
+  #[test]
+  fn read_only_replica_is_refreshed_from_primary_snapshot() {
+    let tempdir = TempDir::new().unwrap();
+
+    let snapshot = tempdir.path().join("index.snapshot");
+
+    let primary = Context::builder()
+      .arg("--replica-snapshot")
+      .arg(&snapshot)
+      .build();
+
+    primary.mine_blocks(1);
+
+    let txid = primary.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    primary.mine_blocks(1);
+
+    primary.index.write_replica_snapshot().unwrap();
+
+    let first = InscriptionId { txid, index: 0 };
+
+    let replica = Context::builder()
+      .args(["--read-only", "--replica-snapshot"])
+      .arg(&snapshot)
+      .build();
+
+    assert_eq!(replica.index.block_count().unwrap(), 3);
+    assert!(replica.index.inscription_exists(first).unwrap());
+
+    let txid = primary.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    primary.mine_blocks(1);
+
+    let second = InscriptionId { txid, index: 0 };
+
+    replica.index.update().unwrap();
+
+    assert!(!replica.index.inscription_exists(second).unwrap());
+
+    primary.index.write_replica_snapshot().unwrap();
+
+    assert!(!replica.index.inscription_exists(second).unwrap());
+
+    let rtx = replica.index.database.begin_read().unwrap();
+
+    replica.index.update().unwrap();
+
+    assert_eq!(replica.index.block_count().unwrap(), 4);
+    assert!(replica.index.inscription_exists(second).unwrap());
+
+    assert_eq!(
+      rtx
+        .open_table(HEIGHT_TO_BLOCK_HEADER)
+        .unwrap()
+        .len()
+        .unwrap(),
+      3,
+    );
+
+    assert!(!replica
+      .index
+      .path
+      .with_extension("redb.next")
+      .try_exists()
+      .unwrap());
+
+    assert_eq!(
+      replica.index.rollback(2).unwrap_err().to_string(),
+      "cannot roll back index in read-only mode",
+    );
+
+    assert_eq!(replica.index.status(false).unwrap().replica_lag, Some(0));
+    assert_eq!(primary.index.status(false).unwrap().replica_lag, None);
+  }
+
+  #[test]
+  fn readiness() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.readiness(1).unwrap(),
+      api::Readiness {
+        ready: true,
+        height: Some(1),
+        lag: Some(0),
+        reason: None,
+      }
+    );
+
+    context.core.mine_blocks(2);
+
+    assert!(context.index.readiness(2).unwrap().ready);
+
+    assert_eq!(
+      context.index.readiness(1).unwrap(),
+      api::Readiness {
+        ready: false,
+        height: Some(1),
+        lag: Some(2),
+        reason: Some("index is 2 blocks behind Bitcoin Core, maximum lag is 1".into()),
+      }
+    );
+
+    context.index.update().unwrap();
+
+    context
+      .index
+      .unrecoverably_reorged
+      .store(true, atomic::Ordering::Relaxed);
+
+    assert_eq!(
+      context.index.readiness(1).unwrap(),
+      api::Readiness {
+        ready: false,
+        height: Some(3),
+        lag: Some(0),
+        reason: Some("index has been unrecoverably reorged".into()),
+      }
+    );
+  }
+
+  #[test]
+  fn rune_indexes_require_index_runes() {
+    for flag in ["--index-rune-history", "--index-rune-holders"] {
+      assert_eq!(
+        Context::builder()
+          .arg(flag)
+          .try_build()
+          .err()
+          .unwrap()
+          .to_string(),
+        format!("{flag} requires --index-runes"),
+      );
+    }
+  }
+
+  #[test]
+  fn read_only_index_must_exist() {
+    let tempdir = TempDir::new().unwrap();
+
+    let path = tempdir.path().to_owned();
+
+    let delimiter = if cfg!(windows) { '\\' } else { '/' };
+
+    assert_eq!(
+      Context::builder()
+        .arg("--read-only")
+        .tempdir(tempdir)
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "index `{}{delimiter}regtest{delimiter}index.redb` does not exist, and cannot be created in read-only mode",
+        path.display()
+      ),
+    );
+  }
+
+  #[test]
+  fn read_only_index_is_not_migrated() {
+    let tempdir = {
+      let context = Context::builder().build();
+
+      let wtx = context.index.database.begin_write().unwrap();
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &30)
+        .unwrap();
+
+      wtx.commit().unwrap();
+
+      context.tempdir
+    };
+
+    let path = tempdir.path().to_owned();
+
+    let delimiter = if cfg!(windows) { '\\' } else { '/' };
+
+    assert_eq!(
+      Context::builder()
+        .arg("--read-only")
+        .tempdir(tempdir)
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      format!(
+        "index at `{}{delimiter}regtest{delimiter}index.redb` has schema 30 but ord schema is {SCHEMA_VERSION}, and cannot be migrated in read-only mode",
+        path.display()
+      ),
+    );
+  }
+
+  #[test]
+  fn inscription_without_parent_tag_has_no_parent_entry() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      assert!(context
+        .index
+        .get_inscription_entry(inscription_id)
+        .unwrap()
+        .unwrap()
+        .parents
+        .is_empty());
+    }
+  }
+
+  #[test]
+  fn inscription_with_parent_tag_without_parent_has_no_parent_entry() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let parent_txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
+      };
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          0,
+          0,
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
-            parents: vec![parent_inscription_id.value(), parent_inscription_id.value()],
+            parents: vec![parent_inscription_id.value()],
             ..default()
           }
           .to_witness(),
@@ -5025,23 +6910,18 @@ mod tests {
 
       let inscription_id = InscriptionId { txid, index: 0 };
 
-      assert_eq!(
-        context.index.get_parents_by_inscription_id(inscription_id),
-        vec![parent_inscription_id]
-      );
-
-      assert_eq!(
-        context
-          .index
-          .get_children_by_inscription_id(parent_inscription_id)
-          .unwrap(),
-        vec![inscription_id]
-      );
+      assert!(context
+        .index
+        .get_inscription_entry(inscription_id)
+        .unwrap()
+        .unwrap()
+        .parents
+        .is_empty());
     }
   }
 
   #[test]
-  fn inscription_with_distinct_parent_tag_encodings_for_same_parent_has_singular_parent_entry() {
+  fn inscription_with_parent_tag_and_parent_has_parent_entry() {
     for context in Context::configurations() {
       context.mine_blocks(1);
 
@@ -5057,12 +6937,6 @@ mod tests {
         index: 0,
       };
 
-      let trailing_zero_inscription_id: Vec<u8> = parent_inscription_id
-        .value()
-        .into_iter()
-        .chain(vec![0, 0, 0, 0])
-        .collect();
-
       let txid = context.core.broadcast_tx(TransactionTemplate {
         inputs: &[(
           2,
@@ -5071,7 +6945,7 @@ mod tests {
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
-            parents: vec![parent_inscription_id.value(), trailing_zero_inscription_id],
+            parents: vec![parent_inscription_id.value()],
             ..default()
           }
           .to_witness(),
@@ -5099,9 +6973,9 @@ mod tests {
   }
 
   #[test]
-  fn inscription_with_three_parent_tags_and_two_parents_has_two_parent_entries() {
+  fn inscription_with_two_parent_tags_and_parents_has_parent_entries() {
     for context in Context::configurations() {
-      context.mine_blocks(3);
+      context.mine_blocks(2);
 
       let parent_txid_a = context.core.broadcast_tx(TransactionTemplate {
         inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
@@ -5111,10 +6985,6 @@ mod tests {
         inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
         ..default()
       });
-      let parent_txid_c = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(3, 0, 0, inscription("text/plain", "wazzup").to_witness())],
-        ..default()
-      });
 
       context.mine_blocks(1);
 
@@ -5126,10 +6996,6 @@ mod tests {
         txid: parent_txid_b,
         index: 0,
       };
-      let parent_inscription_id_c = InscriptionId {
-        txid: parent_txid_c,
-        index: 0,
-      };
 
       let multi_parent_inscription = Inscription {
         content_type: Some("text/plain".into()),
@@ -5137,18 +7003,17 @@ mod tests {
         parents: vec![
           parent_inscription_id_a.value(),
           parent_inscription_id_b.value(),
-          parent_inscription_id_c.value(),
         ],
         ..default()
       };
       let multi_parent_witness = multi_parent_inscription.to_witness();
 
-      let revealing_parent_a_input = (4, 1, 0, multi_parent_witness);
+      let revelation_input = (3, 1, 0, multi_parent_witness);
 
-      let parent_c_input = (4, 3, 0, Witness::new());
+      let parent_b_input = (3, 2, 0, Witness::new());
 
       let txid = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[revealing_parent_a_input, parent_c_input],
+        inputs: &[revelation_input, parent_b_input],
         ..default()
       });
 
@@ -5158,7 +7023,7 @@ mod tests {
 
       assert_eq!(
         context.index.get_parents_by_inscription_id(inscription_id),
-        vec![parent_inscription_id_a, parent_inscription_id_c]
+        vec![parent_inscription_id_a, parent_inscription_id_b]
       );
 
       assert_eq!(
@@ -5173,22 +7038,212 @@ mod tests {
           .index
           .get_children_by_inscription_id(parent_inscription_id_b)
           .unwrap(),
-        Vec::new()
-      );
-      assert_eq!(
-        context
-          .index
-          .get_children_by_inscription_id(parent_inscription_id_c)
-          .unwrap(),
         vec![inscription_id]
       );
     }
   }
 
   #[test]
-  fn inscription_with_valid_and_malformed_parent_tags_only_lists_valid_entries() {
+  fn inscription_with_repeated_parent_tags_and_parents_has_singular_parent_entry() {
     for context in Context::configurations() {
-      context.mine_blocks(3);
+      context.mine_blocks(1);
+
+      let parent_txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
+      };
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          1,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value(), parent_inscription_id.value()],
+            ..default()
+          }
+          .to_witness(),
+        )],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      assert_eq!(
+        context.index.get_parents_by_inscription_id(inscription_id),
+        vec![parent_inscription_id]
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id)
+          .unwrap(),
+        vec![inscription_id]
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_with_distinct_parent_tag_encodings_for_same_parent_has_singular_parent_entry() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let parent_txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let parent_inscription_id = InscriptionId {
+        txid: parent_txid,
+        index: 0,
+      };
+
+      let trailing_zero_inscription_id: Vec<u8> = parent_inscription_id
+        .value()
+        .into_iter()
+        .chain(vec![0, 0, 0, 0])
+        .collect();
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          1,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value(), trailing_zero_inscription_id],
+            ..default()
+          }
+          .to_witness(),
+        )],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      assert_eq!(
+        context.index.get_parents_by_inscription_id(inscription_id),
+        vec![parent_inscription_id]
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id)
+          .unwrap(),
+        vec![inscription_id]
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_with_three_parent_tags_and_two_parents_has_two_parent_entries() {
+    for context in Context::configurations() {
+      context.mine_blocks(3);
+
+      let parent_txid_a = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+      let parent_txid_b = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
+        ..default()
+      });
+      let parent_txid_c = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(3, 0, 0, inscription("text/plain", "wazzup").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let parent_inscription_id_a = InscriptionId {
+        txid: parent_txid_a,
+        index: 0,
+      };
+      let parent_inscription_id_b = InscriptionId {
+        txid: parent_txid_b,
+        index: 0,
+      };
+      let parent_inscription_id_c = InscriptionId {
+        txid: parent_txid_c,
+        index: 0,
+      };
+
+      let multi_parent_inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![
+          parent_inscription_id_a.value(),
+          parent_inscription_id_b.value(),
+          parent_inscription_id_c.value(),
+        ],
+        ..default()
+      };
+      let multi_parent_witness = multi_parent_inscription.to_witness();
+
+      let revealing_parent_a_input = (4, 1, 0, multi_parent_witness);
+
+      let parent_c_input = (4, 3, 0, Witness::new());
+
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[revealing_parent_a_input, parent_c_input],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      assert_eq!(
+        context.index.get_parents_by_inscription_id(inscription_id),
+        vec![parent_inscription_id_a, parent_inscription_id_c]
+      );
+
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id_a)
+          .unwrap(),
+        vec![inscription_id]
+      );
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id_b)
+          .unwrap(),
+        Vec::new()
+      );
+      assert_eq!(
+        context
+          .index
+          .get_children_by_inscription_id(parent_inscription_id_c)
+          .unwrap(),
+        vec![inscription_id]
+      );
+    }
+  }
+
+  #[test]
+  fn inscription_with_valid_and_malformed_parent_tags_only_lists_valid_entries() {
+    for context in Context::configurations() {
+      context.mine_blocks(3);
 
       let parent_txid_a = context.core.broadcast_tx(TransactionTemplate {
         inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
@@ -6072,13 +8127,9 @@ mod tests {
         .unwrap()
         .unwrap();
 
-      assert!(Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Cursed));
+      assert!(Charm::charms(entry.charms).contains(&Charm::Cursed));
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Vindicated));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Vindicated));
 
       let sat = entry.sat;
 
@@ -6106,13 +8157,9 @@ mod tests {
 
       assert_eq!(entry.inscription_number, 0);
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Cursed));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Cursed));
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Vindicated));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Vindicated));
 
       assert_eq!(sat, entry.sat);
 
@@ -6136,13 +8183,9 @@ mod tests {
         .unwrap()
         .unwrap();
 
-      assert!(Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Cursed));
+      assert!(Charm::charms(entry.charms).contains(&Charm::Cursed));
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Vindicated));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Vindicated));
 
       assert_eq!(entry.inscription_number, -2);
 
@@ -6183,13 +8226,9 @@ mod tests {
         .unwrap()
         .unwrap();
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Cursed));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Cursed));
 
-      assert!(Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Vindicated));
+      assert!(Charm::charms(entry.charms).contains(&Charm::Vindicated));
 
       let sat = entry.sat;
 
@@ -6215,13 +8254,9 @@ mod tests {
         .unwrap()
         .unwrap();
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Cursed));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Cursed));
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Vindicated));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Vindicated));
 
       assert_eq!(entry.inscription_number, 1);
 
@@ -6247,13 +8282,9 @@ mod tests {
         .unwrap()
         .unwrap();
 
-      assert!(!Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Cursed));
+      assert!(!Charm::charms(entry.charms).contains(&Charm::Cursed));
 
-      assert!(Charm::charms(entry.charms)
-        .iter()
-        .any(|charm| *charm == Charm::Vindicated));
+      assert!(Charm::charms(entry.charms).contains(&Charm::Vindicated));
 
       assert_eq!(entry.inscription_number, 2);
 
@@ -6439,59 +8470,335 @@ mod tests {
   }
 
   #[test]
-  fn inscription_event_sender_channel() {
-    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
-    let context = Context::builder().event_sender(event_sender).build();
+  fn event_log_is_replayable() {
+    let context = Context::builder().arg("--index-events").build();
 
-    context.mine_blocks(1);
+    assert!(context.index.has_event_index());
 
-    let inscription = Inscription::default();
-    let create_txid = context.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription.to_witness())],
-      fee: 0,
-      outputs: 1,
+    context.mine_blocks(2);
+
+    let first = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
       ..default()
     });
 
-    context.mine_blocks(1);
-
-    let inscription_id = InscriptionId {
-      txid: create_txid,
-      index: 0,
-    };
-    let create_event = event_receiver.blocking_recv().unwrap();
-    let expected_charms = if context.index.index_sats { 513 } else { 0 };
-    assert_eq!(
-      create_event,
-      Event::InscriptionCreated {
-        inscription_id,
-        location: Some(SatPoint {
-          outpoint: OutPoint {
-            txid: create_txid,
-            vout: 0
-          },
-          offset: 0
-        }),
-        sequence_number: 0,
-        block_height: 2,
-        charms: expected_charms,
-        parent_inscription_ids: Vec::new(),
-      }
-    );
-
-    // Transfer inscription
-    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 1, 0, Default::default())],
-      fee: 0,
-      outputs: 1,
+    let second = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
       ..default()
     });
 
     context.mine_blocks(1);
 
-    let transfer_event = event_receiver.blocking_recv().unwrap();
+    let events = context.index.get_events(0, None, 100).unwrap();
+
+    assert!(!events.more);
+    assert_eq!(events.next, 2);
+
     assert_eq!(
-      transfer_event,
+      events
+        .events
+        .iter()
+        .map(|logged| match logged.event {
+          Event::InscriptionCreated { inscription_id, .. } =>
+            (logged.height, logged.sequence, inscription_id),
+          _ => panic!("unexpected event: {:?}", logged.event),
+        })
+        .collect::<Vec<(u32, u64, InscriptionId)>>(),
+      [
+        (
+          3,
+          0,
+          InscriptionId {
+            txid: first,
+            index: 0
+          }
+        ),
+        (
+          3,
+          1,
+          InscriptionId {
+            txid: second,
+            index: 0
+          }
+        ),
+      ],
+    );
+
+    let page = context.index.get_events(0, None, 1).unwrap();
+    assert!(page.more);
+    assert_eq!(page.next, 1);
+    assert_eq!(page.events, events.events[..1]);
+
+    let page = context.index.get_events(page.next, None, 1).unwrap();
+    assert!(!page.more);
+    assert_eq!(page.next, 2);
+    assert_eq!(page.events, events.events[1..]);
+
+    let page = context.index.get_events(page.next, None, 100).unwrap();
+    assert!(page.events.is_empty());
+    assert_eq!(page.next, 2);
+
+    assert_eq!(context.index.get_events(0, Some(3), 100).unwrap(), events);
+
+    let page = context.index.get_events(0, Some(4), 100).unwrap();
+    assert!(page.events.is_empty());
+    assert_eq!(page.next, 2);
+  }
+
+  #[test]
+  fn event_log_is_disabled_by_default() {
+    let context = Context::builder().build();
+
+    assert!(!context.index.has_event_index());
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(context
+      .index
+      .get_events(0, None, 100)
+      .unwrap()
+      .events
+      .is_empty());
+  }
+
+  #[test]
+  fn reorg_emits_reverted_events() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+
+    let mut context = Context::builder()
+      .arg("--index-events")
+      .arg("--index-runes")
+      .event_sender(event_sender)
+      .build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(6);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    context.mine_blocks(1);
+
+    let block_hash = context.index.block_hash(Some(8)).unwrap().unwrap();
+
+    while event_receiver.try_recv().is_ok() {}
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    let reverted = std::iter::from_fn(|| event_receiver.try_recv().ok()).collect::<Vec<Event>>();
+
+    assert_eq!(
+      reverted[..2],
+      [
+        Event::InscriptionReverted {
+          block_height: 8,
+          inscription_id,
+        },
+        Event::BlockReverted {
+          block_hash,
+          block_height: 8,
+        },
+      ],
+    );
+
+    let logged = context
+      .index
+      .get_events(0, None, 1000)
+      .unwrap()
+      .events
+      .into_iter()
+      .map(|logged| logged.event)
+      .collect::<Vec<Event>>();
+
+    assert!(logged.contains(&Event::InscriptionCreated {
+      block_height: 8,
+      charms: 0,
+      inscription_id,
+      location: Some(SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      }),
+      parent_inscription_ids: Vec::new(),
+      sequence_number: 1,
+    }));
+
+    let start = logged
+      .iter()
+      .position(|event| *event == reverted[0])
+      .unwrap();
+
+    assert_eq!(logged[start..start + reverted.len()], reverted);
+
+    assert!(logged[start + reverted.len()..]
+      .iter()
+      .all(|event| !matches!(
+        event,
+        Event::BlockReverted { .. }
+          | Event::InscriptionReverted { .. }
+          | Event::RuneReverted { .. }
+      )));
+
+    assert!(!context.index.inscription_exists(inscription_id).unwrap());
+  }
+
+  #[test]
+  fn rollback_appends_reverted_events() {
+    let context = Context::builder()
+      .args(["--index-events", "--undo-depth", "10"])
+      .build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let before = context.index.get_events(0, None, 100).unwrap();
+
+    assert_eq!(before.events.len(), 1);
+
+    let block_hash = context.index.block_hash(Some(2)).unwrap().unwrap();
+
+    context.index.rollback(1).unwrap();
+
+    let after = context.index.get_events(before.next, None, 100).unwrap();
+
+    assert_eq!(
+      after
+        .events
+        .iter()
+        .map(|logged| (logged.sequence, logged.event.clone()))
+        .collect::<Vec<(u64, Event)>>(),
+      [
+        (
+          1,
+          Event::InscriptionReverted {
+            block_height: 2,
+            inscription_id,
+          }
+        ),
+        (
+          2,
+          Event::BlockReverted {
+            block_hash,
+            block_height: 2,
+          }
+        ),
+      ],
+    );
+
+    assert_eq!(
+      context.index.get_events(0, None, 1).unwrap().events,
+      before.events
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_events(0, Some(2), 100)
+        .unwrap()
+        .events
+        .len(),
+      3,
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_events(0, Some(1), 100)
+        .unwrap()
+        .events
+        .len(),
+      3,
+    );
+
+    assert!(context
+      .index
+      .get_events(0, Some(3), 100)
+      .unwrap()
+      .events
+      .is_empty());
+  }
+
+  #[test]
+  fn inscription_event_sender_channel() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    context.mine_blocks(1);
+
+    let inscription = Inscription::default();
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription.to_witness())],
+      fee: 0,
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+    let create_event = event_receiver.blocking_recv().unwrap();
+    let expected_charms = if context.index.index_sats { 513 } else { 0 };
+    assert_eq!(
+      create_event,
+      Event::InscriptionCreated {
+        inscription_id,
+        location: Some(SatPoint {
+          outpoint: OutPoint {
+            txid: create_txid,
+            vout: 0
+          },
+          offset: 0
+        }),
+        sequence_number: 0,
+        block_height: 2,
+        charms: expected_charms,
+        parent_inscription_ids: Vec::new(),
+      }
+    );
+
+    // Transfer inscription
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 0,
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let transfer_event = event_receiver.blocking_recv().unwrap();
+    assert_eq!(
+      transfer_event,
       Event::InscriptionTransferred {
         block_height: 3,
         inscription_id,
@@ -6751,6 +9058,1148 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inscription_history_records_every_location() {
+    let context = Context::builder()
+      .arg("--index-inscription-history")
+      .build();
+
+    assert!(context.index.has_inscription_history_index());
+
+    context.mine_blocks(1);
+
+    let create_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    let inscription_id = InscriptionId {
+      txid: create_txid,
+      index: 0,
+    };
+
+    context.mine_blocks(1);
+
+    let transfer_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let created = SatPoint {
+      outpoint: OutPoint {
+        txid: create_txid,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    let transferred = SatPoint {
+      outpoint: OutPoint {
+        txid: transfer_txid,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history(inscription_id)
+        .unwrap(),
+      Some(vec![
+        api::InscriptionLocation {
+          address: Some(context.core.address(created.outpoint).to_string()),
+          height: 2,
+          new_satpoint: created,
+          old_satpoint: None,
+          txid: create_txid,
+        },
+        api::InscriptionLocation {
+          address: Some(context.core.address(transferred.outpoint).to_string()),
+          height: 3,
+          new_satpoint: transferred,
+          old_satpoint: Some(created),
+          txid: transfer_txid,
+        },
+      ]),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history(crate::test::inscription_id(1))
+        .unwrap(),
+      None,
+    );
+  }
+
+  #[test]
+  fn inscription_history_is_not_recorded_by_default() {
+    let context = Context::builder().build();
+
+    assert!(!context.index.has_inscription_history_index());
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_history(InscriptionId { txid, index: 0 })
+        .unwrap(),
+      Some(Vec::new()),
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_indexed_by_content_hash() {
+    let context = Context::builder().arg("--index-content-hashes").build();
+
+    assert!(context.index.has_content_hash_index());
+
+    context.mine_blocks(3);
+
+    let mut ids = Vec::new();
+
+    for (height, content) in [(1, "foo"), (2, "bar"), (3, "foo")] {
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          height,
+          0,
+          0,
+          inscription("text/plain", content).to_witness(),
+        )],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let foo = sha256::Hash::hash(b"foo").to_byte_array();
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_content_hash_paginated(foo, 100, 0)
+        .unwrap(),
+      (vec![ids[0], ids[2]], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_content_hash_paginated(foo, 1, 0)
+        .unwrap(),
+      (vec![ids[0]], true),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_content_hash_paginated(foo, 1, 1)
+        .unwrap(),
+      (vec![ids[2]], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_content_hash_paginated(
+          sha256::Hash::hash(b"bar").to_byte_array(),
+          100,
+          0
+        )
+        .unwrap(),
+      (vec![ids[1]], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_content_hash_paginated(
+          sha256::Hash::hash(b"baz").to_byte_array(),
+          100,
+          0
+        )
+        .unwrap(),
+      (Vec::new(), false),
+    );
+  }
+
+  #[test]
+  fn content_hashes_are_not_indexed_by_default() {
+    let context = Context::builder().build();
+
+    assert!(!context.index.has_content_hash_index());
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_content_hash_paginated(
+          sha256::Hash::hash(b"foo").to_byte_array(),
+          100,
+          0
+        )
+        .unwrap(),
+      (Vec::new(), false),
+    );
+  }
+
+  #[test]
+  fn delegators_are_indexed() {
+    let context = Context::builder().arg("--index-delegates").build();
+
+    assert!(context.index.has_delegate_index());
+
+    context.mine_blocks(3);
+
+    let delegate = InscriptionId {
+      txid: context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    context.mine_blocks(1);
+
+    let mut delegators = Vec::new();
+
+    for height in [2, 3] {
+      delegators.push(InscriptionId {
+        txid: context.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(
+            height,
+            0,
+            0,
+            Inscription {
+              delegate: Some(delegate.value()),
+              ..default()
+            }
+            .to_witness(),
+          )],
+          ..default()
+        }),
+        index: 0,
+      });
+
+      context.mine_blocks(1);
+    }
+
+    assert_eq!(
+      context
+        .index
+        .get_delegators_paginated(delegate, 100, 0)
+        .unwrap(),
+      (delegators.clone(), false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_delegators_paginated(delegate, 1, 0)
+        .unwrap(),
+      (vec![delegators[0]], true),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_delegators_paginated(delegate, 1, 1)
+        .unwrap(),
+      (vec![delegators[1]], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .inscription_info(query::Inscription::Id(delegate), None)
+        .unwrap()
+        .unwrap()
+        .0
+        .delegator_count,
+      Some(2),
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_indexed_by_metadata() {
+    let context = Context::builder().arg("--index-metadata").build();
+
+    assert!(context.index.has_metadata_index());
+
+    context.mine_blocks(3);
+
+    let mut ids = Vec::new();
+
+    for (height, color) in [(1, "blue"), (2, "red"), (3, "blue")] {
+      let mut metadata = Vec::new();
+      ciborium::into_writer(
+        &ciborium::Value::Map(vec![(
+          ciborium::Value::Text("color".into()),
+          ciborium::Value::Text(color.into()),
+        )]),
+        &mut metadata,
+      )
+      .unwrap();
+
+      ids.push(InscriptionId {
+        txid: context.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(
+            height,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("foo".into()),
+              metadata: Some(metadata),
+              ..default()
+            }
+            .to_witness(),
+          )],
+          ..default()
+        }),
+        index: 0,
+      });
+
+      context.mine_blocks(1);
+    }
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metadata_paginated("color", Some("blue"), 100, 0)
+        .unwrap(),
+      (vec![ids[0], ids[2]], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metadata_paginated("color", None, 100, 0)
+        .unwrap(),
+      (vec![ids[0], ids[2], ids[1]], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metadata_paginated("color", None, 2, 1)
+        .unwrap(),
+      (vec![ids[1]], false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metadata_paginated("colo", None, 100, 0)
+        .unwrap(),
+      (Vec::new(), false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_by_metadata_paginated("color", Some("green"), 100, 0)
+        .unwrap(),
+      (Vec::new(), false),
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_filtered_by_attributes() {
+    let context = Context::builder()
+      .arg("--index-inscription-attributes")
+      .build();
+
+    assert!(context.index.has_inscription_attribute_index());
+
+    context.mine_blocks(4);
+
+    let mut ids = Vec::new();
+
+    for inputs in [
+      vec![(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain;charset=utf-8".into()),
+          body: Some("foo".into()),
+          metaprotocol: Some("brc-20".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      vec![
+        (2, 0, 0, Witness::new()),
+        (3, 0, 0, inscription("image/png", [1; 100]).to_witness()),
+      ],
+      vec![(
+        4,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("bar".into()),
+          metaprotocol: Some("brc-20".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+    ] {
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &inputs,
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let filtered = |filter: InscriptionFilter| {
+      context
+        .index
+        .get_inscriptions_from(&filter, Sort::Newest, None, 100)
+        .unwrap()
+        .items
+    };
+
+    assert_eq!(
+      filtered(InscriptionFilter::default()),
+      [ids[2], ids[1], ids[0]]
+    );
+
+    assert_eq!(
+      filtered(InscriptionFilter {
+        metaprotocol: Some("brc-20".into()),
+        ..default()
+      }),
+      [ids[2], ids[0]],
+    );
+
+    assert_eq!(
+      filtered(InscriptionFilter {
+        metaprotocol: Some("brc-21".into()),
+        ..default()
+      }),
+      [],
+    );
+
+    for content_type in ["text/plain", "TEXT/PLAIN; charset=utf-8", "text"] {
+      assert_eq!(
+        filtered(InscriptionFilter {
+          content_type: Some(content_type.into()),
+          ..default()
+        }),
+        [ids[2], ids[0]],
+      );
+    }
+
+    assert_eq!(
+      filtered(InscriptionFilter {
+        content_type: Some("image".into()),
+        ..default()
+      }),
+      [ids[1]],
+    );
+
+    assert_eq!(
+      filtered(InscriptionFilter {
+        content_type: Some("image/png".into()),
+        metaprotocol: Some("brc-20".into()),
+        ..default()
+      }),
+      [],
+    );
+
+    assert_eq!(
+      filtered(InscriptionFilter {
+        charm: Some(Charm::Cursed),
+        ..default()
+      }),
+      [ids[1]],
+    );
+
+    assert_eq!(
+      filtered(InscriptionFilter {
+        from_height: Some(6),
+        ..default()
+      }),
+      [ids[2], ids[1]],
+    );
+
+    assert_eq!(
+      filtered(InscriptionFilter {
+        from_height: Some(7),
+        metaprotocol: Some("brc-20".into()),
+        ..default()
+      }),
+      [ids[2]],
+    );
+
+    let text = InscriptionFilter {
+      content_type: Some("text".into()),
+      ..default()
+    };
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_from(&text, Sort::Newest, None, 1)
+        .unwrap(),
+      Listing {
+        items: vec![ids[2]],
+        prev: None,
+        next: Some(0),
+      },
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_from(&text, Sort::Newest, Some(0), 1)
+        .unwrap(),
+      Listing {
+        items: vec![ids[0]],
+        prev: Some(2),
+        next: None,
+      },
+    );
+  }
+
+  #[test]
+  fn inscriptions_are_listed_from_cursor() {
+    let context = Context::builder()
+      .arg("--index-inscription-attributes")
+      .build();
+
+    context.mine_blocks(4);
+
+    let mut ids = Vec::new();
+
+    for (i, (fee, metaprotocol)) in [
+      (30, Some("brc-20")),
+      (10, None),
+      (40, Some("brc-20")),
+      (10, None),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          i + 1,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("foo".into()),
+            metaprotocol: metaprotocol.map(Into::into),
+            ..default()
+          }
+          .to_witness(),
+        )],
+        fee,
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let listing = |filter: InscriptionFilter, sort, start, page_size| {
+      let listing = context
+        .index
+        .get_inscriptions_from(&filter, sort, start, page_size)
+        .unwrap();
+      (listing.items, listing.prev, listing.next)
+    };
+
+    let all = InscriptionFilter::default;
+
+    assert_eq!(
+      listing(all(), Sort::Newest, None, 2),
+      (vec![ids[3], ids[2]], None, Some(1)),
+    );
+
+    assert_eq!(
+      listing(all(), Sort::Newest, Some(2), 2),
+      (vec![ids[2], ids[1]], Some(3), Some(0)),
+    );
+
+    assert_eq!(
+      listing(all(), Sort::Newest, Some(1), 2),
+      (vec![ids[1], ids[0]], Some(3), None),
+    );
+
+    assert_eq!(
+      listing(all(), Sort::Oldest, None, 3),
+      (vec![ids[0], ids[1], ids[2]], None, Some(3)),
+    );
+
+    assert_eq!(
+      listing(all(), Sort::Oldest, Some(3), 3),
+      (vec![ids[3]], Some(0), None),
+    );
+
+    assert_eq!(
+      listing(all(), Sort::Fee, None, 2),
+      (vec![ids[2], ids[0]], None, Some(3)),
+    );
+
+    assert_eq!(
+      listing(all(), Sort::Fee, Some(3), 2),
+      (vec![ids[3], ids[1]], Some(2), None),
+    );
+
+    let brc20 = || InscriptionFilter {
+      metaprotocol: Some("brc-20".into()),
+      ..default()
+    };
+
+    assert_eq!(
+      listing(brc20(), Sort::Newest, None, 1),
+      (vec![ids[2]], None, Some(0)),
+    );
+
+    assert_eq!(
+      listing(brc20(), Sort::Newest, Some(0), 1),
+      (vec![ids[0]], Some(2), None),
+    );
+
+    assert_eq!(
+      listing(brc20(), Sort::Fee, None, 2),
+      (vec![ids[2], ids[0]], None, None),
+    );
+
+    let from_height = || InscriptionFilter {
+      from_height: Some(7),
+      ..default()
+    };
+
+    assert_eq!(
+      listing(from_height(), Sort::Oldest, None, 10),
+      (vec![ids[2], ids[3]], None, None),
+    );
+
+    assert_eq!(
+      listing(from_height(), Sort::Newest, None, 10),
+      (vec![ids[3], ids[2]], None, None),
+    );
+
+    assert_eq!(
+      listing(from_height(), Sort::Fee, None, 10),
+      (vec![ids[2], ids[3]], None, None),
+    );
+  }
+
+  #[test]
+  fn listing_scan_examines_at_most_limit_candidates() {
+    let scan = |start: u32, page_size, limit| {
+      let listing = Listing::scan(
+        (start..20).map(|key| Ok((key, (key % 5 == 0).then_some(key)))),
+        (0..start).rev().map(|key| Ok((key, key % 5 == 0))),
+        page_size,
+        limit,
+      )
+      .unwrap();
+      (listing.items, listing.prev, listing.next)
+    };
+
+    assert_eq!(scan(0, 2, usize::MAX), (vec![0, 5], None, Some(10)));
+    assert_eq!(scan(0, 2, 3), (vec![0], None, Some(3)));
+    assert_eq!(scan(3, 2, 3), (vec![5], Some(0), Some(6)));
+    assert_eq!(scan(12, 2, usize::MAX), (vec![15], Some(5), None));
+    assert_eq!(scan(12, 2, 3), (Vec::new(), Some(9), Some(15)));
+  }
+
+  #[test]
+  fn sorting_inscriptions_by_fee_requires_attribute_index() {
+    let context = Context::builder().build();
+
+    assert_eq!(
+      context
+        .index
+        .get_inscriptions_from(&InscriptionFilter::default(), Sort::Fee, None, 10)
+        .unwrap_err()
+        .to_string(),
+      "sorting by fee requires index created with `--index-inscription-attributes`",
+    );
+  }
+
+  #[test]
+  fn check_finds_no_violations_in_consistent_index() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder()
+      .arg("--index-sats")
+      .arg("--index-runes")
+      .build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks_with_subsidy(1, 0);
+
+    context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert_eq!(context.index.check(Some(10)).unwrap(), Vec::new());
+  }
+
+  #[test]
+  fn check_finds_violations() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder()
+      .arg("--index-sats")
+      .arg("--index-runes")
+      .build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let (_, id) = context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let outpoint = OutPoint { txid, vout: 0 };
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::BlessedInscriptions.key(), &2)
+        .unwrap();
+
+      wtx
+        .open_table(OUTPOINT_TO_UTXO_ENTRY)
+        .unwrap()
+        .remove(&outpoint.store())
+        .unwrap();
+
+      let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY).unwrap();
+
+      let mut entry = RuneEntry::load(
+        rune_id_to_rune_entry
+          .get(&id.store())
+          .unwrap()
+          .unwrap()
+          .value(),
+      );
+
+      entry.burned = 1;
+
+      rune_id_to_rune_entry
+        .insert(&id.store(), entry.store())
+        .unwrap();
+
+      drop(rune_id_to_rune_entry);
+
+      wtx.commit().unwrap();
+    }
+
+    let expected = Height(context.index.block_count().unwrap())
+      .starting_sat()
+      .n();
+
+    assert_eq!(
+      context.index.check(None).unwrap(),
+      [
+        check::Violation::Statistic {
+          statistic: "inscription entries".into(),
+          counter: 2,
+          actual: 1,
+        },
+        check::Violation::Statistic {
+          statistic: "inscription ids".into(),
+          counter: 2,
+          actual: 1,
+        },
+        check::Violation::Statistic {
+          statistic: "inscription numbers".into(),
+          counter: 2,
+          actual: 1,
+        },
+        check::Violation::SatSupply {
+          expected,
+          actual: expected - 50 * COIN_VALUE,
+        },
+        check::Violation::RuneSupply {
+          rune: SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          supply: 1000,
+          outstanding: 1000,
+          burned: 1,
+        },
+        check::Violation::InscriptionLocation {
+          inscription: InscriptionId { txid, index: 0 },
+          satpoint: SatPoint {
+            outpoint,
+            offset: 0,
+          },
+        },
+      ],
+    );
+  }
+
+  #[test]
+  fn rune_balances_at_height() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder()
+      .arg("--index-runes")
+      .arg("--index-rune-history")
+      .build();
+
+    assert!(context.index.has_rune_history_index());
+
+    let (txid0, id) = context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(8, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 400,
+            output: 1,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let spaced_rune = SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 0,
+    };
+
+    let pile = |amount| Pile {
+      amount,
+      divisibility: 0,
+      symbol: None,
+    };
+
+    let outpoint0 = OutPoint {
+      txid: txid0,
+      vout: 0,
+    };
+
+    let outpoint1 = OutPoint {
+      txid: txid1,
+      vout: 0,
+    };
+
+    let outpoint2 = OutPoint {
+      txid: txid1,
+      vout: 1,
+    };
+
+    let at_height = |target: AddressOrOutPoint, height| {
+      context
+        .index
+        .get_rune_balances_at_height(&target, height)
+        .unwrap()
+        .unwrap()
+    };
+
+    assert_eq!(
+      at_height(AddressOrOutPoint::OutPoint(outpoint0), 7),
+      [].into()
+    );
+
+    assert_eq!(
+      at_height(AddressOrOutPoint::OutPoint(outpoint0), 8),
+      [(outpoint0, [(spaced_rune, pile(1000))].into())].into(),
+    );
+
+    assert_eq!(
+      at_height(AddressOrOutPoint::OutPoint(outpoint0), 9),
+      [].into()
+    );
+
+    assert_eq!(
+      at_height(AddressOrOutPoint::OutPoint(outpoint1), 8),
+      [].into()
+    );
+
+    assert_eq!(
+      at_height(AddressOrOutPoint::OutPoint(outpoint1), 9),
+      [(outpoint1, [(spaced_rune, pile(600))].into())].into(),
+    );
+
+    let address = context.core.address(outpoint0);
+
+    assert_eq!(context.core.address(outpoint1), address);
+
+    assert_eq!(
+      at_height(AddressOrOutPoint::Address(address.clone()), 8),
+      [(outpoint0, [(spaced_rune, pile(1000))].into())].into(),
+    );
+
+    assert_eq!(
+      at_height(AddressOrOutPoint::Address(address), 9),
+      [
+        (outpoint1, [(spaced_rune, pile(600))].into()),
+        (outpoint2, [(spaced_rune, pile(400))].into()),
+      ]
+      .into(),
+    );
+  }
+
+  #[test]
+  fn rune_balances_at_height_require_rune_history_index() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    assert!(!context.index.has_rune_history_index());
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_balances_at_height(&AddressOrOutPoint::OutPoint(OutPoint::null()), 0)
+        .unwrap(),
+      None,
+    );
+  }
+
+  #[test]
+  fn rune_holders() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder()
+      .arg("--index-runes")
+      .arg("--index-rune-holders")
+      .build();
+
+    assert!(context.index.has_rune_holder_index());
+
+    let (txid0, id) = context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let script_pubkey = |txid, vout| {
+      context
+        .core
+        .tx_by_id(txid)
+        .output
+        .into_iter()
+        .nth(vout)
+        .unwrap()
+        .script_pubkey
+    };
+
+    assert_eq!(context.index.get_rune_holder_count(id).unwrap(), Some(1));
+
+    assert_eq!(
+      context.index.get_rune_holders(id, None, 10).unwrap(),
+      Some(Listing {
+        items: vec![(script_pubkey(txid0, 0), 1000)],
+        prev: None,
+        next: None,
+      }),
+    );
+
+    let txid1 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(8, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 500,
+            output: 1,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.get_rune_holder_count(id).unwrap(), Some(2));
+
+    // holders with equal balances are listed in script pubkey order
+    let mut holders = vec![
+      (script_pubkey(txid1, 0), 500),
+      (script_pubkey(txid1, 1), 500),
+    ];
+
+    holders.sort();
+
+    let cursor = |i: usize| (holders[i].1, holders[i].0.clone());
+
+    assert_eq!(
+      context.index.get_rune_holders(id, None, 10).unwrap(),
+      Some(Listing {
+        items: holders.clone(),
+        prev: None,
+        next: None,
+      }),
+    );
+
+    assert_eq!(
+      context.index.get_rune_holders(id, None, 1).unwrap(),
+      Some(Listing {
+        items: vec![holders[0].clone()],
+        prev: None,
+        next: Some(cursor(1)),
+      }),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_holders(id, Some(cursor(1)), 1)
+        .unwrap(),
+      Some(Listing {
+        items: vec![holders[1].clone()],
+        prev: Some(cursor(0)),
+        next: None,
+      }),
+    );
+
+    let txid2 = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(9, 1, 0, Witness::new()), (9, 1, 1, Witness::new())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.get_rune_holder_count(id).unwrap(), Some(1));
+
+    assert_eq!(
+      context.index.get_rune_holders(id, None, 10).unwrap(),
+      Some(Listing {
+        items: vec![(script_pubkey(txid2, 0), 1000)],
+        prev: None,
+        next: None,
+      }),
+    );
+  }
+
+  #[test]
+  fn rune_holders_require_rune_holder_index() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    assert!(!context.index.has_rune_holder_index());
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_holder_count(RuneId::default())
+        .unwrap(),
+      None
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_holders(RuneId::default(), None, 10)
+        .unwrap(),
+      None
+    );
+  }
+
   #[test]
   fn assert_schema_statistic_key_is_zero() {
     // other schema statistic keys may change when the schema changes, but for
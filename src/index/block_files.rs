@@ -0,0 +1,369 @@
+use {
+  super::*,
+  std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+  },
+};
+
+/// Reads blocks directly from Bitcoin Core's `blk*.dat` files. Files are
+/// scanned lazily in order, recording the location of each block by hash, so
+/// that blocks can be fetched in chain order using hashes from the header
+/// chain. Blocks which have not been written to disk yet, for example those
+/// near the tip, are not found, and must be fetched over RPC instead.
+pub(crate) struct BlockFiles {
+  dir: PathBuf,
+  file: u32,
+  key: [u8; 8],
+  locations: HashMap<BlockHash, Location>,
+  magic: [u8; 4],
+  offset: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Location {
+  file: u32,
+  offset: u64,
+  size: u64,
+}
+
+impl BlockFiles {
+  pub(crate) fn open(dir: PathBuf, network: Network) -> Result<Self> {
+    // Bitcoin Core 28.0 and later obfuscate block files with the key in
+    // `xor.dat`. Older versions do not write `xor.dat`, and do not obfuscate
+    // block files.
+    let key = match fs::read(dir.join("xor.dat")) {
+      Ok(key) => key
+        .try_into()
+        .map_err(|key: Vec<u8>| anyhow!("invalid xor.dat length: {}", key.len()))?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => [0; 8],
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(Self {
+      dir,
+      file: 0,
+      key,
+      locations: HashMap::new(),
+      magic: network.magic().to_bytes(),
+      offset: 0,
+    })
+  }
+
+  pub(crate) fn get_block(&mut self, hash: BlockHash, header_only: bool) -> Result<Option<Block>> {
+    if !self.locations.contains_key(&hash) && !self.scan(hash)? {
+      return Ok(None);
+    }
+
+    let location = self.locations.remove(&hash).unwrap();
+
+    let mut file = File::open(self.path(location.file))?;
+
+    let block = if header_only {
+      let header = self
+        .read(&mut file, location.offset, 80)?
+        .context("block file truncated")?;
+
+      Block {
+        header: consensus::encode::deserialize(&header)?,
+        txdata: Vec::new(),
+      }
+    } else {
+      let block = self
+        .read(
+          &mut file,
+          location.offset,
+          location.size.try_into().unwrap(),
+        )?
+        .context("block file truncated")?;
+
+      consensus::encode::deserialize(&block)?
+    };
+
+    ensure!(
+      block.block_hash() == hash,
+      "block at {}:{} has unexpected hash {}",
+      self.path(location.file).display(),
+      location.offset,
+      block.block_hash(),
+    );
+
+    Ok(Some(block))
+  }
+
+  /// Forget the locations of blocks which have been scanned but not yet read,
+  /// since they may have been orphaned by a reorg. Scanning resumes where it
+  /// left off, so blocks of the new chain which were already scanned are
+  /// fetched over RPC instead.
+  pub(crate) fn handle_reorg(&mut self) {
+    self.locations.clear();
+  }
+
+  fn path(&self, file: u32) -> PathBuf {
+    self.dir.join(format!("blk{file:05}.dat"))
+  }
+
+  fn read(&self, file: &mut File, offset: u64, len: usize) -> Result<Option<Vec<u8>>> {
+    file.seek(SeekFrom::Start(offset))?;
+
+    let mut buffer = vec![0; len];
+
+    match file.read_exact(&mut buffer) {
+      Ok(()) => {}
+      Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+      Err(err) => return Err(err.into()),
+    }
+
+    for (i, byte) in buffer.iter_mut().enumerate() {
+      *byte ^= self.key[usize::try_from((offset + u64::try_from(i).unwrap()) % 8).unwrap()];
+    }
+
+    Ok(Some(buffer))
+  }
+
+  /// Scan block files from where the last scan stopped, until `target` is
+  /// found or there are no more complete blocks.
+  fn scan(&mut self, target: BlockHash) -> Result<bool> {
+    loop {
+      let mut file = match File::open(self.path(self.file)) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err.into()),
+      };
+
+      let len = file.metadata()?.len();
+
+      // Bitcoin Core preallocates block files, so the end of the blocks in a
+      // file is marked by missing magic rather than the end of the file.
+      while let Some(prefix) = self.read(&mut file, self.offset, 8)? {
+        if prefix[..4] != self.magic {
+          break;
+        }
+
+        let size = u64::from(u32::from_le_bytes(prefix[4..].try_into().unwrap()));
+
+        let offset = self.offset + 8;
+
+        if offset + size > len {
+          break;
+        }
+
+        let header = self
+          .read(&mut file, offset, 80)?
+          .context("block file truncated")?;
+
+        let hash = consensus::encode::deserialize::<Header>(&header)?.block_hash();
+
+        self.locations.insert(
+          hash,
+          Location {
+            file: self.file,
+            offset,
+            size,
+          },
+        );
+
+        self.offset = offset + size;
+
+        if hash == target {
+          return Ok(true);
+        }
+      }
+
+      if !self.path(self.file + 1).try_exists()? {
+        return Ok(false);
+      }
+
+      self.file += 1;
+      self.offset = 0;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context, tempfile::TempDir};
+
+  fn write_block_files(dir: &Path, key: Option<[u8; 8]>, files: &[&[&Block]]) {
+    fs::create_dir_all(dir).unwrap();
+
+    if let Some(key) = key {
+      fs::write(dir.join("xor.dat"), key).unwrap();
+    }
+
+    for (i, blocks) in files.iter().enumerate() {
+      let mut contents = Vec::new();
+
+      for block in *blocks {
+        let block = consensus::encode::serialize(block);
+        contents.extend(Network::Regtest.magic().to_bytes());
+        contents.extend(u32::try_from(block.len()).unwrap().to_le_bytes());
+        contents.extend(block);
+      }
+
+      // preallocated space
+      contents.extend([0; 64]);
+
+      if let Some(key) = key {
+        for (i, byte) in contents.iter_mut().enumerate() {
+          *byte ^= key[i % 8];
+        }
+      }
+
+      fs::write(dir.join(format!("blk{i:05}.dat")), contents).unwrap();
+    }
+  }
+
+  #[test]
+  fn blocks_are_read_out_of_order_across_files() {
+    let context = Context::builder().build();
+
+    let blocks = context.mine_blocks_with_update(3, false);
+
+    let dir = context.tempdir.path().join("blocks");
+
+    write_block_files(&dir, None, &[&[&blocks[1], &blocks[0]], &[&blocks[2]]]);
+
+    let mut block_files = BlockFiles::open(dir, Network::Regtest).unwrap();
+
+    for block in &blocks {
+      assert_eq!(
+        block_files.get_block(block.block_hash(), false).unwrap(),
+        Some(block.clone()),
+      );
+    }
+  }
+
+  #[test]
+  fn obfuscated_blocks_are_read() {
+    let context = Context::builder().build();
+
+    let blocks = context.mine_blocks_with_update(2, false);
+
+    let dir = context.tempdir.path().join("blocks");
+
+    write_block_files(
+      &dir,
+      Some([1, 2, 3, 4, 5, 6, 7, 8]),
+      &[&[&blocks[0], &blocks[1]]],
+    );
+
+    let mut block_files = BlockFiles::open(dir, Network::Regtest).unwrap();
+
+    assert_eq!(
+      block_files
+        .get_block(blocks[1].block_hash(), false)
+        .unwrap(),
+      Some(blocks[1].clone()),
+    );
+
+    assert_eq!(
+      block_files.get_block(blocks[0].block_hash(), true).unwrap(),
+      Some(Block {
+        header: blocks[0].header,
+        txdata: Vec::new(),
+      }),
+    );
+  }
+
+  #[test]
+  fn missing_blocks_are_not_found() {
+    let context = Context::builder().build();
+
+    let blocks = context.mine_blocks_with_update(2, false);
+
+    let dir = context.tempdir.path().join("blocks");
+
+    write_block_files(&dir, None, &[&[&blocks[0]]]);
+
+    let mut block_files = BlockFiles::open(dir.clone(), Network::Regtest).unwrap();
+
+    assert_eq!(
+      block_files
+        .get_block(blocks[1].block_hash(), false)
+        .unwrap(),
+      None,
+    );
+
+    write_block_files(&dir, None, &[&[&blocks[0]], &[&blocks[1]]]);
+
+    assert_eq!(
+      block_files
+        .get_block(blocks[1].block_hash(), false)
+        .unwrap(),
+      Some(blocks[1].clone()),
+    );
+  }
+
+  #[test]
+  fn reorg_drops_scanned_block_locations() {
+    let context = Context::builder().build();
+
+    let blocks = context.mine_blocks_with_update(2, false);
+
+    let dir = context.tempdir.path().join("blocks");
+
+    write_block_files(&dir, None, &[&[&blocks[0], &blocks[1]]]);
+
+    let mut block_files = BlockFiles::open(dir, Network::Regtest).unwrap();
+
+    assert_eq!(
+      block_files
+        .get_block(blocks[1].block_hash(), false)
+        .unwrap(),
+      Some(blocks[1].clone()),
+    );
+
+    assert_eq!(block_files.locations.len(), 1);
+
+    block_files.handle_reorg();
+
+    assert!(block_files.locations.is_empty());
+
+    assert_eq!(
+      block_files
+        .get_block(blocks[0].block_hash(), false)
+        .unwrap(),
+      None,
+    );
+  }
+
+  #[test]
+  fn index_can_be_built_from_block_files() {
+    let tempdir = TempDir::new().unwrap();
+
+    let context = Context::builder()
+      .arg("--read-block-files")
+      .arg("--bitcoin-data-dir")
+      .arg(tempdir.path())
+      .build();
+
+    let mut blocks = vec![context.core.mine_blocks(1)[0].clone()];
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    blocks.extend(context.core.mine_blocks(1));
+
+    write_block_files(
+      &tempdir.path().join("regtest/blocks"),
+      Some([0xff; 8]),
+      &[&blocks.iter().collect::<Vec<&Block>>()],
+    );
+
+    let get_block_calls = context.core.state().get_block_calls;
+
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 3);
+
+    assert_eq!(context.core.state().get_block_calls, get_block_calls);
+
+    assert!(context
+      .index
+      .inscription_exists(InscriptionId { txid, index: 0 })
+      .unwrap());
+  }
+}
@@ -0,0 +1,108 @@
+use {super::*, esplora::Esplora};
+
+/// Source of blocks and transactions, either Bitcoin Core's JSON-RPC API or an
+/// Esplora-compatible REST API. Lookups of things that do not exist return
+/// `Ok(None)`.
+pub(crate) trait ChainSource: Send + Sync {
+  fn block(&self, hash: BlockHash) -> Result<Option<Block>>;
+
+  /// Height of the chain tip
+  fn block_count(&self) -> Result<u64>;
+
+  fn block_hash(&self, height: u64) -> Result<Option<BlockHash>>;
+
+  fn block_header(&self, hash: BlockHash) -> Result<Option<Header>>;
+
+  /// Only available from Bitcoin Core
+  fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>>;
+
+  /// Only available from Bitcoin Core
+  fn block_stats(&self, height: u64) -> Result<Option<GetBlockStatsResult>>;
+
+  /// Height of the best known header, which may be ahead of the tip during
+  /// initial block download
+  fn header_count(&self) -> Result<u64>;
+
+  fn mempool_txids(&self) -> Result<Vec<Txid>>;
+
+  fn output_unspent(&self, outpoint: OutPoint) -> Result<bool>;
+
+  fn transaction(&self, txid: Txid) -> Result<Option<Transaction>>;
+
+  /// Transaction `txid` and the height of the block containing it, or `None`
+  /// if it is unconfirmed
+  fn transaction_with_height(&self, txid: Txid) -> Result<Option<(Transaction, Option<u32>)>>;
+}
+
+pub(crate) fn open(settings: &Settings) -> Result<Box<dyn ChainSource>> {
+  Ok(match settings.esplora_url() {
+    Some(url) => Box::new(Esplora::new(
+      url,
+      settings.chain(),
+      settings.esplora_concurrency(),
+    )?),
+    None => Box::new(settings.bitcoin_rpc_client(None)?),
+  })
+}
+
+impl ChainSource for Client {
+  fn block(&self, hash: BlockHash) -> Result<Option<Block>> {
+    self.get_block(&hash).into_option()
+  }
+
+  fn block_count(&self) -> Result<u64> {
+    Ok(self.get_block_count()?)
+  }
+
+  fn block_hash(&self, height: u64) -> Result<Option<BlockHash>> {
+    self.get_block_hash(height).into_option()
+  }
+
+  fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
+    self.get_block_header(&hash).into_option()
+  }
+
+  fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
+    self.get_block_header_info(&hash).into_option()
+  }
+
+  fn block_stats(&self, height: u64) -> Result<Option<GetBlockStatsResult>> {
+    self.get_block_stats(height).into_option()
+  }
+
+  fn header_count(&self) -> Result<u64> {
+    Ok(self.get_blockchain_info()?.headers)
+  }
+
+  fn mempool_txids(&self) -> Result<Vec<Txid>> {
+    Ok(self.get_raw_mempool()?)
+  }
+
+  fn output_unspent(&self, outpoint: OutPoint) -> Result<bool> {
+    Ok(
+      self
+        .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+        .is_some(),
+    )
+  }
+
+  fn transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    self.get_raw_transaction(&txid, None).into_option()
+  }
+
+  fn transaction_with_height(&self, txid: Txid) -> Result<Option<(Transaction, Option<u32>)>> {
+    let Some(info) = self.get_raw_transaction_info(&txid, None).into_option()? else {
+      return Ok(None);
+    };
+
+    let height = match info.blockhash {
+      Some(block_hash) => self
+        .get_block_header_info(&block_hash)
+        .into_option()?
+        .map(|info| u32::try_from(info.height).unwrap()),
+      None => None,
+    };
+
+    Ok(Some((info.transaction()?, height)))
+  }
+}
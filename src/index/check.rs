@@ -0,0 +1,507 @@
+use {
+  super::*,
+  redb::ReadTransaction,
+  std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    io::{BufWriter, Seek, SeekFrom, Write},
+  },
+};
+
+/// Number of sat ranges sorted in memory at once, 64 MiB worth
+const SAT_RANGE_RUN_LEN: usize = 1 << 22;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "violation")]
+pub enum Violation {
+  BlockHash {
+    height: u32,
+    index: BlockHash,
+    core: Option<BlockHash>,
+  },
+  InscriptionLocation {
+    inscription: InscriptionId,
+    satpoint: SatPoint,
+  },
+  Output {
+    outpoint: OutPoint,
+    index: u64,
+    core: Option<u64>,
+  },
+  OverlappingSatRanges {
+    first: (u64, u64),
+    second: (u64, u64),
+  },
+  RuneSupply {
+    rune: SpacedRune,
+    supply: u128,
+    outstanding: u128,
+    burned: u128,
+  },
+  SatSupply {
+    expected: u64,
+    actual: u64,
+  },
+  Statistic {
+    statistic: String,
+    counter: u64,
+    actual: u64,
+  },
+}
+
+impl Index {
+  /// Verify invariants that should hold between the tables of the index. If
+  /// `spot_check` is given, that many blocks and outputs, evenly spaced, are
+  /// also compared against the chain source.
+  pub fn check(&self, spot_check: Option<usize>) -> Result<Vec<Violation>> {
+    let rtx = self.database.begin_read()?;
+
+    let statistic_to_count = rtx.open_table(STATISTIC_TO_COUNT)?;
+
+    let statistic = |statistic: Statistic| -> Result<u64> {
+      Ok(
+        statistic_to_count
+          .get(&statistic.key())?
+          .map(|count| count.value())
+          .unwrap_or_default(),
+      )
+    };
+
+    let mut counters = Vec::new();
+
+    if self.index_inscriptions {
+      let inscriptions =
+        statistic(Statistic::BlessedInscriptions)? + statistic(Statistic::CursedInscriptions)?;
+
+      let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+      counters.push((
+        "inscription entries",
+        inscriptions,
+        sequence_number_to_entry.len()?,
+      ));
+
+      counters.push((
+        "inscription ids",
+        inscriptions,
+        rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?.len()?,
+      ));
+
+      counters.push((
+        "inscription numbers",
+        inscriptions,
+        rtx
+          .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+          .len()?,
+      ));
+
+      let mut unbound = 0;
+      for result in sequence_number_to_entry.iter()? {
+        if Charm::Unbound.is_set(InscriptionEntry::load(result?.1.value()).charms) {
+          unbound += 1;
+        }
+      }
+
+      counters.push((
+        "unbound inscriptions",
+        statistic(Statistic::UnboundInscriptions)?,
+        unbound,
+      ));
+    }
+
+    if self.index_runes {
+      let runes = statistic(Statistic::Runes)?;
+
+      counters.push((
+        "rune entries",
+        runes,
+        rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.len()?,
+      ));
+
+      counters.push(("rune ids", runes, rtx.open_table(RUNE_TO_RUNE_ID)?.len()?));
+    }
+
+    let mut violations = counters
+      .into_iter()
+      .filter(|(_, counter, actual)| counter != actual)
+      .map(|(statistic, counter, actual)| Violation::Statistic {
+        statistic: statistic.into(),
+        counter,
+        actual,
+      })
+      .collect::<Vec<Violation>>();
+
+    if self.index_sats {
+      let lost_sats = statistic(Statistic::LostSats)?;
+      violations.extend(self.check_sat_ranges(&rtx, lost_sats)?);
+    }
+
+    if self.index_runes {
+      violations.extend(self.check_rune_supply(&rtx)?);
+    }
+
+    if self.index_inscriptions {
+      violations.extend(self.check_inscription_locations(&rtx)?);
+    }
+
+    if let Some(spot_check) = spot_check {
+      violations.extend(self.spot_check(&rtx, spot_check)?);
+    }
+
+    Ok(violations)
+  }
+
+  fn check_inscription_locations(&self, rtx: &ReadTransaction) -> Result<Vec<Violation>> {
+    log::info!("Checking inscription locations…");
+
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut violations = Vec::new();
+
+    for result in rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?.iter()? {
+      let (sequence_number, satpoint) = result?;
+
+      let satpoint = SatPoint::load(*satpoint.value());
+
+      if Self::is_special_outpoint(satpoint.outpoint)
+        || outpoint_to_utxo_entry
+          .get(&satpoint.outpoint.store())?
+          .is_some()
+      {
+        continue;
+      }
+
+      let inscription = sequence_number_to_entry
+        .get(sequence_number.value())?
+        .map(|entry| InscriptionEntry::load(entry.value()).id)
+        .ok_or_else(|| anyhow!("no entry for sequence number {}", sequence_number.value()))?;
+
+      violations.push(Violation::InscriptionLocation {
+        inscription,
+        satpoint,
+      });
+    }
+
+    Ok(violations)
+  }
+
+  fn check_rune_supply(&self, rtx: &ReadTransaction) -> Result<Vec<Violation>> {
+    log::info!("Checking rune supply…");
+
+    let mut outstanding = HashMap::<RuneId, u128>::new();
+
+    for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+      let (_, balances) = result?;
+      let balances = balances.value();
+
+      let mut i = 0;
+      while i < balances.len() {
+        let ((id, amount), length) = Self::decode_rune_balance(&balances[i..])?;
+        i += length;
+        *outstanding.entry(id).or_default() += amount;
+      }
+    }
+
+    let mut violations = Vec::new();
+
+    for result in rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.iter()? {
+      let (id, entry) = result?;
+
+      let entry = RuneEntry::load(entry.value());
+
+      let outstanding = outstanding
+        .get(&RuneId::load(id.value()))
+        .copied()
+        .unwrap_or_default();
+
+      if outstanding.checked_add(entry.burned) != Some(entry.supply()) {
+        violations.push(Violation::RuneSupply {
+          rune: entry.spaced_rune,
+          supply: entry.supply(),
+          outstanding,
+          burned: entry.burned,
+        });
+      }
+    }
+
+    Ok(violations)
+  }
+
+  fn check_sat_ranges(&self, rtx: &ReadTransaction, lost_sats: u64) -> Result<Vec<Violation>> {
+    log::info!("Checking sat ranges…");
+
+    let mut ranges = SatRangeSorter::new(SAT_RANGE_RUN_LEN);
+    let mut lost = 0;
+    let mut unspent = 0;
+
+    for result in rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?.iter()? {
+      let (outpoint, entry) = result?;
+
+      let outpoint = OutPoint::load(*outpoint.value());
+
+      if outpoint == unbound_outpoint() {
+        continue;
+      }
+
+      for chunk in entry.value().parse(self).sat_ranges().chunks_exact(11) {
+        let range = SatRange::load(chunk.try_into().unwrap());
+
+        if outpoint == OutPoint::null() {
+          lost += range.1 - range.0;
+        } else {
+          unspent += range.1 - range.0;
+        }
+
+        ranges.push(range)?;
+      }
+    }
+
+    let mut violations = Vec::new();
+    let mut previous = None::<SatRange>;
+
+    ranges.for_each(|range| {
+      if let Some(previous) = previous {
+        if previous.1 > range.0 {
+          violations.push(Violation::OverlappingSatRanges {
+            first: previous,
+            second: range,
+          });
+        }
+      }
+
+      previous = Some(range);
+    })?;
+
+    let block_count = self.block_count()?;
+
+    // the coinbase outputs of blocks 91722 and 91812 were overwritten by
+    // coinbase transactions with the same txids in blocks 91880 and 91842
+    let overwritten = if self.settings.chain() == Chain::Mainnet {
+      [91842, 91880]
+        .into_iter()
+        .filter(|height| *height < block_count)
+        .map(|height| Height(height).subsidy())
+        .sum()
+    } else {
+      0
+    };
+
+    let supply = Height(block_count).starting_sat().n() - overwritten;
+
+    if unspent != supply.saturating_sub(lost_sats) {
+      violations.push(Violation::SatSupply {
+        expected: supply.saturating_sub(lost_sats),
+        actual: unspent,
+      });
+    }
+
+    if lost != lost_sats {
+      violations.push(Violation::Statistic {
+        statistic: "lost sats".into(),
+        counter: lost_sats,
+        actual: lost,
+      });
+    }
+
+    Ok(violations)
+  }
+
+  fn spot_check(&self, rtx: &ReadTransaction, samples: usize) -> Result<Vec<Violation>> {
+    log::info!("Spot checking {samples} blocks and outputs against chain source…");
+
+    let mut violations = Vec::new();
+
+    if samples == 0 {
+      return Ok(violations);
+    }
+
+    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+
+    let step = usize::try_from(height_to_block_header.len()?).unwrap() / samples;
+
+    for result in height_to_block_header
+      .iter()?
+      .step_by(step.max(1))
+      .take(samples)
+    {
+      let (height, header) = result?;
+
+      let height = height.value();
+      let index = Header::load(*header.value()).block_hash();
+      let core = self.client.block_hash(height.into())?;
+
+      if core != Some(index) {
+        violations.push(Violation::BlockHash {
+          height,
+          index,
+          core,
+        });
+      }
+    }
+
+    let outpoint_to_utxo_entry = rtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+
+    let step = usize::try_from(outpoint_to_utxo_entry.len()?).unwrap() / samples;
+
+    for result in outpoint_to_utxo_entry
+      .iter()?
+      .filter(|result| {
+        // the genesis coinbase transaction cannot be fetched from the chain source
+        result
+          .as_ref()
+          .map(|(outpoint, _)| {
+            let outpoint = OutPoint::load(*outpoint.value());
+            !Self::is_special_outpoint(outpoint)
+              && outpoint.txid != self.genesis_block_coinbase_txid
+          })
+          .unwrap_or(true)
+      })
+      .step_by(step.max(1))
+      .take(samples)
+    {
+      let (outpoint, entry) = result?;
+
+      let outpoint = OutPoint::load(*outpoint.value());
+      let index = entry.value().parse(self).total_value();
+
+      let core = self.client.transaction(outpoint.txid)?.and_then(|tx| {
+        tx.output
+          .get(usize::try_from(outpoint.vout).unwrap())
+          .map(|output| output.value.to_sat())
+      });
+
+      if core != Some(index) {
+        violations.push(Violation::Output {
+          outpoint,
+          index,
+          core,
+        });
+      }
+    }
+
+    Ok(violations)
+  }
+}
+
+/// Sorts sat ranges without holding them all in memory. Ranges are sorted in
+/// runs of bounded length, which are written to temporary files and merged.
+struct SatRangeSorter {
+  run: Vec<SatRange>,
+  run_len: usize,
+  runs: Vec<BufReader<File>>,
+}
+
+impl SatRangeSorter {
+  fn new(run_len: usize) -> Self {
+    Self {
+      run: Vec::new(),
+      run_len,
+      runs: Vec::new(),
+    }
+  }
+
+  fn push(&mut self, range: SatRange) -> Result {
+    self.run.push(range);
+
+    if self.run.len() == self.run_len {
+      self.spill()?;
+    }
+
+    Ok(())
+  }
+
+  fn spill(&mut self) -> Result {
+    self.run.sort_unstable();
+
+    let mut file = tempfile::tempfile()?;
+
+    let mut writer = BufWriter::new(&mut file);
+
+    for (start, end) in self.run.drain(..) {
+      writer.write_all(&start.to_le_bytes())?;
+      writer.write_all(&end.to_le_bytes())?;
+    }
+
+    writer.flush()?;
+    drop(writer);
+
+    file.seek(SeekFrom::Start(0))?;
+
+    self.runs.push(BufReader::new(file));
+
+    Ok(())
+  }
+
+  fn read(run: &mut BufReader<File>) -> Result<Option<SatRange>> {
+    let mut buffer = [0; 16];
+
+    match run.read_exact(&mut buffer) {
+      Ok(()) => Ok(Some((
+        u64::from_le_bytes(buffer[..8].try_into().unwrap()),
+        u64::from_le_bytes(buffer[8..].try_into().unwrap()),
+      ))),
+      Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  /// Call `f` with each range, in ascending order
+  fn for_each(mut self, mut f: impl FnMut(SatRange)) -> Result {
+    if self.runs.is_empty() {
+      self.run.sort_unstable();
+      self.run.into_iter().for_each(f);
+      return Ok(());
+    }
+
+    if !self.run.is_empty() {
+      self.spill()?;
+    }
+
+    let mut heap = BinaryHeap::new();
+
+    for (i, run) in self.runs.iter_mut().enumerate() {
+      if let Some(range) = Self::read(run)? {
+        heap.push(Reverse((range, i)));
+      }
+    }
+
+    while let Some(Reverse((range, i))) = heap.pop() {
+      f(range);
+
+      if let Some(range) = Self::read(&mut self.runs[i])? {
+        heap.push(Reverse((range, i)));
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sat_ranges_are_sorted_across_runs() {
+    let ranges = [(5, 6), (0, 1), (9, 10), (3, 4), (1, 2), (8, 9), (2, 3)];
+
+    for run_len in [1, 2, 3, 100] {
+      let mut sorter = SatRangeSorter::new(run_len);
+
+      for range in ranges {
+        sorter.push(range).unwrap();
+      }
+
+      let mut sorted = Vec::new();
+
+      sorter.for_each(|range| sorted.push(range)).unwrap();
+
+      assert_eq!(
+        sorted,
+        [(0, 1), (1, 2), (2, 3), (3, 4), (5, 6), (8, 9), (9, 10)],
+        "run length {run_len}",
+      );
+    }
+  }
+}
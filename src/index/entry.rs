@@ -357,6 +357,47 @@ impl Entry for InscriptionEntry {
   }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct InscriptionHistoryEntry {
+  pub(crate) height: u32,
+  pub(crate) new_satpoint: SatPoint,
+  pub(crate) old_satpoint: Option<SatPoint>,
+  pub(crate) script_pubkey: Option<ScriptBuf>,
+  pub(crate) txid: Txid,
+}
+
+pub(crate) type InscriptionHistoryEntryValue = (
+  u32,                   // height
+  SatPointValue,         // new satpoint
+  Option<SatPointValue>, // old satpoint
+  Option<Vec<u8>>,       // script pubkey
+  TxidValue,             // txid
+);
+
+impl Entry for InscriptionHistoryEntry {
+  type Value = InscriptionHistoryEntryValue;
+
+  fn load((height, new_satpoint, old_satpoint, script_pubkey, txid): Self::Value) -> Self {
+    Self {
+      height,
+      new_satpoint: SatPoint::load(new_satpoint),
+      old_satpoint: old_satpoint.map(SatPoint::load),
+      script_pubkey: script_pubkey.map(ScriptBuf::from_bytes),
+      txid: Txid::load(txid),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.height,
+      self.new_satpoint.store(),
+      self.old_satpoint.map(SatPoint::store),
+      self.script_pubkey.map(ScriptBuf::into_bytes),
+      self.txid.store(),
+    )
+  }
+}
+
 pub(crate) type InscriptionIdValue = (u128, u128, u32);
 
 impl Entry for InscriptionId {
@@ -492,6 +533,39 @@ impl Entry for Txid {
 mod tests {
   use super::*;
 
+  #[test]
+  fn inscription_history_entry() {
+    let entry = InscriptionHistoryEntry {
+      height: 1,
+      new_satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: txid(2),
+          vout: 3,
+        },
+        offset: 4,
+      },
+      old_satpoint: Some(SatPoint {
+        outpoint: OutPoint {
+          txid: txid(5),
+          vout: 6,
+        },
+        offset: 7,
+      }),
+      script_pubkey: Some(ScriptBuf::from_bytes(vec![8, 9])),
+      txid: txid(2),
+    };
+
+    assert_eq!(InscriptionHistoryEntry::load(entry.clone().store()), entry);
+
+    let entry = InscriptionHistoryEntry {
+      old_satpoint: None,
+      script_pubkey: None,
+      ..entry
+    };
+
+    assert_eq!(InscriptionHistoryEntry::load(entry.clone().store()), entry);
+  }
+
   #[test]
   fn inscription_entry() {
     let id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdefi0"
@@ -0,0 +1,527 @@
+use {
+  super::*,
+  bitcoincore_rpc::json::FeeRatePercentiles,
+  fetcher::TransactionFetcher,
+  futures::{stream, StreamExt, TryStreamExt},
+  reqwest::{blocking::Response, StatusCode},
+  serde::de::DeserializeOwned,
+};
+
+/// Client for an Esplora-compatible REST API, such as the ones served by
+/// electrs and mempool.space.
+pub(crate) struct Esplora {
+  blocking: reqwest::blocking::Client,
+  client: reqwest::Client,
+  concurrency: usize,
+  url: String,
+}
+
+/// Block summary, as returned by `/block/<HASH>`
+#[derive(Deserialize)]
+struct BlockInfo {
+  bits: u32,
+  difficulty: f64,
+  height: u32,
+  id: BlockHash,
+  mediantime: u64,
+  merkle_root: TxMerkleNode,
+  nonce: u32,
+  previousblockhash: Option<BlockHash>,
+  size: usize,
+  timestamp: u64,
+  tx_count: usize,
+  version: i32,
+  weight: usize,
+}
+
+#[derive(Deserialize)]
+struct BlockStatus {
+  in_best_chain: bool,
+  next_best: Option<BlockHash>,
+}
+
+#[derive(Deserialize)]
+struct OutputStatus {
+  spent: bool,
+}
+
+/// Transaction with its confirmation status, as returned by `/tx/<TXID>`, so
+/// that both can be fetched with a single request
+#[derive(Deserialize)]
+struct TransactionInfo {
+  locktime: u32,
+  status: TransactionStatus,
+  version: i32,
+  vin: Vec<TransactionInput>,
+  vout: Vec<TransactionOutput>,
+}
+
+#[derive(Deserialize)]
+struct TransactionInput {
+  scriptsig: ScriptBuf,
+  sequence: u32,
+  txid: Txid,
+  vout: u32,
+  #[serde(default)]
+  witness: Witness,
+}
+
+#[derive(Deserialize)]
+struct TransactionOutput {
+  scriptpubkey: ScriptBuf,
+  value: u64,
+}
+
+#[derive(Deserialize)]
+struct TransactionStatus {
+  block_height: Option<u32>,
+}
+
+impl From<TransactionInfo> for Transaction {
+  fn from(info: TransactionInfo) -> Self {
+    Self {
+      version: Version(info.version),
+      lock_time: LockTime::from_consensus(info.locktime),
+      input: info
+        .vin
+        .into_iter()
+        .map(|input| TxIn {
+          previous_output: OutPoint::new(input.txid, input.vout),
+          script_sig: input.scriptsig,
+          sequence: Sequence(input.sequence),
+          witness: input.witness,
+        })
+        .collect(),
+      output: info
+        .vout
+        .into_iter()
+        .map(|output| TxOut {
+          script_pubkey: output.scriptpubkey,
+          value: Amount::from_sat(output.value),
+        })
+        .collect(),
+    }
+  }
+}
+
+impl Esplora {
+  pub(crate) fn new(url: &str, chain: Chain, concurrency: usize) -> Result<Self> {
+    ensure!(concurrency > 0, "Esplora concurrency must be at least 1");
+
+    let esplora = Self {
+      blocking: reqwest::blocking::Client::new(),
+      client: reqwest::Client::new(),
+      concurrency,
+      url: url.trim_end_matches('/').into(),
+    };
+
+    let genesis_block_hash = esplora
+      .block_hash(0)
+      .with_context(|| format!("failed to connect to Esplora API at `{url}`"))?
+      .with_context(|| format!("Esplora API at `{url}` has no genesis block"))?;
+
+    ensure!(
+      genesis_block_hash == chain.genesis_block().block_hash(),
+      "Esplora API at `{url}` is not on {chain}",
+    );
+
+    Ok(esplora)
+  }
+
+  async fn try_get_transaction(&self, txid: Txid) -> Result<Transaction> {
+    let bytes = self
+      .client
+      .get(format!("{}/tx/{txid}/raw", self.url))
+      .send()
+      .await?
+      .error_for_status()?
+      .bytes()
+      .await?;
+
+    Ok(consensus::encode::deserialize(&bytes)?)
+  }
+
+  async fn get_transaction(&self, txid: Txid) -> Result<Transaction> {
+    let mut retries = 0;
+
+    loop {
+      match self.try_get_transaction(txid).await {
+        Ok(transaction) => return Ok(transaction),
+        Err(error) => {
+          if retries >= 5 {
+            return Err(anyhow!(
+              "failed to fetch transaction {txid} after 5 retries: {error}"
+            ));
+          }
+
+          log::info!("failed to fetch transaction {txid}, retrying: {error}");
+
+          tokio::time::sleep(Duration::from_millis(100 * u64::pow(2, retries))).await;
+          retries += 1;
+        }
+      }
+    }
+  }
+
+  fn get(&self, path: &str) -> Result<Option<Response>> {
+    let response = self.blocking.get(format!("{}{path}", self.url)).send()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    Ok(Some(response.error_for_status()?))
+  }
+
+  fn get_consensus<T: consensus::Decodable>(&self, path: &str) -> Result<Option<T>> {
+    self
+      .get(path)?
+      .map(|response| Ok(consensus::encode::deserialize(&response.bytes()?)?))
+      .transpose()
+  }
+
+  fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+    self
+      .get(path)?
+      .map(|response| Ok(response.json()?))
+      .transpose()
+  }
+
+  fn get_text<T: FromStr>(&self, path: &str) -> Result<Option<T>>
+  where
+    T::Err: std::error::Error + Send + Sync + 'static,
+  {
+    self
+      .get(path)?
+      .map(|response| Ok(response.text()?.trim().parse()?))
+      .transpose()
+  }
+}
+
+impl ChainSource for Esplora {
+  fn block(&self, hash: BlockHash) -> Result<Option<Block>> {
+    self.get_consensus(&format!("/block/{hash}/raw"))
+  }
+
+  fn block_count(&self) -> Result<u64> {
+    self
+      .get_text("/blocks/tip/height")?
+      .context("Esplora API returned no tip height")
+  }
+
+  fn block_hash(&self, height: u64) -> Result<Option<BlockHash>> {
+    self.get_text(&format!("/block-height/{height}"))
+  }
+
+  fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
+    self
+      .get_text::<String>(&format!("/block/{hash}/header"))?
+      .map(|header| Ok(consensus::encode::deserialize_hex(&header)?))
+      .transpose()
+  }
+
+  fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
+    let Some(info) = self.get_json::<BlockInfo>(&format!("/block/{hash}"))? else {
+      return Ok(None);
+    };
+
+    let status = self
+      .get_json::<BlockStatus>(&format!("/block/{hash}/status"))?
+      .with_context(|| format!("Esplora API returned no status for block {hash}"))?;
+
+    let confirmations = if status.in_best_chain {
+      (self.block_count()? + 1 - u64::from(info.height)).try_into()?
+    } else {
+      -1
+    };
+
+    Ok(Some(GetBlockHeaderResult {
+      bits: format!("{:08x}", info.bits),
+      // Esplora does not report cumulative chain work
+      chainwork: vec![0; 32],
+      confirmations,
+      difficulty: info.difficulty,
+      hash: info.id,
+      height: info.height.try_into().unwrap(),
+      median_time: Some(info.mediantime.try_into().unwrap()),
+      merkle_root: info.merkle_root,
+      n_tx: info.tx_count,
+      next_block_hash: status.next_best,
+      nonce: info.nonce,
+      previous_block_hash: info.previousblockhash,
+      time: info.timestamp.try_into().unwrap(),
+      version: bitcoin::block::Version::from_consensus(info.version),
+      version_hex: Some(info.version.to_be_bytes().to_vec()),
+    }))
+  }
+
+  /// Esplora only reports block totals, so fee and per-transaction statistics
+  /// are zero.
+  fn block_stats(&self, height: u64) -> Result<Option<GetBlockStatsResult>> {
+    let Some(hash) = self.block_hash(height)? else {
+      return Ok(None);
+    };
+
+    let Some(info) = self.get_json::<BlockInfo>(&format!("/block/{hash}"))? else {
+      return Ok(None);
+    };
+
+    Ok(Some(GetBlockStatsResult {
+      avg_fee: Amount::ZERO,
+      avg_fee_rate: Amount::ZERO,
+      avg_tx_size: 0,
+      block_hash: info.id,
+      fee_rate_percentiles: FeeRatePercentiles {
+        fr_10th: Amount::ZERO,
+        fr_25th: Amount::ZERO,
+        fr_50th: Amount::ZERO,
+        fr_75th: Amount::ZERO,
+        fr_90th: Amount::ZERO,
+      },
+      height,
+      ins: 0,
+      max_fee: Amount::ZERO,
+      max_fee_rate: Amount::ZERO,
+      max_tx_size: 0,
+      median_fee: Amount::ZERO,
+      median_time: info.mediantime,
+      median_tx_size: 0,
+      min_fee: Amount::ZERO,
+      min_fee_rate: Amount::ZERO,
+      min_tx_size: 0,
+      outs: 0,
+      subsidy: Amount::from_sat(Height(info.height).subsidy()),
+      sw_total_size: 0,
+      sw_total_weight: 0,
+      sw_txs: 0,
+      time: info.timestamp,
+      total_out: Amount::ZERO,
+      total_size: info.size,
+      total_weight: info.weight,
+      total_fee: Amount::ZERO,
+      txs: info.tx_count,
+      utxo_increase: 0,
+      utxo_size_inc: 0,
+    }))
+  }
+
+  fn header_count(&self) -> Result<u64> {
+    self.block_count()
+  }
+
+  fn mempool_txids(&self) -> Result<Vec<Txid>> {
+    Ok(self.get_json("/mempool/txids")?.unwrap_or_default())
+  }
+
+  fn output_unspent(&self, outpoint: OutPoint) -> Result<bool> {
+    Ok(
+      self
+        .get_json::<OutputStatus>(&format!("/tx/{}/outspend/{}", outpoint.txid, outpoint.vout))?
+        .is_some_and(|status| !status.spent),
+    )
+  }
+
+  fn transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    self.get_consensus(&format!("/tx/{txid}/raw"))
+  }
+
+  fn transaction_with_height(&self, txid: Txid) -> Result<Option<(Transaction, Option<u32>)>> {
+    Ok(
+      self
+        .get_json::<TransactionInfo>(&format!("/tx/{txid}"))?
+        .map(|info| {
+          let height = info.status.block_height;
+          (info.into(), height)
+        }),
+    )
+  }
+}
+
+impl TransactionFetcher for Esplora {
+  async fn get_transactions(&self, txids: Vec<Txid>) -> Result<Vec<Transaction>> {
+    stream::iter(txids)
+      .map(|txid| self.get_transaction(txid))
+      .buffered(self.concurrency)
+      .try_collect()
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn missing_items_are_not_found() {
+    let core = mockcore::builder().network(Network::Regtest).build();
+
+    let esplora = Esplora::new(&core.esplora_url(), Chain::Regtest, 8).unwrap();
+
+    assert_eq!(esplora.block_count().unwrap(), 0);
+    assert_eq!(esplora.block_hash(1).unwrap(), None);
+    assert_eq!(esplora.block(BlockHash::all_zeros()).unwrap(), None);
+    assert_eq!(
+      esplora.block_header_info(BlockHash::all_zeros()).unwrap(),
+      None
+    );
+    assert_eq!(esplora.block_stats(1).unwrap(), None);
+    assert_eq!(esplora.transaction(Txid::all_zeros()).unwrap(), None);
+    assert_eq!(
+      esplora.transaction_with_height(Txid::all_zeros()).unwrap(),
+      None
+    );
+    assert!(!esplora
+      .output_unspent(OutPoint::new(Txid::all_zeros(), 0))
+      .unwrap());
+  }
+
+  #[test]
+  fn transactions_are_fetched_with_retries() {
+    let core = mockcore::builder().network(Network::Regtest).build();
+
+    let transactions = core
+      .mine_blocks(4)
+      .into_iter()
+      .map(|block| block.txdata[0].clone())
+      .collect::<Vec<Transaction>>();
+
+    let esplora = Esplora::new(&core.esplora_url(), Chain::Regtest, 2).unwrap();
+
+    core.state().esplora_failures = 3;
+
+    let fetched = tokio::runtime::Builder::new_current_thread()
+      .enable_all()
+      .build()
+      .unwrap()
+      .block_on(
+        esplora.get_transactions(transactions.iter().map(Transaction::compute_txid).collect()),
+      )
+      .unwrap();
+
+    assert_eq!(fetched, transactions);
+    assert_eq!(core.state().esplora_failures, 0);
+  }
+
+  #[test]
+  fn transactions_are_fetched_with_height() {
+    let core = mockcore::builder().network(Network::Regtest).build();
+
+    core.mine_blocks(1);
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    let esplora = Esplora::new(&core.esplora_url(), Chain::Regtest, 8).unwrap();
+
+    assert_eq!(
+      esplora.transaction_with_height(txid).unwrap(),
+      Some((core.tx_by_id(txid), Some(2))),
+    );
+  }
+
+  #[test]
+  fn block_info_is_fetched() {
+    let core = mockcore::builder().network(Network::Regtest).build();
+
+    let blocks = core.mine_blocks(2);
+
+    let esplora = Esplora::new(&core.esplora_url(), Chain::Regtest, 8).unwrap();
+
+    let block = &blocks[0];
+
+    let hash = block.block_hash();
+
+    let info = esplora.block_header_info(hash).unwrap().unwrap();
+
+    assert_eq!(info.hash, hash);
+    assert_eq!(info.height, 1);
+    assert_eq!(info.confirmations, 2);
+    assert_eq!(
+      info.bits,
+      format!("{:08x}", block.header.bits.to_consensus())
+    );
+    assert_eq!(info.merkle_root, block.header.merkle_root);
+    assert_eq!(info.n_tx, block.txdata.len());
+    assert_eq!(info.nonce, block.header.nonce);
+    assert_eq!(info.previous_block_hash, Some(block.header.prev_blockhash));
+    assert_eq!(info.next_block_hash, Some(blocks[1].block_hash()));
+    assert_eq!(info.time, usize::try_from(block.header.time).unwrap());
+    assert_eq!(info.version, block.header.version);
+
+    let stats = esplora.block_stats(1).unwrap().unwrap();
+
+    assert_eq!(stats.block_hash, hash);
+    assert_eq!(stats.height, 1);
+    assert_eq!(stats.subsidy, Amount::from_sat(Height(1).subsidy()));
+    assert_eq!(stats.total_size, block.total_size());
+    assert_eq!(
+      u64::try_from(stats.total_weight).unwrap(),
+      block.weight().to_wu()
+    );
+    assert_eq!(stats.txs, block.txdata.len());
+  }
+
+  #[test]
+  fn concurrency_must_be_positive() {
+    let core = mockcore::builder().network(Network::Regtest).build();
+
+    assert_eq!(
+      Esplora::new(&core.esplora_url(), Chain::Regtest, 0)
+        .err()
+        .unwrap()
+        .to_string(),
+      "Esplora concurrency must be at least 1",
+    );
+  }
+
+  #[test]
+  fn chain_mismatch_is_an_error() {
+    let core = mockcore::builder().network(Network::Signet).build();
+
+    assert_eq!(
+      Esplora::new(&core.esplora_url(), Chain::Regtest, 8)
+        .err()
+        .unwrap()
+        .to_string(),
+      format!("Esplora API at `{}` is not on regtest", core.esplora_url()),
+    );
+  }
+
+  #[test]
+  fn index_can_be_built_from_esplora() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").esplora().build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(context
+      .index
+      .inscription_exists(InscriptionId { txid, index: 0 })
+      .unwrap());
+
+    let (_, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    assert_eq!(context.index.get_rune_by_id(id).unwrap(), Some(Rune(RUNE)));
+  }
+}
@@ -1,7 +1,12 @@
 use super::*;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Event {
+  BlockReverted {
+    block_hash: BlockHash,
+    block_height: u32,
+  },
   InscriptionCreated {
     block_height: u32,
     charms: u16,
@@ -17,6 +22,10 @@ pub enum Event {
     old_location: SatPoint,
     sequence_number: u32,
   },
+  InscriptionReverted {
+    block_height: u32,
+    inscription_id: InscriptionId,
+  },
   RuneBurned {
     amount: u128,
     block_height: u32,
@@ -34,6 +43,11 @@ pub enum Event {
     rune_id: RuneId,
     txid: Txid,
   },
+  RuneReverted {
+    block_height: u32,
+    rune_id: RuneId,
+    txid: Txid,
+  },
   RuneTransferred {
     amount: u128,
     block_height: u32,
@@ -42,3 +56,19 @@ pub enum Event {
     txid: Txid,
   },
 }
+
+impl Event {
+  pub fn block_height(&self) -> u32 {
+    match self {
+      Self::BlockReverted { block_height, .. }
+      | Self::InscriptionCreated { block_height, .. }
+      | Self::InscriptionTransferred { block_height, .. }
+      | Self::InscriptionReverted { block_height, .. }
+      | Self::RuneBurned { block_height, .. }
+      | Self::RuneEtched { block_height, .. }
+      | Self::RuneMinted { block_height, .. }
+      | Self::RuneReverted { block_height, .. }
+      | Self::RuneTransferred { block_height, .. } => *block_height,
+    }
+  }
+}
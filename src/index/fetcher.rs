@@ -9,6 +9,12 @@ use {
   serde_json::{json, Value},
 };
 
+/// Fetches transactions in bulk, to look up the outputs spent by inputs
+/// which are not in the UTXO cache.
+pub(crate) trait TransactionFetcher {
+  async fn get_transactions(&self, txids: Vec<Txid>) -> Result<Vec<Transaction>>;
+}
+
 pub(crate) struct Fetcher {
   auth: String,
   client: Client<HttpConnector, Full<Bytes>>,
@@ -98,7 +104,7 @@ impl Fetcher {
     }
 
     // Results from batched JSON-RPC requests can come back in any order, so we must sort them by id
-    results.sort_by(|a, b| a.id.cmp(&b.id));
+    results.sort_by_key(|result| result.id);
 
     let txs = results
       .into_iter()
@@ -146,3 +152,9 @@ impl Fetcher {
     Ok(results)
   }
 }
+
+impl TransactionFetcher for Fetcher {
+  async fn get_transactions(&self, txids: Vec<Txid>) -> Result<Vec<Transaction>> {
+    Fetcher::get_transactions(self, txids).await
+  }
+}
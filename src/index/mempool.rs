@@ -0,0 +1,432 @@
+use super::*;
+
+/// In-memory overlay of inscription and rune activity in Bitcoin Core's
+/// mempool. The overlay is never written to the database, and is discarded
+/// whenever the indexed tip changes.
+#[derive(Default)]
+pub(crate) struct Mempool {
+  outputs: HashMap<OutPoint, PendingOutput>,
+  spent: HashMap<OutPoint, Txid>,
+  tip: Option<BlockHash>,
+  transactions: BTreeMap<Txid, Option<api::MempoolTransaction>>,
+}
+
+/// Inscriptions, at their offsets, and runes which may be on an output of a
+/// transaction in the overlay, so that transactions spending it can be
+/// followed before it confirms
+#[derive(Clone, Default)]
+struct PendingOutput {
+  inscriptions: Vec<(u64, InscriptionId)>,
+  runes: Vec<SpacedRune>,
+  value: u64,
+}
+
+impl Mempool {
+  pub(crate) fn update(index: &Index) -> Result {
+    let tip = index.block_hash(None)?;
+
+    let txids = index.client.mempool_txids()?;
+
+    let (known, mut outputs) = {
+      let mut mempool = index.mempool.lock().unwrap();
+
+      if mempool.tip != tip {
+        *mempool = Self { tip, ..default() };
+      }
+
+      let live = txids.iter().copied().collect::<HashSet<Txid>>();
+
+      mempool.transactions.retain(|txid, _| live.contains(txid));
+      mempool.spent.retain(|_, txid| live.contains(txid));
+      mempool
+        .outputs
+        .retain(|outpoint, _| live.contains(&outpoint.txid));
+
+      (
+        mempool
+          .transactions
+          .keys()
+          .copied()
+          .collect::<HashSet<Txid>>(),
+        mempool.outputs.clone(),
+      )
+    };
+
+    let mut pending = HashMap::new();
+
+    for txid in txids {
+      if known.contains(&txid) {
+        continue;
+      }
+
+      // transactions may be evicted or confirmed between `getrawmempool` and
+      // `getrawtransaction`, in which case we skip them
+      let Ok(Some(tx)) = index.client.transaction(txid) else {
+        continue;
+      };
+
+      pending.insert(txid, tx);
+    }
+
+    let mut parsed = HashSet::new();
+    let mut transactions = Vec::new();
+
+    for txid in pending.keys() {
+      Self::parse_after_parents(
+        index,
+        *txid,
+        &pending,
+        &mut parsed,
+        &mut outputs,
+        &mut transactions,
+      )?;
+    }
+
+    let mut mempool = index.mempool.lock().unwrap();
+
+    if mempool.tip != tip {
+      return Ok(());
+    }
+
+    for (txid, transaction) in transactions {
+      for input in &pending[&txid].input {
+        mempool.spent.insert(input.previous_output, txid);
+      }
+
+      mempool.transactions.insert(txid, transaction);
+    }
+
+    mempool.outputs.extend(outputs);
+
+    Ok(())
+  }
+
+  /// Parse `txid`, after any of its parents which are also new to the
+  /// overlay, so that its inputs can be resolved against their outputs
+  fn parse_after_parents(
+    index: &Index,
+    txid: Txid,
+    pending: &HashMap<Txid, Transaction>,
+    parsed: &mut HashSet<Txid>,
+    outputs: &mut HashMap<OutPoint, PendingOutput>,
+    transactions: &mut Vec<(Txid, Option<api::MempoolTransaction>)>,
+  ) -> Result {
+    if !parsed.insert(txid) {
+      return Ok(());
+    }
+
+    let tx = &pending[&txid];
+
+    for input in &tx.input {
+      if pending.contains_key(&input.previous_output.txid) {
+        Self::parse_after_parents(
+          index,
+          input.previous_output.txid,
+          pending,
+          parsed,
+          outputs,
+          transactions,
+        )?;
+      }
+    }
+
+    transactions.push((txid, Self::parse(index, txid, tx, outputs)?));
+
+    Ok(())
+  }
+
+  fn parse(
+    index: &Index,
+    txid: Txid,
+    tx: &Transaction,
+    outputs: &mut HashMap<OutPoint, PendingOutput>,
+  ) -> Result<Option<api::MempoolTransaction>> {
+    let envelopes = if index.index_inscriptions {
+      ParsedEnvelope::from_transaction(tx)
+    } else {
+      Vec::new()
+    };
+
+    let inscriptions = (0..envelopes.len())
+      .map(|i| InscriptionId {
+        txid,
+        index: u32::try_from(i).unwrap(),
+      })
+      .collect::<Vec<InscriptionId>>();
+
+    // inscriptions on inputs, along with the input and their offset in it
+    let mut transferred = Vec::new();
+    let mut runes = Vec::new();
+
+    for (input, tx_in) in tx.input.iter().enumerate() {
+      let outpoint = tx_in.previous_output;
+
+      if let Some(output) = outputs.get(&outpoint) {
+        transferred.extend(
+          output
+            .inscriptions
+            .iter()
+            .map(|(offset, id)| (input, *offset, *id)),
+        );
+        runes.extend(output.runes.iter().copied());
+        continue;
+      }
+
+      if let Some(inscriptions) = index.get_inscriptions_on_output_with_satpoints(outpoint)? {
+        transferred.extend(
+          inscriptions
+            .into_iter()
+            .map(|(satpoint, id)| (input, satpoint.offset, id)),
+        );
+      }
+
+      if let Some(balances) = index.get_rune_balances_for_output(outpoint)? {
+        runes.extend(balances.into_keys());
+      }
+    }
+
+    runes.sort();
+    runes.dedup();
+
+    let artifact = if index.index_runes {
+      Runestone::decipher(tx)
+    } else {
+      None
+    };
+
+    let (etching, premine, mint) = match &artifact {
+      Some(Artifact::Runestone(runestone)) => (
+        runestone.etching.and_then(|etching| {
+          etching.rune.map(|rune| SpacedRune {
+            rune,
+            spacers: etching.spacers.unwrap_or_default(),
+          })
+        }),
+        runestone
+          .etching
+          .is_some_and(|etching| etching.premine.unwrap_or_default() > 0),
+        runestone.mint,
+      ),
+      Some(Artifact::Cenotaph(cenotaph)) => (
+        cenotaph.etching.map(|rune| SpacedRune { rune, spacers: 0 }),
+        false,
+        cenotaph.mint,
+      ),
+      None => (None, false, None),
+    };
+
+    let transferred_inscriptions = transferred
+      .iter()
+      .map(|(_input, _offset, id)| *id)
+      .collect::<Vec<InscriptionId>>();
+
+    if inscriptions.is_empty()
+      && transferred_inscriptions.is_empty()
+      && runes.is_empty()
+      && etching.is_none()
+      && mint.is_none()
+    {
+      return Ok(None);
+    }
+
+    let mut carried = runes.clone();
+
+    if premine {
+      carried.extend(etching);
+    }
+
+    if let Some(mint) = mint {
+      if let Some(rune) = index.get_rune_by_id(mint)? {
+        if let Some((_id, entry, _parent)) = index.rune(rune)? {
+          carried.push(entry.spaced_rune);
+        }
+      }
+    }
+
+    carried.sort();
+    carried.dedup();
+
+    let located = transferred
+      .iter()
+      .map(|(input, offset, id)| (*input, *offset, None, *id))
+      .chain(envelopes.iter().zip(&inscriptions).map(|(envelope, id)| {
+        (
+          usize::try_from(envelope.input).unwrap(),
+          0,
+          envelope.payload.pointer(),
+          *id,
+        )
+      }));
+
+    let mut placed = vec![Vec::new(); tx.output.len()];
+
+    let total = tx
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value.to_sat())
+      .sum::<u64>();
+
+    let mut input_offsets = vec![0];
+
+    for (input, offset, pointer, id) in located {
+      // new inscriptions with a valid pointer are placed at it, everything
+      // else at its offset into the inputs
+      let offset = match pointer.filter(|pointer| *pointer < total) {
+        Some(pointer) => pointer,
+        None => {
+          let Some(start) = Self::input_offset(index, tx, outputs, &mut input_offsets, input)?
+          else {
+            continue;
+          };
+          start + offset
+        }
+      };
+
+      let mut start = 0;
+
+      for (vout, tx_out) in tx.output.iter().enumerate() {
+        let end = start + tx_out.value.to_sat();
+
+        if offset < end {
+          placed[vout].push((offset - start, id));
+          break;
+        }
+
+        start = end;
+      }
+    }
+
+    let recipients = if carried.is_empty() {
+      BTreeSet::new()
+    } else {
+      Self::rune_recipients(tx, artifact.as_ref())
+    };
+
+    for (vout, (tx_out, inscriptions)) in tx.output.iter().zip(placed).enumerate() {
+      let runes = if recipients.contains(&vout) {
+        carried.clone()
+      } else {
+        Vec::new()
+      };
+
+      if inscriptions.is_empty() && runes.is_empty() {
+        continue;
+      }
+
+      outputs.insert(
+        OutPoint {
+          txid,
+          vout: u32::try_from(vout).unwrap(),
+        },
+        PendingOutput {
+          inscriptions,
+          runes,
+          value: tx_out.value.to_sat(),
+        },
+      );
+    }
+
+    Ok(Some(api::MempoolTransaction {
+      etching,
+      inscriptions,
+      mint,
+      runes,
+      transferred_inscriptions,
+      txid,
+    }))
+  }
+
+  /// Offset of the first sat of `input` in the transaction's inputs, summing
+  /// the values of the inputs before it, which are looked up as needed
+  fn input_offset(
+    index: &Index,
+    tx: &Transaction,
+    outputs: &HashMap<OutPoint, PendingOutput>,
+    offsets: &mut Vec<u64>,
+    input: usize,
+  ) -> Result<Option<u64>> {
+    while offsets.len() <= input {
+      let outpoint = tx.input[offsets.len() - 1].previous_output;
+
+      let value = match outputs.get(&outpoint) {
+        Some(output) => Some(output.value),
+        None => index.get_transaction(outpoint.txid)?.and_then(|tx| {
+          tx.output
+            .get(usize::try_from(outpoint.vout).unwrap())
+            .map(|tx_out| tx_out.value.to_sat())
+        }),
+      };
+
+      let Some(value) = value else {
+        return Ok(None);
+      };
+
+      offsets.push(offsets.last().unwrap() + value);
+    }
+
+    Ok(Some(offsets[input]))
+  }
+
+  /// Outputs which may receive runes. Runes are burned by cenotaphs, and
+  /// otherwise go to the outputs of edicts and to the default output, which
+  /// is the pointer, or else the first non-`OP_RETURN` output.
+  fn rune_recipients(tx: &Transaction, artifact: Option<&Artifact>) -> BTreeSet<usize> {
+    let spendable = tx
+      .output
+      .iter()
+      .enumerate()
+      .filter(|(_vout, tx_out)| !tx_out.script_pubkey.is_op_return())
+      .map(|(vout, _tx_out)| vout)
+      .collect::<Vec<usize>>();
+
+    let mut recipients = BTreeSet::new();
+
+    let pointer = match artifact {
+      Some(Artifact::Cenotaph(_)) => return recipients,
+      Some(Artifact::Runestone(runestone)) => {
+        for edict in &runestone.edicts {
+          let output = usize::try_from(edict.output).unwrap();
+
+          if output == tx.output.len() {
+            recipients.extend(&spendable);
+          } else if spendable.contains(&output) {
+            recipients.insert(output);
+          }
+        }
+
+        runestone
+          .pointer
+          .map(|pointer| usize::try_from(pointer).unwrap())
+      }
+      None => None,
+    };
+
+    match pointer {
+      Some(pointer) => {
+        if spendable.contains(&pointer) {
+          recipients.insert(pointer);
+        }
+      }
+      None => recipients.extend(spendable.first()),
+    }
+
+    recipients
+  }
+
+  pub(crate) fn discard_if_stale(&mut self, tip: Option<BlockHash>) {
+    if self.tip != tip {
+      *self = Self::default();
+    }
+  }
+
+  pub(crate) fn spender(&self, outpoint: OutPoint) -> Option<Txid> {
+    self.spent.get(&outpoint).copied()
+  }
+
+  pub(crate) fn summary(&self) -> api::Mempool {
+    api::Mempool {
+      tip: self.tip,
+      transactions: self.transactions.values().flatten().cloned().collect(),
+    }
+  }
+}
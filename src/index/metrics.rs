@@ -0,0 +1,346 @@
+use {
+  super::*,
+  std::{fmt::Write, sync::atomic::AtomicU64},
+};
+
+/// Upper bounds of histogram buckets in seconds, the Prometheus client
+/// library defaults
+const BUCKETS: [f64; 11] = [
+  0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Default)]
+struct Histogram {
+  buckets: [u64; BUCKETS.len()],
+  count: u64,
+  sum: f64,
+}
+
+impl Histogram {
+  fn observe(&mut self, duration: Duration) {
+    let seconds = duration.as_secs_f64();
+
+    for (bucket, bound) in self.buckets.iter_mut().zip(BUCKETS) {
+      if seconds <= bound {
+        *bucket += 1;
+      }
+    }
+
+    self.count += 1;
+    self.sum += seconds;
+  }
+
+  fn write(&self, out: &mut String, name: &str, labels: &str) -> fmt::Result {
+    let separator = if labels.is_empty() { "" } else { "," };
+
+    for (count, bound) in self.buckets.iter().zip(BUCKETS) {
+      writeln!(
+        out,
+        "{name}_bucket{{{labels}{separator}le=\"{bound}\"}} {count}"
+      )?;
+    }
+
+    writeln!(
+      out,
+      "{name}_bucket{{{labels}{separator}le=\"+Inf\"}} {}",
+      self.count
+    )?;
+
+    let labels = if labels.is_empty() {
+      String::new()
+    } else {
+      format!("{{{labels}}}")
+    };
+
+    writeln!(out, "{name}_sum{labels} {}", self.sum)?;
+    writeln!(out, "{name}_count{labels} {}", self.count)
+  }
+}
+
+/// Counters and latency histograms recorded by the indexer and server, and
+/// exposed in Prometheus text format at `/metrics`
+#[derive(Default)]
+pub(crate) struct Metrics {
+  commits: Mutex<Histogram>,
+  fetcher_errors: Mutex<BTreeMap<&'static str, u64>>,
+  fetcher_requests: Mutex<BTreeMap<&'static str, Histogram>>,
+  http_request_durations: Mutex<BTreeMap<String, Histogram>>,
+  http_requests: Mutex<BTreeMap<(String, u16), u64>>,
+  reorgs: AtomicU64,
+}
+
+impl Metrics {
+  pub(crate) fn record_commit(&self, duration: Duration) {
+    self.commits.lock().unwrap().observe(duration);
+  }
+
+  pub(crate) fn record_fetcher_error(&self, request: &'static str) {
+    *self
+      .fetcher_errors
+      .lock()
+      .unwrap()
+      .entry(request)
+      .or_default() += 1;
+  }
+
+  pub(crate) fn record_fetcher_request(&self, request: &'static str, duration: Duration) {
+    self
+      .fetcher_requests
+      .lock()
+      .unwrap()
+      .entry(request)
+      .or_default()
+      .observe(duration);
+  }
+
+  pub(crate) fn record_http_request(&self, route: &str, status: u16, duration: Duration) {
+    self
+      .http_request_durations
+      .lock()
+      .unwrap()
+      .entry(route.into())
+      .or_default()
+      .observe(duration);
+
+    *self
+      .http_requests
+      .lock()
+      .unwrap()
+      .entry((route.into(), status))
+      .or_default() += 1;
+  }
+
+  pub(crate) fn record_reorg(&self) {
+    self.reorgs.fetch_add(1, atomic::Ordering::Relaxed);
+  }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) -> fmt::Result {
+  writeln!(out, "# HELP {name} {help}")?;
+  writeln!(out, "# TYPE {name} {kind}")
+}
+
+fn escape(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}
+
+impl Index {
+  pub(crate) fn metrics(&self) -> &Arc<Metrics> {
+    &self.metrics
+  }
+
+  /// Render index and server metrics in Prometheus text exposition format
+  pub(crate) fn render_metrics(&self) -> Result<String> {
+    let rtx = self.database.begin_read()?;
+
+    let blocks_indexed = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    let outputs_traversed = rtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::OutputsTraversed.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    let tables = Self::table_info(&rtx)?;
+
+    drop(rtx);
+
+    let metrics = &self.metrics;
+    let mut out = String::new();
+
+    header(
+      &mut out,
+      "ord_blocks_indexed",
+      "gauge",
+      "Number of blocks indexed.",
+    )?;
+    writeln!(out, "ord_blocks_indexed {blocks_indexed}")?;
+
+    match self.client.header_count() {
+      Ok(headers) => {
+        header(
+          &mut out,
+          "ord_index_lag_blocks",
+          "gauge",
+          "Number of block headers known to Bitcoin Core which have not been indexed.",
+        )?;
+        writeln!(
+          out,
+          "ord_index_lag_blocks {}",
+          (headers + 1).saturating_sub(blocks_indexed.into())
+        )?;
+      }
+      Err(err) => log::warn!("failed to fetch header count for metrics: {err}"),
+    }
+
+    header(
+      &mut out,
+      "ord_table_stored_bytes",
+      "gauge",
+      "Bytes of user data stored in each index table.",
+    )?;
+    for (name, table) in &tables {
+      writeln!(
+        out,
+        "ord_table_stored_bytes{{table=\"{}\"}} {}",
+        escape(name),
+        table.stored_bytes
+      )?;
+    }
+
+    header(
+      &mut out,
+      "ord_table_total_bytes",
+      "gauge",
+      "Bytes used by each index table, including metadata and fragmentation.",
+    )?;
+    for (name, table) in &tables {
+      writeln!(
+        out,
+        "ord_table_total_bytes{{table=\"{}\"}} {}",
+        escape(name),
+        table.total_bytes
+      )?;
+    }
+
+    header(
+      &mut out,
+      "ord_outputs_traversed_total",
+      "counter",
+      "Number of transaction outputs traversed while indexing.",
+    )?;
+    writeln!(out, "ord_outputs_traversed_total {outputs_traversed}")?;
+
+    header(
+      &mut out,
+      "ord_commit_duration_seconds",
+      "histogram",
+      "Time taken to commit index write transactions.",
+    )?;
+    metrics
+      .commits
+      .lock()
+      .unwrap()
+      .write(&mut out, "ord_commit_duration_seconds", "")?;
+
+    header(
+      &mut out,
+      "ord_fetcher_request_duration_seconds",
+      "histogram",
+      "Latency of block and transaction requests made while indexing.",
+    )?;
+    for (request, histogram) in metrics.fetcher_requests.lock().unwrap().iter() {
+      histogram.write(
+        &mut out,
+        "ord_fetcher_request_duration_seconds",
+        &format!("request=\"{request}\""),
+      )?;
+    }
+
+    header(
+      &mut out,
+      "ord_fetcher_errors_total",
+      "counter",
+      "Number of failed block and transaction requests made while indexing.",
+    )?;
+    for (request, errors) in metrics.fetcher_errors.lock().unwrap().iter() {
+      writeln!(
+        out,
+        "ord_fetcher_errors_total{{request=\"{request}\"}} {errors}"
+      )?;
+    }
+
+    header(
+      &mut out,
+      "ord_reorgs_total",
+      "counter",
+      "Number of reorgs handled since startup.",
+    )?;
+    writeln!(
+      out,
+      "ord_reorgs_total {}",
+      metrics.reorgs.load(atomic::Ordering::Relaxed)
+    )?;
+
+    header(
+      &mut out,
+      "ord_http_requests_total",
+      "counter",
+      "Number of HTTP requests served, by route and status.",
+    )?;
+    for ((route, status), count) in metrics.http_requests.lock().unwrap().iter() {
+      writeln!(
+        out,
+        "ord_http_requests_total{{route=\"{}\",status=\"{status}\"}} {count}",
+        escape(route),
+      )?;
+    }
+
+    header(
+      &mut out,
+      "ord_http_request_duration_seconds",
+      "histogram",
+      "Latency of HTTP requests, by route.",
+    )?;
+    for (route, histogram) in metrics.http_request_durations.lock().unwrap().iter() {
+      histogram.write(
+        &mut out,
+        "ord_http_request_duration_seconds",
+        &format!("route=\"{}\"", escape(route)),
+      )?;
+    }
+
+    Ok(out)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn histogram() {
+    let mut histogram = Histogram::default();
+
+    histogram.observe(Duration::from_millis(20));
+    histogram.observe(Duration::from_secs(1));
+    histogram.observe(Duration::from_secs(60));
+
+    let mut out = String::new();
+    histogram.write(&mut out, "foo", "bar=\"baz\"").unwrap();
+
+    pretty_assert_eq!(
+      out,
+      r#"foo_bucket{bar="baz",le="0.005"} 0
+foo_bucket{bar="baz",le="0.01"} 0
+foo_bucket{bar="baz",le="0.025"} 1
+foo_bucket{bar="baz",le="0.05"} 1
+foo_bucket{bar="baz",le="0.1"} 1
+foo_bucket{bar="baz",le="0.25"} 1
+foo_bucket{bar="baz",le="0.5"} 1
+foo_bucket{bar="baz",le="1"} 2
+foo_bucket{bar="baz",le="2.5"} 2
+foo_bucket{bar="baz",le="5"} 2
+foo_bucket{bar="baz",le="10"} 2
+foo_bucket{bar="baz",le="+Inf"} 3
+foo_sum{bar="baz"} 61.02
+foo_count{bar="baz"} 3
+"#
+    );
+  }
+
+  #[test]
+  fn escape_label_values() {
+    assert_eq!(escape(r#"a"b\c"#), r#"a\"b\\c"#);
+    assert_eq!(escape("a\nb"), r"a\nb");
+  }
+}
@@ -0,0 +1,120 @@
+use {super::*, redb::WriteTransaction};
+
+/// Upgrades an index from schema version `from` to `to`. Indexes with schema
+/// versions that are not the `from` of any migration cannot be upgraded in
+/// place, and must be rebuilt.
+struct Migration {
+  description: &'static str,
+  from: u64,
+  run: fn(&WriteTransaction) -> Result,
+  to: u64,
+}
+
+const MIGRATIONS: &[Migration] = &[
+  // every index added in schema 31 is either created empty, or is enabled by a
+  // statistic which is unset in older indexes, so only tables need to be
+  // created
+  Migration {
+    description: "create tables",
+    from: 30,
+    run: |wtx| {
+      wtx.open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?;
+      wtx.open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBER)?;
+      wtx.open_multimap_table(RUNE_BALANCE_TO_HOLDER)?;
+      wtx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNIC_OUTPOINT)?;
+      wtx.open_table(CONTENT_TYPE_TO_SEQUENCE_NUMBER)?;
+      wtx.open_table(EVENT_SEQUENCE_TO_EVENT)?;
+      wtx.open_table(FEE_TO_SEQUENCE_NUMBER)?;
+      wtx.open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE)?;
+      wtx.open_table(HEIGHT_TO_UNDO)?;
+      wtx.open_table(MEDIA_TO_SEQUENCE_NUMBER)?;
+      wtx.open_table(METADATA_TO_SEQUENCE_NUMBER)?;
+      wtx.open_table(METAPROTOCOL_TO_SEQUENCE_NUMBER)?;
+      wtx.open_table(RUNE_HOLDER_TO_BALANCE)?;
+      wtx.open_table(RUNE_ID_TO_HOLDER_COUNT)?;
+      wtx.open_table(RUNIC_OUTPOINT_TO_HEIGHT)?;
+      wtx.open_table(RUNIC_OUTPOINT_TO_SCRIPT_PUBKEY)?;
+      wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_HISTORY)?;
+      wtx.open_table(SPENT_RUNIC_OUTPOINT_TO_BALANCES)?;
+      Ok(())
+    },
+    to: 31,
+  },
+];
+
+impl Index {
+  /// Migrate `database` from `schema_version` to `SCHEMA_VERSION`, one
+  /// migration per write transaction, so an interrupted migration resumes
+  /// where it left off.
+  pub(super) fn migrate(
+    database: &Database,
+    schema_version: u64,
+    path: &Path,
+    progress: bool,
+  ) -> Result {
+    let mut migrations = Vec::new();
+
+    let mut version = schema_version;
+
+    while version < SCHEMA_VERSION {
+      let Some(migration) = MIGRATIONS
+        .iter()
+        .find(|migration| migration.from == version)
+      else {
+        bail!(
+          "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+          path.display()
+        );
+      };
+
+      migrations.push(migration);
+
+      version = migration.to;
+    }
+
+    let progress_bar = if progress {
+      let progress_bar = ProgressBar::new(migrations.len().try_into().unwrap());
+      progress_bar.set_style(
+        ProgressStyle::with_template("[migrating index] {wide_bar} {pos}/{len} {msg}").unwrap(),
+      );
+      Some(progress_bar)
+    } else {
+      None
+    };
+
+    for migration in migrations {
+      log::info!(
+        "Migrating index schema from {} to {}: {}",
+        migration.from,
+        migration.to,
+        migration.description,
+      );
+
+      if let Some(progress_bar) = &progress_bar {
+        progress_bar.set_message(migration.description);
+      }
+
+      let wtx = database.begin_write()?;
+
+      (migration.run)(&wtx)?;
+
+      Self::set_statistic(
+        &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+        Statistic::Schema,
+        migration.to,
+      )?;
+
+      wtx.commit()?;
+
+      if let Some(progress_bar) = &progress_bar {
+        progress_bar.inc(1);
+      }
+    }
+
+    if let Some(progress_bar) = progress_bar {
+      progress_bar.finish_and_clear();
+    }
+
+    Ok(())
+  }
+}
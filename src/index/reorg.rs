@@ -1,4 +1,4 @@
-use {super::*, updater::BlockData};
+use {super::*, redb::WriteTransaction, updater::BlockData};
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum Error {
@@ -33,15 +33,22 @@ impl Reorg {
         let max_recoverable_reorg_depth =
           (max_savepoints - 1) * savepoint_interval + height % savepoint_interval;
 
-        for depth in 1..max_recoverable_reorg_depth {
+        let undo_depth = index.settings.undo_depth().unwrap_or_default();
+
+        for depth in 1..max_recoverable_reorg_depth.max(undo_depth + 1) {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
           let bitcoind_block_hash = index
             .client
-            .get_block_hash(u64::from(height.saturating_sub(depth)))
-            .into_option()?;
+            .block_hash(u64::from(height.saturating_sub(depth)))?;
 
           if index_block_hash == bitcoind_block_hash {
-            return Err(anyhow!(reorg::Error::Recoverable { height, depth }));
+            if depth < max_recoverable_reorg_depth
+              || index.can_roll_back(height.saturating_sub(depth))?
+            {
+              return Err(anyhow!(reorg::Error::Recoverable { height, depth }));
+            }
+
+            break;
           }
         }
 
@@ -54,28 +61,203 @@ impl Reorg {
   pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result {
     log::info!("rolling back database after reorg of depth {depth} at height {height}");
 
+    if let Some(block_files) = index.block_files.lock().unwrap().as_mut() {
+      block_files.handle_reorg();
+    }
+
+    if index.can_roll_back(height.saturating_sub(depth))? {
+      return index.rollback(height.saturating_sub(depth));
+    }
+
     if let redb::Durability::None = index.durability {
       panic!("set index durability to `Durability::Immediate` to test reorg handling");
     }
 
+    let savepoint_interval = u32::try_from(index.settings.savepoint_interval()).unwrap();
+    let max_savepoints = u32::try_from(index.settings.max_savepoints()).unwrap();
+
+    let oldest = index
+      .block_count()?
+      .saturating_sub((max_savepoints + 1) * savepoint_interval);
+
+    let reverted = if index.index_events || index.event_sender.is_some() {
+      Self::reverted_events(index, oldest)?
+    } else {
+      Vec::new()
+    };
+
+    // restoring the savepoint also restores the event log, so events logged
+    // since the savepoint was taken are saved and logged again afterwards
+    let logged = if index.index_events {
+      Self::logged_events(index, oldest)?
+    } else {
+      Vec::new()
+    };
+
     let mut wtx = index.begin_write()?;
 
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let oldest_savepoint = wtx.get_persistent_savepoint(
+      wtx
+        .list_persistent_savepoints()?
+        .min()
+        .ok_or_else(|| anyhow!(reorg::Error::Unrecoverable))?,
+    )?;
 
     wtx.restore_savepoint(&oldest_savepoint)?;
 
-    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    let block_count = wtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    let reverted = reverted
+      .into_iter()
+      .filter(|(height, _event)| *height >= block_count)
+      .map(|(_height, event)| event)
+      .collect::<Vec<Event>>();
+
+    if index.index_events {
+      Self::restore_logged_events(&wtx, logged)?;
+      Index::log_events(&wtx, &reverted)?;
+    }
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1, &mut UndoLog::default())?;
     wtx.commit()?;
 
-    log::info!(
-      "successfully rolled back database to height {}",
-      index.begin_read()?.block_count()?
-    );
+    if let Some(sender) = &index.event_sender {
+      for event in reverted {
+        sender.blocking_send(event)?;
+      }
+    }
+
+    log::info!("successfully rolled back database to height {block_count}");
 
     Ok(())
   }
 
+  /// Returns the tail of the event log, with every event logged for a block at
+  /// or above `oldest`, as sequence number, block height, and event.
+  fn logged_events(index: &Index, oldest: u32) -> Result<Vec<(u64, u32, Vec<u8>)>> {
+    let rtx = index.database.begin_read()?;
+
+    let mut events = Vec::new();
+
+    for result in rtx.open_table(EVENT_SEQUENCE_TO_EVENT)?.iter()?.rev() {
+      let (sequence, value) = result?;
+      let (height, event) = value.value();
+
+      if height < oldest {
+        break;
+      }
+
+      events.push((sequence.value(), height, event.to_vec()));
+    }
+
+    events.reverse();
+
+    Ok(events)
+  }
+
+  /// Log events saved by `logged_events` which were removed by restoring a
+  /// savepoint, with their original sequence numbers.
+  fn restore_logged_events(wtx: &WriteTransaction, logged: Vec<(u64, u32, Vec<u8>)>) -> Result {
+    let mut event_sequence_to_event = wtx.open_table(EVENT_SEQUENCE_TO_EVENT)?;
+    let mut height_to_first_event_sequence = wtx.open_table(HEIGHT_TO_FIRST_EVENT_SEQUENCE)?;
+
+    let next = event_sequence_to_event
+      .last()?
+      .map(|(sequence, _)| sequence.value() + 1)
+      .unwrap_or(0);
+
+    if let Some((first, _, _)) = logged.first() {
+      ensure!(
+        *first <= next,
+        "cannot restore event log, events {next} to {} were not saved",
+        first - 1,
+      );
+    }
+
+    for (sequence, height, event) in logged {
+      if sequence >= next {
+        event_sequence_to_event.insert(sequence, (height, event.as_slice()))?;
+        Index::log_first_event_sequence(&mut height_to_first_event_sequence, height, sequence)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns reversal events, newest first, for every block from `oldest` to
+  /// the tip, paired with the height of the block they revert.
+  pub(crate) fn reverted_events(index: &Index, oldest: u32) -> Result<Vec<(u32, Event)>> {
+    let block_count = index.block_count()?;
+
+    let mut events = Vec::new();
+
+    for height in (oldest..block_count).rev() {
+      let Some(block_hash) = index.block_hash(Some(height))? else {
+        continue;
+      };
+
+      if index.index_inscriptions {
+        for inscription_id in index.get_inscriptions_in_block(height)?.into_iter().rev() {
+          events.push((
+            height,
+            Event::InscriptionReverted {
+              block_height: height,
+              inscription_id,
+            },
+          ));
+        }
+      }
+
+      if index.index_runes {
+        let rtx = index.database.begin_read()?;
+
+        let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+        let min_id = RuneId {
+          block: height.into(),
+          tx: 0,
+        };
+
+        let max_id = RuneId {
+          block: height.into(),
+          tx: u32::MAX,
+        };
+
+        for result in rune_id_to_rune_entry
+          .range(min_id.store()..=max_id.store())?
+          .rev()
+        {
+          let (id, entry) = result?;
+
+          events.push((
+            height,
+            Event::RuneReverted {
+              block_height: height,
+              rune_id: RuneId::load(id.value()),
+              txid: RuneEntry::load(entry.value()).etching,
+            },
+          ));
+        }
+      }
+
+      events.push((
+        height,
+        Event::BlockReverted {
+          block_hash,
+          block_height: height,
+        },
+      ));
+    }
+
+    Ok(events)
+  }
+
   pub(crate) fn is_savepoint_required(index: &Index, height: u32) -> Result<bool> {
     if let redb::Durability::None = index.durability {
       return Ok(false);
@@ -91,7 +273,7 @@ impl Reorg {
       .map(|last_savepoint_height| last_savepoint_height.value())
       .unwrap_or(0);
 
-    let blocks = index.client.get_blockchain_info()?.headers;
+    let blocks = index.client.header_count()?;
 
     let savepoint_interval = u64::try_from(index.settings.savepoint_interval()).unwrap();
     let max_savepoints = u64::try_from(index.settings.max_savepoints()).unwrap();
@@ -129,7 +311,9 @@ impl Reorg {
         wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
       }
 
-      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+      UndoLog::append(&wtx, height - 1, |undo| {
+        Index::increment_statistic(&wtx, Statistic::Commits, 1, undo)
+      })?;
       wtx.commit()?;
 
       let wtx = index.begin_write()?;
@@ -138,11 +322,15 @@ impl Reorg {
 
       wtx.persistent_savepoint()?;
 
+      // savepoints are not affected by undo records, so neither is the height
+      // of the last one
       wtx
         .open_table(STATISTIC_TO_COUNT)?
         .insert(&Statistic::LastSavepointHeight.key(), &height.into())?;
 
-      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+      UndoLog::append(&wtx, height - 1, |undo| {
+        Index::increment_statistic(&wtx, Statistic::Commits, 1, undo)
+      })?;
       wtx.commit()?;
     }
 
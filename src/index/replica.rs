@@ -0,0 +1,187 @@
+use {
+  super::*,
+  redb::{ReadTransaction, TransactionError},
+  std::sync::RwLock,
+};
+
+/// The index database, which a read-only replica replaces with a newly
+/// restored copy when the primary writes a new snapshot. Transactions begun
+/// before it is replaced keep reading from the old copy.
+pub(crate) struct DatabaseHandle(RwLock<Database>);
+
+impl DatabaseHandle {
+  pub(crate) fn new(database: Database) -> Self {
+    Self(RwLock::new(database))
+  }
+
+  pub(crate) fn begin_read(&self) -> Result<ReadTransaction, TransactionError> {
+    self.0.read().unwrap().begin_read()
+  }
+
+  pub(crate) fn begin_write(&self) -> Result<WriteTransaction, TransactionError> {
+    self.0.read().unwrap().begin_write()
+  }
+
+  fn replace(&self, database: Database) {
+    *self.0.write().unwrap() = database;
+  }
+}
+
+/// Tracks the snapshot shared between a primary and its read-only replicas
+#[derive(Default)]
+pub(crate) struct Replica {
+  /// Tip of the last snapshot written by a primary
+  written: Option<BlockHash>,
+  /// Modification time of the last snapshot loaded by a replica
+  loaded: Option<SystemTime>,
+}
+
+impl Replica {
+  pub(crate) fn new(loaded: Option<SystemTime>) -> Self {
+    Self {
+      written: None,
+      loaded,
+    }
+  }
+}
+
+impl Index {
+  /// Write a snapshot of the index to `--replica-snapshot`, if the tip has
+  /// changed since the last one was written. The snapshot is written to a
+  /// temporary file and renamed into place, so replicas never see a partial
+  /// snapshot. Since this copies the whole index, it is not called after
+  /// every update, but by `ord server` every `--replica-snapshot-interval`,
+  /// and by `ord index update` when it finishes.
+  pub(crate) fn write_replica_snapshot(&self) -> Result {
+    let Some(path) = self.settings.replica_snapshot() else {
+      return Ok(());
+    };
+
+    let tip = self.block_hash(None)?;
+
+    if tip.is_none() || self.replica.lock().unwrap().written == tip {
+      return Ok(());
+    }
+
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    let (header, _checksum) = self.snapshot(&partial)?;
+
+    fs::rename(&partial, path)?;
+
+    log::info!(
+      "Wrote replica snapshot at height {} to {}",
+      header.height.unwrap(),
+      path.display(),
+    );
+
+    self.replica.lock().unwrap().written = header.block_hash;
+
+    Ok(())
+  }
+
+  /// Load the snapshot at `--replica-snapshot`, if it has changed since it
+  /// was last loaded. The snapshot is restored to a separate file, which is
+  /// opened and swapped in for the current database, so the current database
+  /// is never written to, and readers see either the old or the new snapshot.
+  /// The restored file then replaces the replica's own copy of the index,
+  /// never the primary's. This happens at most as often as the primary writes
+  /// snapshots.
+  pub(crate) fn refresh_replica(&self) -> Result {
+    let Some(path) = self.settings.replica_snapshot() else {
+      return Ok(());
+    };
+
+    let modified = fs::metadata(path)?.modified()?;
+
+    if self.replica.lock().unwrap().loaded == Some(modified) {
+      return Ok(());
+    }
+
+    let mut next = self.path.as_os_str().to_owned();
+    next.push(".next");
+    let next = PathBuf::from(next);
+
+    if next.try_exists()? {
+      fs::remove_file(&next)?;
+    }
+
+    let header = Self::restore_to(&self.settings, path, &next)?;
+
+    let database = Database::builder()
+      .set_cache_size(self.settings.index_cache_size())
+      .open(&next)?;
+
+    {
+      let rtx = database.begin_read()?;
+      let statistics = rtx.open_table(STATISTIC_TO_COUNT)?;
+
+      for (statistic, enabled) in [
+        (Statistic::IndexAddresses, self.index_addresses),
+        (Statistic::IndexContentHashes, self.index_content_hashes),
+        (Statistic::IndexDelegates, self.index_delegates),
+        (Statistic::IndexEvents, self.index_events),
+        (
+          Statistic::IndexInscriptionAttributes,
+          self.index_inscription_attributes,
+        ),
+        (
+          Statistic::IndexInscriptionHistory,
+          self.index_inscription_history,
+        ),
+        (Statistic::IndexInscriptions, self.index_inscriptions),
+        (Statistic::IndexMetadata, self.index_metadata),
+        (Statistic::IndexRuneHistory, self.index_rune_history),
+        (Statistic::IndexRuneHolders, self.index_rune_holders),
+        (Statistic::IndexRunes, self.index_runes),
+        (Statistic::IndexSats, self.index_sats),
+        (Statistic::IndexTransactions, self.index_transactions),
+      ] {
+        ensure!(
+          Self::is_statistic_set(&statistics, statistic)? == enabled,
+          "snapshot `{}` was taken with different index flags than the replica's index",
+          path.display(),
+        );
+      }
+    }
+
+    self.database.replace(database);
+
+    fs::rename(&next, &self.path)?;
+
+    self.replica.lock().unwrap().loaded = Some(modified);
+
+    self
+      .mempool
+      .lock()
+      .unwrap()
+      .discard_if_stale(header.block_hash);
+
+    log::info!(
+      "Refreshed replica from snapshot at height {:?}",
+      header.height
+    );
+
+    Ok(())
+  }
+
+  /// Number of blocks the index is behind the chain tip, if it is a read-only
+  /// replica
+  pub(crate) fn replica_lag(&self) -> Result<Option<u64>> {
+    if !self.settings.read_only() {
+      return Ok(None);
+    }
+
+    let block_count = u64::from(self.block_count()?);
+
+    Ok(
+      self
+        .client
+        .block_count()
+        .ok()
+        .map(|tip| (tip + 1).saturating_sub(block_count)),
+    )
+  }
+}
@@ -0,0 +1,462 @@
+use {
+  super::*,
+  bitcoin::hashes::{sha256, HashEngine},
+  brotli::{CompressorWriter, Decompressor},
+  redb::{Key, MultimapTableDefinition, ReadTransaction, TableDefinition, WriteTransaction},
+  std::io::{BufReader, BufWriter, Read, Seek, Write},
+};
+
+const MAGIC: &[u8; 8] = b"ordsnap\0";
+
+// number of entries inserted per write transaction when restoring
+const BATCH_SIZE: usize = 1 << 20;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Header {
+  pub block_hash: Option<BlockHash>,
+  pub chain: Chain,
+  pub height: Option<u32>,
+  pub schema_version: u64,
+}
+
+pub(super) trait RawTable {
+  fn name(&self) -> &str;
+
+  fn dump(&self, rtx: &ReadTransaction, writer: &mut dyn Write) -> Result;
+
+  /// Returns false if the snapshot ended before the end of the table
+  fn load(&self, wtx: &WriteTransaction, reader: &mut dyn Read, limit: usize) -> Result<bool>;
+
+  /// Insert `key` and `value` if `present`, otherwise remove them
+  fn revert(&self, wtx: &WriteTransaction, key: &[u8], value: &[u8], present: bool) -> Result;
+}
+
+impl<K: Key + 'static, V: redb::Value + 'static> RawTable for TableDefinition<'static, K, V> {
+  fn name(&self) -> &str {
+    redb::TableHandle::name(self)
+  }
+
+  fn dump(&self, rtx: &ReadTransaction, writer: &mut dyn Write) -> Result {
+    for result in rtx.open_table(*self)?.iter()? {
+      let (key, value) = result?;
+      write_entry(
+        writer,
+        K::as_bytes(&key.value()).as_ref(),
+        V::as_bytes(&value.value()).as_ref(),
+      )?;
+    }
+
+    writer.write_all(&[0])?;
+
+    Ok(())
+  }
+
+  fn load(&self, wtx: &WriteTransaction, reader: &mut dyn Read, limit: usize) -> Result<bool> {
+    let mut table = wtx.open_table(*self)?;
+
+    for _ in 0..limit {
+      let Some((key, value)) = read_entry(reader)? else {
+        return Ok(false);
+      };
+
+      table.insert(K::from_bytes(&key), V::from_bytes(&value))?;
+    }
+
+    Ok(true)
+  }
+
+  fn revert(&self, wtx: &WriteTransaction, key: &[u8], value: &[u8], present: bool) -> Result {
+    let mut table = wtx.open_table(*self)?;
+
+    if present {
+      table.insert(K::from_bytes(key), V::from_bytes(value))?;
+    } else {
+      table.remove(K::from_bytes(key))?;
+    }
+
+    Ok(())
+  }
+}
+
+impl<K: Key + 'static, V: Key + 'static> RawTable for MultimapTableDefinition<'static, K, V> {
+  fn name(&self) -> &str {
+    redb::MultimapTableHandle::name(self)
+  }
+
+  fn dump(&self, rtx: &ReadTransaction, writer: &mut dyn Write) -> Result {
+    for result in rtx.open_multimap_table(*self)?.iter()? {
+      let (key, values) = result?;
+      for value in values {
+        write_entry(
+          writer,
+          K::as_bytes(&key.value()).as_ref(),
+          V::as_bytes(&value?.value()).as_ref(),
+        )?;
+      }
+    }
+
+    writer.write_all(&[0])?;
+
+    Ok(())
+  }
+
+  fn load(&self, wtx: &WriteTransaction, reader: &mut dyn Read, limit: usize) -> Result<bool> {
+    let mut table = wtx.open_multimap_table(*self)?;
+
+    for _ in 0..limit {
+      let Some((key, value)) = read_entry(reader)? else {
+        return Ok(false);
+      };
+
+      table.insert(K::from_bytes(&key), V::from_bytes(&value))?;
+    }
+
+    Ok(true)
+  }
+
+  fn revert(&self, wtx: &WriteTransaction, key: &[u8], value: &[u8], present: bool) -> Result {
+    let mut table = wtx.open_multimap_table(*self)?;
+
+    if present {
+      table.insert(K::from_bytes(key), V::from_bytes(value))?;
+    } else {
+      table.remove(K::from_bytes(key), V::from_bytes(value))?;
+    }
+
+    Ok(())
+  }
+}
+
+pub(super) const TABLES: &[&dyn RawTable] = &[
+  &CONTENT_HASH_TO_SEQUENCE_NUMBER,
+  &DELEGATE_TO_SEQUENCE_NUMBER,
+  &RUNE_BALANCE_TO_HOLDER,
+  &SAT_TO_SEQUENCE_NUMBER,
+  &SEQUENCE_NUMBER_TO_CHILDREN,
+  &SCRIPT_PUBKEY_TO_OUTPOINT,
+  &SCRIPT_PUBKEY_TO_RUNIC_OUTPOINT,
+  &CONTENT_TYPE_TO_SEQUENCE_NUMBER,
+  &EVENT_SEQUENCE_TO_EVENT,
+  &FEE_TO_SEQUENCE_NUMBER,
+  &HEIGHT_TO_BLOCK_HEADER,
+  &HEIGHT_TO_FIRST_EVENT_SEQUENCE,
+  &HEIGHT_TO_LAST_SEQUENCE_NUMBER,
+  &HEIGHT_TO_UNDO,
+  &HOME_INSCRIPTIONS,
+  &INSCRIPTION_ID_TO_SEQUENCE_NUMBER,
+  &INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
+  &MEDIA_TO_SEQUENCE_NUMBER,
+  &METADATA_TO_SEQUENCE_NUMBER,
+  &METAPROTOCOL_TO_SEQUENCE_NUMBER,
+  &OUTPOINT_TO_RUNE_BALANCES,
+  &OUTPOINT_TO_UTXO_ENTRY,
+  &RUNE_HOLDER_TO_BALANCE,
+  &RUNE_ID_TO_HOLDER_COUNT,
+  &RUNE_ID_TO_RUNE_ENTRY,
+  &RUNE_TO_RUNE_ID,
+  &RUNIC_OUTPOINT_TO_HEIGHT,
+  &RUNIC_OUTPOINT_TO_SCRIPT_PUBKEY,
+  &SAT_TO_SATPOINT,
+  &SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY,
+  &SEQUENCE_NUMBER_TO_INSCRIPTION_HISTORY,
+  &SEQUENCE_NUMBER_TO_RUNE_ID,
+  &SEQUENCE_NUMBER_TO_SATPOINT,
+  &SPENT_RUNIC_OUTPOINT_TO_BALANCES,
+  &STATISTIC_TO_COUNT,
+  &TRANSACTION_ID_TO_RUNE,
+  &TRANSACTION_ID_TO_TRANSACTION,
+  &WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP,
+];
+
+pub(super) fn write_bytes(writer: &mut dyn Write, bytes: &[u8]) -> io::Result<()> {
+  writer.write_all(&u32::try_from(bytes.len()).unwrap().to_le_bytes())?;
+  writer.write_all(bytes)
+}
+
+fn write_entry(writer: &mut dyn Write, key: &[u8], value: &[u8]) -> io::Result<()> {
+  writer.write_all(&[1])?;
+  write_bytes(writer, key)?;
+  write_bytes(writer, value)
+}
+
+pub(super) fn read_bytes(reader: &mut dyn Read) -> io::Result<Vec<u8>> {
+  let mut len = [0; 4];
+  reader.read_exact(&mut len)?;
+  let mut bytes = vec![0; u32::from_le_bytes(len).try_into().unwrap()];
+  reader.read_exact(&mut bytes)?;
+  Ok(bytes)
+}
+
+fn read_entry(reader: &mut dyn Read) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+  let mut tag = [0];
+  reader.read_exact(&mut tag)?;
+
+  match tag[0] {
+    0 => Ok(None),
+    1 => Ok(Some((read_bytes(reader)?, read_bytes(reader)?))),
+    tag => bail!("invalid snapshot entry tag {tag}"),
+  }
+}
+
+struct HashWriter<W> {
+  engine: sha256::HashEngine,
+  inner: W,
+}
+
+impl<W: Write> Write for HashWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let n = self.inner.write(buf)?;
+    self.engine.input(&buf[..n]);
+    Ok(n)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+struct HashEngineWriter<'a>(&'a mut sha256::HashEngine);
+
+impl Write for HashEngineWriter<'_> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.0.input(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+fn read_header(reader: &mut dyn Read) -> Result<Header> {
+  let mut magic = [0; MAGIC.len()];
+  reader.read_exact(&mut magic)?;
+  ensure!(&magic == MAGIC, "file is not an ord index snapshot");
+  Ok(serde_json::from_slice(&read_bytes(reader)?)?)
+}
+
+impl Index {
+  /// Write a compressed snapshot of every table to `path`, followed by the
+  /// SHA-256 hash of the preceding bytes. The snapshot is taken from a single
+  /// read transaction, so it is consistent even while the index is updated.
+  pub fn snapshot(&self, path: &Path) -> Result<(Header, sha256::Hash)> {
+    let file =
+      File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+
+    self.write_snapshot(file)
+  }
+
+  /// Write a snapshot to `writer`, in the same format as `snapshot`
+  pub(crate) fn write_snapshot(&self, writer: impl Write) -> Result<(Header, sha256::Hash)> {
+    let rtx = self.database.begin_read()?;
+
+    let tables = rtx
+      .list_tables()?
+      .map(|table| table.name().to_string())
+      .chain(
+        rtx
+          .list_multimap_tables()?
+          .map(|table| table.name().to_string()),
+      )
+      .collect::<BTreeSet<String>>();
+
+    for name in &tables {
+      ensure!(
+        TABLES.iter().any(|table| table.name() == name),
+        "index contains table {name} unknown to snapshot",
+      );
+    }
+
+    let (height, block_hash) = match rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+    {
+      Some((height, header)) => (
+        Some(height.value()),
+        Some(bitcoin::block::Header::load(*header.value()).block_hash()),
+      ),
+      None => (None, None),
+    };
+
+    let header = Header {
+      block_hash,
+      chain: self.settings.chain(),
+      height,
+      schema_version: SCHEMA_VERSION,
+    };
+
+    let mut writer = HashWriter {
+      engine: sha256::Hash::engine(),
+      inner: BufWriter::new(writer),
+    };
+
+    writer.write_all(MAGIC)?;
+    write_bytes(&mut writer, &serde_json::to_vec(&header)?)?;
+
+    let mut compressor = CompressorWriter::new(writer, 1 << 16, 5, 22);
+
+    for table in TABLES.iter().filter(|table| tables.contains(table.name())) {
+      log::info!("Writing table {}…", table.name());
+      write_bytes(&mut compressor, table.name().as_bytes())?;
+      table.dump(&rtx, &mut compressor)?;
+    }
+
+    write_bytes(&mut compressor, &[])?;
+
+    let writer = compressor.into_inner();
+
+    let checksum = sha256::Hash::from_engine(writer.engine);
+
+    let mut writer = writer.inner;
+    writer.write_all(checksum.as_byte_array())?;
+    writer.flush()?;
+
+    Ok((header, checksum))
+  }
+
+  /// Validate the snapshot at `path` and install it as the index configured
+  /// by `settings`, which must not exist yet.
+  pub fn restore(settings: &Settings, path: &Path) -> Result<Header> {
+    let index_path = settings.index();
+
+    ensure!(
+      !index_path.try_exists()?,
+      "index `{}` already exists, remove it before restoring a snapshot",
+      index_path.display(),
+    );
+
+    if let Some(parent) = index_path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    Self::restore_to(settings, path, index_path)
+  }
+
+  /// Restore the snapshot at `path` to a new index at `destination`. The
+  /// index is written to a temporary file and renamed into place, so a
+  /// partially restored index is never opened.
+  pub(super) fn restore_to(settings: &Settings, path: &Path, destination: &Path) -> Result<Header> {
+    let (header, mut reader) = Self::open_snapshot(settings, path)?;
+
+    let mut partial = destination.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    if partial.try_exists()? {
+      fs::remove_file(&partial)?;
+    }
+
+    let database = Database::builder()
+      .set_cache_size(settings.index_cache_size())
+      .create(&partial)?;
+
+    Self::read_tables(&mut reader, |table, reader| loop {
+      let wtx = database.begin_write()?;
+      let more = table.load(&wtx, reader, BATCH_SIZE)?;
+      wtx.commit()?;
+
+      if !more {
+        return Ok(());
+      }
+    })?;
+
+    // savepoints are not part of the snapshot
+    {
+      let wtx = database.begin_write()?;
+      wtx
+        .open_table(STATISTIC_TO_COUNT)?
+        .remove(&Statistic::LastSavepointHeight.key())?;
+      wtx.commit()?;
+    }
+
+    drop(database);
+
+    fs::rename(&partial, destination)?;
+
+    Ok(header)
+  }
+
+  /// Verify the snapshot at `path`, and return its header and checksum
+  pub fn verify_snapshot(settings: &Settings, path: &Path) -> Result<(Header, sha256::Hash)> {
+    let (header, _reader) = Self::open_snapshot(settings, path)?;
+
+    let mut file = File::open(path)?;
+    file.seek(io::SeekFrom::End(-32))?;
+    let mut checksum = [0; 32];
+    file.read_exact(&mut checksum)?;
+
+    Ok((header, sha256::Hash::from_byte_array(checksum)))
+  }
+
+  /// Verify the header and checksum of the snapshot at `path`, and return the
+  /// header and a reader positioned at the start of the snapshot's tables.
+  pub(super) fn open_snapshot(settings: &Settings, path: &Path) -> Result<(Header, impl Read)> {
+    let len = fs::metadata(path)?.len();
+
+    ensure!(len >= 32, "snapshot file is truncated");
+
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let header = read_header(&mut reader)?;
+
+    ensure!(
+      header.chain == settings.chain(),
+      "snapshot is for {} but ord is configured for {}",
+      header.chain,
+      settings.chain(),
+    );
+
+    ensure!(
+      header.schema_version == SCHEMA_VERSION,
+      "snapshot has index schema {} but ord schema is {SCHEMA_VERSION}",
+      header.schema_version,
+    );
+
+    log::info!("Verifying snapshot checksum…");
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut engine = sha256::Hash::engine();
+    io::copy(
+      &mut (&mut reader).take(len - 32),
+      &mut HashEngineWriter(&mut engine),
+    )?;
+    let mut checksum = [0; 32];
+    reader.read_exact(&mut checksum)?;
+
+    ensure!(
+      sha256::Hash::from_engine(engine).to_byte_array() == checksum,
+      "snapshot checksum mismatch",
+    );
+
+    let mut reader = BufReader::new(File::open(path)?).take(len - 32);
+    read_header(&mut reader)?;
+
+    Ok((header, Decompressor::new(reader, 1 << 16)))
+  }
+
+  /// Call `load` with each table in the snapshot read by `reader`, which must
+  /// read the table's entries.
+  pub(super) fn read_tables(
+    reader: &mut impl Read,
+    mut load: impl FnMut(&dyn RawTable, &mut dyn Read) -> Result,
+  ) -> Result {
+    loop {
+      let name = String::from_utf8(read_bytes(reader)?)?;
+
+      if name.is_empty() {
+        return Ok(());
+      }
+
+      let table = TABLES
+        .iter()
+        .find(|table| table.name() == name)
+        .ok_or_else(|| anyhow!("snapshot contains unknown table {name}"))?;
+
+      log::info!("Restoring table {name}…");
+
+      load(*table, reader)?;
+    }
+  }
+}
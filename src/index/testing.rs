@@ -3,6 +3,7 @@ use {super::*, bitcoin::script::PushBytes, std::ffi::OsString, tempfile::TempDir
 pub(crate) struct ContextBuilder {
   args: Vec<OsString>,
   chain: Chain,
+  esplora: bool,
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   tempdir: Option<TempDir>,
 }
@@ -30,7 +31,14 @@ impl ContextBuilder {
       format!("--chain={}", self.chain).into(),
     ];
 
-    let options = Options::try_parse_from(command.into_iter().chain(self.args)).unwrap();
+    let esplora = if self.esplora {
+      vec!["--esplora-url".into(), core.esplora_url().into()]
+    } else {
+      Vec::new()
+    };
+
+    let options =
+      Options::try_parse_from(command.into_iter().chain(esplora).chain(self.args)).unwrap();
 
     let index = Index::open_with_event_sender(
       &Settings::from_options(options).or_defaults().unwrap(),
@@ -61,6 +69,11 @@ impl ContextBuilder {
     self
   }
 
+  pub(crate) fn esplora(mut self) -> Self {
+    self.esplora = true;
+    self
+  }
+
   pub(crate) fn tempdir(mut self, tempdir: TempDir) -> Self {
     self.tempdir = Some(tempdir);
     self
@@ -84,6 +97,7 @@ impl Context {
     ContextBuilder {
       args: Vec::new(),
       chain: Chain::Regtest,
+      esplora: false,
       event_sender: None,
       tempdir: None,
     }
@@ -0,0 +1,313 @@
+use {
+  super::*,
+  redb::{AccessGuard, Key, MultimapTable, MultimapTableHandle, TableHandle, WriteTransaction},
+  snapshot::{read_bytes, write_bytes, TABLES},
+  std::{borrow::Borrow, io::Read},
+};
+
+/// Records the previous state of every key written while indexing a block, so
+/// that the block can be undone later. Writes made through a disabled log are
+/// performed without being recorded.
+///
+/// Each record is the table name, the key, whether the key, or for multimap
+/// tables the key-value pair, was present, and the previous value.
+#[derive(Default)]
+pub(crate) struct UndoLog {
+  records: Option<Vec<u8>>,
+}
+
+impl UndoLog {
+  pub(crate) fn new(enabled: bool) -> Self {
+    Self {
+      records: enabled.then(Vec::new),
+    }
+  }
+
+  pub(crate) fn is_enabled(&self) -> bool {
+    self.records.is_some()
+  }
+
+  /// Make writes on behalf of the block at `height` after it has been
+  /// indexed, such as statistics updated when committing, appending them to
+  /// the block's undo records if it has any.
+  pub(crate) fn append(
+    wtx: &WriteTransaction,
+    height: u32,
+    f: impl FnOnce(&mut Self) -> Result,
+  ) -> Result {
+    let mut undo = Self {
+      records: wtx
+        .open_table(HEIGHT_TO_UNDO)?
+        .get(height)?
+        .map(|records| records.value().to_vec()),
+    };
+
+    f(&mut undo)?;
+
+    if let Some(records) = undo.records {
+      wtx
+        .open_table(HEIGHT_TO_UNDO)?
+        .insert(height, records.as_slice())?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn insert<'k, 'v, K: Key + 'static, V: redb::Value + 'static>(
+    &mut self,
+    table: &mut Table<K, V>,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result {
+    self.record_key(table.name(), K::as_bytes(key.borrow()).as_ref())?;
+    let old = table.insert(key, value)?;
+    self.record_value::<V>(old.as_ref())
+  }
+
+  pub(crate) fn remove<'t, 'k, K: Key + 'static, V: redb::Value + 'static>(
+    &mut self,
+    table: &'t mut Table<K, V>,
+    key: impl Borrow<K::SelfType<'k>>,
+  ) -> Result<Option<AccessGuard<'t, V>>> {
+    self.record_key(table.name(), K::as_bytes(key.borrow()).as_ref())?;
+    let old = table.remove(key)?;
+    self.record_value::<V>(old.as_ref())?;
+    Ok(old)
+  }
+
+  pub(crate) fn pop_first<K: Key + 'static, V: redb::Value + 'static>(
+    &mut self,
+    table: &mut Table<K, V>,
+  ) -> Result {
+    let Some(key) = table
+      .first()?
+      .map(|(key, _value)| K::as_bytes(&key.value()).as_ref().to_vec())
+    else {
+      return Ok(());
+    };
+
+    self.remove(table, K::from_bytes(&key))?;
+
+    Ok(())
+  }
+
+  pub(crate) fn multimap_insert<'k, 'v, K: Key + 'static, V: Key + 'static>(
+    &mut self,
+    table: &mut MultimapTable<K, V>,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<bool> {
+    let present = table.insert(key.borrow(), value.borrow())?;
+    self.record_pair::<K, V>(table.name(), key, value, present)?;
+    Ok(present)
+  }
+
+  pub(crate) fn multimap_remove<'k, 'v, K: Key + 'static, V: Key + 'static>(
+    &mut self,
+    table: &mut MultimapTable<K, V>,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<bool> {
+    let present = table.remove(key.borrow(), value.borrow())?;
+    self.record_pair::<K, V>(table.name(), key, value, present)?;
+    Ok(present)
+  }
+
+  fn record_key(&mut self, table: &str, key: &[u8]) -> Result {
+    if let Some(records) = &mut self.records {
+      write_bytes(records, table.as_bytes())?;
+      write_bytes(records, key)?;
+    }
+
+    Ok(())
+  }
+
+  fn record_value<V: redb::Value + 'static>(&mut self, old: Option<&AccessGuard<V>>) -> Result {
+    if let Some(records) = &mut self.records {
+      match old {
+        Some(old) => {
+          records.push(1);
+          write_bytes(records, V::as_bytes(&old.value()).as_ref())?;
+        }
+        None => {
+          records.push(0);
+          write_bytes(records, &[])?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn record_pair<'k, 'v, K: Key + 'static, V: Key + 'static>(
+    &mut self,
+    table: &str,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+    present: bool,
+  ) -> Result {
+    if let Some(records) = &mut self.records {
+      write_bytes(records, table.as_bytes())?;
+      write_bytes(records, K::as_bytes(key.borrow()).as_ref())?;
+      records.push(present.into());
+      write_bytes(records, V::as_bytes(value.borrow()).as_ref())?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Index {
+  /// Store the undo records for the block at `height`, and prune records more
+  /// than `--undo-depth` blocks old.
+  pub(crate) fn write_undo(&self, wtx: &WriteTransaction, height: u32, undo: UndoLog) -> Result {
+    let Some(records) = undo.records else {
+      return Ok(());
+    };
+
+    let mut height_to_undo = wtx.open_table(HEIGHT_TO_UNDO)?;
+
+    height_to_undo.insert(height, records.as_slice())?;
+
+    let depth = self.settings.undo_depth().unwrap_or_default();
+
+    height_to_undo.retain_in(..(height + 1).saturating_sub(depth), |_, _| false)?;
+
+    Ok(())
+  }
+
+  /// Returns true if every block above `height` can be undone
+  pub(crate) fn can_roll_back(&self, height: u32) -> Result<bool> {
+    let rtx = self.database.begin_read()?;
+
+    let block_count = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    let height_to_undo = rtx.open_table(HEIGHT_TO_UNDO)?;
+
+    for height in height + 1..block_count {
+      if height_to_undo.get(height)?.is_none() {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+
+  /// Undo every block above `height` using undo records, so that `height`
+  /// becomes the tip of the index. Persistent savepoints which may contain
+  /// blocks that have been undone are deleted.
+  pub fn rollback(&self, height: u32) -> Result {
+    ensure!(
+      !self.settings.read_only(),
+      "cannot roll back index in read-only mode"
+    );
+
+    let block_count = self.block_count()?;
+
+    ensure!(
+      height < block_count,
+      "cannot roll back to height {height}, index tip is at height {}",
+      i64::from(block_count) - 1,
+    );
+
+    ensure!(
+      self.can_roll_back(height)?,
+      "cannot roll back to height {height}, undo records are only kept for blocks within `--undo-depth` of the tip",
+    );
+
+    log::info!(
+      "Rolling back index from height {} to {height}",
+      block_count - 1
+    );
+
+    let reverted = if self.index_events || self.event_sender.is_some() {
+      Reorg::reverted_events(self, height + 1)?
+    } else {
+      Vec::new()
+    };
+
+    let wtx = self.begin_write()?;
+
+    for block in (height + 1..block_count).rev() {
+      let records = wtx
+        .open_table(HEIGHT_TO_UNDO)?
+        .remove(block)?
+        .unwrap()
+        .value()
+        .to_vec();
+
+      let mut reader = records.as_slice();
+      let mut entries = Vec::new();
+
+      while !reader.is_empty() {
+        let table = String::from_utf8(read_bytes(&mut reader)?)?;
+        let key = read_bytes(&mut reader)?;
+        let mut present = [0];
+        reader.read_exact(&mut present)?;
+        let value = read_bytes(&mut reader)?;
+        entries.push((table, key, present[0] != 0, value));
+      }
+
+      for (name, key, present, value) in entries.into_iter().rev() {
+        let table = TABLES
+          .iter()
+          .find(|table| table.name() == name)
+          .ok_or_else(|| anyhow!("undo record for unknown table {name}"))?;
+
+        table.revert(&wtx, &key, &value, present)?;
+      }
+    }
+
+    // a savepoint taken at height `h` contains blocks below `h`, so if the
+    // last savepoint was taken at or below the new tip, every savepoint is
+    // still valid. otherwise, since the heights of earlier savepoints are not
+    // recorded, they are all deleted, and a new one is taken after the
+    // rollback is committed.
+    let savepoints_valid = wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::LastSavepointHeight.key())?
+      .is_some_and(|last| last.value() <= u64::from(height) + 1);
+
+    if !savepoints_valid {
+      for savepoint in wtx.list_persistent_savepoints()?.collect::<Vec<u64>>() {
+        wtx.delete_persistent_savepoint(savepoint)?;
+      }
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)?
+        .remove(&Statistic::LastSavepointHeight.key())?;
+    }
+
+    let reverted = reverted
+      .into_iter()
+      .map(|(_height, event)| event)
+      .collect::<Vec<Event>>();
+
+    if self.index_events {
+      Index::log_events(&wtx, &reverted)?;
+    }
+
+    Index::increment_statistic(&wtx, Statistic::Commits, 1, &mut UndoLog::default())?;
+    wtx.commit()?;
+
+    if !savepoints_valid {
+      Reorg::update_savepoints(self, height + 1)?;
+    }
+
+    if let Some(sender) = &self.event_sender {
+      for event in reverted {
+        sender.blocking_send(event)?;
+      }
+    }
+
+    log::info!("Successfully rolled back index to height {height}");
+
+    Ok(())
+  }
+}
@@ -1,6 +1,10 @@
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
-  super::{fetcher::Fetcher, *},
+  super::{
+    esplora::Esplora,
+    fetcher::{Fetcher, TransactionFetcher},
+    *,
+  },
   futures::future::try_join_all,
   tokio::sync::{
     broadcast::{self, error::TryRecvError},
@@ -43,19 +47,9 @@ pub(crate) struct Updater<'index> {
 impl Updater<'_> {
   pub(crate) fn update_index(&mut self, mut wtx: WriteTransaction) -> Result {
     let start = Instant::now();
-    let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
+    let starting_height = u32::try_from(self.index.client.block_count()?).unwrap() + 1;
     let starting_index_height = self.height;
 
-    wtx
-      .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
-      .insert(
-        &self.height,
-        &SystemTime::now()
-          .duration_since(SystemTime::UNIX_EPOCH)
-          .map(|duration| duration.as_millis())
-          .unwrap_or(0),
-      )?;
-
     let mut progress_bar = if cfg!(test)
       || log_enabled!(log::Level::Info)
       || starting_height <= self.height
@@ -78,19 +72,38 @@ impl Updater<'_> {
     let mut uncommitted = 0;
     let mut utxo_cache = HashMap::new();
     while let Ok(block) = rx.recv() {
+      let mut undo = UndoLog::new(
+        self
+          .index
+          .settings
+          .undo_depth()
+          .is_some_and(|depth| starting_height.saturating_sub(self.height) <= depth),
+      );
+
+      if uncommitted == 0 {
+        undo.insert(
+          &mut wtx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?,
+          &self.height,
+          &SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis(),
+        )?;
+      }
+
       self.index_block(
         &mut output_sender,
         &mut txout_receiver,
         &mut wtx,
         block,
         &mut utxo_cache,
+        undo,
       )?;
 
       if let Some(progress_bar) = &mut progress_bar {
         progress_bar.inc(1);
 
         if progress_bar.position() > progress_bar.length().unwrap() {
-          if let Ok(count) = self.index.client.get_block_count() {
+          if let Ok(count) = self.index.client.block_count() {
             progress_bar.set_length(count + 1);
           } else {
             log::warn!("Failed to fetch latest block height");
@@ -120,14 +133,6 @@ impl Updater<'_> {
           // write transaction
           break;
         }
-        wtx
-          .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
-          .insert(
-            &self.height,
-            &SystemTime::now()
-              .duration_since(SystemTime::UNIX_EPOCH)?
-              .as_millis(),
-          )?;
       }
 
       if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
@@ -136,10 +141,13 @@ impl Updater<'_> {
     }
 
     if starting_index_height == 0 && self.height > 0 {
-      wtx.open_table(STATISTIC_TO_COUNT)?.insert(
-        Statistic::InitialSyncTime.key(),
-        &u64::try_from(start.elapsed().as_micros())?,
-      )?;
+      UndoLog::append(&wtx, self.height - 1, |undo| {
+        undo.insert(
+          &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+          Statistic::InitialSyncTime.key(),
+          &u64::try_from(start.elapsed().as_micros())?,
+        )
+      })?;
     }
 
     if uncommitted > 0 {
@@ -163,7 +171,26 @@ impl Updater<'_> {
 
     let height_limit = index.height_limit;
 
-    let client = index.settings.bitcoin_rpc_client(None)?;
+    let metrics = index.metrics().clone();
+
+    let client = chain_source::open(&index.settings)?;
+
+    let block_files = index.block_files.clone();
+
+    {
+      let mut block_files = block_files.lock().unwrap();
+
+      // block files are opened once Bitcoin Core has created the blocks
+      // directory, since the obfuscation key is read when they are opened
+      if block_files.is_none() {
+        *block_files = index
+          .settings
+          .block_files_dir()?
+          .filter(|dir| dir.is_dir())
+          .map(|dir| BlockFiles::open(dir, index.settings.chain().network()))
+          .transpose()?;
+      }
+    }
 
     thread::spawn(move || loop {
       if let Some(height_limit) = height_limit {
@@ -172,7 +199,15 @@ impl Updater<'_> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, first_index_height) {
+      let result = Self::get_block_with_retries(
+        &*client,
+        block_files.lock().unwrap().as_mut(),
+        height,
+        first_index_height,
+        &metrics,
+      );
+
+      match result {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -192,30 +227,49 @@ impl Updater<'_> {
   }
 
   fn get_block_with_retries(
-    client: &Client,
+    client: &dyn ChainSource,
+    mut block_files: Option<&mut BlockFiles>,
     height: u32,
     first_index_height: u32,
+    metrics: &Metrics,
   ) -> Result<Option<Block>> {
     let mut errors = 0;
     loop {
-      match client
-        .get_block_hash(height.into())
-        .into_option()
-        .and_then(|option| {
-          option
-            .map(|hash| {
-              if height >= first_index_height {
-                Ok(client.get_block(&hash)?)
-              } else {
-                Ok(Block {
-                  header: client.get_block_header(&hash)?,
-                  txdata: Vec::new(),
-                })
+      let start = Instant::now();
+
+      let result = client.block_hash(height.into()).and_then(|option| {
+        option
+          .map(|hash| {
+            if let Some(block_files) = block_files.as_deref_mut() {
+              match block_files.get_block(hash, height < first_index_height) {
+                Ok(Some(block)) => return Ok(block),
+                Ok(None) => {}
+                Err(err) => log::warn!("failed to read block {hash} from block files: {err}"),
               }
-            })
-            .transpose()
-        }) {
+            }
+
+            if height >= first_index_height {
+              client
+                .block(hash)?
+                .with_context(|| format!("block {hash} not found"))
+            } else {
+              Ok(Block {
+                header: client
+                  .block_header(hash)?
+                  .with_context(|| format!("block header {hash} not found"))?,
+                txdata: Vec::new(),
+              })
+            }
+          })
+          .transpose()
+      });
+
+      metrics.record_fetcher_request("block", start.elapsed());
+
+      match result {
         Err(err) => {
+          metrics.record_fetcher_error("block");
+
           if cfg!(test) {
             return Err(err);
           }
@@ -237,8 +291,23 @@ impl Updater<'_> {
   }
 
   fn spawn_fetcher(index: &Index) -> Result<(mpsc::Sender<OutPoint>, broadcast::Receiver<TxOut>)> {
-    let fetcher = Fetcher::new(&index.settings)?;
+    match index.settings.esplora_url() {
+      Some(url) => Self::spawn_transaction_fetcher(
+        index,
+        Esplora::new(
+          url,
+          index.settings.chain(),
+          index.settings.esplora_concurrency(),
+        )?,
+      ),
+      None => Self::spawn_transaction_fetcher(index, Fetcher::new(&index.settings)?),
+    }
+  }
 
+  fn spawn_transaction_fetcher(
+    index: &Index,
+    fetcher: impl TransactionFetcher + Send + 'static,
+  ) -> Result<(mpsc::Sender<OutPoint>, broadcast::Receiver<TxOut>)> {
     // A block probably has no more than 20k inputs
     const CHANNEL_BUFFER_SIZE: usize = 20_000;
 
@@ -254,6 +323,8 @@ impl Updater<'_> {
     // else runs a request, we keep this to 12.
     let parallel_requests: usize = index.settings.bitcoin_rpc_limit().try_into().unwrap();
 
+    let metrics = index.metrics().clone();
+
     thread::spawn(move || {
       let rt = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -281,8 +352,17 @@ impl Updater<'_> {
           let mut futs = Vec::with_capacity(parallel_requests);
           for chunk in outpoints.chunks(chunk_size) {
             let txids = chunk.iter().map(|outpoint| outpoint.txid).collect();
-            let fut = fetcher.get_transactions(txids);
-            futs.push(fut);
+            let fetcher = &fetcher;
+            let metrics = &metrics;
+            futs.push(async move {
+              let start = Instant::now();
+              let result = fetcher.get_transactions(txids).await;
+              metrics.record_fetcher_request("transactions", start.elapsed());
+              if result.is_err() {
+                metrics.record_fetcher_error("transactions");
+              }
+              result
+            });
           }
 
           let txs = match try_join_all(futs).await {
@@ -316,9 +396,17 @@ impl Updater<'_> {
     wtx: &mut WriteTransaction,
     block: BlockData,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
+    mut undo: UndoLog,
   ) -> Result<()> {
     Reorg::detect_reorg(&block, self.height, self.index)?;
 
+    // when recording undo records, which is only done within `--undo-depth`
+    // blocks of the tip, the UTXO cache is flushed after every block, so that
+    // all writes can be attributed to the block that made them
+    if undo.is_enabled() && !utxo_cache.is_empty() {
+      self.flush_utxo_cache(wtx, mem::take(utxo_cache), &mut UndoLog::default())?;
+    }
+
     let start = Instant::now();
     let mut sat_ranges_written = 0;
     let mut outputs_in_block = 0;
@@ -335,6 +423,8 @@ impl Updater<'_> {
       wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
 
+    let mut event_log = self.index.index_events.then(Vec::new);
+
     if self.index.index_inscriptions || self.index.index_addresses || self.index.index_sats {
       self.index_utxo_entries(
         &block,
@@ -342,6 +432,8 @@ impl Updater<'_> {
         output_sender,
         utxo_cache,
         wtx,
+        event_log.as_mut(),
+        &mut undo,
         &mut inscription_id_to_sequence_number,
         &mut statistic_to_count,
         &mut sat_ranges_written,
@@ -352,8 +444,17 @@ impl Updater<'_> {
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+      let mut rune_balance_to_holder = wtx.open_multimap_table(RUNE_BALANCE_TO_HOLDER)?;
+      let mut rune_holder_to_balance = wtx.open_table(RUNE_HOLDER_TO_BALANCE)?;
+      let mut rune_id_to_holder_count = wtx.open_table(RUNE_ID_TO_HOLDER_COUNT)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
+      let mut runic_outpoint_to_height = wtx.open_table(RUNIC_OUTPOINT_TO_HEIGHT)?;
+      let mut runic_outpoint_to_script_pubkey = wtx.open_table(RUNIC_OUTPOINT_TO_SCRIPT_PUBKEY)?;
+      let mut script_pubkey_to_runic_outpoint =
+        wtx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNIC_OUTPOINT)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+      let mut spent_runic_outpoint_to_balances =
+        wtx.open_table(SPENT_RUNIC_OUTPOINT_TO_BALANCES)?;
       let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
 
       let runes = statistic_to_count
@@ -362,11 +463,14 @@ impl Updater<'_> {
         .unwrap_or(0);
 
       let mut rune_updater = RuneUpdater {
+        event_log: event_log.as_mut(),
         event_sender: self.index.event_sender.as_ref(),
         block_time: block.header.time,
         burned: HashMap::new(),
-        client: &self.index.client,
+        client: &*self.index.client,
         height: self.height,
+        history: self.index.index_rune_history,
+        holders: self.index.index_rune_holders,
         id_to_entry: &mut rune_id_to_rune_entry,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
         minimum: Rune::minimum_at_height(
@@ -374,11 +478,19 @@ impl Updater<'_> {
           Height(self.height),
         ),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        rune_balance_to_holder: &mut rune_balance_to_holder,
+        rune_holder_to_balance: &mut rune_holder_to_balance,
+        rune_id_to_holder_count: &mut rune_id_to_holder_count,
         rune_to_id: &mut rune_to_rune_id,
         runes,
+        runic_outpoint_to_height: &mut runic_outpoint_to_height,
+        runic_outpoint_to_script_pubkey: &mut runic_outpoint_to_script_pubkey,
+        script_pubkey_to_runic_outpoint: &mut script_pubkey_to_runic_outpoint,
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
+        spent_runic_outpoint_to_balances: &mut spent_runic_outpoint_to_balances,
         statistic_to_count: &mut statistic_to_count,
         transaction_id_to_rune: &mut transaction_id_to_rune,
+        undo: &mut undo,
       };
 
       for (i, (tx, txid)) in block.txdata.iter().enumerate() {
@@ -388,7 +500,21 @@ impl Updater<'_> {
       rune_updater.update()?;
     }
 
-    height_to_block_header.insert(&self.height, &block.header.store())?;
+    undo.insert(
+      &mut height_to_block_header,
+      &self.height,
+      &block.header.store(),
+    )?;
+
+    if let Some(event_log) = event_log {
+      Index::log_events(wtx, &event_log)?;
+    }
+
+    if undo.is_enabled() {
+      self.flush_utxo_cache(wtx, mem::take(utxo_cache), &mut undo)?;
+    }
+
+    self.index.write_undo(wtx, self.height, undo)?;
 
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
@@ -408,15 +534,25 @@ impl Updater<'_> {
     output_sender: &mut mpsc::Sender<OutPoint>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
     wtx: &'wtx WriteTransaction,
+    event_log: Option<&mut Vec<Event>>,
+    undo: &mut UndoLog,
     inscription_id_to_sequence_number: &mut Table<'wtx, (u128, u128, u32), u32>,
     statistic_to_count: &mut Table<'wtx, u64, u64>,
     sat_ranges_written: &mut u64,
     outputs_in_block: &mut u64,
   ) -> Result<(), Error> {
+    let mut content_hash_to_sequence_number =
+      wtx.open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBER)?;
+    let mut content_type_to_sequence_number = wtx.open_table(CONTENT_TYPE_TO_SEQUENCE_NUMBER)?;
+    let mut delegate_to_sequence_number = wtx.open_multimap_table(DELEGATE_TO_SEQUENCE_NUMBER)?;
+    let mut fee_to_sequence_number = wtx.open_table(FEE_TO_SEQUENCE_NUMBER)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
     let mut inscription_number_to_sequence_number =
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut media_to_sequence_number = wtx.open_table(MEDIA_TO_SEQUENCE_NUMBER)?;
+    let mut metadata_to_sequence_number = wtx.open_table(METADATA_TO_SEQUENCE_NUMBER)?;
+    let mut metaprotocol_to_sequence_number = wtx.open_table(METAPROTOCOL_TO_SEQUENCE_NUMBER)?;
     let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
     let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
@@ -424,6 +560,8 @@ impl Updater<'_> {
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let mut sequence_number_to_inscription_history =
+      wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_HISTORY)?;
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
     let index_inscriptions = self.height >= self.index.settings.first_inscription_height()
@@ -503,7 +641,12 @@ impl Updater<'_> {
 
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
+      content_hash_to_sequence_number: &mut content_hash_to_sequence_number,
+      content_type_to_sequence_number: &mut content_type_to_sequence_number,
       cursed_inscription_count,
+      delegate_to_sequence_number: &mut delegate_to_sequence_number,
+      event_log,
+      fee_to_sequence_number: &mut fee_to_sequence_number,
       flotsam: Vec::new(),
       height: self.height,
       home_inscription_count,
@@ -511,11 +654,15 @@ impl Updater<'_> {
       id_to_sequence_number: inscription_id_to_sequence_number,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
       lost_sats,
+      media_to_sequence_number: &mut media_to_sequence_number,
+      metadata_to_sequence_number: &mut metadata_to_sequence_number,
+      metaprotocol_to_sequence_number: &mut metaprotocol_to_sequence_number,
       next_sequence_number,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
+      sequence_number_to_history: &mut sequence_number_to_inscription_history,
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
@@ -554,10 +701,10 @@ impl Updater<'_> {
             let entry = if let Some(entry) = utxo_cache.remove(&OutPoint::load(outpoint)) {
               self.outputs_cached += 1;
               entry
-            } else if let Some(entry) = outpoint_to_utxo_entry.remove(&outpoint)? {
+            } else if let Some(entry) = undo.remove(&mut outpoint_to_utxo_entry, &outpoint)? {
               if self.index.index_addresses {
                 let script_pubkey = entry.value().parse(self.index).script_pubkey();
-                if !script_pubkey_to_outpoint.remove(script_pubkey, outpoint)? {
+                if !undo.multimap_remove(&mut script_pubkey_to_outpoint, script_pubkey, outpoint)? {
                   panic!("script pubkey entry ({script_pubkey:?}, {outpoint:?}) not found");
                 }
               }
@@ -618,6 +765,7 @@ impl Updater<'_> {
           tx,
           *txid,
           &mut sat_to_satpoint,
+          undo,
           &mut output_utxo_entries,
           input_sat_ranges.as_ref().unwrap(),
           leftover_sat_ranges,
@@ -643,6 +791,7 @@ impl Updater<'_> {
           &input_utxo_entries,
           &mut output_utxo_entries,
           utxo_cache,
+          undo,
           self.index,
           input_sat_ranges.as_ref(),
         )?;
@@ -655,8 +804,11 @@ impl Updater<'_> {
     }
 
     if index_inscriptions {
-      height_to_last_sequence_number
-        .insert(&self.height, inscription_updater.next_sequence_number)?;
+      undo.insert(
+        &mut height_to_last_sequence_number,
+        &self.height,
+        inscription_updater.next_sequence_number,
+      )?;
     }
 
     if !lost_sat_ranges.is_empty() {
@@ -670,7 +822,8 @@ impl Updater<'_> {
       for chunk in lost_sat_ranges.chunks_exact(11) {
         let (start, end) = SatRange::load(chunk.try_into().unwrap());
         if !Sat(start).common() {
-          sat_to_satpoint.insert(
+          undo.insert(
+            &mut sat_to_satpoint,
             &start,
             &SatPoint {
               outpoint: OutPoint::null(),
@@ -692,7 +845,8 @@ impl Updater<'_> {
       *utxo_entry = UtxoEntryBuf::merged(utxo_entry, &new_utxo_entry, self.index);
     }
 
-    statistic_to_count.insert(
+    undo.insert(
+      statistic_to_count,
       &Statistic::LostSats.key(),
       &if self.index.index_sats {
         lost_sats
@@ -701,17 +855,20 @@ impl Updater<'_> {
       },
     )?;
 
-    statistic_to_count.insert(
+    undo.insert(
+      statistic_to_count,
       &Statistic::CursedInscriptions.key(),
       &inscription_updater.cursed_inscription_count,
     )?;
 
-    statistic_to_count.insert(
+    undo.insert(
+      statistic_to_count,
       &Statistic::BlessedInscriptions.key(),
       &inscription_updater.blessed_inscription_count,
     )?;
 
-    statistic_to_count.insert(
+    undo.insert(
+      statistic_to_count,
       &Statistic::UnboundInscriptions.key(),
       &inscription_updater.unbound_inscriptions,
     )?;
@@ -734,6 +891,7 @@ impl Updater<'_> {
     tx: &Transaction,
     txid: Txid,
     sat_to_satpoint: &mut Table<u64, &SatPointValue>,
+    undo: &mut UndoLog,
     output_utxo_entries: &mut [UtxoEntryBuf],
     input_sat_ranges: &[&[u8]],
     leftover_sat_ranges: &mut Vec<u8>,
@@ -774,7 +932,8 @@ impl Updater<'_> {
         });
 
         if !Sat(range.0).common() {
-          sat_to_satpoint.insert(
+          undo.insert(
+            sat_to_satpoint,
             &range.0,
             &SatPoint {
               outpoint,
@@ -829,48 +988,85 @@ impl Updater<'_> {
       self.outputs_cached
     );
 
-    {
-      let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
-      let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
-      let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
-
-      for (outpoint, mut utxo_entry) in utxo_cache {
-        if Index::is_special_outpoint(outpoint) {
-          if let Some(old_entry) = outpoint_to_utxo_entry.get(&outpoint.store())? {
-            utxo_entry = UtxoEntryBuf::merged(old_entry.value(), &utxo_entry, self.index);
-          }
-        }
-
-        outpoint_to_utxo_entry.insert(&outpoint.store(), utxo_entry.as_ref())?;
-
-        let utxo_entry = utxo_entry.parse(self.index);
-        if self.index.index_addresses {
-          let script_pubkey = utxo_entry.script_pubkey();
-          script_pubkey_to_outpoint.insert(script_pubkey, &outpoint.store())?;
-        }
+    let start = Instant::now();
 
-        if self.index.index_inscriptions {
-          for (sequence_number, offset) in utxo_entry.parse_inscriptions() {
-            let satpoint = SatPoint { outpoint, offset };
-            sequence_number_to_satpoint.insert(sequence_number, &satpoint.store())?;
-          }
-        }
-      }
-    }
+    self.flush_utxo_cache(&wtx, utxo_cache, &mut UndoLog::default())?;
 
-    Index::increment_statistic(&wtx, Statistic::OutputsTraversed, self.outputs_traversed)?;
+    UndoLog::append(&wtx, self.height - 1, |undo| {
+      Index::increment_statistic(
+        &wtx,
+        Statistic::OutputsTraversed,
+        self.outputs_traversed,
+        undo,
+      )?;
+      Index::increment_statistic(
+        &wtx,
+        Statistic::SatRanges,
+        self.sat_ranges_since_flush,
+        undo,
+      )?;
+      Index::increment_statistic(&wtx, Statistic::Commits, 1, undo)
+    })?;
     self.outputs_traversed = 0;
-    Index::increment_statistic(&wtx, Statistic::SatRanges, self.sat_ranges_since_flush)?;
     self.sat_ranges_since_flush = 0;
-    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
     // Commit twice since due to a bug redb will only reuse pages freed in the
     // transaction before last.
     self.index.begin_write()?.commit()?;
 
+    self.index.metrics().record_commit(start.elapsed());
+
     Reorg::update_savepoints(self.index, self.height)?;
 
     Ok(())
   }
+
+  fn flush_utxo_cache(
+    &mut self,
+    wtx: &WriteTransaction,
+    utxo_cache: HashMap<OutPoint, UtxoEntryBuf>,
+    undo: &mut UndoLog,
+  ) -> Result {
+    let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+    let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+    let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    for (outpoint, mut utxo_entry) in utxo_cache {
+      if Index::is_special_outpoint(outpoint) {
+        if let Some(old_entry) = outpoint_to_utxo_entry.get(&outpoint.store())? {
+          utxo_entry = UtxoEntryBuf::merged(old_entry.value(), &utxo_entry, self.index);
+        }
+      }
+
+      undo.insert(
+        &mut outpoint_to_utxo_entry,
+        &outpoint.store(),
+        utxo_entry.as_ref(),
+      )?;
+
+      let utxo_entry = utxo_entry.parse(self.index);
+      if self.index.index_addresses {
+        let script_pubkey = utxo_entry.script_pubkey();
+        undo.multimap_insert(
+          &mut script_pubkey_to_outpoint,
+          script_pubkey,
+          &outpoint.store(),
+        )?;
+      }
+
+      if self.index.index_inscriptions {
+        for (sequence_number, offset) in utxo_entry.parse_inscriptions() {
+          let satpoint = SatPoint { outpoint, offset };
+          undo.insert(
+            &mut sequence_number_to_satpoint,
+            sequence_number,
+            &satpoint.store(),
+          )?;
+        }
+      }
+    }
+
+    Ok(())
+  }
 }
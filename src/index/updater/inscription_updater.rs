@@ -13,6 +13,25 @@ enum Curse {
   UnrecognizedEvenField,
 }
 
+/// Attributes of new inscriptions stored in secondary indices when the index
+/// is created with `--index-inscription-attributes`
+#[derive(Debug, Clone)]
+struct Attributes {
+  content_type: Option<String>,
+  media: Media,
+  metaprotocol: Option<String>,
+}
+
+impl Attributes {
+  fn new(inscription: &Inscription) -> Self {
+    Self {
+      content_type: inscription.content_type().map(normalize_content_type),
+      media: inscription.media(),
+      metaprotocol: inscription.metaprotocol().map(str::to_string),
+    }
+  }
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct Flotsam {
   inscription_id: InscriptionId,
@@ -23,9 +42,13 @@ pub(super) struct Flotsam {
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    attributes: Option<Attributes>,
+    content_hashes: Vec<[u8; 32]>,
     cursed: bool,
+    delegate: Option<InscriptionId>,
     fee: u64,
     hidden: bool,
+    metadata: Vec<(String, String)>,
     parents: Vec<InscriptionId>,
     reinscription: bool,
     unbound: bool,
@@ -39,7 +62,12 @@ enum Origin {
 
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
+  pub(super) content_hash_to_sequence_number: &'a mut MultimapTable<'tx, &'static [u8; 32], u32>,
+  pub(super) content_type_to_sequence_number: &'a mut Table<'tx, (&'static str, u32), ()>,
   pub(super) cursed_inscription_count: u64,
+  pub(super) delegate_to_sequence_number: &'a mut MultimapTable<'tx, InscriptionIdValue, u32>,
+  pub(super) event_log: Option<&'a mut Vec<Event>>,
+  pub(super) fee_to_sequence_number: &'a mut Table<'tx, (u64, u32), ()>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
@@ -47,6 +75,9 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) id_to_sequence_number: &'a mut Table<'tx, InscriptionIdValue, u32>,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'tx, i32, u32>,
   pub(super) lost_sats: u64,
+  pub(super) media_to_sequence_number: &'a mut Table<'tx, (&'static str, u32), ()>,
+  pub(super) metadata_to_sequence_number: &'a mut Table<'tx, (&'static str, &'static str, u32), ()>,
+  pub(super) metaprotocol_to_sequence_number: &'a mut Table<'tx, (&'static str, u32), ()>,
   pub(super) next_sequence_number: u32,
   pub(super) reward: u64,
   pub(super) transaction_buffer: Vec<u8>,
@@ -54,6 +85,8 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
+  pub(super) sequence_number_to_history:
+    &'a mut Table<'tx, (u32, u32), InscriptionHistoryEntryValue>,
   pub(super) timestamp: u32,
   pub(super) unbound_inscriptions: u64,
 }
@@ -66,6 +99,7 @@ impl InscriptionUpdater<'_, '_> {
     input_utxo_entries: &[ParsedUtxoEntry],
     output_utxo_entries: &mut [UtxoEntryBuf],
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
+    undo: &mut UndoLog,
     index: &Index,
     input_sat_ranges: Option<&Vec<&[u8]>>,
   ) -> Result {
@@ -196,9 +230,26 @@ impl InscriptionUpdater<'_, '_> {
           inscription_id,
           offset,
           origin: Origin::New {
+            attributes: index
+              .index_inscription_attributes
+              .then(|| Attributes::new(&inscription.payload)),
+            content_hashes: if index.index_content_hashes {
+              inscription.payload.content_hashes()
+            } else {
+              Vec::new()
+            },
             cursed: curse.is_some() && !jubilant,
+            delegate: inscription
+              .payload
+              .delegate()
+              .filter(|_| index.index_delegates),
             fee: 0,
             hidden: inscription.payload.hidden(),
+            metadata: if index.index_metadata {
+              inscription.payload.metadata_attributes()
+            } else {
+              Vec::new()
+            },
             parents: inscription.payload.parents(),
             reinscription: inscribed_offsets.contains_key(&offset),
             unbound: input_value == 0
@@ -222,9 +273,11 @@ impl InscriptionUpdater<'_, '_> {
       tx.consensus_encode(&mut self.transaction_buffer)
         .expect("in-memory writers don't error");
 
-      self
-        .transaction_id_to_transaction
-        .insert(&txid.store(), self.transaction_buffer.as_slice())?;
+      undo.insert(
+        self.transaction_id_to_transaction,
+        &txid.store(),
+        self.transaction_buffer.as_slice(),
+      )?;
 
       self.transaction_buffer.clear();
     }
@@ -291,27 +344,25 @@ impl InscriptionUpdater<'_, '_> {
           offset: flotsam.offset - output_value,
         };
 
-        new_locations.push((
-          new_satpoint,
-          inscriptions.next().unwrap(),
-          txout.script_pubkey.is_op_return(),
-        ));
+        new_locations.push((new_satpoint, inscriptions.next().unwrap(), txout));
       }
 
       output_value = end;
     }
 
-    for (new_satpoint, flotsam, op_return) in new_locations.into_iter() {
+    for (new_satpoint, flotsam, txout) in new_locations.into_iter() {
       let output_utxo_entry =
         &mut output_utxo_entries[usize::try_from(new_satpoint.outpoint.vout).unwrap()];
 
       self.update_inscription_location(
         input_sat_ranges,
         flotsam,
+        txid,
         new_satpoint,
-        op_return,
+        Some(&txout.script_pubkey),
         Some(output_utxo_entry),
         utxo_cache,
+        undo,
         index,
       )?;
     }
@@ -325,10 +376,12 @@ impl InscriptionUpdater<'_, '_> {
         self.update_inscription_location(
           input_sat_ranges,
           flotsam,
+          txid,
           new_satpoint,
-          false,
+          None,
           None,
           utxo_cache,
+          undo,
           index,
         )?;
       }
@@ -364,17 +417,36 @@ impl InscriptionUpdater<'_, '_> {
     unreachable!()
   }
 
+  fn emit(&mut self, index: &Index, event: Event) -> Result {
+    if let Some(event_log) = self.event_log.as_mut() {
+      event_log.push(event.clone());
+    }
+
+    if let Some(sender) = &index.event_sender {
+      sender.blocking_send(event)?;
+    }
+
+    Ok(())
+  }
+
   fn update_inscription_location(
     &mut self,
     input_sat_ranges: Option<&Vec<&[u8]>>,
     flotsam: Flotsam,
+    txid: Txid,
     new_satpoint: SatPoint,
-    op_return: bool,
+    script_pubkey: Option<&Script>,
     mut normal_output_utxo_entry: Option<&mut UtxoEntryBuf>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
+    undo: &mut UndoLog,
     index: &Index,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
+    let op_return = script_pubkey.is_some_and(Script::is_op_return);
+    let old_satpoint = match flotsam.origin {
+      Origin::Old { old_satpoint, .. } => Some(old_satpoint),
+      Origin::New { .. } => None,
+    };
     let (unbound, sequence_number) = match flotsam.origin {
       Origin::Old {
         sequence_number,
@@ -392,28 +464,34 @@ impl InscriptionUpdater<'_, '_> {
           let mut charms = entry.charms;
           Charm::Burned.set(&mut charms);
 
-          self.sequence_number_to_entry.insert(
+          undo.insert(
+            self.sequence_number_to_entry,
             sequence_number,
             &InscriptionEntry { charms, ..entry }.store(),
           )?;
         }
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
+        self.emit(
+          index,
+          Event::InscriptionTransferred {
             block_height: self.height,
             inscription_id,
             new_location: new_satpoint,
             old_location: old_satpoint,
             sequence_number,
-          })?;
-        }
+          },
+        )?;
 
         (false, sequence_number)
       }
       Origin::New {
+        attributes,
+        content_hashes,
         cursed,
+        delegate,
         fee,
         hidden,
+        metadata,
         parents,
         reinscription,
         unbound,
@@ -432,9 +510,11 @@ impl InscriptionUpdater<'_, '_> {
         let sequence_number = self.next_sequence_number;
         self.next_sequence_number += 1;
 
-        self
-          .inscription_number_to_sequence_number
-          .insert(inscription_number, sequence_number)?;
+        undo.insert(
+          self.inscription_number_to_sequence_number,
+          inscription_number,
+          sequence_number,
+        )?;
 
         let sat = if unbound {
           None
@@ -473,7 +553,53 @@ impl InscriptionUpdater<'_, '_> {
         }
 
         if let Some(Sat(n)) = sat {
-          self.sat_to_sequence_number.insert(&n, &sequence_number)?;
+          undo.multimap_insert(self.sat_to_sequence_number, &n, &sequence_number)?;
+        }
+
+        for hash in &content_hashes {
+          undo.multimap_insert(self.content_hash_to_sequence_number, hash, sequence_number)?;
+        }
+
+        if let Some(delegate) = delegate {
+          undo.multimap_insert(
+            self.delegate_to_sequence_number,
+            &delegate.store(),
+            sequence_number,
+          )?;
+        }
+
+        for (key, value) in &metadata {
+          undo.insert(
+            self.metadata_to_sequence_number,
+            (key.as_str(), value.as_str(), sequence_number),
+            (),
+          )?;
+        }
+
+        if let Some(attributes) = attributes {
+          undo.insert(self.fee_to_sequence_number, (fee, sequence_number), ())?;
+
+          if let Some(content_type) = &attributes.content_type {
+            undo.insert(
+              self.content_type_to_sequence_number,
+              (content_type.as_str(), sequence_number),
+              (),
+            )?;
+          }
+
+          undo.insert(
+            self.media_to_sequence_number,
+            (attributes.media.to_string().as_str(), sequence_number),
+            (),
+          )?;
+
+          if let Some(metaprotocol) = &attributes.metaprotocol {
+            undo.insert(
+              self.metaprotocol_to_sequence_number,
+              (metaprotocol.as_str(), sequence_number),
+              (),
+            )?;
+          }
         }
 
         let parent_sequence_numbers = parents
@@ -485,26 +611,30 @@ impl InscriptionUpdater<'_, '_> {
               .unwrap()
               .value();
 
-            self
-              .sequence_number_to_children
-              .insert(parent_sequence_number, sequence_number)?;
+            undo.multimap_insert(
+              self.sequence_number_to_children,
+              parent_sequence_number,
+              sequence_number,
+            )?;
 
             Ok(parent_sequence_number)
           })
           .collect::<Result<Vec<u32>>>()?;
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionCreated {
+        self.emit(
+          index,
+          Event::InscriptionCreated {
             block_height: self.height,
             charms,
             inscription_id,
             location: (!unbound).then_some(new_satpoint),
             parent_inscription_ids: parents,
             sequence_number,
-          })?;
-        }
+          },
+        )?;
 
-        self.sequence_number_to_entry.insert(
+        undo.insert(
+          self.sequence_number_to_entry,
           sequence_number,
           &InscriptionEntry {
             charms,
@@ -520,17 +650,21 @@ impl InscriptionUpdater<'_, '_> {
           .store(),
         )?;
 
-        self
-          .id_to_sequence_number
-          .insert(&inscription_id.store(), sequence_number)?;
+        undo.insert(
+          self.id_to_sequence_number,
+          &inscription_id.store(),
+          sequence_number,
+        )?;
 
         if !hidden {
-          self
-            .home_inscriptions
-            .insert(&sequence_number, inscription_id.store())?;
+          undo.insert(
+            self.home_inscriptions,
+            &sequence_number,
+            inscription_id.store(),
+          )?;
 
           if self.home_inscription_count == 100 {
-            self.home_inscriptions.pop_first()?;
+            undo.pop_first(self.home_inscriptions)?;
           } else {
             self.home_inscription_count += 1;
           }
@@ -565,6 +699,31 @@ impl InscriptionUpdater<'_, '_> {
 
     output_utxo_entry.push_inscription(sequence_number, satpoint.offset, index);
 
+    if index.index_inscription_history {
+      let next = self
+        .sequence_number_to_history
+        .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+        .next_back()
+        .transpose()?
+        .map(|(key, _)| key.value().1 + 1)
+        .unwrap_or_default();
+
+      undo.insert(
+        self.sequence_number_to_history,
+        (sequence_number, next),
+        InscriptionHistoryEntry {
+          height: self.height,
+          new_satpoint: satpoint,
+          old_satpoint,
+          script_pubkey: (!unbound)
+            .then(|| script_pubkey.map(Script::to_owned))
+            .flatten(),
+          txid,
+        }
+        .store(),
+      )?;
+    }
+
     Ok(())
   }
 }
@@ -3,18 +3,32 @@ use super::*;
 pub(super) struct RuneUpdater<'a, 'tx, 'client> {
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
-  pub(super) client: &'client Client,
+  pub(super) client: &'client dyn ChainSource,
+  pub(super) event_log: Option<&'a mut Vec<Event>>,
   pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
   pub(super) height: u32,
+  pub(super) history: bool,
+  pub(super) holders: bool,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) rune_balance_to_holder: &'a mut MultimapTable<'tx, (RuneIdValue, u128), &'static [u8]>,
+  pub(super) rune_holder_to_balance: &'a mut Table<'tx, (RuneIdValue, &'static [u8]), u128>,
+  pub(super) rune_id_to_holder_count: &'a mut Table<'tx, RuneIdValue, u64>,
   pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
+  pub(super) runic_outpoint_to_height: &'a mut Table<'tx, &'static OutPointValue, u32>,
+  pub(super) runic_outpoint_to_script_pubkey:
+    &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) script_pubkey_to_runic_outpoint:
+    &'a mut MultimapTable<'tx, &'static [u8], OutPointValue>,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
+  pub(super) spent_runic_outpoint_to_balances:
+    &'a mut Table<'tx, &'static OutPointValue, (u32, &'static [u8])>,
   pub(super) statistic_to_count: &'a mut Table<'tx, u64, u64>,
   pub(super) transaction_id_to_rune: &'a mut Table<'tx, &'static TxidValue, u128>,
+  pub(super) undo: &'a mut UndoLog,
 }
 
 impl RuneUpdater<'_, '_, '_> {
@@ -30,14 +44,12 @@ impl RuneUpdater<'_, '_, '_> {
         if let Some(amount) = self.mint(id)? {
           *unallocated.entry(id).or_default() += amount;
 
-          if let Some(sender) = self.event_sender {
-            sender.blocking_send(Event::RuneMinted {
-              block_height: self.height,
-              txid,
-              rune_id: id,
-              amount: amount.n(),
-            })?;
-          }
+          self.emit(Event::RuneMinted {
+            block_height: self.height,
+            txid,
+            rune_id: id,
+            amount: amount.n(),
+          })?;
         }
       }
 
@@ -195,37 +207,63 @@ impl RuneUpdater<'_, '_, '_> {
         vout: vout.try_into().unwrap(),
       };
 
+      let script_pubkey = tx.output[vout].script_pubkey.as_bytes();
+
+      if self.holders {
+        self.undo.insert(
+          self.runic_outpoint_to_script_pubkey,
+          &outpoint.store(),
+          script_pubkey,
+        )?;
+      }
+
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::RuneTransferred {
-            outpoint,
-            block_height: self.height,
-            txid,
-            rune_id: id,
-            amount: balance.0,
-          })?;
+        if self.holders {
+          self.update_holder_balance(id, script_pubkey, balance.n(), 0)?;
         }
+
+        self.emit(Event::RuneTransferred {
+          outpoint,
+          block_height: self.height,
+          txid,
+          rune_id: id,
+          amount: balance.0,
+        })?;
       }
 
-      self
-        .outpoint_to_balances
-        .insert(&outpoint.store(), buffer.as_slice())?;
+      self.undo.insert(
+        self.outpoint_to_balances,
+        &outpoint.store(),
+        buffer.as_slice(),
+      )?;
+
+      if self.history {
+        self.undo.insert(
+          self.runic_outpoint_to_height,
+          &outpoint.store(),
+          self.height,
+        )?;
+
+        self.undo.multimap_insert(
+          self.script_pubkey_to_runic_outpoint,
+          script_pubkey,
+          &outpoint.store(),
+        )?;
+      }
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
       *self.burned.entry(id).or_default() += amount;
 
-      if let Some(sender) = self.event_sender {
-        sender.blocking_send(Event::RuneBurned {
-          block_height: self.height,
-          txid,
-          rune_id: id,
-          amount: amount.n(),
-        })?;
-      }
+      self.emit(Event::RuneBurned {
+        block_height: self.height,
+        txid,
+        rune_id: id,
+        amount: amount.n(),
+      })?;
     }
 
     Ok(())
@@ -235,7 +273,9 @@ impl RuneUpdater<'_, '_, '_> {
     for (rune_id, burned) in self.burned {
       let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
       entry.burned = entry.burned.checked_add(burned.n()).unwrap();
-      self.id_to_entry.insert(&rune_id.store(), entry.store())?;
+      self
+        .undo
+        .insert(self.id_to_entry, &rune_id.store(), entry.store())?;
     }
 
     Ok(())
@@ -248,17 +288,21 @@ impl RuneUpdater<'_, '_, '_> {
     id: RuneId,
     rune: Rune,
   ) -> Result {
-    self.rune_to_id.insert(rune.store(), id.store())?;
     self
-      .transaction_id_to_rune
-      .insert(&txid.store(), rune.store())?;
+      .undo
+      .insert(self.rune_to_id, rune.store(), id.store())?;
+    self
+      .undo
+      .insert(self.transaction_id_to_rune, &txid.store(), rune.store())?;
 
     let number = self.runes;
     self.runes += 1;
 
-    self
-      .statistic_to_count
-      .insert(&Statistic::Runes.into(), self.runes)?;
+    self.undo.insert(
+      self.statistic_to_count,
+      &Statistic::Runes.into(),
+      self.runes,
+    )?;
 
     let entry = match artifact {
       Artifact::Cenotaph(_) => RuneEntry {
@@ -306,15 +350,15 @@ impl RuneUpdater<'_, '_, '_> {
       }
     };
 
-    self.id_to_entry.insert(id.store(), entry.store())?;
+    self
+      .undo
+      .insert(self.id_to_entry, id.store(), entry.store())?;
 
-    if let Some(sender) = self.event_sender {
-      sender.blocking_send(Event::RuneEtched {
-        block_height: self.height,
-        txid,
-        rune_id: id,
-      })?;
-    }
+    self.emit(Event::RuneEtched {
+      block_height: self.height,
+      txid,
+      rune_id: id,
+    })?;
 
     let inscription_id = InscriptionId { txid, index: 0 };
 
@@ -322,9 +366,23 @@ impl RuneUpdater<'_, '_, '_> {
       .inscription_id_to_sequence_number
       .get(&inscription_id.store())?
     {
-      self
-        .sequence_number_to_rune_id
-        .insert(sequence_number.value(), id.store())?;
+      self.undo.insert(
+        self.sequence_number_to_rune_id,
+        sequence_number.value(),
+        id.store(),
+      )?;
+    }
+
+    Ok(())
+  }
+
+  fn emit(&mut self, event: Event) -> Result {
+    if let Some(event_log) = self.event_log.as_mut() {
+      event_log.push(event.clone());
+    }
+
+    if let Some(sender) = self.event_sender {
+      sender.blocking_send(event)?;
     }
 
     Ok(())
@@ -363,9 +421,11 @@ impl RuneUpdater<'_, '_, '_> {
         .map(|entry| entry.value())
         .unwrap_or_default();
 
-      self
-        .statistic_to_count
-        .insert(&Statistic::ReservedRunes.into(), reserved_runes + 1)?;
+      self.undo.insert(
+        self.statistic_to_count,
+        &Statistic::ReservedRunes.into(),
+        reserved_runes + 1,
+      )?;
 
       Rune::reserved(self.height.into(), tx_index)
     };
@@ -394,7 +454,9 @@ impl RuneUpdater<'_, '_, '_> {
 
     rune_entry.mints += 1;
 
-    self.id_to_entry.insert(&id.store(), rune_entry.store())?;
+    self
+      .undo
+      .insert(self.id_to_entry, &id.store(), rune_entry.store())?;
 
     Ok(Some(Lot(amount)))
   }
@@ -424,10 +486,9 @@ impl RuneUpdater<'_, '_, '_> {
           continue;
         }
 
-        let Some(tx_info) = self
+        let Some((commit_tx, commit_tx_height)) = self
           .client
-          .get_raw_transaction_info(&input.previous_output.txid, None)
-          .into_option()?
+          .transaction_with_height(input.previous_output.txid)?
         else {
           panic!(
             "can't get input transaction: {}",
@@ -435,27 +496,17 @@ impl RuneUpdater<'_, '_, '_> {
           );
         };
 
-        let taproot = tx_info.vout[input.previous_output.vout.into_usize()]
-          .script_pub_key
-          .script()?
+        let taproot = commit_tx.output[input.previous_output.vout.into_usize()]
+          .script_pubkey
           .is_p2tr();
 
         if !taproot {
           continue;
         }
 
-        let commit_tx_height = self
-          .client
-          .get_block_header_info(&tx_info.blockhash.unwrap())
-          .into_option()?
-          .unwrap()
-          .height;
+        let commit_tx_height = commit_tx_height.unwrap();
 
-        let confirmations = self
-          .height
-          .checked_sub(commit_tx_height.try_into().unwrap())
-          .unwrap()
-          + 1;
+        let confirmations = self.height.checked_sub(commit_tx_height).unwrap() + 1;
 
         if confirmations >= Runestone::COMMIT_CONFIRMATIONS.into() {
           return Ok(true);
@@ -470,22 +521,116 @@ impl RuneUpdater<'_, '_, '_> {
     // map of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
 
+    // holder balances debited by spending tx inputs
+    let mut debits = Vec::new();
+
     // increment unallocated runes with the runes in tx inputs
     for input in &tx.input {
       if let Some(guard) = self
-        .outpoint_to_balances
-        .remove(&input.previous_output.store())?
+        .undo
+        .remove(self.outpoint_to_balances, &input.previous_output.store())?
       {
+        let script_pubkey = if self.holders {
+          self
+            .undo
+            .remove(
+              self.runic_outpoint_to_script_pubkey,
+              &input.previous_output.store(),
+            )?
+            .map(|script_pubkey| script_pubkey.value().to_vec())
+        } else {
+          None
+        };
+
         let buffer = guard.value();
         let mut i = 0;
         while i < buffer.len() {
           let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
           i += len;
           *unallocated.entry(id).or_default() += balance;
+
+          if let Some(script_pubkey) = &script_pubkey {
+            debits.push((id, script_pubkey.clone(), balance));
+          }
+        }
+
+        if self.history {
+          self.undo.insert(
+            self.spent_runic_outpoint_to_balances,
+            &input.previous_output.store(),
+            (self.height, buffer),
+          )?;
         }
       }
     }
 
+    for (id, script_pubkey, balance) in debits {
+      self.update_holder_balance(id, &script_pubkey, 0, balance)?;
+    }
+
     Ok(unallocated)
   }
+
+  fn update_holder_balance(
+    &mut self,
+    id: RuneId,
+    script_pubkey: &[u8],
+    credit: u128,
+    debit: u128,
+  ) -> Result {
+    let old = self
+      .rune_holder_to_balance
+      .get((id.store(), script_pubkey))?
+      .map(|balance| balance.value())
+      .unwrap_or_default();
+
+    let new = old.checked_add(credit).unwrap().checked_sub(debit).unwrap();
+
+    if old == new {
+      return Ok(());
+    }
+
+    if old > 0 {
+      self.undo.multimap_remove(
+        self.rune_balance_to_holder,
+        (id.store(), old),
+        script_pubkey,
+      )?;
+    }
+
+    if new > 0 {
+      self.undo.insert(
+        self.rune_holder_to_balance,
+        (id.store(), script_pubkey),
+        new,
+      )?;
+      self.undo.multimap_insert(
+        self.rune_balance_to_holder,
+        (id.store(), new),
+        script_pubkey,
+      )?;
+    } else {
+      self
+        .undo
+        .remove(self.rune_holder_to_balance, (id.store(), script_pubkey))?;
+    }
+
+    let count = self
+      .rune_id_to_holder_count
+      .get(id.store())?
+      .map(|count| count.value())
+      .unwrap_or_default();
+
+    if old == 0 {
+      self
+        .undo
+        .insert(self.rune_id_to_holder_count, id.store(), count + 1)?;
+    } else if new == 0 {
+      self
+        .undo
+        .insert(self.rune_id_to_holder_count, id.store(), count - 1)?;
+    }
+
+    Ok(())
+  }
 }
@@ -2,7 +2,7 @@ use {
   super::*,
   anyhow::ensure,
   axum::http::header::HeaderValue,
-  bitcoin::blockdata::opcodes,
+  bitcoin::{blockdata::opcodes, hashes::sha256},
   brotli::enc::{writer::CompressorWriter, BrotliEncoderParams},
   io::Write,
   std::str,
@@ -192,6 +192,35 @@ impl Inscription {
     Some(self.body()?.len())
   }
 
+  /// SHA-256 hashes of the body and, if the body is brotli-encoded, of the
+  /// decoded body. A small encoded body may decode to an arbitrarily large
+  /// one, so the decoded body is not hashed if it exceeds
+  /// `MAX_DECODED_CONTENT_SIZE`.
+  pub fn content_hashes(&self) -> Vec<[u8; 32]> {
+    const MAX_DECODED_CONTENT_SIZE: u64 = 16 * 1024 * 1024;
+
+    let Some(body) = self.body() else {
+      return Vec::new();
+    };
+
+    let mut hashes = vec![sha256::Hash::hash(body).to_byte_array()];
+
+    if self.content_encoding.as_deref() == Some(b"br") {
+      let mut decoded = Vec::new();
+
+      if brotli::Decompressor::new(body, 4096)
+        .take(MAX_DECODED_CONTENT_SIZE + 1)
+        .read_to_end(&mut decoded)
+        .is_ok()
+        && u64::try_from(decoded.len()).unwrap() <= MAX_DECODED_CONTENT_SIZE
+      {
+        hashes.push(sha256::Hash::hash(&decoded).to_byte_array());
+      }
+    }
+
+    hashes
+  }
+
   pub fn content_type(&self) -> Option<&str> {
     str::from_utf8(self.content_type.as_ref()?).ok()
   }
@@ -208,6 +237,67 @@ impl Inscription {
     ciborium::from_reader(Cursor::new(self.metadata.as_ref()?)).ok()
   }
 
+  /// Top-level text keys of a metadata map with scalar values, rendered as
+  /// strings for indexing.
+  pub fn metadata_attributes(&self) -> Vec<(String, String)> {
+    let Some(Value::Map(map)) = self.metadata() else {
+      return Vec::new();
+    };
+
+    map
+      .into_iter()
+      .filter_map(|(key, value)| {
+        let Value::Text(key) = key else {
+          return None;
+        };
+
+        let value = match value {
+          Value::Bool(x) => x.to_string(),
+          Value::Float(x) => x.to_string(),
+          Value::Integer(x) => i128::from(x).to_string(),
+          Value::Text(x) => x,
+          _ => return None,
+        };
+
+        Some((key, value))
+      })
+      .collect()
+  }
+
+  pub fn metadata_json(&self) -> Option<serde_json::Value> {
+    Some(Self::cbor_to_json(self.metadata()?))
+  }
+
+  fn cbor_to_json(value: Value) -> serde_json::Value {
+    match value {
+      Value::Array(x) => x.into_iter().map(Self::cbor_to_json).collect(),
+      Value::Bool(x) => x.into(),
+      Value::Bytes(x) => hex::encode(x).into(),
+      Value::Float(x) => x.into(),
+      Value::Integer(x) => {
+        let x = i128::from(x);
+        i64::try_from(x)
+          .map(serde_json::Value::from)
+          .or_else(|_| u64::try_from(x).map(serde_json::Value::from))
+          .unwrap_or_else(|_| x.to_string().into())
+      }
+      Value::Map(x) => serde_json::Value::Object(
+        x.into_iter()
+          .map(|(key, value)| {
+            let key = match key {
+              Value::Text(key) => key,
+              key => Self::cbor_to_json(key).to_string(),
+            };
+            (key, Self::cbor_to_json(value))
+          })
+          .collect(),
+      ),
+      Value::Tag(_, x) => Self::cbor_to_json(*x),
+      Value::Text(x) => x.into(),
+      _ => serde_json::Value::Null,
+    }
+  }
+
   pub fn metaprotocol(&self) -> Option<&str> {
     str::from_utf8(self.metaprotocol.as_ref()?).ok()
   }
@@ -715,6 +805,56 @@ mod tests {
     );
   }
 
+  #[test]
+  fn metadata_attributes() {
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("name".into()), Value::Text("foo".into())),
+        (Value::Text("rank".into()), Value::Integer((-3).into())),
+        (Value::Text("rare".into()), Value::Bool(true)),
+        (Value::Text("traits".into()), Value::Array(Vec::new())),
+        (Value::Integer(1.into()), Value::Text("bar".into())),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    let inscription = Inscription {
+      metadata: Some(metadata),
+      ..default()
+    };
+
+    assert_eq!(
+      inscription.metadata_attributes(),
+      [
+        ("name".into(), "foo".into()),
+        ("rank".into(), "-3".into()),
+        ("rare".into(), "true".into()),
+      ],
+    );
+
+    assert_eq!(
+      inscription.metadata_json().unwrap(),
+      serde_json::json!({
+        "name": "foo",
+        "rank": -3,
+        "rare": true,
+        "traits": [],
+        "1": "bar",
+      }),
+    );
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(vec![0x44, 0, 1, 2, 3]),
+        ..default()
+      }
+      .metadata_attributes(),
+      Vec::new(),
+    );
+  }
+
   #[test]
   fn pointer_decode() {
     assert_eq!(
@@ -944,4 +1084,51 @@ mod tests {
     }
     .hidden());
   }
+
+  #[test]
+  fn content_hashes() {
+    assert_eq!(
+      Inscription::default().content_hashes(),
+      Vec::<[u8; 32]>::new()
+    );
+
+    assert_eq!(
+      Inscription {
+        body: Some(b"foo".into()),
+        ..default()
+      }
+      .content_hashes(),
+      [sha256::Hash::hash(b"foo").to_byte_array()],
+    );
+
+    let mut compressed = Vec::new();
+
+    {
+      let mut writer = CompressorWriter::new(&mut compressed, 4096, 11, 22);
+      writer.write_all(b"foo").unwrap();
+    }
+
+    assert_eq!(
+      Inscription {
+        body: Some(compressed.clone()),
+        content_encoding: Some(b"br".into()),
+        ..default()
+      }
+      .content_hashes(),
+      [
+        sha256::Hash::hash(&compressed).to_byte_array(),
+        sha256::Hash::hash(b"foo").to_byte_array(),
+      ],
+    );
+
+    assert_eq!(
+      Inscription {
+        body: Some(b"foo".into()),
+        content_encoding: Some(b"br".into()),
+        ..default()
+      }
+      .content_hashes(),
+      [sha256::Hash::hash(b"foo").to_byte_array()],
+    );
+  }
 }
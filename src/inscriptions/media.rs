@@ -22,6 +22,28 @@ pub enum Media {
   Video,
 }
 
+impl Display for Media {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Audio => "audio",
+        Self::Code(_) => "code",
+        Self::Font => "font",
+        Self::Iframe => "iframe",
+        Self::Image(_) => "image",
+        Self::Markdown => "markdown",
+        Self::Model => "model",
+        Self::Pdf => "pdf",
+        Self::Text => "text",
+        Self::Unknown => "unknown",
+        Self::Video => "video",
+      }
+    )
+  }
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Language {
   Css,
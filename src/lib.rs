@@ -95,7 +95,7 @@ use {
 pub use self::{
   chain::Chain,
   fee_rate::FeeRate,
-  index::{Index, RuneEntry},
+  index::{AddressOrOutPoint, Index, InscriptionFilter, RuneEntry, Sort},
   inscriptions::{Envelope, Inscription, InscriptionId},
   object::Object,
   options::Options,
@@ -305,6 +305,12 @@ pub fn main() {
 
   match args.run() {
     Err(err) => {
+      if let SnafuError::Anyhow { err } = &err {
+        if let Some(err) = err.downcast_ref::<subcommand::OutputError>() {
+          err.output.print(format.unwrap_or_default());
+        }
+      }
+
       eprintln!("error: {err}");
 
       if let SnafuError::Anyhow { err } = err {
@@ -45,19 +45,51 @@ pub struct Options {
   pub(crate) cookie_file: Option<PathBuf>,
   #[arg(long, alias = "datadir", help = "Store index in <DATA_DIR>.")]
   pub(crate) data_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Make at most <ESPLORA_CONCURRENCY> concurrent requests to Esplora REST API. [default: 8]"
+  )]
+  pub(crate) esplora_concurrency: Option<usize>,
+  #[arg(
+    long,
+    help = "Fetch blocks and transactions from Esplora REST API at <ESPLORA_URL> instead of Bitcoin Core RPC."
+  )]
+  pub(crate) esplora_url: Option<String>,
   #[arg(long, help = "Limit index to <HEIGHT_LIMIT> blocks.")]
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
   #[arg(long, help = "Track unspent output addresses.")]
   pub(crate) index_addresses: bool,
+  #[arg(long, help = "Track inscriptions by SHA-256 hash of content.")]
+  pub(crate) index_content_hashes: bool,
   #[arg(
     long,
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(long, help = "Track inscriptions by delegate.")]
+  pub(crate) index_delegates: bool,
+  #[arg(long, help = "Store a replayable log of index events.")]
+  pub(crate) index_events: bool,
+  #[arg(long, help = "Track inscriptions by metaprotocol and content type.")]
+  pub(crate) index_inscription_attributes: bool,
+  #[arg(long, help = "Store the location history of every inscription.")]
+  pub(crate) index_inscription_history: bool,
+  #[arg(
+    long,
+    help = "Track inscriptions by top-level CBOR metadata keys and values."
+  )]
+  pub(crate) index_metadata: bool,
   #[arg(long, help = "Track location of runes.")]
   pub(crate) index_runes: bool,
+  #[arg(
+    long,
+    help = "Store rune balances of spent outputs. Requires `--index-runes`."
+  )]
+  pub(crate) index_rune_history: bool,
+  #[arg(long, help = "Track rune holders. Requires `--index-runes`.")]
+  pub(crate) index_rune_holders: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
@@ -73,6 +105,26 @@ pub struct Options {
     help = "Do not index inscriptions."
   )]
   pub(crate) no_index_inscriptions: bool,
+  #[arg(
+    long,
+    help = "Read blocks from Bitcoin Core's `blk*.dat` files instead of over RPC when available."
+  )]
+  pub(crate) read_block_files: bool,
+  #[arg(
+    long,
+    help = "Run as a read-only replica. The index is never updated from Bitcoin Core, only replaced with --replica-snapshot, and must already exist or be created from it. The index is a private copy, which is rewritten with the whole snapshot whenever the snapshot changes, and which, like any index, can only be opened by one process at a time."
+  )]
+  pub(crate) read_only: bool,
+  #[arg(
+    long,
+    help = "Share the index with read-only replicas through the snapshot at <REPLICA_SNAPSHOT>. Without --read-only, `ord server` writes a snapshot there every --replica-snapshot-interval seconds if the index has changed, and `ord index update` writes one when it finishes. With --read-only, refresh the index from it whenever it changes."
+  )]
+  pub(crate) replica_snapshot: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Write a replica snapshot at most every <REPLICA_SNAPSHOT_INTERVAL> seconds. [default: 60]"
+  )]
+  pub(crate) replica_snapshot_interval: Option<u32>,
   #[arg(
     long,
     help = "Require basic HTTP authentication with <SERVER_PASSWORD>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
@@ -91,4 +143,9 @@ pub struct Options {
   pub(crate) testnet: bool,
   #[arg(long, help = "Use testnet4. Equivalent to `--chain testnet4`.")]
   pub(crate) testnet4: bool,
+  #[arg(
+    long,
+    help = "Keep undo records for the last <UNDO_DEPTH> blocks, allowing recovery from reorgs and rollbacks up to that depth."
+  )]
+  pub(crate) undo_depth: Option<u32>,
 }
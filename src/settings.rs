@@ -16,20 +16,35 @@ pub struct Settings {
   config_dir: Option<PathBuf>,
   cookie_file: Option<PathBuf>,
   data_dir: Option<PathBuf>,
+  esplora_concurrency: Option<usize>,
+  esplora_url: Option<String>,
   height_limit: Option<u32>,
   hidden: Option<HashSet<InscriptionId>>,
   http_port: Option<u16>,
   index: Option<PathBuf>,
   index_addresses: bool,
+  index_content_hashes: bool,
   index_cache_size: Option<usize>,
+  index_delegates: bool,
+  index_events: bool,
+  index_inscription_attributes: bool,
+  index_inscription_history: bool,
+  index_metadata: bool,
   index_runes: bool,
+  index_rune_history: bool,
+  index_rune_holders: bool,
   index_sats: bool,
   index_transactions: bool,
   integration_test: bool,
   no_index_inscriptions: bool,
+  read_block_files: bool,
+  read_only: bool,
+  replica_snapshot: Option<PathBuf>,
+  replica_snapshot_interval: Option<u32>,
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
+  undo_depth: Option<u32>,
 }
 
 impl Settings {
@@ -123,6 +138,8 @@ impl Settings {
       config_dir: self.config_dir.or(source.config_dir),
       cookie_file: self.cookie_file.or(source.cookie_file),
       data_dir: self.data_dir.or(source.data_dir),
+      esplora_concurrency: self.esplora_concurrency.or(source.esplora_concurrency),
+      esplora_url: self.esplora_url.or(source.esplora_url),
       height_limit: self.height_limit.or(source.height_limit),
       hidden: Some(
         self
@@ -136,15 +153,31 @@ impl Settings {
       http_port: self.http_port.or(source.http_port),
       index: self.index.or(source.index),
       index_addresses: self.index_addresses || source.index_addresses,
+      index_content_hashes: self.index_content_hashes || source.index_content_hashes,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_delegates: self.index_delegates || source.index_delegates,
+      index_events: self.index_events || source.index_events,
+      index_inscription_attributes: self.index_inscription_attributes
+        || source.index_inscription_attributes,
+      index_inscription_history: self.index_inscription_history || source.index_inscription_history,
+      index_metadata: self.index_metadata || source.index_metadata,
       index_runes: self.index_runes || source.index_runes,
+      index_rune_history: self.index_rune_history || source.index_rune_history,
+      index_rune_holders: self.index_rune_holders || source.index_rune_holders,
       index_sats: self.index_sats || source.index_sats,
       index_transactions: self.index_transactions || source.index_transactions,
       integration_test: self.integration_test || source.integration_test,
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
+      read_block_files: self.read_block_files || source.read_block_files,
+      read_only: self.read_only || source.read_only,
+      replica_snapshot: self.replica_snapshot.or(source.replica_snapshot),
+      replica_snapshot_interval: self
+        .replica_snapshot_interval
+        .or(source.replica_snapshot_interval),
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
+      undo_depth: self.undo_depth.or(source.undo_depth),
     }
   }
 
@@ -169,20 +202,35 @@ impl Settings {
       config_dir: options.config_dir,
       cookie_file: options.cookie_file,
       data_dir: options.data_dir,
+      esplora_concurrency: options.esplora_concurrency,
+      esplora_url: options.esplora_url,
       height_limit: options.height_limit,
       hidden: None,
       http_port: None,
       index: options.index,
       index_addresses: options.index_addresses,
+      index_content_hashes: options.index_content_hashes,
       index_cache_size: options.index_cache_size,
+      index_delegates: options.index_delegates,
+      index_events: options.index_events,
+      index_inscription_attributes: options.index_inscription_attributes,
+      index_inscription_history: options.index_inscription_history,
+      index_metadata: options.index_metadata,
       index_runes: options.index_runes,
+      index_rune_history: options.index_rune_history,
+      index_rune_holders: options.index_rune_holders,
       index_sats: options.index_sats,
       index_transactions: options.index_transactions,
       integration_test: options.integration_test,
       no_index_inscriptions: options.no_index_inscriptions,
+      read_block_files: options.read_block_files,
+      read_only: options.read_only,
+      replica_snapshot: options.replica_snapshot,
+      replica_snapshot_interval: options.replica_snapshot_interval,
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
+      undo_depth: options.undo_depth,
     }
   }
 
@@ -259,20 +307,35 @@ impl Settings {
       config_dir: get_path("CONFIG_DIR"),
       cookie_file: get_path("COOKIE_FILE"),
       data_dir: get_path("DATA_DIR"),
+      esplora_concurrency: get_usize("ESPLORA_CONCURRENCY")?,
+      esplora_url: get_string("ESPLORA_URL"),
       height_limit: get_u32("HEIGHT_LIMIT")?,
       hidden: inscriptions("HIDDEN")?,
       http_port: get_u16("HTTP_PORT")?,
       index: get_path("INDEX"),
       index_addresses: get_bool("INDEX_ADDRESSES"),
+      index_content_hashes: get_bool("INDEX_CONTENT_HASHES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_delegates: get_bool("INDEX_DELEGATES"),
+      index_events: get_bool("INDEX_EVENTS"),
+      index_inscription_attributes: get_bool("INDEX_INSCRIPTION_ATTRIBUTES"),
+      index_inscription_history: get_bool("INDEX_INSCRIPTION_HISTORY"),
+      index_metadata: get_bool("INDEX_METADATA"),
       index_runes: get_bool("INDEX_RUNES"),
+      index_rune_history: get_bool("INDEX_RUNE_HISTORY"),
+      index_rune_holders: get_bool("INDEX_RUNE_HOLDERS"),
       index_sats: get_bool("INDEX_SATS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
       integration_test: get_bool("INTEGRATION_TEST"),
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
+      read_block_files: get_bool("READ_BLOCK_FILES"),
+      read_only: get_bool("READ_ONLY"),
+      replica_snapshot: get_path("REPLICA_SNAPSHOT"),
+      replica_snapshot_interval: get_u32("REPLICA_SNAPSHOT_INTERVAL")?,
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
+      undo_depth: get_u32("UNDO_DEPTH")?,
     })
   }
 
@@ -291,20 +354,35 @@ impl Settings {
       config_dir: None,
       cookie_file: None,
       data_dir: Some(dir.into()),
+      esplora_concurrency: None,
+      esplora_url: None,
       height_limit: None,
       hidden: None,
       http_port: None,
       index: None,
       index_addresses: true,
+      index_content_hashes: false,
       index_cache_size: None,
+      index_delegates: false,
+      index_events: false,
+      index_inscription_attributes: false,
+      index_inscription_history: false,
+      index_metadata: false,
       index_runes: true,
+      index_rune_history: false,
+      index_rune_holders: false,
       index_sats: true,
       index_transactions: false,
       integration_test: false,
       no_index_inscriptions: false,
+      read_block_files: false,
+      read_only: false,
+      replica_snapshot: None,
+      replica_snapshot_interval: None,
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
+      undo_depth: None,
     }
   }
 
@@ -360,11 +438,14 @@ impl Settings {
       config_dir: None,
       cookie_file: Some(cookie_file),
       data_dir: Some(data_dir),
+      esplora_concurrency: Some(self.esplora_concurrency.unwrap_or(8)),
+      esplora_url: self.esplora_url,
       height_limit: self.height_limit,
       hidden: self.hidden,
       http_port: self.http_port,
       index: Some(index),
       index_addresses: self.index_addresses,
+      index_content_hashes: self.index_content_hashes,
       index_cache_size: Some(match self.index_cache_size {
         Some(index_cache_size) => index_cache_size,
         None => {
@@ -373,14 +454,26 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_delegates: self.index_delegates,
+      index_events: self.index_events,
+      index_inscription_attributes: self.index_inscription_attributes,
+      index_inscription_history: self.index_inscription_history,
+      index_metadata: self.index_metadata,
       index_runes: self.index_runes,
+      index_rune_history: self.index_rune_history,
+      index_rune_holders: self.index_rune_holders,
       index_sats: self.index_sats,
       index_transactions: self.index_transactions,
       integration_test: self.integration_test,
       no_index_inscriptions: self.no_index_inscriptions,
+      read_block_files: self.read_block_files,
+      read_only: self.read_only,
+      replica_snapshot: self.replica_snapshot,
+      replica_snapshot_interval: Some(self.replica_snapshot_interval.unwrap_or(60)),
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
+      undo_depth: self.undo_depth,
     })
   }
 
@@ -474,6 +567,37 @@ impl Settings {
     Ok(client)
   }
 
+  fn bitcoin_data_dir(&self) -> Result<PathBuf> {
+    if let Some(bitcoin_data_dir) = &self.bitcoin_data_dir {
+      Ok(bitcoin_data_dir.clone())
+    } else if cfg!(target_os = "linux") {
+      Ok(
+        dirs::home_dir()
+          .ok_or_else(|| anyhow!("failed to get Bitcoin data dir: could not get home dir"))?
+          .join(".bitcoin"),
+      )
+    } else {
+      Ok(
+        dirs::data_dir()
+          .ok_or_else(|| anyhow!("failed to get Bitcoin data dir: could not get data dir"))?
+          .join("Bitcoin"),
+      )
+    }
+  }
+
+  pub fn block_files_dir(&self) -> Result<Option<PathBuf>> {
+    if !self.read_block_files {
+      return Ok(None);
+    }
+
+    Ok(Some(
+      self
+        .chain()
+        .join_with_data_dir(self.bitcoin_data_dir()?)
+        .join("blocks"),
+    ))
+  }
+
   pub fn chain(&self) -> Chain {
     self.chain.unwrap()
   }
@@ -543,6 +667,22 @@ impl Settings {
     self.height_limit
   }
 
+  pub fn undo_depth(&self) -> Option<u32> {
+    self.undo_depth
+  }
+
+  pub fn read_only(&self) -> bool {
+    self.read_only
+  }
+
+  pub fn replica_snapshot(&self) -> Option<&Path> {
+    self.replica_snapshot.as_deref()
+  }
+
+  pub fn replica_snapshot_interval(&self) -> Duration {
+    Duration::from_secs(self.replica_snapshot_interval.unwrap().into())
+  }
+
   pub fn index(&self) -> &Path {
     self.index.as_ref().unwrap()
   }
@@ -551,6 +691,30 @@ impl Settings {
     self.index_addresses
   }
 
+  pub fn index_content_hashes_raw(&self) -> bool {
+    self.index_content_hashes
+  }
+
+  pub fn index_delegates_raw(&self) -> bool {
+    self.index_delegates
+  }
+
+  pub fn index_events_raw(&self) -> bool {
+    self.index_events
+  }
+
+  pub fn index_inscription_attributes_raw(&self) -> bool {
+    self.index_inscription_attributes
+  }
+
+  pub fn index_inscription_history_raw(&self) -> bool {
+    self.index_inscription_history
+  }
+
+  pub fn index_metadata_raw(&self) -> bool {
+    self.index_metadata
+  }
+
   pub fn index_inscriptions_raw(&self) -> bool {
     !self.no_index_inscriptions
   }
@@ -559,6 +723,14 @@ impl Settings {
     self.index_runes
   }
 
+  pub fn index_rune_history_raw(&self) -> bool {
+    self.index_rune_history
+  }
+
+  pub fn index_rune_holders_raw(&self) -> bool {
+    self.index_rune_holders
+  }
+
   pub fn index_cache_size(&self) -> usize {
     self.index_cache_size.unwrap()
   }
@@ -595,6 +767,14 @@ impl Settings {
     self.bitcoin_rpc_limit.unwrap()
   }
 
+  pub fn esplora_concurrency(&self) -> usize {
+    self.esplora_concurrency.unwrap()
+  }
+
+  pub fn esplora_url(&self) -> Option<&str> {
+    self.esplora_url.as_deref()
+  }
+
   pub fn server_url(&self) -> Option<&str> {
     self.server_url.as_deref()
   }
@@ -813,6 +993,28 @@ mod tests {
     }));
   }
 
+  #[test]
+  fn block_files_dir() {
+    assert_eq!(parse(&[]).block_files_dir().unwrap(), None);
+
+    let block_files_dir = Settings {
+      chain: Some(Chain::Signet),
+      read_block_files: true,
+      ..default()
+    }
+    .block_files_dir()
+    .unwrap()
+    .unwrap()
+    .display()
+    .to_string();
+
+    assert!(block_files_dir.ends_with(if cfg!(windows) {
+      r"Bitcoin\signet\blocks"
+    } else {
+      ".bitcoin/signet/blocks"
+    }));
+  }
+
   #[test]
   fn mainnet_data_dir() {
     let data_dir = parse(&[]).data_dir().display().to_string();
@@ -1072,20 +1274,35 @@ mod tests {
       ("CONFIG_DIR", "config dir"),
       ("COOKIE_FILE", "cookie file"),
       ("DATA_DIR", "/data/dir"),
+      ("ESPLORA_CONCURRENCY", "4"),
+      ("ESPLORA_URL", "esplora url"),
       ("HEIGHT_LIMIT", "3"),
       ("HIDDEN", "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 703e5f7c49d82aab99e605af306b9a30e991e57d42f982908a962a81ac439832i0"),
     ("HTTP_PORT", "8080"),
       ("INDEX", "index"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_DELEGATES", "1"),
+      ("INDEX_EVENTS", "1"),
+      ("INDEX_INSCRIPTION_HISTORY", "1"),
+      ("INDEX_METADATA", "1"),
+      ("INDEX_INSCRIPTION_ATTRIBUTES", "1"),
       ("INDEX_ADDRESSES", "1"),
+      ("INDEX_CONTENT_HASHES", "1"),
       ("INDEX_RUNES", "1"),
+      ("INDEX_RUNE_HISTORY", "1"),
+      ("INDEX_RUNE_HOLDERS", "1"),
       ("INDEX_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
       ("INTEGRATION_TEST", "1"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
+      ("READ_BLOCK_FILES", "1"),
+      ("READ_ONLY", "1"),
+      ("REPLICA_SNAPSHOT", "replica snapshot"),
+      ("REPLICA_SNAPSHOT_INTERVAL", "30"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
+      ("UNDO_DEPTH", "5"),
     ]
     .into_iter()
     .map(|(key, value)| (key.into(), value.into()))
@@ -1107,6 +1324,8 @@ mod tests {
         config_dir: Some("config dir".into()),
         cookie_file: Some("cookie file".into()),
         data_dir: Some("/data/dir".into()),
+        esplora_concurrency: Some(4),
+        esplora_url: Some("esplora url".into()),
         height_limit: Some(3),
         hidden: Some(
           vec![
@@ -1123,15 +1342,28 @@ mod tests {
         http_port: Some(8080),
         index: Some("index".into()),
         index_addresses: true,
+        index_content_hashes: true,
         index_cache_size: Some(4),
+        index_delegates: true,
+        index_events: true,
+        index_inscription_attributes: true,
+        index_inscription_history: true,
+        index_metadata: true,
         index_runes: true,
+        index_rune_history: true,
+        index_rune_holders: true,
         index_sats: true,
         index_transactions: true,
         integration_test: true,
         no_index_inscriptions: true,
+        read_block_files: true,
+        read_only: true,
+        replica_snapshot: Some("replica snapshot".into()),
+        replica_snapshot_interval: Some(30),
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
+        undo_depth: Some(5),
       }
     );
   }
@@ -1155,17 +1387,32 @@ mod tests {
           "--config-dir=config dir",
           "--cookie-file=cookie file",
           "--datadir=/data/dir",
+          "--esplora-concurrency=4",
+          "--esplora-url=esplora url",
           "--height-limit=3",
           "--index-addresses",
+          "--index-content-hashes",
           "--index-cache-size=4",
+          "--index-delegates",
+          "--index-events",
+          "--index-inscription-history",
+          "--index-metadata",
+          "--index-inscription-attributes",
           "--index-runes",
+          "--index-rune-history",
+          "--index-rune-holders",
           "--index-sats",
           "--index-transactions",
           "--index=index",
           "--integration-test",
           "--no-index-inscriptions",
+          "--read-block-files",
+          "--read-only",
+          "--replica-snapshot=replica snapshot",
+          "--replica-snapshot-interval=30",
           "--server-password=server password",
           "--server-username=server username",
+          "--undo-depth=5",
         ])
         .unwrap()
       ),
@@ -1183,20 +1430,35 @@ mod tests {
         config_dir: Some("config dir".into()),
         cookie_file: Some("cookie file".into()),
         data_dir: Some("/data/dir".into()),
+        esplora_concurrency: Some(4),
+        esplora_url: Some("esplora url".into()),
         height_limit: Some(3),
         hidden: None,
         http_port: None,
         index: Some("index".into()),
         index_addresses: true,
+        index_content_hashes: true,
         index_cache_size: Some(4),
+        index_delegates: true,
+        index_events: true,
+        index_inscription_attributes: true,
+        index_inscription_history: true,
+        index_metadata: true,
         index_runes: true,
+        index_rune_history: true,
+        index_rune_holders: true,
         index_sats: true,
         index_transactions: true,
         integration_test: true,
         no_index_inscriptions: true,
+        read_block_files: true,
+        read_only: true,
+        replica_snapshot: Some("replica snapshot".into()),
+        replica_snapshot_interval: Some(30),
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
+        undo_depth: Some(5),
       }
     );
   }
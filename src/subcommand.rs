@@ -6,6 +6,7 @@ pub mod env;
 pub mod epochs;
 pub mod find;
 pub mod index;
+pub mod inscription;
 pub mod list;
 pub mod parse;
 pub mod runes;
@@ -33,12 +34,14 @@ pub(crate) enum Subcommand {
   Find(find::Find),
   #[command(subcommand, about = "Index commands")]
   Index(index::IndexSubcommand),
+  #[command(subcommand, about = "Inscription commands")]
+  Inscription(inscription::InscriptionSubcommand),
   #[command(about = "List the satoshis in an output")]
   List(list::List),
   #[command(about = "Parse a satoshi from ordinal notation")]
   Parse(parse::Parse),
   #[command(about = "List all runes")]
-  Runes,
+  Runes(runes::Runes),
   #[command(about = "Run the explorer server")]
   Server(server::Server),
   #[command(about = "Display settings")]
@@ -68,14 +71,19 @@ impl Subcommand {
       Self::Epochs => epochs::run(),
       Self::Find(find) => find.run(settings),
       Self::Index(index) => index.run(settings),
+      Self::Inscription(inscription) => inscription.run(settings),
       Self::List(list) => list.run(settings),
       Self::Parse(parse) => parse.run(),
-      Self::Runes => runes::run(settings),
+      Self::Runes(runes) => runes.run(settings),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&settings)?);
+        let (event_sender, event_receiver) = tokio::sync::mpsc::channel(server::EVENT_CAPACITY);
+        let index = Arc::new(Index::open_with_event_sender(
+          &settings,
+          Some(event_sender),
+        )?);
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(settings, index, handle)
+        server.run(settings, index, handle, event_receiver)
       }
       Self::Settings => settings::run(settings),
       Self::Subsidy(subsidy) => subsidy.run(),
@@ -116,3 +124,24 @@ where
 }
 
 pub(crate) type SubcommandResult = Result<Option<Box<dyn Output>>>;
+
+/// Error for subcommands whose output reports a failure, so that the output
+/// is printed before exiting with a non-zero exit code
+pub(crate) struct OutputError {
+  pub(crate) message: String,
+  pub(crate) output: Box<dyn Output + Sync>,
+}
+
+impl fmt::Debug for OutputError {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.message)
+  }
+}
+
+impl Display for OutputError {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.message)
+  }
+}
+
+impl std::error::Error for OutputError {}
@@ -1,15 +1,27 @@
 use super::*;
 
+pub mod check;
 mod export;
 pub mod info;
+pub mod restore;
+pub mod rollback;
+pub mod snapshot;
 mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
+  #[command(about = "Check index for internal consistency")]
+  Check(check::Check),
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Restore index from a snapshot")]
+  Restore(restore::Restore),
+  #[command(about = "Roll back index to an earlier block using undo records")]
+  Rollback(rollback::Rollback),
+  #[command(about = "Write a checksummed snapshot of the index")]
+  Snapshot(snapshot::Snapshot),
   #[command(about = "Update the index", alias = "run")]
   Update,
 }
@@ -17,8 +29,12 @@ pub(crate) enum IndexSubcommand {
 impl IndexSubcommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self {
+      Self::Check(check) => check.run(settings),
       Self::Export(export) => export.run(settings),
       Self::Info(info) => info.run(settings),
+      Self::Restore(restore) => restore.run(settings),
+      Self::Rollback(rollback) => rollback.run(settings),
+      Self::Snapshot(snapshot) => snapshot.run(settings),
       Self::Update => update::run(settings),
     }
   }
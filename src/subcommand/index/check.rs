@@ -0,0 +1,40 @@
+use {super::*, crate::index::check::Violation};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Check {
+  #[arg(
+    long,
+    value_name = "N",
+    help = "Compare <N> blocks and outputs against Bitcoin Core."
+  )]
+  spot_check: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub violations: Vec<Violation>,
+}
+
+impl Check {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    let violations = index.check(self.spot_check)?;
+
+    if violations.is_empty() {
+      return Ok(Some(Box::new(Output { violations })));
+    }
+
+    Err(
+      OutputError {
+        message: format!(
+          "index check found {} violation{}",
+          violations.len(),
+          if violations.len() == 1 { "" } else { "s" },
+        ),
+        output: Box::new(Output { violations }),
+      }
+      .into(),
+    )
+  }
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Restore {
+  #[arg(help = "Restore index from snapshot <FILE>.")]
+  file: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub header: crate::index::snapshot::Header,
+}
+
+impl Restore {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let header = Index::restore(&settings, &self.file)?;
+
+    Ok(Some(Box::new(Output { header })))
+  }
+}
@@ -0,0 +1,71 @@
+use {super::*, bitcoin::hashes::sha256};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Snapshot {
+  #[arg(
+    long,
+    help = "Request snapshot from `ord server` at <SERVER_URL>, which holds the index lock while running. The server must be started with `--server-username` and `--server-password`, which are used to authenticate."
+  )]
+  server_url: Option<Url>,
+  #[arg(help = "Write snapshot to <FILE>.")]
+  file: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub checksum: String,
+  pub header: crate::index::snapshot::Header,
+}
+
+impl Snapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let (header, checksum) = match &self.server_url {
+      Some(server_url) => self.request(&settings, server_url)?,
+      None => Index::open(&settings)?.snapshot(&self.file)?,
+    };
+
+    Ok(Some(Box::new(Output {
+      checksum: checksum.to_string(),
+      header,
+    })))
+  }
+
+  fn request(
+    &self,
+    settings: &Settings,
+    server_url: &Url,
+  ) -> Result<(crate::index::snapshot::Header, sha256::Hash)> {
+    let Some((username, password)) = settings.credentials() else {
+      bail!("`--server-username` and `--server-password` are required to request a snapshot");
+    };
+
+    let mut response = reqwest::blocking::Client::builder()
+      .timeout(None)
+      .build()?
+      .post(server_url.join("snapshot")?)
+      .basic_auth(username, Some(password))
+      .header(reqwest::header::ACCEPT_ENCODING, "identity")
+      .send()?;
+
+    ensure!(
+      response.status().is_success(),
+      "server responded with {} to snapshot request",
+      response.status(),
+    );
+
+    let mut partial = self.file.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    let mut file =
+      File::create(&partial).with_context(|| format!("failed to create {}", partial.display()))?;
+
+    response.copy_to(&mut file)?;
+
+    let snapshot = Index::verify_snapshot(settings, &partial)?;
+
+    fs::rename(&partial, &self.file)?;
+
+    Ok(snapshot)
+  }
+}
@@ -5,5 +5,7 @@ pub(crate) fn run(settings: Settings) -> SubcommandResult {
 
   index.update()?;
 
+  index.write_replica_snapshot()?;
+
   Ok(None)
 }
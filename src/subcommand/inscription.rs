@@ -0,0 +1,21 @@
+use super::*;
+
+mod history;
+pub mod list;
+
+#[derive(Debug, Parser)]
+pub(crate) enum InscriptionSubcommand {
+  #[command(about = "List every location an inscription has been transferred to")]
+  History(history::History),
+  #[command(about = "List inscriptions, newest first, optionally filtered")]
+  List(list::List),
+}
+
+impl InscriptionSubcommand {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    match self {
+      Self::History(history) => history.run(settings),
+      Self::List(list) => list.run(settings),
+    }
+  }
+}
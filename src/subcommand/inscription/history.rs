@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct History {
+  #[arg(help = "List location history of <INSCRIPTION_ID>.")]
+  id: InscriptionId,
+}
+
+impl History {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    if !index.has_inscription_history_index() {
+      bail!(
+        "`ord inscription history` requires index created with `--index-inscription-history` flag"
+      );
+    }
+
+    index.update()?;
+
+    let locations = index
+      .get_inscription_history(self.id)?
+      .ok_or_else(|| anyhow!("inscription {} not found", self.id))?;
+
+    Ok(Some(Box::new(api::InscriptionHistory {
+      id: self.id,
+      locations,
+    })))
+  }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct List {
+  #[command(flatten)]
+  filter: InscriptionFilter,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub inscriptions: Vec<InscriptionId>,
+}
+
+impl List {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    if self.filter.requires_attribute_index() && !index.has_inscription_attribute_index() {
+      bail!(
+        "filtering by content type or metaprotocol requires index created with `--index-inscription-attributes` flag"
+      );
+    }
+
+    index.update()?;
+
+    let mut inscriptions = Vec::new();
+    let mut cursor = None;
+
+    loop {
+      let page = index.get_inscriptions_from(&self.filter, Sort::Newest, cursor, 1000)?;
+
+      inscriptions.extend(page.items);
+
+      cursor = page.next;
+
+      if cursor.is_none() {
+        break;
+      }
+    }
+
+    Ok(Some(Box::new(Output { inscriptions })))
+  }
+}
@@ -1,5 +1,28 @@
 use super::*;
 
+pub mod holders;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Runes {
+  #[command(subcommand)]
+  subcommand: Option<RunesSubcommand>,
+}
+
+#[derive(Debug, Parser)]
+enum RunesSubcommand {
+  #[command(about = "List holders of a rune")]
+  Holders(holders::Holders),
+}
+
+impl Runes {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    match self.subcommand {
+      Some(RunesSubcommand::Holders(holders)) => holders.run(settings),
+      None => run(settings),
+    }
+  }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub runes: BTreeMap<Rune, RuneInfo>,
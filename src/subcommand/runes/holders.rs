@@ -0,0 +1,63 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Holders {
+  #[arg(help = "List holders of <RUNE>.")]
+  rune: SpacedRune,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub count: u64,
+  pub holders: Vec<Holder>,
+  pub id: RuneId,
+  pub rune: SpacedRune,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Holder {
+  pub address: Option<String>,
+  pub amount: Decimal,
+  pub script_pubkey: ScriptBuf,
+}
+
+impl Holders {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    ensure!(
+      index.has_rune_holder_index(),
+      "`ord runes holders` requires index created with `--index-runes` and `--index-rune-holders` flags",
+    );
+
+    index.update()?;
+
+    let (id, entry, _parent) = index
+      .rune(self.rune.rune)?
+      .ok_or_else(|| anyhow!("rune {} has not been etched", self.rune))?;
+
+    let holders = index.get_rune_holders(id, None, usize::MAX)?.unwrap().items;
+
+    let chain = settings.chain();
+
+    Ok(Some(Box::new(Output {
+      count: index.get_rune_holder_count(id)?.unwrap(),
+      holders: holders
+        .into_iter()
+        .map(|(script_pubkey, amount)| Holder {
+          address: chain
+            .address_from_script(&script_pubkey)
+            .ok()
+            .map(|address| address.to_string()),
+          amount: Decimal {
+            value: amount,
+            scale: entry.divisibility,
+          },
+          script_pubkey,
+        })
+        .collect(),
+      id,
+      rune: entry.spaced_rune,
+    })))
+  }
+}
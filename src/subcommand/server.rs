@@ -2,25 +2,33 @@ use {
   self::{
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
+    conditional_request::ConditionalRequest,
     error::{OptionExt, ServerError, ServerResult},
+    listing::{CursorKey, ListingQuery},
   },
   super::*,
+  crate::index::{entry::InscriptionEntry, event::Event, Listing},
   crate::templates::{
-    AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml,
-    InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent,
-    PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RareTxt, RuneHtml, RuneNotFoundHtml, RunesHtml, SatHtml, SatscardHtml,
-    TransactionHtml,
+    AddressHtml, AddressRuneBalancesHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg,
+    CollectionsHtml, DelegatorsHtml, HomeHtml, InputHtml, InscriptionHistoryHtml, InscriptionHtml,
+    InscriptionsBlockHtml, InscriptionsHashHtml, InscriptionsHtml, InscriptionsMetadataHtml,
+    OutputHtml, PageContent, PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml,
+    PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml,
+    PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RareTxt, RuneHoldersHtml, RuneHtml,
+    RuneNotFoundHtml, RunesHtml, SatHtml, SatscardHtml, TransactionHtml,
   },
   axum::{
-    extract::{DefaultBodyLimit, Extension, Json, Path, Query},
+    extract::{DefaultBodyLimit, Extension, Json, MatchedPath, Path, Query},
     http::{self, header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
-    response::{IntoResponse, Redirect, Response},
+    response::{
+      sse::{self, KeepAlive, Sse},
+      IntoResponse, Redirect, Response,
+    },
     routing::{get, post},
     Router,
   },
   axum_server::Handle,
+  bitcoin::hashes::sha256,
   brotli::Decompressor,
   rust_embed::RustEmbed,
   rustls_acme::{
@@ -30,6 +38,7 @@ use {
     AcmeConfig,
   },
   std::{str, sync::Arc},
+  tokio::sync::{broadcast, mpsc},
   tokio_stream::StreamExt,
   tower_http::{
     compression::CompressionLayer,
@@ -43,10 +52,18 @@ pub use server_config::ServerConfig;
 
 mod accept_encoding;
 mod accept_json;
+mod conditional_request;
 mod error;
+mod graphql;
+mod listing;
+mod openapi;
 pub mod query;
 mod r;
 mod server_config;
+mod v1;
+mod zmq;
+
+pub const EVENT_CAPACITY: usize = 1024;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -54,6 +71,11 @@ enum SpawnConfig {
   Redirect(String),
 }
 
+#[derive(Deserialize)]
+pub(crate) struct AddressQuery {
+  pub(crate) height: Option<u32>,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct OutputsQuery {
   #[serde(rename = "type")]
@@ -70,6 +92,107 @@ pub(crate) enum OutputType {
   Runic,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum EventKind {
+  BlockReverted,
+  InscriptionCreated,
+  InscriptionReverted,
+  InscriptionTransferred,
+  RuneBurned,
+  RuneEtched,
+  RuneMinted,
+  RuneReverted,
+  RuneTransferred,
+}
+
+impl From<&Event> for EventKind {
+  fn from(event: &Event) -> Self {
+    match event {
+      Event::BlockReverted { .. } => Self::BlockReverted,
+      Event::InscriptionCreated { .. } => Self::InscriptionCreated,
+      Event::InscriptionReverted { .. } => Self::InscriptionReverted,
+      Event::InscriptionTransferred { .. } => Self::InscriptionTransferred,
+      Event::RuneBurned { .. } => Self::RuneBurned,
+      Event::RuneEtched { .. } => Self::RuneEtched,
+      Event::RuneMinted { .. } => Self::RuneMinted,
+      Event::RuneReverted { .. } => Self::RuneReverted,
+      Event::RuneTransferred { .. } => Self::RuneTransferred,
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct MetadataQuery {
+  key: String,
+  value: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EventLogQuery {
+  from_height: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub(crate) struct EventsQuery {
+  pub(crate) inscription: Option<InscriptionId>,
+  pub(crate) kind: Option<EventKind>,
+  pub(crate) parent: Option<InscriptionId>,
+  pub(crate) rune: Option<RuneId>,
+}
+
+impl EventsQuery {
+  fn matches(&self, event: &Event) -> bool {
+    if let Some(kind) = self.kind {
+      if kind != EventKind::from(event) {
+        return false;
+      }
+    }
+
+    let (inscription_id, parent_inscription_ids, rune_id) = match event {
+      Event::BlockReverted { .. } => (None, [].as_slice(), None),
+      Event::InscriptionCreated {
+        inscription_id,
+        parent_inscription_ids,
+        ..
+      } => (
+        Some(inscription_id),
+        parent_inscription_ids.as_slice(),
+        None,
+      ),
+      Event::InscriptionReverted { inscription_id, .. }
+      | Event::InscriptionTransferred { inscription_id, .. } => {
+        (Some(inscription_id), [].as_slice(), None)
+      }
+      Event::RuneBurned { rune_id, .. }
+      | Event::RuneEtched { rune_id, .. }
+      | Event::RuneMinted { rune_id, .. }
+      | Event::RuneReverted { rune_id, .. }
+      | Event::RuneTransferred { rune_id, .. } => (None, [].as_slice(), Some(rune_id)),
+    };
+
+    if let Some(inscription) = &self.inscription {
+      if inscription_id != Some(inscription) {
+        return false;
+      }
+    }
+
+    if let Some(parent) = &self.parent {
+      if !parent_inscription_ids.contains(parent) {
+        return false;
+      }
+    }
+
+    if let Some(rune) = &self.rune {
+      if rune_id != Some(rune) {
+        return false;
+      }
+    }
+
+    true
+  }
+}
+
 #[derive(Deserialize)]
 struct Search {
   query: String,
@@ -103,6 +226,36 @@ pub struct Server {
   pub(crate) decompress: bool,
   #[arg(long, env = "ORD_SERVER_DISABLE_JSON_API", help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
+  #[arg(
+    long,
+    default_value = "1",
+    help = "Report not ready at `/readyz` while the index is more than <MAX_INDEX_LAG> blocks behind Bitcoin Core."
+  )]
+  pub(crate) max_index_lag: u64,
+  #[arg(
+    long,
+    env = "ORD_SERVER_DISABLE_METRICS",
+    help = "Disable Prometheus metrics at `/metrics`."
+  )]
+  pub(crate) disable_metrics: bool,
+  #[arg(
+    long,
+    env = "ORD_SERVER_GRAPHQL",
+    help = "Serve GraphQL API at `/graphql`."
+  )]
+  pub(crate) graphql: bool,
+  #[arg(
+    long,
+    default_value = "5000",
+    help = "Reject GraphQL queries with complexity greater than <GRAPHQL_MAX_COMPLEXITY>. Each selected field costs one, and the fields selected within a list field cost `limit` times as much."
+  )]
+  pub(crate) graphql_max_complexity: u64,
+  #[arg(
+    long,
+    default_value = "10",
+    help = "Reject GraphQL queries with fields nested more than <GRAPHQL_MAX_DEPTH> deep."
+  )]
+  pub(crate) graphql_max_depth: usize,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -124,6 +277,11 @@ pub struct Server {
   pub(crate) https: bool,
   #[arg(long, help = "Redirect HTTP traffic to HTTPS.")]
   pub(crate) redirect_http_to_https: bool,
+  #[arg(
+    long,
+    help = "Track unconfirmed inscriptions and rune transfers in the mempool. Requires index to be updated."
+  )]
+  pub(crate) mempool: bool,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
   #[arg(
@@ -137,14 +295,63 @@ pub struct Server {
     help = "Poll Bitcoin Core every <POLLING_INTERVAL>."
   )]
   pub(crate) polling_interval: humantime::Duration,
+  #[arg(
+    long,
+    help = "Update index as soon as Bitcoin Core publishes ZMQ `hashblock` notifications to <ZMQ_URL>, and mempool on `rawtx` notifications if `--mempool` is given. May be given multiple times. Polling continues as a fallback."
+  )]
+  pub(crate) zmq_url: Vec<String>,
 }
 
 impl Server {
-  pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+  pub fn run(
+    self,
+    settings: Settings,
+    index: Arc<Index>,
+    handle: Handle,
+    mut event_receiver: mpsc::Receiver<Event>,
+  ) -> SubcommandResult {
     Runtime::new()?.block_on(async {
+      let (event_sender, _) = broadcast::channel(EVENT_CAPACITY);
+
+      {
+        let event_sender = event_sender.clone();
+        tokio::spawn(async move {
+          while let Some(event) = event_receiver.recv().await {
+            event_sender.send(event).ok();
+          }
+        });
+      }
+
       let index_clone = index.clone();
       let integration_test = settings.integration_test();
 
+      let polling_interval = if integration_test {
+        Duration::from_millis(100)
+      } else {
+        self.polling_interval.into()
+      };
+
+      let (index_wake, mempool_wake) = if self.zmq_url.is_empty() || self.no_sync {
+        (None, None)
+      } else {
+        let (index_sender, index_receiver) = std::sync::mpsc::sync_channel(1);
+
+        let (mempool_sender, mempool_receiver) = if self.mempool {
+          let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+          (Some(sender), Some(receiver))
+        } else {
+          (None, None)
+        };
+
+        tokio::spawn(zmq::subscribe(
+          self.zmq_url.clone(),
+          index_sender,
+          mempool_sender,
+        ));
+
+        (Some(index_receiver), mempool_receiver)
+      };
+
       let index_thread = thread::spawn(move || loop {
         if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
           break;
@@ -156,15 +363,66 @@ impl Server {
           }
         }
 
-        thread::sleep(if integration_test {
-          Duration::from_millis(100)
-        } else {
-          self.polling_interval.into()
-        });
+        match &index_wake {
+          Some(index_wake) => {
+            index_wake.recv_timeout(polling_interval).ok();
+          }
+          None => thread::sleep(polling_interval),
+        }
       });
 
       INDEXER.lock().unwrap().replace(index_thread);
 
+      // updating the mempool fetches every new unconfirmed transaction, so it
+      // is done on its own thread, where it cannot delay indexing
+      if self.mempool && !self.no_sync {
+        let index = index.clone();
+
+        thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          if let Err(error) = index.update_mempool() {
+            log::warn!("Updating mempool: {error}");
+          }
+
+          match &mempool_wake {
+            Some(mempool_wake) => {
+              if mempool_wake.recv_timeout(polling_interval).is_ok() {
+                thread::sleep(zmq::MEMPOOL_DEBOUNCE);
+                mempool_wake.try_recv().ok();
+              }
+            }
+            None => thread::sleep(polling_interval),
+          }
+        });
+      }
+
+      // snapshots copy the whole index, so they are written periodically on
+      // their own thread instead of after every update
+      if settings.replica_snapshot().is_some() && !settings.read_only() {
+        let index = index.clone();
+
+        let interval = if integration_test {
+          Duration::from_millis(100)
+        } else {
+          settings.replica_snapshot_interval()
+        };
+
+        thread::spawn(move || loop {
+          thread::sleep(interval);
+
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          if let Err(error) = index.write_replica_snapshot() {
+            log::warn!("Writing replica snapshot: {error}");
+          }
+        });
+      }
+
       let settings = Arc::new(settings);
       let acme_domains = self.acme_domains()?;
 
@@ -173,8 +431,11 @@ impl Server {
         csp_origin: self.csp_origin.clone(),
         decompress: self.decompress,
         domain: acme_domains.first().cloned(),
+        graphql_max_complexity: self.graphql_max_complexity,
+        graphql_max_depth: self.graphql_max_depth,
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
+        max_index_lag: self.max_index_lag,
         proxy: self.proxy.clone(),
       });
 
@@ -195,11 +456,25 @@ impl Server {
         .route("/collections", get(Self::collections))
         .route("/collections/{page}", get(Self::collections_paginated))
         .route("/decode/{txid}", get(Self::decode))
+        .route("/events", get(Self::events))
+        .route("/events/{sequence}", get(Self::event_log))
         .route("/faq", get(Self::faq))
         .route("/favicon.ico", get(Self::favicon))
         .route("/feed.xml", get(Self::feed))
         .route("/input/{block}/{transaction}/{input}", get(Self::input))
         .route("/inscription/{inscription_query}", get(Self::inscription))
+        .route(
+          "/inscription/{inscription_query}/delegators",
+          get(Self::inscription_delegators),
+        )
+        .route(
+          "/inscription/{inscription_query}/delegators/{page}",
+          get(Self::inscription_delegators_paginated),
+        )
+        .route(
+          "/inscription/{inscription_query}/history",
+          get(Self::inscription_history),
+        )
         .route(
           "/inscription/{inscription_query}/{child}",
           get(Self::inscription_child),
@@ -214,8 +489,26 @@ impl Server {
           "/inscriptions/block/{height}/{page}",
           get(Self::inscriptions_in_block_paginated),
         )
+        .route(
+          "/inscriptions/hash/{hash}",
+          get(Self::inscriptions_with_content_hash),
+        )
+        .route(
+          "/inscriptions/hash/{hash}/{page}",
+          get(Self::inscriptions_with_content_hash_paginated),
+        )
+        .route(
+          "/inscriptions/metadata",
+          get(Self::inscriptions_with_metadata),
+        )
+        .route(
+          "/inscriptions/metadata/{page}",
+          get(Self::inscriptions_with_metadata_paginated),
+        )
         .route("/inscriptions/{page}", get(Self::inscriptions_paginated))
         .route("/install.sh", get(Self::install_script))
+        .route("/mempool", get(Self::mempool))
+        .route("/openapi.json", get(Self::openapi))
         .route("/ordinal/{sat}", get(Self::ordinal))
         .route("/output/{output}", get(Self::output))
         .route("/outputs", post(Self::outputs))
@@ -228,6 +521,7 @@ impl Server {
         .route("/preview/{inscription_id}", get(Self::preview))
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/{rune}", get(Self::rune))
+        .route("/rune/{rune}/holders", get(Self::rune_holders))
         .route("/runes", get(Self::runes))
         .route("/runes/{page}", get(Self::runes_paginated))
         .route("/sat/{sat}", get(Self::sat))
@@ -259,6 +553,18 @@ impl Server {
           "/r/children/{inscription_id}/inscriptions/{page}",
           get(r::children_inscriptions_paginated),
         )
+        .route("/r/content-hash/{hash}", get(r::content_hash))
+        .route(
+          "/r/content-hash/{hash}/{page}",
+          get(r::content_hash_paginated),
+        )
+        .route("/r/delegates/{inscription_id}", get(r::delegates))
+        .route(
+          "/r/delegates/{inscription_id}/{page}",
+          get(r::delegates_paginated),
+        )
+        .route("/r/mempool", get(r::mempool))
+        .route("/r/metadata", post(r::metadata_batch))
         .route("/r/parents/{inscription_id}", get(r::parents))
         .route(
           "/r/parents/{inscription_id}/{page}",
@@ -290,6 +596,10 @@ impl Server {
           get(r::children_paginated),
         )
         .route("/r/inscription/{inscription_id}", get(r::inscription))
+        .route(
+          "/r/inscription/{inscription_id}/history",
+          get(r::inscription_history),
+        )
         .route("/r/metadata/{inscription_id}", get(r::metadata))
         .route(
           "/r/sat/{sat_number}/at/{index}/content",
@@ -299,8 +609,59 @@ impl Server {
 
       let router = router.merge(proxiable_routes);
 
+      let router = if server_config.json_api_enabled {
+        router
+          .route("/api/v1/addresses/{address}", get(v1::address))
+          .route("/api/v1/blocks/{query}", get(v1::block))
+          .route("/api/v1/inscriptions", get(v1::inscriptions))
+          .route("/api/v1/inscriptions/{query}", get(v1::inscription))
+          .route(
+            "/api/v1/inscriptions/{query}/children",
+            get(v1::inscription_children),
+          )
+          .route("/api/v1/outputs/{outpoint}", get(v1::output))
+          .route("/api/v1/runes", get(v1::runes))
+          .route("/api/v1/runes/{query}", get(v1::rune))
+          .route("/api/v1/sats/{sat}", get(v1::sat))
+          .route("/api/v1/status", get(v1::status))
+          .route("/api/v1/{*path}", get(v1::not_found))
+      } else {
+        router.route("/api/v1/{*path}", get(v1::disabled))
+      };
+
+      let router = if self.graphql {
+        router.route("/graphql", get(Self::graphql_schema).post(Self::graphql))
+      } else {
+        router
+      };
+
+      // snapshots copy the whole index, so they are only served to
+      // authenticated clients
+      let router = if settings.credentials().is_some() {
+        router.route("/snapshot", post(Self::snapshot))
+      } else {
+        router
+      };
+
+      let router = if self.disable_metrics {
+        router.fallback(Self::fallback)
+      } else {
+        router
+          .route("/metrics", get(Self::metrics))
+          .fallback(Self::fallback)
+          .layer(axum::middleware::from_fn(Self::record_metrics))
+      };
+
+      // probes are not authenticated, so orchestrators can use them without
+      // credentials
+      let probes = Router::new()
+        .route("/healthz", get(Self::healthz))
+        .route("/readyz", get(Self::readyz))
+        .layer(Extension(index.clone()))
+        .layer(Extension(server_config.clone()));
+
       let router = router
-        .fallback(Self::fallback)
+        .layer(Extension(event_sender))
         .layer(Extension(index))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
@@ -333,6 +694,8 @@ impl Server {
         router
       };
 
+      let router = router.merge(probes);
+
       match (self.http_port(), self.https_port()) {
         (Some(http_port), None) => {
           self
@@ -550,6 +913,28 @@ impl Server {
     Ok(response)
   }
 
+  async fn record_metrics(
+    Extension(index): Extension<Arc<Index>>,
+    request: http::Request<axum::body::Body>,
+    next: axum::middleware::Next,
+  ) -> Response {
+    let route = request
+      .extensions()
+      .get::<MatchedPath>()
+      .map(|path| path.as_str().to_string())
+      .unwrap_or_else(|| "fallback".into());
+
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    index
+      .metrics()
+      .record_http_request(&route, response.status().as_u16(), start.elapsed());
+
+    response
+  }
+
   fn index_height(index: &Index) -> ServerResult<Height> {
     index.block_height()?.ok_or_not_found(|| "genesis block")
   }
@@ -659,60 +1044,18 @@ impl Server {
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let inscriptions = index.get_inscription_ids_by_sat(sat)?;
-      let satpoint = index.rare_sat_satpoint(sat)?.or_else(|| {
-        inscriptions.first().and_then(|&first_inscription_id| {
-          index
-            .get_inscription_satpoint_by_id(first_inscription_id)
-            .ok()
-            .flatten()
-        })
-      });
-      let blocktime = index.block_time(sat.height())?;
-
-      let charms = sat.charms();
+      let (inscriptions, satpoint, address) = Self::sat_location(&index, &server_config, sat)?;
 
-      let address = if let Some(satpoint) = satpoint {
-        if satpoint.outpoint == unbound_outpoint() {
-          None
-        } else {
-          let tx = index
-            .get_transaction(satpoint.outpoint.txid)?
-            .context("could not get transaction for sat")?;
-
-          let tx_out = tx
-            .output
-            .get::<usize>(satpoint.outpoint.vout.try_into().unwrap())
-            .context("could not get vout for sat")?;
-
-          server_config
-            .chain
-            .address_from_script(&tx_out.script_pubkey)
-            .ok()
-        }
-      } else {
-        None
-      };
+      let blocktime = index.block_time(sat.height())?;
 
       Ok(if accept_json {
-        Json(api::Sat {
-          address: address.map(|address| address.to_string()),
-          block: sat.height().0,
-          charms: Charm::charms(charms),
-          cycle: sat.cycle(),
-          decimal: sat.decimal().to_string(),
-          degree: sat.degree().to_string(),
-          epoch: sat.epoch().0,
+        Json(api::Sat::new(
+          sat,
+          address,
+          blocktime,
           inscriptions,
-          name: sat.name(),
-          number: sat.0,
-          offset: sat.third(),
-          percentile: sat.percentile(),
-          period: sat.period(),
-          rarity: sat.rarity(),
           satpoint,
-          timestamp: blocktime.timestamp().timestamp(),
-        })
+        ))
         .into_response()
       } else {
         SatHtml {
@@ -728,10 +1071,123 @@ impl Server {
     })
   }
 
+  /// Inscriptions on `sat`, and its satpoint and address, if known
+  fn sat_location(
+    index: &Index,
+    server_config: &ServerConfig,
+    sat: Sat,
+  ) -> ServerResult<(Vec<InscriptionId>, Option<SatPoint>, Option<Address>)> {
+    let inscriptions = index.get_inscription_ids_by_sat(sat)?;
+
+    let satpoint = index.rare_sat_satpoint(sat)?.or_else(|| {
+      inscriptions.first().and_then(|&first_inscription_id| {
+        index
+          .get_inscription_satpoint_by_id(first_inscription_id)
+          .ok()
+          .flatten()
+      })
+    });
+
+    let address = if let Some(satpoint) = satpoint {
+      if satpoint.outpoint == unbound_outpoint() {
+        None
+      } else {
+        let tx = index
+          .get_transaction(satpoint.outpoint.txid)?
+          .context("could not get transaction for sat")?;
+
+        let tx_out = tx
+          .output
+          .get::<usize>(satpoint.outpoint.vout.try_into().unwrap())
+          .context("could not get vout for sat")?;
+
+        server_config
+          .chain
+          .address_from_script(&tx_out.script_pubkey)
+          .ok()
+      }
+    } else {
+      None
+    };
+
+    Ok((inscriptions, satpoint, address))
+  }
+
   async fn ordinal(Path(sat): Path<String>) -> Redirect {
     Redirect::to(&format!("/sat/{sat}"))
   }
 
+  async fn event_log(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(sequence): Path<u64>,
+    Query(query): Query<EventLogQuery>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !server_config.json_api_enabled {
+        return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API disabled").into_response());
+      }
+
+      if !index.has_event_index() {
+        return Err(ServerError::NotFound(
+          "this server has no event index".to_string(),
+        ));
+      }
+
+      Ok(Json(index.get_events(sequence, query.from_height, 100)?).into_response())
+    })
+  }
+
+  async fn events(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(event_sender): Extension<broadcast::Sender<Event>>,
+    Query(query): Query<EventsQuery>,
+  ) -> ServerResult {
+    if !server_config.json_api_enabled {
+      return Ok((StatusCode::NOT_ACCEPTABLE, "JSON API disabled").into_response());
+    }
+
+    let stream = futures::stream::unfold(
+      (event_sender.subscribe(), query),
+      |(mut receiver, query)| async move {
+        loop {
+          match receiver.recv().await {
+            Ok(event) if query.matches(&event) => {
+              return Some((sse::Event::default().json_data(event), (receiver, query)));
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+              log::warn!("event stream lagged, skipped {skipped} events");
+            }
+            Err(broadcast::error::RecvError::Closed) => return None,
+          }
+        }
+      },
+    );
+
+    Ok(
+      Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response(),
+    )
+  }
+
+  async fn mempool(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let mempool = index.mempool();
+
+      Ok(if accept_json {
+        Json(mempool).into_response()
+      } else {
+        mempool.page(server_config).into_response()
+      })
+    })
+  }
+
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -751,6 +1207,7 @@ impl Server {
           inscriptions: output_info.inscriptions,
           outpoint,
           output: txout,
+          pending: output_info.pending,
           runes: output_info.runes,
           sat_ranges: output_info.sat_ranges,
           spent: output_info.spent,
@@ -923,15 +1380,7 @@ impl Server {
         ));
       }
 
-      let rune = match rune_query {
-        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
-        query::Rune::Id(rune_id) => index
-          .get_rune_by_id(rune_id)?
-          .ok_or_not_found(|| format!("rune {rune_id}"))?,
-        query::Rune::Number(number) => index
-          .get_rune_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("rune number {number}"))?,
-      };
+      let rune = Self::rune_from_query(&index, rune_query)?;
 
       let Some((id, entry, parent)) = index.rune(rune)? else {
         return Ok(if accept_json {
@@ -955,10 +1404,13 @@ impl Server {
 
       let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
 
+      let holders = index.get_rune_holder_count(id)?;
+
       Ok(if accept_json {
         Json(api::Rune {
           entry,
           id,
+          holders,
           mintable,
           parent,
         })
@@ -967,6 +1419,7 @@ impl Server {
         RuneHtml {
           entry,
           id,
+          holders,
           mintable,
           parent,
         }
@@ -976,54 +1429,160 @@ impl Server {
     })
   }
 
-  async fn runes(
-    Extension(server_config): Extension<Arc<ServerConfig>>,
-    Extension(index): Extension<Arc<Index>>,
-    accept_json: AcceptJson,
-  ) -> ServerResult<Response> {
-    Self::runes_paginated(
-      Extension(server_config),
-      Extension(index),
-      Path(0),
-      accept_json,
-    )
-    .await
+  fn rune_from_query(index: &Index, rune_query: query::Rune) -> ServerResult<Rune> {
+    Ok(match rune_query {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => index
+        .get_rune_by_id(rune_id)?
+        .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      query::Rune::Number(number) => index
+        .get_rune_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("rune number {number}"))?,
+    })
   }
 
-  async fn runes_paginated(
+  async fn rune_holders(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(page_index): Path<usize>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    Query(listing): Query<ListingQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (entries, more) = index.runes_paginated(50, page_index)?;
+      if listing.sort.is_some() {
+        return Err(ServerError::BadRequest(
+          "rune holders are always sorted by balance".into(),
+        ));
+      }
 
-      let prev = page_index.checked_sub(1);
+      let no_index = || ServerError::NotFound("this server has no rune holder index".into());
 
-      let next = more.then_some(page_index + 1);
+      if !index.has_rune_holder_index() {
+        return Err(no_index());
+      }
 
-      Ok(if accept_json {
-        Json(RunesHtml {
-          entries,
-          more,
-          prev,
-          next,
-        })
-        .into_response()
+      let rune = Self::rune_from_query(&index, rune_query)?;
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let holders = index
+        .get_rune_holders(id, listing.cursor()?, listing.page_size(50)?)?
+        .ok_or_else(no_index)?;
+
+      let count = index.get_rune_holder_count(id)?.ok_or_else(no_index)?;
+
+      let holders = RuneHoldersHtml {
+        count,
+        divisibility: entry.divisibility,
+        holders: holders
+          .items
+          .into_iter()
+          .map(|(script_pubkey, amount)| api::RuneHolder {
+            address: server_config
+              .chain
+              .address_from_script(&script_pubkey)
+              .ok()
+              .map(|address| address.to_string()),
+            amount,
+            script_pubkey,
+          })
+          .collect(),
+        id,
+        limit: listing.limit,
+        more: holders.next.is_some(),
+        next_cursor: holders.next.map(|holder| holder.encode()),
+        prev_cursor: holders.prev.map(|holder| holder.encode()),
+        rune: entry.spaced_rune,
+        symbol: entry.symbol,
+      };
+
+      Ok(if accept_json {
+        Json(holders).into_response()
       } else {
-        RunesHtml {
-          entries,
-          more,
-          prev,
-          next,
-        }
-        .page(server_config)
-        .into_response()
+        holders.page(server_config).into_response()
       })
     })
   }
 
+  async fn runes(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Query(listing): Query<ListingQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let runes = index.runes_from(
+        listing.sort(Sort::Newest, "runes")?,
+        listing.cursor()?,
+        listing.page_size(50)?,
+      )?;
+
+      // without a cursor, this is the same as the first numbered page
+      let next = (listing.cursor.is_none() && runes.next.is_some()).then_some(1);
+
+      Ok(Self::runes_response(
+        server_config,
+        listing,
+        runes,
+        None,
+        next,
+        accept_json,
+      ))
+    })
+  }
+
+  async fn runes_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<usize>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let runes = index.runes_page(50, page_index)?;
+
+      let prev = page_index.checked_sub(1);
+
+      let next = runes.next.is_some().then_some(page_index + 1);
+
+      Ok(Self::runes_response(
+        server_config,
+        ListingQuery::default(),
+        runes,
+        prev,
+        next,
+        accept_json,
+      ))
+    })
+  }
+
+  fn runes_response(
+    server_config: Arc<ServerConfig>,
+    listing: ListingQuery,
+    runes: Listing<(RuneId, RuneEntry), RuneId>,
+    prev: Option<usize>,
+    next: Option<usize>,
+    accept_json: bool,
+  ) -> Response {
+    let runes = RunesHtml {
+      more: runes.next.is_some(),
+      entries: runes.items,
+      prev,
+      next,
+      prev_cursor: runes.prev.map(|id| id.encode()),
+      next_cursor: runes.next.map(|id| id.encode()),
+      limit: listing.limit,
+      sort: listing.sort,
+    };
+
+    if accept_json {
+      Json(runes).into_response()
+    } else {
+      runes.page(server_config).into_response()
+    }
+  }
+
   async fn home(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1071,6 +1630,7 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(address): Path<Address<NetworkUnchecked>>,
+    Query(query): Query<AddressQuery>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
@@ -1078,6 +1638,10 @@ impl Server {
         .require_network(server_config.chain.network())
         .map_err(|err| ServerError::BadRequest(err.to_string()))?;
 
+      if let Some(height) = query.height {
+        return Self::address_rune_balances(server_config, &index, address, height, accept_json);
+      }
+
       let Some(info) = Self::address_info(&index, &address)? else {
         return Err(ServerError::NotFound(
           "this server has no address index".to_string(),
@@ -1108,6 +1672,48 @@ impl Server {
     })
   }
 
+  fn address_rune_balances(
+    server_config: Arc<ServerConfig>,
+    index: &Index,
+    address: Address,
+    height: u32,
+    accept_json: bool,
+  ) -> ServerResult {
+    if index.block_count()? <= height {
+      return Err(ServerError::NotFound(format!("block {height} not found")));
+    }
+
+    let Some(balances) =
+      index.get_rune_balances_at_height(&AddressOrOutPoint::Address(address.clone()), height)?
+    else {
+      return Err(ServerError::NotFound(
+        "this server has no rune history index".to_string(),
+      ));
+    };
+
+    let outputs = balances.keys().copied().collect();
+
+    let runes_balances = Index::aggregate_rune_balances(balances.into_values());
+
+    Ok(if accept_json {
+      Json(api::AddressRuneBalances {
+        height,
+        outputs,
+        runes_balances,
+      })
+      .into_response()
+    } else {
+      AddressRuneBalancesHtml {
+        address,
+        height,
+        outputs,
+        runes_balances,
+      }
+      .page(server_config)
+      .into_response()
+    })
+  }
+
   fn address_info(index: &Index, address: &Address) -> ServerResult<Option<api::AddressInfo>> {
     if !index.has_address_index() {
       return Ok(None);
@@ -1138,26 +1744,7 @@ impl Server {
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (block, height) = match query {
-        query::Block::Height(height) => {
-          let block = index
-            .get_block_by_height(height)?
-            .ok_or_not_found(|| format!("block {height}"))?;
-
-          (block, height)
-        }
-        query::Block::Hash(hash) => {
-          let info = index
-            .block_header_info(hash)?
-            .ok_or_not_found(|| format!("block {hash}"))?;
-
-          let block = index
-            .get_block_by_hash(hash)?
-            .ok_or_not_found(|| format!("block {hash}"))?;
-
-          (block, u32::try_from(info.height).unwrap())
-        }
-      };
+      let (block, height) = Self::block_from_query(&index, query)?;
 
       let runes = index.get_runes_in_block(u64::from(height))?;
       Ok(if accept_json {
@@ -1187,6 +1774,29 @@ impl Server {
     })
   }
 
+  fn block_from_query(index: &Index, query: query::Block) -> ServerResult<(Block, u32)> {
+    Ok(match query {
+      query::Block::Height(height) => {
+        let block = index
+          .get_block_by_height(height)?
+          .ok_or_not_found(|| format!("block {height}"))?;
+
+        (block, height)
+      }
+      query::Block::Hash(hash) => {
+        let info = index
+          .block_header_info(hash)?
+          .ok_or_not_found(|| format!("block {hash}"))?;
+
+        let block = index
+          .get_block_by_hash(hash)?
+          .ok_or_not_found(|| format!("block {hash}"))?;
+
+        (block, u32::try_from(info.height).unwrap())
+      }
+    })
+  }
+
   async fn transaction(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1279,6 +1889,50 @@ impl Server {
     })
   }
 
+  async fn healthz(Extension(index): Extension<Arc<Index>>) -> Response {
+    task::block_in_place(|| match index.block_height() {
+      Ok(height) => Json(api::Health {
+        healthy: true,
+        height: height.map(|height| height.n()),
+        reason: None,
+      })
+      .into_response(),
+      Err(err) => (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(api::Health {
+          healthy: false,
+          height: None,
+          reason: Some(format!("failed to read index: {err}")),
+        }),
+      )
+        .into_response(),
+    })
+  }
+
+  async fn readyz(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+  ) -> Response {
+    task::block_in_place(|| {
+      let readiness = index
+        .readiness(server_config.max_index_lag)
+        .unwrap_or_else(|err| api::Readiness {
+          ready: false,
+          height: None,
+          lag: None,
+          reason: Some(format!("failed to read index: {err}")),
+        });
+
+      let status = if readiness.ready {
+        StatusCode::OK
+      } else {
+        StatusCode::SERVICE_UNAVAILABLE
+      };
+
+      (status, Json(readiness)).into_response()
+    })
+  }
+
   async fn search_by_query(
     Extension(index): Extension<Arc<Index>>,
     Query(search): Query<Search>,
@@ -1300,6 +1954,17 @@ impl Server {
       if re::HASH.is_match(query) {
         if index.block_header(query.parse().unwrap())?.is_some() {
           Ok(Redirect::to(&format!("/block/{query}")))
+        } else if index.has_content_hash_index()
+          && !index
+            .get_inscriptions_by_content_hash_paginated(
+              sha256::Hash::from_str(query).unwrap().to_byte_array(),
+              1,
+              0,
+            )?
+            .0
+            .is_empty()
+        {
+          Ok(Redirect::to(&format!("/inscriptions/hash/{query}")))
         } else {
           Ok(Redirect::to(&format!("/tx/{query}")))
         }
@@ -1402,6 +2067,77 @@ impl Server {
     )
   }
 
+  async fn graphql(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Json(request): Json<graphql::Request>,
+  ) -> Response {
+    task::block_in_place(|| graphql::execute(&index, &server_config, request).into_response())
+  }
+
+  async fn graphql_schema() -> String {
+    graphql::sdl()
+  }
+
+  async fn openapi() -> Json<serde_json::Value> {
+    Json(openapi::spec())
+  }
+
+  async fn metrics(Extension(index): Extension<Arc<Index>>) -> ServerResult {
+    task::block_in_place(|| {
+      Ok(
+        (
+          [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4"),
+          )],
+          index.render_metrics()?,
+        )
+          .into_response(),
+      )
+    })
+  }
+
+  /// Stream a snapshot of the index, taken from a read transaction, so that
+  /// `ord index snapshot` can be used while the server holds the index lock
+  async fn snapshot(Extension(index): Extension<Arc<Index>>) -> Response {
+    struct ChannelWriter(mpsc::Sender<io::Result<Vec<u8>>>);
+
+    impl io::Write for ChannelWriter {
+      fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self
+          .0
+          .blocking_send(Ok(buf.to_vec()))
+          .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "snapshot request closed"))?;
+        Ok(buf.len())
+      }
+
+      fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+      }
+    }
+
+    let (sender, receiver) = mpsc::channel(16);
+
+    task::spawn_blocking(move || {
+      if let Err(err) = index.write_snapshot(ChannelWriter(sender.clone())) {
+        log::error!("failed to write snapshot: {err}");
+        sender
+          .blocking_send(Err(io::Error::other(err.to_string())))
+          .ok();
+      }
+    });
+
+    (
+      [(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/octet-stream"),
+      )],
+      axum::body::Body::from_stream(tokio_stream::wrappers::ReceiverStream::new(receiver)),
+    )
+      .into_response()
+  }
+
   async fn block_count(Extension(index): Extension<Arc<Index>>) -> ServerResult<String> {
     task::block_in_place(|| Ok(index.block_count()?.to_string()))
   }
@@ -1546,6 +2282,117 @@ impl Server {
     Self::inscription_inner(server_config, &index, accept_json, query, Some(child)).await
   }
 
+  async fn inscription_delegators(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    accept_json: AcceptJson,
+    Path(query): Path<DeserializeFromStr<query::Inscription>>,
+  ) -> ServerResult {
+    Self::inscription_delegators_paginated(
+      Extension(server_config),
+      Extension(index),
+      accept_json,
+      Path((query, 0)),
+    )
+    .await
+  }
+
+  async fn inscription_delegators_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Path((DeserializeFromStr(query), page)): Path<(DeserializeFromStr<query::Inscription>, usize)>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_delegate_index() {
+        return Err(ServerError::NotFound(
+          "this server has no delegate index".to_string(),
+        ));
+      }
+
+      let id = Self::inscription_id(&index, query)?;
+
+      let entry = index
+        .get_inscription_entry(id)?
+        .ok_or_not_found(|| format!("inscription {id}"))?;
+
+      let (delegators, more) = index.get_delegators_paginated(id, 100, page)?;
+
+      Ok(if accept_json {
+        Json(api::Delegators {
+          ids: delegators,
+          more,
+          page,
+        })
+        .into_response()
+      } else {
+        DelegatorsHtml {
+          delegate: id,
+          delegate_number: entry.inscription_number,
+          delegators,
+          prev_page: page.checked_sub(1),
+          next_page: more.then_some(page + 1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  /// Look up the ID of the inscription matched by an inscription ID, number,
+  /// or sat name
+  fn inscription_id(index: &Index, query: query::Inscription) -> ServerResult<InscriptionId> {
+    match query {
+      query::Inscription::Id(id) => Some(id),
+      query::Inscription::Number(number) => {
+        index.get_inscription_id_by_inscription_number(number)?
+      }
+      query::Inscription::Sat(sat) => {
+        if !index.has_sat_index() {
+          return Err(ServerError::NotFound("sat index required".into()));
+        }
+
+        index.get_inscription_ids_by_sat(sat)?.first().copied()
+      }
+    }
+    .ok_or_not_found(|| format!("inscription {query}"))
+  }
+
+  async fn inscription_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_inscription_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no inscription history index".to_string(),
+        ));
+      }
+
+      let id = Self::inscription_id(&index, query)?;
+
+      let locations = index
+        .get_inscription_history(id)?
+        .ok_or_not_found(|| format!("inscription {id}"))?;
+
+      Ok(if accept_json {
+        Json(api::InscriptionHistory { id, locations }).into_response()
+      } else {
+        let entry = index.get_inscription_entry(id)?.unwrap();
+
+        InscriptionHistoryHtml {
+          id,
+          locations,
+          number: entry.inscription_number,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn inscription_inner(
     server_config: Arc<ServerConfig>,
     index: &Index,
@@ -1554,13 +2401,11 @@ impl Server {
     child: Option<usize>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      if let query::Inscription::Sat(_) = query {
-        if !index.has_sat_index() {
-          return Err(ServerError::NotFound("sat index required".into()));
-        }
-      }
-
-      let inscription_info = index.inscription_info(query, child)?;
+      let inscription_info = match Self::inscription_id(index, query) {
+        Ok(id) => index.inscription_info(query::Inscription::Id(id), child)?,
+        Err(ServerError::NotFound(_)) if accept_json => None,
+        Err(err) => return Err(err),
+      };
 
       Ok(if accept_json {
         let status_code = if inscription_info.is_none() {
@@ -1582,6 +2427,7 @@ impl Server {
           })),
           child_count: info.child_count,
           children: info.children,
+          delegator_count: info.delegator_count,
           fee: info.fee,
           height: info.height,
           inscription,
@@ -1590,6 +2436,7 @@ impl Server {
           next: info.next,
           output: txout,
           parents: info.parents,
+          pending: info.pending,
           previous: info.previous,
           rune: info.rune,
           sat: info.sat,
@@ -1660,14 +2507,28 @@ impl Server {
   async fn children(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(inscription_id): Path<InscriptionId>,
+    Path(parent): Path<InscriptionId>,
+    Query(listing): Query<ListingQuery>,
   ) -> ServerResult {
-    Self::children_paginated(
-      Extension(server_config),
-      Extension(index),
-      Path((inscription_id, 0)),
-    )
-    .await
+    task::block_in_place(|| {
+      let entry = index
+        .get_inscription_entry(parent)?
+        .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+      let children = index.get_children_by_sequence_number_from(
+        entry.sequence_number,
+        listing.sort(Sort::Oldest, "children")?,
+        listing.cursor()?,
+        listing.page_size(100)?,
+      )?;
+
+      Ok(Self::children_response(
+        server_config,
+        entry,
+        listing,
+        children,
+      ))
+    })
   }
 
   async fn children_paginated(
@@ -1680,73 +2541,150 @@ impl Server {
         .get_inscription_entry(parent)?
         .ok_or_not_found(|| format!("inscription {parent}"))?;
 
-      let parent_number = entry.inscription_number;
-
-      let (children, more_children) =
+      let (children, _) =
         index.get_children_by_sequence_number_paginated(entry.sequence_number, 100, page)?;
 
-      let prev_page = page.checked_sub(1);
-
-      let next_page = more_children.then_some(page + 1);
+      let children = match children.first() {
+        Some(child) => index.get_children_by_sequence_number_from(
+          entry.sequence_number,
+          Sort::Oldest,
+          index
+            .get_inscription_entry(*child)?
+            .map(|child| child.sequence_number),
+          100,
+        )?,
+        None => Listing {
+          items: Vec::new(),
+          prev: None,
+          next: None,
+        },
+      };
 
-      Ok(
-        ChildrenHtml {
-          parent,
-          parent_number,
-          children,
-          prev_page,
-          next_page,
-        }
-        .page(server_config)
-        .into_response(),
-      )
+      Ok(Self::children_response(
+        server_config,
+        entry,
+        ListingQuery::default(),
+        children,
+      ))
     })
   }
 
+  fn children_response(
+    server_config: Arc<ServerConfig>,
+    parent: InscriptionEntry,
+    listing: ListingQuery,
+    children: Listing<InscriptionId, u32>,
+  ) -> Response {
+    ChildrenHtml {
+      parent: parent.id,
+      parent_number: parent.inscription_number,
+      children: children.items,
+      limit: listing.limit,
+      prev: children.prev.map(|child| child.encode()),
+      next: children.next.map(|child| child.encode()),
+      sort: listing.sort,
+    }
+    .page(server_config)
+    .into_response()
+  }
+
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    accept_json: AcceptJson,
+    Query(filter): Query<InscriptionFilter>,
+    Query(listing): Query<ListingQuery>,
+    AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
-    Self::inscriptions_paginated(
-      Extension(server_config),
-      Extension(index),
-      Path(0),
-      accept_json,
-    )
-    .await
+    task::block_in_place(|| {
+      if filter.requires_attribute_index() && !index.has_inscription_attribute_index() {
+        return Err(ServerError::NotFound(
+          "this server has no inscription attribute index".to_string(),
+        ));
+      }
+
+      let inscriptions = index.get_inscriptions_from(
+        &filter,
+        listing.inscription_sort(&index)?,
+        listing.cursor()?,
+        listing.page_size(100)?,
+      )?;
+
+      Ok(Self::inscriptions_response(
+        server_config,
+        filter,
+        listing,
+        inscriptions,
+        0,
+        accept_json,
+      ))
+    })
   }
 
   async fn inscriptions_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<u32>,
+    Query(filter): Query<InscriptionFilter>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (inscriptions, more) = index.get_inscriptions_paginated(100, page_index)?;
+      if filter.requires_attribute_index() && !index.has_inscription_attribute_index() {
+        return Err(ServerError::NotFound(
+          "this server has no inscription attribute index".to_string(),
+        ));
+      }
 
-      let prev = page_index.checked_sub(1);
+      let inscriptions =
+        index.get_inscriptions_page(&filter, 100, page_index.try_into().unwrap())?;
 
-      let next = more.then_some(page_index + 1);
+      Ok(Self::inscriptions_response(
+        server_config,
+        filter,
+        ListingQuery::default(),
+        inscriptions,
+        page_index,
+        accept_json,
+      ))
+    })
+  }
 
-      Ok(if accept_json {
-        Json(api::Inscriptions {
-          ids: inscriptions,
-          page_index,
-          more,
-        })
-        .into_response()
-      } else {
-        InscriptionsHtml {
-          inscriptions,
-          next,
-          prev,
-        }
-        .page(server_config)
-        .into_response()
+  fn inscriptions_response(
+    server_config: Arc<ServerConfig>,
+    filter: InscriptionFilter,
+    listing: ListingQuery,
+    inscriptions: Listing<InscriptionId, u32>,
+    page_index: u32,
+    accept_json: bool,
+  ) -> Response {
+    let prev = inscriptions
+      .prev
+      .map(|sequence_number| sequence_number.encode());
+
+    let next = inscriptions
+      .next
+      .map(|sequence_number| sequence_number.encode());
+
+    if accept_json {
+      Json(api::Inscriptions {
+        ids: inscriptions.items,
+        more: next.is_some(),
+        next_cursor: next,
+        page_index,
+        prev_cursor: prev,
       })
-    })
+      .into_response()
+    } else {
+      InscriptionsHtml {
+        filter,
+        inscriptions: inscriptions.items,
+        limit: listing.limit,
+        prev,
+        next,
+        sort: listing.sort,
+      }
+      .page(server_config)
+      .into_response()
+    }
   }
 
   async fn inscriptions_in_block(
@@ -1794,6 +2732,8 @@ impl Server {
           ids: inscriptions,
           page_index,
           more,
+          next_cursor: None,
+          prev_cursor: None,
         })
         .into_response()
       } else {
@@ -1810,6 +2750,122 @@ impl Server {
     })
   }
 
+  async fn inscriptions_with_content_hash(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(hash): Path<DeserializeFromStr<sha256::Hash>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::inscriptions_with_content_hash_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((hash, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn inscriptions_with_content_hash_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(hash), page_index)): Path<(DeserializeFromStr<sha256::Hash>, u32)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_content_hash_index() {
+        return Err(ServerError::NotFound(
+          "this server has no content hash index".to_string(),
+        ));
+      }
+
+      let (inscriptions, more) = index.get_inscriptions_by_content_hash_paginated(
+        hash.to_byte_array(),
+        100,
+        page_index.try_into().unwrap(),
+      )?;
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: inscriptions,
+          page_index,
+          more,
+          next_cursor: None,
+          prev_cursor: None,
+        })
+        .into_response()
+      } else {
+        InscriptionsHashHtml {
+          hash,
+          inscriptions,
+          next: more.then_some(page_index + 1),
+          prev: page_index.checked_sub(1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn inscriptions_with_metadata(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    query: Query<MetadataQuery>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::inscriptions_with_metadata_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      query,
+      accept_json,
+    )
+    .await
+  }
+
+  async fn inscriptions_with_metadata_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<u32>,
+    Query(MetadataQuery { key, value }): Query<MetadataQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_metadata_index() {
+        return Err(ServerError::NotFound(
+          "this server has no metadata index".to_string(),
+        ));
+      }
+
+      let (inscriptions, more) = index.get_inscriptions_by_metadata_paginated(
+        &key,
+        value.as_deref(),
+        100,
+        page_index.try_into().unwrap(),
+      )?;
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: inscriptions,
+          page_index,
+          more,
+          next_cursor: None,
+          prev_cursor: None,
+        })
+        .into_response()
+      } else {
+        InscriptionsMetadataHtml {
+          inscriptions,
+          key,
+          next: more.then_some(page_index + 1),
+          prev: page_index.checked_sub(1),
+          value,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn parents(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1903,7 +2959,10 @@ mod tests {
       StatusCode, Url,
     },
     serde::de::DeserializeOwned,
-    std::net::TcpListener,
+    std::{
+      io::{BufRead, BufReader},
+      net::TcpListener,
+    },
     tempfile::TempDir,
   };
 
@@ -2014,8 +3073,10 @@ mod tests {
       args.push("--http-port".into());
       args.push(port.to_string());
 
-      args.push("--polling-interval".into());
-      args.push("100ms".into());
+      if !self.server_args.contains_key("--polling-interval") {
+        args.push("--polling-interval".into());
+        args.push("100ms".into());
+      }
 
       for (arg, value) in self.server_args {
         args.push(arg);
@@ -2036,13 +3097,19 @@ mod tests {
         .or_defaults()
         .unwrap();
 
-      let index = Arc::new(Index::open(&settings).unwrap());
+      let (event_sender, event_receiver) = mpsc::channel(EVENT_CAPACITY);
+
+      let index = Arc::new(Index::open_with_event_sender(&settings, Some(event_sender)).unwrap());
       let ord_server_handle = Handle::new();
 
       {
         let index = index.clone();
         let ord_server_handle = ord_server_handle.clone();
-        thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+        thread::spawn(|| {
+          server
+            .run(settings, index, ord_server_handle, event_receiver)
+            .unwrap()
+        });
       }
 
       while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -2084,6 +3151,10 @@ mod tests {
       self.ord_flag("--index-addresses")
     }
 
+    fn index_events(self) -> Self {
+      self.ord_flag("--index-events")
+    }
+
     fn index_runes(self) -> Self {
       self.ord_flag("--index-runes")
     }
@@ -2922,6 +3993,10 @@ mod tests {
         more: false,
         prev: None,
         next: None,
+        prev_cursor: None,
+        next_cursor: None,
+        limit: None,
+        sort: None,
       },
     );
 
@@ -2984,6 +4059,10 @@ mod tests {
         more: false,
         prev: None,
         next: None,
+        prev_cursor: None,
+        next_cursor: None,
+        limit: None,
+        sort: None,
       },
     );
   }
@@ -3054,6 +4133,7 @@ mod tests {
       RuneHtml {
         id,
         entry,
+        holders: None,
         mintable: false,
         parent: Some(parent),
       },
@@ -3117,6 +4197,203 @@ mod tests {
     );
   }
 
+  #[test]
+  fn address_rune_balances_at_height() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .ord_flag("--index-rune-history")
+      .build();
+
+    server.mine_blocks(1);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let outpoint = OutPoint { txid, vout: 0 };
+
+    let address = server.core.address(outpoint);
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let height = u32::try_from(id.block).unwrap();
+
+    let balances = |height| {
+      server.get_json::<api::AddressRuneBalances>(format!("/address/{address}?height={height}"))
+    };
+
+    pretty_assert_eq!(
+      balances(height - 1),
+      api::AddressRuneBalances {
+        height: height - 1,
+        outputs: Vec::new(),
+        runes_balances: Vec::new(),
+      },
+    );
+
+    let rune_balance = (
+      SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0,
+      },
+      Decimal {
+        value: 1000,
+        scale: 0,
+      },
+      None,
+    );
+
+    pretty_assert_eq!(
+      balances(height),
+      api::AddressRuneBalances {
+        height,
+        outputs: vec![outpoint],
+        runes_balances: vec![rune_balance],
+      },
+    );
+
+    pretty_assert_eq!(
+      balances(height + 1),
+      api::AddressRuneBalances {
+        height: height + 1,
+        outputs: vec![OutPoint {
+          txid: transfer,
+          vout: 0,
+        }],
+        runes_balances: vec![rune_balance],
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/address/{address}?height={height}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Address {address} at Height {height}</title>.*<dt>rune balances</dt>.*<dd><a class=monospace href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a>: 1000¤</dd>.*<li><a class=collapse href=/output/{outpoint}>{outpoint}</a></li>.*"
+      ),
+    );
+
+    server.assert_response(
+      format!("/address/{address}?height={}", height + 2),
+      StatusCode::NOT_FOUND,
+      &format!("block {} not found", height + 2),
+    );
+  }
+
+  #[test]
+  fn address_rune_balances_at_height_requires_rune_history_index() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.assert_response(
+      "/address/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202?height=0",
+      StatusCode::NOT_FOUND,
+      "this server has no rune history index",
+    );
+  }
+
+  #[test]
+  fn rune_holders() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .ord_flag("--index-rune-holders")
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let address = server.core.address(OutPoint { txid, vout: 0 });
+
+    server.assert_response_regex(
+      format!("/rune/{rune}"),
+      StatusCode::OK,
+      format!(".*<dt>holders</dt>\n  <dd><a href=/rune/{rune}/holders>1</a></dd>.*"),
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{rune}/holders")),
+      api::RuneHolders {
+        count: 1,
+        divisibility: 0,
+        holders: vec![api::RuneHolder {
+          address: Some(address.to_string()),
+          amount: 1000,
+          script_pubkey: address.script_pubkey(),
+        }],
+        id,
+        limit: None,
+        more: false,
+        next_cursor: None,
+        prev_cursor: None,
+        rune: SpacedRune { rune, spacers: 0 },
+        symbol: None,
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/rune/{id}/holders"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Rune {rune} Holders</title>.*<li><a class=collapse href=/address/{address}>{address}</a>: 1000.¤</li>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn rune_holders_require_rune_holder_index() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.assert_response(
+      format!("/rune/{}/holders", Rune(RUNE)),
+      StatusCode::NOT_FOUND,
+      "this server has no rune holder index",
+    );
+  }
+
   #[test]
   fn runes_are_spaced() {
     let server = TestServer::builder()
@@ -3372,6 +4649,7 @@ mod tests {
         indexed: true,
         inscriptions: Some(Vec::new()),
         outpoint: output,
+        pending: None,
         runes: Some(
           vec![(
             SpacedRune {
@@ -3413,11 +4691,1583 @@ mod tests {
       .assert_redirect("/", &format!("https://{}/", System::host_name().unwrap()));
   }
 
+  #[track_caller]
+  fn next_event(lines: &mut impl Iterator<Item = io::Result<String>>) -> Event {
+    let data = lines
+      .find_map(|line| line.unwrap().strip_prefix("data: ").map(str::to_string))
+      .unwrap();
+
+    serde_json::from_str(&data).unwrap()
+  }
+
   #[test]
-  fn status() {
+  fn events_are_streamed() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
 
-    server.mine_blocks(3);
+    server.mine_blocks(1);
+
+    let response = reqwest::blocking::get(server.join_url("/events")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      next_event(&mut BufReader::new(response).lines()),
+      Event::InscriptionCreated {
+        block_height: 2,
+        charms: 0,
+        inscription_id,
+        location: Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        }),
+        parent_inscription_ids: Vec::new(),
+        sequence_number: 0,
+      }
+    );
+  }
+
+  #[test]
+  fn events_can_be_filtered() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let response =
+      reqwest::blocking::get(server.join_url("/events?kind=rune_minted&rune=8:1")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(1),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    assert_eq!(id, RuneId { block: 8, tx: 1 });
+
+    let mint = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(5, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      next_event(&mut BufReader::new(response).lines()),
+      Event::RuneMinted {
+        amount: 1000,
+        block_height: 9,
+        rune_id: id,
+        txid: mint,
+      }
+    );
+  }
+
+  #[test]
+  fn events_query_matches() {
+    let parent = inscription_id(1);
+    let child = inscription_id(2);
+
+    let created = Event::InscriptionCreated {
+      block_height: 0,
+      charms: 0,
+      inscription_id: child,
+      location: None,
+      parent_inscription_ids: vec![parent],
+      sequence_number: 0,
+    };
+
+    let etched = Event::RuneEtched {
+      block_height: 0,
+      rune_id: RuneId { block: 1, tx: 1 },
+      txid: txid(1),
+    };
+
+    assert!(EventsQuery::default().matches(&created));
+    assert!(EventsQuery::default().matches(&etched));
+
+    let query = EventsQuery {
+      kind: Some(EventKind::InscriptionCreated),
+      ..default()
+    };
+    assert!(query.matches(&created));
+    assert!(!query.matches(&etched));
+
+    let query = EventsQuery {
+      parent: Some(parent),
+      ..default()
+    };
+    assert!(query.matches(&created));
+    assert!(!query.matches(&etched));
+
+    let query = EventsQuery {
+      inscription: Some(parent),
+      ..default()
+    };
+    assert!(!query.matches(&created));
+
+    let query = EventsQuery {
+      inscription: Some(child),
+      ..default()
+    };
+    assert!(query.matches(&created));
+
+    let query = EventsQuery {
+      rune: Some(RuneId { block: 1, tx: 1 }),
+      ..default()
+    };
+    assert!(!query.matches(&created));
+    assert!(query.matches(&etched));
+
+    let query = EventsQuery {
+      rune: Some(RuneId { block: 1, tx: 2 }),
+      ..default()
+    };
+    assert!(!query.matches(&etched));
+  }
+
+  #[test]
+  fn event_log_can_be_replayed() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_events()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let created = Event::InscriptionCreated {
+      block_height: 2,
+      charms: 0,
+      inscription_id: InscriptionId { txid, index: 0 },
+      location: Some(SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      }),
+      parent_inscription_ids: Vec::new(),
+      sequence_number: 0,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events/0"),
+      api::Events {
+        events: vec![api::LoggedEvent {
+          event: created,
+          height: 2,
+          sequence: 0,
+        }],
+        more: false,
+        next: 1,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events/1"),
+      api::Events {
+        events: Vec::new(),
+        more: false,
+        next: 1,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events/0?from_height=2"),
+      server.get_json::<api::Events>("/events/0"),
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events/0?from_height=3"),
+      api::Events {
+        events: Vec::new(),
+        more: false,
+        next: 1,
+      }
+    );
+  }
+
+  #[test]
+  fn event_log_requires_event_index() {
+    TestServer::new().assert_response(
+      "/events/0",
+      StatusCode::NOT_FOUND,
+      "this server has no event index",
+    );
+  }
+
+  #[test]
+  fn events_require_json_api() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--disable-json-api")
+      .build()
+      .assert_response("/events", StatusCode::NOT_ACCEPTABLE, "JSON API disabled");
+  }
+
+  #[test]
+  fn metrics() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(3);
+
+    server.assert_response("/blockcount", StatusCode::OK, "4");
+
+    let response = server.get("/metrics");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/plain; version=0.0.4"
+    );
+
+    let metrics = response.text().unwrap();
+
+    for line in [
+      "ord_blocks_indexed 4",
+      "ord_index_lag_blocks 0",
+      "ord_reorgs_total 0",
+      "ord_commit_duration_seconds_count ",
+      "ord_fetcher_request_duration_seconds_count{request=\"block\"} ",
+      "ord_table_total_bytes{table=\"HEIGHT_TO_BLOCK_HEADER\"} ",
+      "ord_http_requests_total{route=\"/blockcount\",status=\"200\"} 1",
+      "ord_http_request_duration_seconds_count{route=\"/blockcount\"} 1",
+    ] {
+      assert!(
+        metrics.lines().any(|metric| metric.starts_with(line)),
+        "missing `{line}` in:\n{metrics}"
+      );
+    }
+  }
+
+  #[test]
+  fn healthz() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      "/healthz",
+      StatusCode::OK,
+      r#"{"healthy":true,"height":1,"reason":null}"#,
+    );
+  }
+
+  #[test]
+  fn readyz() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_option("--height-limit", "2")
+      .build();
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      "/readyz",
+      StatusCode::OK,
+      r#"{"ready":true,"height":1,"lag":0,"reason":null}"#,
+    );
+
+    server.mine_blocks(2);
+
+    server.assert_response(
+      "/readyz",
+      StatusCode::SERVICE_UNAVAILABLE,
+      r#"{"ready":false,"height":1,"lag":2,"reason":"index is 2 blocks behind Bitcoin Core, maximum lag is 1"}"#,
+    );
+  }
+
+  #[test]
+  fn readyz_max_index_lag() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_option("--height-limit", "2")
+      .server_option("--max-index-lag", "2")
+      .build();
+
+    server.mine_blocks(3);
+
+    server.assert_response(
+      "/readyz",
+      StatusCode::OK,
+      r#"{"ready":true,"height":1,"lag":2,"reason":null}"#,
+    );
+  }
+
+  #[test]
+  fn metrics_can_be_disabled() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--disable-metrics")
+      .build();
+
+    assert_eq!(server.get("/metrics").status(), StatusCode::NOT_FOUND);
+  }
+
+  fn graphql(server: &TestServer, query: &str) -> (StatusCode, serde_json::Value) {
+    server.index.update().unwrap();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/graphql"))
+      .json(&serde_json::json!({ "query": query }))
+      .send()
+      .unwrap();
+
+    (response.status(), response.json().unwrap())
+  }
+
+  #[test]
+  fn graphql_is_disabled_by_default() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    assert_eq!(server.get("/graphql").status(), StatusCode::NOT_FOUND);
+  }
+
+  #[test]
+  fn graphql_schema() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--graphql")
+      .build();
+
+    server.assert_response_regex(
+      "/graphql",
+      StatusCode::OK,
+      ".*type Query \\{\n  address\\(address: String!\\): Address\n.*",
+    );
+  }
+
+  #[test]
+  fn graphql_connected_query() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .server_flag("--graphql")
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId { txid, index: 0 };
+
+    let (status, response) = graphql(
+      &server,
+      &format!(
+        r#"
+        query {{
+          blockCount
+          inscription(query: "{child}") {{
+            __typename
+            number
+            sat {{ number block {{ height }} }}
+            output {{ outpoint inscriptions {{ id }} }}
+            parents {{ id children {{ ...Id }} }}
+          }}
+          block(query: "3") {{ inscriptions(limit: 1) {{ ...Id }} }}
+        }}
+
+        fragment Id on Inscription {{ id }}
+        "#
+      ),
+    );
+
+    assert_eq!(status, StatusCode::OK);
+
+    pretty_assert_eq!(
+      response,
+      serde_json::json!({
+        "data": {
+          "blockCount": 4,
+          "inscription": {
+            "__typename": "Inscription",
+            "number": 1,
+            "sat": {
+              "number": Height(2).starting_sat().n(),
+              "block": { "height": 2 },
+            },
+            "output": {
+              "outpoint": format!("{txid}:0"),
+              "inscriptions": [
+                { "id": parent.to_string() },
+                { "id": child.to_string() },
+              ],
+            },
+            "parents": [{
+              "id": parent.to_string(),
+              "children": [{ "id": child.to_string() }],
+            }],
+          },
+          "block": {
+            "inscriptions": [{ "id": child.to_string() }],
+          },
+        },
+      }),
+    );
+  }
+
+  #[test]
+  fn graphql_field_errors_are_reported_with_path() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--graphql")
+      .build();
+
+    let (status, response) = graphql(
+      &server,
+      r#"{ blockCount inscription(query: "1.5") { id } }"#,
+    );
+
+    assert_eq!(status, StatusCode::OK);
+
+    pretty_assert_eq!(
+      response,
+      serde_json::json!({
+        "data": {
+          "blockCount": 1,
+          "inscription": null,
+        },
+        "errors": [{
+          "message": "bad inscription query 1.5",
+          "path": ["inscription"],
+        }],
+      }),
+    );
+  }
+
+  #[test]
+  fn graphql_limits() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--graphql")
+      .server_option("--graphql-max-depth", "3")
+      .server_option("--graphql-max-complexity", "50")
+      .build();
+
+    let (status, response) = graphql(
+      &server,
+      r#"{ sat(query: "0") { block { inscriptions(limit: 1) { id } } } }"#,
+    );
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    pretty_assert_eq!(
+      response,
+      serde_json::json!({
+        "errors": [{ "message": "query depth 4 exceeds maximum of 3" }],
+      }),
+    );
+
+    let (status, response) = graphql(
+      &server,
+      r#"{ sat(query: "0") { inscriptions(limit: 50) { id } } }"#,
+    );
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    pretty_assert_eq!(
+      response,
+      serde_json::json!({
+        "errors": [{ "message": "query complexity 52 exceeds maximum of 50" }],
+      }),
+    );
+  }
+
+  #[test]
+  fn api_v1() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let mut children = Vec::new();
+
+    // the parent is on the first output of the first transaction, and then
+    // on the second output of each child's transaction
+    for (height, vout) in [(2, 0), (3, 1)] {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[
+          (
+            height,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("child".into()),
+              parents: vec![parent.value()],
+              ..default()
+            }
+            .to_witness(),
+          ),
+          (height, 1, vout, Default::default()),
+        ],
+        outputs: 2,
+        ..default()
+      });
+
+      server.mine_blocks(1);
+
+      children.push(InscriptionId { txid, index: 0 });
+    }
+
+    let (etching, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          symbol: Some('$'),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let child = server.get_json::<api::Inscription>(format!("/inscription/{}", children[1]));
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>(format!("/api/v1/inscriptions/{}", children[1])),
+      serde_json::json!({
+        "address": child.address,
+        "charms": child.charms,
+        "child_count": 0,
+        "content_length": 5,
+        "content_type": "text/plain",
+        "fee": child.fee,
+        "height": 4,
+        "id": children[1],
+        "metaprotocol": null,
+        "number": 2,
+        "parents": [parent],
+        "rune": null,
+        "sat": child.sat,
+        "satpoint": child.satpoint,
+        "timestamp": child.timestamp,
+        "value": child.value,
+      }),
+    );
+
+    let entry = server.index.rune(Rune(RUNE)).unwrap().unwrap().1;
+
+    pretty_assert_eq!(
+      server.get_json::<serde_json::Value>("/api/v1/runes/AAAAAAAAAAAAA"),
+      serde_json::json!({
+        "block": id.block,
+        "burned": 0,
+        "divisibility": 0,
+        "etching": etching,
+        "holders": null,
+        "id": id,
+        "mintable": false,
+        "mints": 0,
+        "name": "AAAAAAAAAAAAA",
+        "number": 0,
+        "parent": null,
+        "premine": 1000,
+        "symbol": "$",
+        "terms": null,
+        "timestamp": entry.timestamp,
+        "turbo": false,
+      }),
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::v1::Output>(format!("/api/v1/outputs/{etching}:0")),
+      api::v1::Output {
+        address: Some(
+          server
+            .core
+            .address(OutPoint {
+              txid: etching,
+              vout: 0
+            })
+            .to_string()
+        ),
+        inscriptions: Some(Vec::new()),
+        outpoint: OutPoint {
+          txid: etching,
+          vout: 0,
+        },
+        runes: Some(vec![api::v1::RuneBalance {
+          amount: 1000,
+          divisibility: 0,
+          rune: SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          symbol: Some('$'),
+        }]),
+        sat_ranges: server
+          .get_json::<api::Output>(format!("/output/{etching}:0"))
+          .sat_ranges,
+        script_pubkey: server
+          .get_json::<api::Output>(format!("/output/{etching}:0"))
+          .script_pubkey,
+        spent: false,
+        value: 5000000000,
+      },
+    );
+
+    let block = server.get_json::<api::v1::Block>("/api/v1/blocks/4");
+    assert_eq!(block.height, 4);
+    assert_eq!(block.inscriptions, vec![children[1]]);
+    assert_eq!(block.transaction_count, 2);
+    assert_eq!(
+      block.previous_block,
+      Some(server.get_json::<api::v1::Block>("/api/v1/blocks/3").hash)
+    );
+    assert_eq!(
+      server
+        .get_json::<api::v1::Block>("/api/v1/blocks/0")
+        .previous_block,
+      None
+    );
+
+    let sat = server.get_json::<api::v1::Sat>("/api/v1/sats/0");
+    assert_eq!(sat.name, "nvtdijuwxlp");
+    assert_eq!(sat.rarity, Rarity::Mythic);
+
+    let status = server.get_json::<api::v1::Status>("/api/v1/status");
+    assert_eq!(status.chain, Chain::Regtest);
+    assert_eq!(status.inscriptions, 3);
+    assert_eq!(status.runes, 1);
+    assert!(status.sat_index);
+    assert_eq!(status.version, env!("CARGO_PKG_VERSION"));
+
+    let ids = |page: &api::v1::Page<api::v1::Inscription>| {
+      page
+        .items
+        .iter()
+        .map(|inscription| inscription.id)
+        .collect::<Vec<InscriptionId>>()
+    };
+
+    let first =
+      server.get_json::<api::v1::Page<api::v1::Inscription>>("/api/v1/inscriptions?limit=2");
+    assert_eq!(ids(&first), vec![children[1], children[0]]);
+
+    let second = server.get_json::<api::v1::Page<api::v1::Inscription>>(format!(
+      "/api/v1/inscriptions?limit=2&cursor={}",
+      first.next.unwrap()
+    ));
+    assert_eq!(ids(&second), vec![parent]);
+    assert_eq!(second.next, None);
+
+    let first = server.get_json::<api::v1::Page<api::v1::Inscription>>(format!(
+      "/api/v1/inscriptions/{parent}/children?limit=1"
+    ));
+    assert_eq!(ids(&first), vec![children[0]]);
+
+    let second = server.get_json::<api::v1::Page<api::v1::Inscription>>(format!(
+      "/api/v1/inscriptions/0/children?limit=1&cursor={}",
+      first.next.unwrap()
+    ));
+    assert_eq!(ids(&second), vec![children[1]]);
+    assert_eq!(second.next, None);
+
+    let runes = server.get_json::<api::v1::Page<api::v1::Rune>>("/api/v1/runes");
+    assert_eq!(runes.items.len(), 1);
+    assert_eq!(runes.items[0].id, id);
+    assert_eq!(runes.next, None);
+  }
+
+  #[test]
+  fn api_v1_errors_are_json() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let assert_error = |path: &str, status: StatusCode, code: api::v1::ErrorCode, message: &str| {
+      let response = server.get(path);
+      assert_eq!(response.status(), status, "{path}");
+      assert_eq!(
+        response.json::<api::v1::Error>().unwrap(),
+        api::v1::Error {
+          code,
+          message: message.into(),
+        },
+        "{path}",
+      );
+    };
+
+    assert_error(
+      &format!("/api/v1/inscriptions/{}", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      api::v1::ErrorCode::NotFound,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+
+    assert_error(
+      "/api/v1/inscriptions/foo:bar",
+      StatusCode::BAD_REQUEST,
+      api::v1::ErrorCode::BadRequest,
+      "invalid inscription query `foo:bar`: bad inscription query foo:bar",
+    );
+
+    assert_error(
+      "/api/v1/inscriptions?limit=1000",
+      StatusCode::BAD_REQUEST,
+      api::v1::ErrorCode::BadRequest,
+      "limit 1000 exceeds maximum of 100",
+    );
+
+    assert_error(
+      "/api/v1/inscriptions?cursor=foo",
+      StatusCode::BAD_REQUEST,
+      api::v1::ErrorCode::BadRequest,
+      "invalid cursor `foo`",
+    );
+
+    assert_error(
+      "/api/v1/runes",
+      StatusCode::NOT_FOUND,
+      api::v1::ErrorCode::NotFound,
+      "this server has no rune index",
+    );
+
+    assert_error(
+      "/api/v1/foo",
+      StatusCode::NOT_FOUND,
+      api::v1::ErrorCode::NotFound,
+      "/api/v1/foo not found",
+    );
+
+    let response = server.get("/api/v1/inscriptions?foo=bar");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.json::<api::v1::Error>().unwrap().code,
+      api::v1::ErrorCode::BadRequest
+    );
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--disable-json-api")
+      .build();
+
+    let response = server.get("/api/v1/status");
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(
+      response.json::<api::v1::Error>().unwrap(),
+      api::v1::Error {
+        code: api::v1::ErrorCode::NotAcceptable,
+        message: "JSON API disabled".into(),
+      },
+    );
+  }
+
+  #[test]
+  fn openapi_describes_json_responses() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .index_sats()
+      .ord_flag("--index-addresses")
+      .ord_flag("--index-content-hashes")
+      .ord_flag("--index-delegates")
+      .ord_flag("--index-events")
+      .ord_flag("--index-inscription-history")
+      .ord_flag("--index-metadata")
+      .ord_flag("--index-rune-history")
+      .ord_flag("--index-rune-holders")
+      .build();
+
+    server.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &ciborium::Value::Map(vec![(
+        ciborium::Value::Text("rank".into()),
+        ciborium::Value::Integer(7.into()),
+      )]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          metadata: Some(metadata),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            delegate: Some(parent.value()),
+            parents: vec![parent.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (2, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId { txid, index: 0 };
+
+    let rune = Rune(RUNE);
+
+    let (etching, _) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          symbol: Some('¢'),
+          terms: Some(Terms {
+            amount: Some(1),
+            cap: Some(10),
+            height: (None, Some(1000)),
+            offset: (None, None),
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let outpoint = OutPoint {
+      txid: etching,
+      vout: 0,
+    };
+
+    let address = server.core.address(outpoint);
+
+    let hash = sha256::Hash::hash(b"hello");
+
+    let sat = Height(2).starting_sat().n();
+
+    let examples = [
+      ("/address/{address}", format!("/address/{address}")),
+      ("/address/{address}", format!("/address/{address}?height=3")),
+      (
+        "/api/v1/addresses/{address}",
+        format!("/api/v1/addresses/{address}"),
+      ),
+      ("/api/v1/blocks/{query}", "/api/v1/blocks/3".into()),
+      (
+        "/api/v1/inscriptions",
+        "/api/v1/inscriptions?limit=1".into(),
+      ),
+      (
+        "/api/v1/inscriptions/{query}",
+        format!("/api/v1/inscriptions/{child}"),
+      ),
+      (
+        "/api/v1/inscriptions/{query}/children",
+        format!("/api/v1/inscriptions/{parent}/children"),
+      ),
+      (
+        "/api/v1/outputs/{outpoint}",
+        format!("/api/v1/outputs/{outpoint}"),
+      ),
+      ("/api/v1/runes", "/api/v1/runes".into()),
+      ("/api/v1/runes/{query}", format!("/api/v1/runes/{rune}")),
+      ("/api/v1/sats/{sat}", format!("/api/v1/sats/{sat}")),
+      ("/api/v1/status", "/api/v1/status".into()),
+      ("/block/{query}", "/block/3".into()),
+      ("/blocks", "/blocks".into()),
+      ("/decode/{txid}", format!("/decode/{txid}")),
+      ("/events/{sequence}", "/events/0".into()),
+      ("/healthz", "/healthz".into()),
+      (
+        "/inscription/{inscription_query}",
+        format!("/inscription/{child}"),
+      ),
+      (
+        "/inscription/{inscription_query}/delegators",
+        format!("/inscription/{parent}/delegators"),
+      ),
+      (
+        "/inscription/{inscription_query}/delegators/{page}",
+        format!("/inscription/{parent}/delegators/0"),
+      ),
+      (
+        "/inscription/{inscription_query}/history",
+        format!("/inscription/{child}/history"),
+      ),
+      (
+        "/inscription/{inscription_query}/{child}",
+        format!("/inscription/{parent}/0"),
+      ),
+      ("/inscriptions", "/inscriptions".into()),
+      (
+        "/inscriptions/block/{height}",
+        "/inscriptions/block/3".into(),
+      ),
+      (
+        "/inscriptions/block/{height}/{page}",
+        "/inscriptions/block/3/0".into(),
+      ),
+      (
+        "/inscriptions/hash/{hash}",
+        format!("/inscriptions/hash/{hash}"),
+      ),
+      (
+        "/inscriptions/hash/{hash}/{page}",
+        format!("/inscriptions/hash/{hash}/0"),
+      ),
+      (
+        "/inscriptions/metadata",
+        "/inscriptions/metadata?key=rank".into(),
+      ),
+      (
+        "/inscriptions/metadata/{page}",
+        "/inscriptions/metadata/0?key=rank&value=7".into(),
+      ),
+      ("/inscriptions/{page}", "/inscriptions/0".into()),
+      ("/mempool", "/mempool".into()),
+      ("/openapi.json", "/openapi.json".into()),
+      ("/output/{output}", format!("/output/{outpoint}")),
+      ("/outputs/{address}", format!("/outputs/{address}")),
+      (
+        "/outputs/{address}",
+        format!("/outputs/{address}?type=runic"),
+      ),
+      ("/readyz", "/readyz".into()),
+      ("/r/blockhash", "/r/blockhash".into()),
+      ("/r/blockhash/{height}", "/r/blockhash/0".into()),
+      ("/r/blockinfo/{query}", "/r/blockinfo/3".into()),
+      (
+        "/r/children/{inscription_id}",
+        format!("/r/children/{parent}"),
+      ),
+      (
+        "/r/children/{inscription_id}/{page}",
+        format!("/r/children/{parent}/0"),
+      ),
+      (
+        "/r/children/{inscription_id}/inscriptions",
+        format!("/r/children/{parent}/inscriptions"),
+      ),
+      (
+        "/r/children/{inscription_id}/inscriptions/{page}",
+        format!("/r/children/{parent}/inscriptions/0"),
+      ),
+      ("/r/content-hash/{hash}", format!("/r/content-hash/{hash}")),
+      (
+        "/r/content-hash/{hash}/{page}",
+        format!("/r/content-hash/{hash}/0"),
+      ),
+      (
+        "/r/delegates/{inscription_id}",
+        format!("/r/delegates/{parent}"),
+      ),
+      (
+        "/r/delegates/{inscription_id}/{page}",
+        format!("/r/delegates/{parent}/0"),
+      ),
+      (
+        "/r/inscription/{inscription_id}",
+        format!("/r/inscription/{child}"),
+      ),
+      (
+        "/r/inscription/{inscription_id}/history",
+        format!("/r/inscription/{child}/history"),
+      ),
+      ("/r/mempool", "/r/mempool".into()),
+      (
+        "/r/metadata/{inscription_id}",
+        format!("/r/metadata/{parent}"),
+      ),
+      ("/r/parents/{inscription_id}", format!("/r/parents/{child}")),
+      (
+        "/r/parents/{inscription_id}/{page}",
+        format!("/r/parents/{child}/0"),
+      ),
+      (
+        "/r/parents/{inscription_id}/inscriptions",
+        format!("/r/parents/{child}/inscriptions"),
+      ),
+      (
+        "/r/parents/{inscription_id}/inscriptions/{page}",
+        format!("/r/parents/{child}/inscriptions/0"),
+      ),
+      ("/r/sat/{sat_number}", format!("/r/sat/{sat}")),
+      ("/r/sat/{sat_number}/{page}", format!("/r/sat/{sat}/0")),
+      (
+        "/r/sat/{sat_number}/at/{index}",
+        format!("/r/sat/{sat}/at/-1"),
+      ),
+      ("/r/tx/{txid}", format!("/r/tx/{txid}")),
+      ("/r/utxo/{outpoint}", format!("/r/utxo/{outpoint}")),
+      ("/rune/{rune}", format!("/rune/{rune}")),
+      ("/rune/{rune}/holders", format!("/rune/{rune}/holders")),
+      ("/runes", "/runes".into()),
+      ("/runes/{page}", "/runes/0".into()),
+      ("/sat/{sat}", format!("/sat/{sat}")),
+      ("/status", "/status".into()),
+      ("/tx/{txid}", format!("/tx/{txid}")),
+    ];
+
+    let posts = [
+      ("/inscriptions", serde_json::json!([parent, child])),
+      ("/outputs", serde_json::json!([outpoint])),
+      ("/r/metadata", serde_json::json!([parent, child])),
+    ];
+
+    let spec = server.get_json::<serde_json::Value>("/openapi.json");
+
+    pretty_assert_eq!(spec, openapi::spec());
+
+    let routes = openapi::routes();
+
+    let client = reqwest::blocking::Client::new();
+
+    let check =
+      |method: openapi::Method, route: &str, request: reqwest::blocking::RequestBuilder| {
+        let route = routes
+          .iter()
+          .find(|candidate| candidate.method == method && candidate.path == route)
+          .unwrap_or_else(|| panic!("{route} is not described"));
+
+        let request = if route.accept_json {
+          request.header(header::ACCEPT, "application/json")
+        } else {
+          request
+        };
+
+        let response = request.send().unwrap();
+
+        let url = response.url().clone();
+
+        assert_eq!(response.status(), StatusCode::OK, "{url}");
+
+        let value = response.json::<serde_json::Value>().unwrap();
+
+        let method = format!("{method:?}").to_lowercase();
+
+        openapi::validate(
+          &spec,
+          &spec["paths"][route.path][method]["responses"]["200"]["content"]["application/json"]
+            ["schema"],
+          &value,
+          url.path(),
+        )
+        .unwrap();
+      };
+
+    server.index.update().unwrap();
+
+    for (route, path) in &examples {
+      check(
+        openapi::Method::Get,
+        route,
+        client.get(server.join_url(path)),
+      );
+    }
+
+    for (route, body) in &posts {
+      check(
+        openapi::Method::Post,
+        route,
+        client.post(server.join_url(route)).json(body),
+      );
+    }
+
+    let exercised = examples
+      .iter()
+      .map(|(route, _)| (openapi::Method::Get, *route))
+      .chain(
+        posts
+          .iter()
+          .map(|(route, _)| (openapi::Method::Post, *route)),
+      )
+      .collect::<Vec<(openapi::Method, &str)>>();
+
+    for route in &routes {
+      assert!(
+        exercised.contains(&(route.method, route.path)),
+        "{:?} {} has no example",
+        route.method,
+        route.path,
+      );
+    }
+  }
+
+  #[test]
+  fn zmq_notifications_trigger_index_updates() {
+    use zeromq::{PubSocket, Socket, SocketSend, ZmqMessage};
+
+    let runtime = Runtime::new().unwrap();
+
+    let mut publisher = PubSocket::new();
+
+    let endpoint = runtime
+      .block_on(publisher.bind("tcp://127.0.0.1:0"))
+      .unwrap();
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--polling-interval", "1h")
+      .server_option("--zmq-url", &endpoint.to_string())
+      .build();
+
+    assert_eq!(server.index.block_count().unwrap(), 1);
+
+    let hash = server.core.mine_blocks(1)[0].block_hash();
+
+    // subscriptions are established asynchronously, so notifications sent
+    // before then are dropped
+    for attempt in 0.. {
+      let mut notification = ZmqMessage::from("hashblock");
+      notification.push_back(hash.to_byte_array().to_vec().into());
+      runtime.block_on(publisher.send(notification)).unwrap();
+
+      if server.index.block_count().unwrap() == 2 {
+        break;
+      }
+
+      assert!(attempt < 100, "index was not updated");
+
+      thread::sleep(Duration::from_millis(50));
+    }
+  }
+
+  #[test]
+  fn zmq_rawtx_notifications_only_update_mempool() {
+    use zeromq::{PubSocket, Socket, SocketSend, ZmqMessage};
+
+    let runtime = Runtime::new().unwrap();
+
+    let mut publisher = PubSocket::new();
+
+    let endpoint = runtime
+      .block_on(publisher.bind("tcp://127.0.0.1:0"))
+      .unwrap();
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--mempool")
+      .server_option("--polling-interval", "1h")
+      .server_option("--zmq-url", &endpoint.to_string())
+      .build();
+
+    server.mine_blocks(1);
+
+    server.core.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    for attempt in 0.. {
+      let mut notification = ZmqMessage::from("rawtx");
+      notification.push_back(txid.to_byte_array().to_vec().into());
+      runtime.block_on(publisher.send(notification)).unwrap();
+
+      if !server.index.mempool().transactions.is_empty() {
+        break;
+      }
+
+      assert!(attempt < 100, "mempool was not updated");
+
+      thread::sleep(Duration::from_millis(50));
+    }
+
+    assert_eq!(server.index.block_count().unwrap(), 2);
+  }
+
+  #[test]
+  fn mempool_shows_unconfirmed_inscriptions() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.index.update_mempool().unwrap();
+
+    let mempool = api::Mempool {
+      tip: server.index.block_hash(None).unwrap(),
+      transactions: vec![api::MempoolTransaction {
+        etching: None,
+        inscriptions: vec![InscriptionId { txid, index: 0 }],
+        mint: None,
+        runes: Vec::new(),
+        transferred_inscriptions: Vec::new(),
+        txid,
+      }],
+    };
+
+    pretty_assert_eq!(server.get_json::<api::Mempool>("/mempool"), mempool);
+    pretty_assert_eq!(server.get_json::<api::Mempool>("/r/mempool"), mempool);
+
+    server.assert_response_regex(
+      "/mempool",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Mempool</h1>
+<h2><a class=collapse href=/tx/{txid}>{txid}</a></h2>
+<dl>
+  <dt>inscriptions</dt>
+  <dd class=collapse>{txid}i0</dd>
+</dl>.*"
+      ),
+    );
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::Mempool>("/mempool"),
+      api::Mempool {
+        tip: None,
+        transactions: Vec::new(),
+      }
+    );
+
+    server.index.update_mempool().unwrap();
+
+    pretty_assert_eq!(
+      server.get_json::<api::Mempool>("/mempool"),
+      api::Mempool {
+        tip: server.index.block_hash(None).unwrap(),
+        transactions: Vec::new(),
+      }
+    );
+  }
+
+  #[test]
+  fn pending_inscription_transfers_are_marked() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let reveal = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId {
+      txid: reveal,
+      index: 0,
+    };
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      r".*<dt>offset</dt>\s*<dd>0</dd>\s*<dt>details</dt>.*",
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.index.update_mempool().unwrap();
+
+    pretty_assert_eq!(
+      server.get_json::<api::Mempool>("/mempool").transactions,
+      [api::MempoolTransaction {
+        etching: None,
+        inscriptions: Vec::new(),
+        mint: None,
+        runes: Vec::new(),
+        transferred_inscriptions: vec![id],
+        txid,
+      }]
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        r".*<dt>offset</dt>\s*<dd>0</dd>\s*<dt>pending</dt>\s*<dd><a class=collapse href=/tx/{txid}>{txid}</a></dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/output/{reveal}:0"),
+      StatusCode::OK,
+      format!(".*<dt>pending</dt><dd><a class=collapse href=/tx/{txid}>{txid}</a></dd>.*"),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{id}"))
+        .pending,
+      Some(txid),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Output>(format!("/output/{reveal}:0"))
+        .pending,
+      Some(txid),
+    );
+  }
+
+  #[test]
+  fn pending_inscription_transfers_follow_unconfirmed_chains() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let reveal = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId {
+      txid: reveal,
+      index: 0,
+    };
+
+    let parent = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    let value = server.core.mempool()[0].output[0].value;
+
+    let child = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint {
+          txid: parent,
+          vout: 0,
+        },
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value,
+        script_pubkey: ScriptBuf::new(),
+      }],
+    };
+
+    let child_txid = child.compute_txid();
+
+    server.core.state().mempool.push(child);
+
+    server.index.update_mempool().unwrap();
+
+    let mut transactions = server.get_json::<api::Mempool>("/mempool").transactions;
+
+    transactions.sort_by_key(|transaction| transaction.txid != parent);
+
+    pretty_assert_eq!(
+      transactions,
+      [
+        api::MempoolTransaction {
+          etching: None,
+          inscriptions: Vec::new(),
+          mint: None,
+          runes: Vec::new(),
+          transferred_inscriptions: vec![id],
+          txid: parent,
+        },
+        api::MempoolTransaction {
+          etching: None,
+          inscriptions: Vec::new(),
+          mint: None,
+          runes: Vec::new(),
+          transferred_inscriptions: vec![id],
+          txid: child_txid,
+        },
+      ]
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Output>(format!("/output/{parent}:0"))
+        .pending,
+      Some(child_txid),
+    );
+  }
+
+  #[test]
+  fn pending_rune_transfers_are_shown_in_mempool() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let (_, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(u128::MAX),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    server.index.update_mempool().unwrap();
+
+    pretty_assert_eq!(
+      server.get_json::<api::Mempool>("/mempool").transactions,
+      [api::MempoolTransaction {
+        etching: None,
+        inscriptions: Vec::new(),
+        mint: Some(id),
+        runes: vec![SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        }],
+        transferred_inscriptions: Vec::new(),
+        txid,
+      }]
+    );
+  }
+
+  #[test]
+  fn status() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(3);
 
     server.core.broadcast_tx(TransactionTemplate {
       inputs: &[(
@@ -4563,98 +7413,426 @@ mod tests {
 
     server.mine_blocks(1);
 
-    server.assert_response_csp(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      "default-src 'self' 'unsafe-inline'",
-      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+    server.assert_response_csp(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-inline'",
+      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+    );
+  }
+
+  #[test]
+  fn iframe_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/html;charset=utf-8", "hello").to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
+      "hello",
+    );
+  }
+
+  #[test]
+  fn unknown_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self'",
+      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn video_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
+      ..default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn inscription_page_title() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      ".*<title>Inscription 0</title>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_history() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-inscription-history")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{id}/history"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 History</title>.*<li>.*<dd><a class=collapse href=/tx/{txid}>{txid}</a></dd>.*</li>.*<li>.*<dd><a class=collapse href=/tx/{transfer}>{transfer}</a></dd>.*</li>.*"
+      ),
+    );
+
+    let history = server.get_json::<api::InscriptionHistory>(format!("/inscription/{id}/history"));
+
+    assert_eq!(history.id, id);
+    assert_eq!(
+      history
+        .locations
+        .iter()
+        .map(|location| (location.height, location.txid))
+        .collect::<Vec<(u32, Txid)>>(),
+      [(2, txid), (3, transfer)],
+    );
+
+    assert_eq!(
+      server.get_json::<api::InscriptionHistory>("/inscription/0/history"),
+      history,
+    );
+
+    assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/r/inscription/{id}/history")),
+      history,
+    );
+
+    server.assert_response(
+      "/inscription/1/history",
+      StatusCode::NOT_FOUND,
+      "inscription 1 not found",
+    );
+
+    server.assert_response(
+      format!("/r/inscription/{}/history", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+  }
+
+  #[test]
+  fn inscription_history_requires_index() {
+    let server = TestServer::new();
+
+    server.assert_response(
+      format!("/inscription/{}/history", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      "this server has no inscription history index",
+    );
+
+    server.assert_response(
+      format!("/r/inscription/{}/history", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      "this server has no inscription history index",
+    );
+  }
+
+  #[test]
+  fn inscriptions_with_content_hash() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-content-hashes")
+      .build();
+
+    server.mine_blocks(2);
+
+    let mut ids = Vec::new();
+
+    for height in 1..=2 {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          height,
+          0,
+          0,
+          inscription("text/plain", "hello").to_witness(),
+        )],
+        ..default()
+      });
+
+      server.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    let hash = sha256::Hash::hash(b"hello");
+
+    server.assert_response_regex(
+      format!("/inscriptions/hash/{hash}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscriptions with Content Hash {hash}</title>.*<dd class=collapse>{hash}</dd>.*<a href=/inscription/{}>.*<a href=/inscription/{}>.*",
+        ids[0], ids[1],
+      ),
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/inscriptions/hash/{hash}")),
+      api::Inscriptions {
+        ids: ids.clone(),
+        more: false,
+        page_index: 0,
+        next_cursor: None,
+        prev_cursor: None,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/content-hash/{hash}")),
+      api::Inscriptions {
+        ids,
+        more: false,
+        next_cursor: None,
+        page_index: 0,
+        prev_cursor: None,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/content-hash/{hash}/1")),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        next_cursor: None,
+        page_index: 1,
+        prev_cursor: None,
+      },
+    );
+
+    server.assert_redirect(
+      &format!("/search/{hash}"),
+      &format!("/inscriptions/hash/{hash}"),
     );
+
+    let unknown = sha256::Hash::hash(b"goodbye");
+
+    server.assert_redirect(&format!("/search/{unknown}"), &format!("/tx/{unknown}"));
   }
 
   #[test]
-  fn iframe_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+  fn inscriptions_with_metadata() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-metadata")
+      .build();
+
     server.mine_blocks(1);
 
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &ciborium::Value::Map(vec![
+        (
+          ciborium::Value::Text("eye color".into()),
+          ciborium::Value::Text("blue".into()),
+        ),
+        (
+          ciborium::Value::Text("rank".into()),
+          ciborium::Value::Integer(7.into()),
+        ),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
     let txid = server.core.broadcast_tx(TransactionTemplate {
       inputs: &[(
         1,
         0,
         0,
-        inscription("text/html;charset=utf-8", "hello").to_witness(),
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("foo".into()),
+          metadata: Some(metadata),
+          ..default()
+        }
+        .to_witness(),
       )],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+    let id = InscriptionId { txid, index: 0 };
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metadata?key=rank&value=7"),
+      api::Inscriptions {
+        ids: vec![id],
+        more: false,
+        page_index: 0,
+        next_cursor: None,
+        prev_cursor: None,
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/metadata/1?key=rank"),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
+        next_cursor: None,
+        prev_cursor: None,
+      },
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/metadata?key=eye%20color&value=blue",
       StatusCode::OK,
-      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
-      "hello",
+      format!(
+        ".*<title>Inscriptions with Metadata eye color=blue</title>.*<a href=/inscription/{id}>.*"
+      ),
     );
-  }
 
-  #[test]
-  fn unknown_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    server.mine_blocks(1);
+    server.assert_response_regex(
+      "/inscriptions/metadata",
+      StatusCode::BAD_REQUEST,
+      ".*missing field `key`.*",
+    );
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..default()
-    });
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/r/metadata"))
+      .json(&vec![id])
+      .send()
+      .unwrap();
 
-    server.mine_blocks(1);
+    assert_eq!(response.status(), StatusCode::OK);
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      "default-src 'self'",
-      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    assert_eq!(
+      serde_json::from_str::<Vec<Option<api::InscriptionMetadata>>>(&response.text().unwrap())
+        .unwrap(),
+      vec![Some(api::InscriptionMetadata {
+        id,
+        metadata: Some(serde_json::json!({
+          "eye color": "blue",
+          "rank": 7,
+        })),
+      })],
     );
-  }
 
-  #[test]
-  fn video_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    server.mine_blocks(1);
+    let missing = InscriptionId { txid, index: 1 };
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
-      ..default()
-    });
-    let inscription_id = InscriptionId { txid, index: 0 };
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/r/metadata"))
+      .json(&vec![missing, id])
+      .send()
+      .unwrap();
 
-    server.mine_blocks(1);
+    assert_eq!(response.status(), StatusCode::OK);
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+    assert_eq!(
+      serde_json::from_str::<Vec<Option<api::InscriptionMetadata>>>(&response.text().unwrap())
+        .unwrap()
+        .iter()
+        .map(|metadata| metadata.as_ref().map(|metadata| metadata.id))
+        .collect::<Vec<Option<InscriptionId>>>(),
+      vec![None, Some(id)],
+    );
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/r/metadata"))
+      .json(&vec![id; 101])
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    assert_eq!(
+      response.text().unwrap(),
+      "batch of 101 inscriptions exceeds maximum of 100",
     );
   }
 
   #[test]
-  fn inscription_page_title() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
-    server.mine_blocks(1);
+  fn inscriptions_with_metadata_requires_index() {
+    TestServer::new().assert_response(
+      "/inscriptions/metadata?key=foo",
+      StatusCode::NOT_FOUND,
+      "this server has no metadata index",
+    );
+  }
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..default()
-    });
+  #[test]
+  fn inscriptions_with_content_hash_requires_index() {
+    let server = TestServer::new();
 
-    server.mine_blocks(1);
+    let hash = sha256::Hash::hash(b"hello");
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      ".*<title>Inscription 0</title>.*",
+    server.assert_response(
+      format!("/inscriptions/hash/{hash}"),
+      StatusCode::NOT_FOUND,
+      "this server has no content hash index",
+    );
+
+    server.assert_response(
+      format!("/r/content-hash/{hash}"),
+      StatusCode::NOT_FOUND,
+      "this server has no content hash index",
     );
   }
 
@@ -4895,7 +8073,7 @@ mod tests {
     server.assert_response_regex(
       "/inscriptions/1",
       StatusCode::OK,
-      ".*<a class=prev href=/inscriptions/0>prev</a>\nnext.*",
+      ".*<a class=prev href=/inscriptions\\?cursor=AAAAZA>prev</a>\nnext.*",
     );
   }
 
@@ -4906,20 +8084,198 @@ mod tests {
       .index_sats()
       .build();
 
-    for i in 0..101 {
-      server.mine_blocks(1);
-      server.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
-        ..default()
-      });
-    }
+    for i in 0..101 {
+      server.mine_blocks(1);
+      server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions/0",
+      StatusCode::OK,
+      ".*prev\n<a class=next href=/inscriptions\\?cursor=AAAAAA>next</a>.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_with_cursor() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let mut ids = Vec::new();
+
+    for i in 0..3 {
+      server.mine_blocks(1);
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..default()
+      });
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions?cursor=AAAAAQ&limit=1&sort=oldest",
+      StatusCode::OK,
+      format!(
+        ".*<a href=/inscription/{}>.*
+</div>
+<div class=center>
+<a class=prev href=/inscriptions\\?cursor=AAAAAA&(amp;)?limit=1&(amp;)?sort=oldest>prev</a>
+<a class=next href=/inscriptions\\?cursor=AAAAAg&(amp;)?limit=1&(amp;)?sort=oldest>next</a>.*",
+        ids[1],
+      ),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscriptions>("/inscriptions?cursor=AAAAAQ&limit=1")
+        .ids,
+      [ids[1]],
+    );
+
+    let inscriptions = server.get_json::<api::Inscriptions>("/inscriptions?limit=2");
+
+    assert_eq!(inscriptions.ids, [ids[2], ids[1]]);
+    assert!(inscriptions.more);
+    assert_eq!(inscriptions.next_cursor, Some("AAAAAA".into()));
+    assert_eq!(inscriptions.prev_cursor, None);
+  }
+
+  #[test]
+  fn inscription_listing_parameters_are_validated() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/inscriptions?limit=101",
+      StatusCode::BAD_REQUEST,
+      "limit 101 exceeds maximum of 100",
+    );
+
+    server.assert_response(
+      "/inscriptions?cursor=foo",
+      StatusCode::BAD_REQUEST,
+      "invalid cursor `foo`",
+    );
+
+    server.assert_response(
+      "/inscriptions?sort=fee",
+      StatusCode::NOT_FOUND,
+      "this server has no inscription attribute index",
+    );
+
+    server.assert_response(
+      "/runes?sort=fee",
+      StatusCode::BAD_REQUEST,
+      "runes cannot be sorted by fee",
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_filtered() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-inscription-attributes")
+      .build();
+
+    server.mine_blocks(2);
+
+    let foo = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("foo".into()),
+          metaprotocol: Some("brc-20".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("image/png", "bar").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let foo = InscriptionId {
+      txid: foo,
+      index: 0,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions?metaprotocol=brc-20&content_type=text"),
+      api::Inscriptions {
+        ids: vec![foo],
+        more: false,
+        page_index: 0,
+        next_cursor: None,
+        prev_cursor: None,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/0?content_type=text%2Fplain"),
+      api::Inscriptions {
+        ids: vec![foo],
+        more: false,
+        page_index: 0,
+        next_cursor: None,
+        prev_cursor: None,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions/1?content_type=text%2Fplain"),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
+        next_cursor: None,
+        prev_cursor: None,
+      },
+    );
+
+    server.assert_response_regex(
+      "/inscriptions?metaprotocol=brc-20",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscriptions</h1>.*<dt>metaprotocol</dt>\n  <dd>brc-20</dd>.*<a href=/inscription/{foo}>.*"
+      ),
+    );
+
+    server.assert_response(
+      "/inscriptions?charm=foo",
+      StatusCode::BAD_REQUEST,
+      "Failed to deserialize query string: charm: invalid charm `foo`",
+    );
+  }
+
+  #[test]
+  fn inscriptions_filtered_by_attribute_requires_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
 
-    server.mine_blocks(1);
+    server.assert_response(
+      "/inscriptions?metaprotocol=brc-20",
+      StatusCode::NOT_FOUND,
+      "this server has no inscription attribute index",
+    );
 
     server.assert_response_regex(
-      "/inscriptions/0",
+      "/inscriptions?charm=cursed&from_height=1",
       StatusCode::OK,
-      ".*prev\n<a class=next href=/inscriptions/1>next</a>.*",
+      ".*<dt>charm</dt>.*",
     );
   }
 
@@ -6707,6 +10063,373 @@ next
     );
   }
 
+  #[test]
+  fn delegators() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-delegates")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    let delegate = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{delegate}/delegators"),
+      StatusCode::OK,
+      ".*<h3>No delegators</h3>.*",
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          delegate: Some(delegate.value()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let delegator = InscriptionId { txid, index: 0 };
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{delegate}"))
+        .delegator_count,
+      Some(1),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{delegate}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>delegators</dt>\n  <dd><a href=/inscription/{delegate}/delegators>1</a></dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{delegate}/delegators"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 Delegators</title>.*<a href=/inscription/{delegator}><iframe .*"
+      ),
+    );
+
+    assert_eq!(
+      server.get_json::<api::Delegators>(format!("/inscription/{delegate}/delegators")),
+      api::Delegators {
+        ids: vec![delegator],
+        more: false,
+        page: 0,
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Delegators>("/inscription/0/delegators/0"),
+      api::Delegators {
+        ids: vec![delegator],
+        more: false,
+        page: 0,
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Delegators>(format!("/r/delegates/{delegate}")),
+      api::Delegators {
+        ids: vec![delegator],
+        more: false,
+        page: 0,
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Delegators>(format!("/r/delegates/{delegate}/1")),
+      api::Delegators {
+        ids: Vec::new(),
+        more: false,
+        page: 1,
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{}/delegators", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      ".*",
+    );
+  }
+
+  #[test]
+  fn delegators_requires_index() {
+    let server = TestServer::new();
+
+    let delegate = inscription_id(1);
+
+    server.assert_response(
+      format!("/inscription/{delegate}/delegators"),
+      StatusCode::NOT_FOUND,
+      "this server has no delegate index",
+    );
+
+    server.assert_response(
+      format!("/r/delegates/{delegate}"),
+      StatusCode::NOT_FOUND,
+      "this server has no delegate index",
+    );
+  }
+
+  #[test]
+  fn content_supports_range_and_conditional_requests() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("video/mp4", "0123456789").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let etag = format!("\"{id}\"");
+
+    let sat = Height(1).starting_sat().n();
+
+    let get = |path: &str, headers: &[(HeaderName, &str)]| {
+      let mut request = reqwest::blocking::Client::new().get(server.join_url(path));
+      for (name, value) in headers {
+        request = request.header(name, *value);
+      }
+      request.send().unwrap()
+    };
+
+    for path in [
+      format!("/content/{id}"),
+      format!("/r/undelegated-content/{id}"),
+      format!("/r/sat/{sat}/at/0/content"),
+    ] {
+      let response = get(&path, &[]);
+      assert_eq!(response.status(), StatusCode::OK);
+      assert_eq!(response.headers()[header::ETAG], etag);
+      assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+      assert_eq!(response.text().unwrap(), "0123456789");
+
+      let response = get(&path, &[(header::RANGE, "bytes=2-5")]);
+      assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+      assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+      assert_eq!(response.headers()[header::CONTENT_TYPE], "video/mp4");
+      assert_eq!(response.text().unwrap(), "2345");
+
+      let response = get(&path, &[(header::RANGE, "bytes=-3")]);
+      assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+      assert_eq!(response.text().unwrap(), "789");
+
+      let response = get(&path, &[(header::RANGE, "bytes=10-")]);
+      assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+      assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */10");
+
+      let response = get(&path, &[(header::IF_NONE_MATCH, &etag)]);
+      assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+      assert_eq!(response.headers()[header::ETAG], etag);
+      assert_eq!(response.text().unwrap(), "");
+
+      let response = get(
+        &path,
+        &[(header::RANGE, "bytes=2-5"), (header::IF_RANGE, "\"foo\"")],
+      );
+      assert_eq!(response.status(), StatusCode::OK);
+      assert_eq!(response.text().unwrap(), "0123456789");
+
+      let response = get(
+        &path,
+        &[(header::RANGE, "bytes=2-5"), (header::IF_RANGE, &etag)],
+      );
+      assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+      assert_eq!(response.text().unwrap(), "2345");
+    }
+  }
+
+  #[test]
+  fn range_responses_are_not_compressed() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain", "foo".repeat(100)).to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let client = reqwest::blocking::Client::builder()
+      .brotli(false)
+      .build()
+      .unwrap();
+
+    let response = client
+      .get(server.join_url(&format!("/content/{id}")))
+      .header(header::ACCEPT_ENCODING, "br")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+    assert_eq!(response.headers()[header::ETAG], format!("\"{id}\""));
+
+    let response = client
+      .get(server.join_url(&format!("/content/{id}")))
+      .header(header::ACCEPT_ENCODING, "br")
+      .header(header::RANGE, "bytes=0-5")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 0-5/300");
+    assert_eq!(response.text().unwrap(), "foofoo");
+  }
+
+  #[test]
+  fn content_etag_is_derived_from_delegate() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    let delegate = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("bar".into()),
+          delegate: Some(delegate.value()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    assert_eq!(
+      server.get(format!("/content/{id}")).headers()[header::ETAG],
+      format!("\"{delegate}\""),
+    );
+
+    assert_eq!(
+      server.get(format!("/r/undelegated-content/{id}")).headers()[header::ETAG],
+      format!("\"{id}\""),
+    );
+  }
+
+  #[test]
+  fn decompressed_content_has_distinct_etag() {
+    use {brotli::enc::writer::CompressorWriter, std::io::Write};
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--decompress")
+      .build();
+
+    server.mine_blocks(1);
+
+    let mut compressed = Vec::new();
+
+    {
+      let mut writer = CompressorWriter::new(&mut compressed, 4096, 11, 22);
+      writer.write_all(b"0123456789").unwrap();
+    }
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          content_encoding: Some("br".into()),
+          body: Some(compressed.clone()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let response = reqwest::blocking::Client::builder()
+      .brotli(false)
+      .build()
+      .unwrap()
+      .get(server.join_url(&format!("/content/{id}")))
+      .header(header::ACCEPT_ENCODING, "br")
+      .header(header::RANGE, "bytes=0-")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+    assert_eq!(response.headers()[header::ETAG], format!("\"{id}\""));
+    assert_eq!(response.headers()[header::VARY], "accept-encoding");
+    assert_eq!(response.bytes().unwrap(), compressed);
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/content/{id}")))
+      .header(header::RANGE, "bytes=2-5")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    assert_eq!(
+      response.headers()[header::ETAG],
+      format!("\"{id}-decompressed\"")
+    );
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+    assert_eq!(response.text().unwrap(), "2345");
+  }
+
   #[test]
   fn undelegated_content() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
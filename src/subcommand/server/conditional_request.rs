@@ -0,0 +1,224 @@
+use {super::*, std::ops::Range};
+
+/// `If-None-Match`, `If-Range`, and `Range` request headers, used to serve
+/// immutable content conditionally and in parts
+#[derive(Default, Debug)]
+pub(crate) struct ConditionalRequest {
+  if_none_match: Option<String>,
+  if_range: Option<String>,
+  range: Option<String>,
+}
+
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for ConditionalRequest {
+  type Rejection = (StatusCode, &'static str);
+
+  async fn from_request_parts(
+    parts: &mut http::request::Parts,
+    _state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    let header = |name| {
+      parts
+        .headers
+        .get(name)
+        .map(|value: &HeaderValue| value.to_str().unwrap_or_default().to_owned())
+    };
+
+    Ok(Self {
+      if_none_match: header(header::IF_NONE_MATCH),
+      if_range: header(header::IF_RANGE),
+      range: header(header::RANGE),
+    })
+  }
+}
+
+#[derive(Debug, PartialEq)]
+enum ByteRange {
+  Ignored,
+  Satisfiable(Range<usize>),
+  Unsatisfiable,
+}
+
+impl ByteRange {
+  /// Parse a `Range` header for a body of `len` bytes. Only single byte ranges
+  /// are supported, and malformed or multipart ranges are ignored, so that the
+  /// full body is returned.
+  fn parse(range: &str, len: usize) -> Self {
+    let Some(spec) = range.trim().strip_prefix("bytes=") else {
+      return Self::Ignored;
+    };
+
+    if spec.contains(',') {
+      return Self::Ignored;
+    }
+
+    let Some((start, end)) = spec.split_once('-') else {
+      return Self::Ignored;
+    };
+
+    match (start.trim(), end.trim()) {
+      ("", suffix) => {
+        let Ok(suffix) = suffix.parse::<usize>() else {
+          return Self::Ignored;
+        };
+
+        if suffix == 0 || len == 0 {
+          Self::Unsatisfiable
+        } else {
+          Self::Satisfiable(len.saturating_sub(suffix)..len)
+        }
+      }
+      (start, end) => {
+        let Ok(start) = start.parse::<usize>() else {
+          return Self::Ignored;
+        };
+
+        let end = if end.is_empty() {
+          len
+        } else {
+          let Ok(end) = end.parse::<usize>() else {
+            return Self::Ignored;
+          };
+
+          if end < start {
+            return Self::Ignored;
+          }
+
+          end.saturating_add(1).min(len)
+        };
+
+        if start >= len {
+          Self::Unsatisfiable
+        } else {
+          Self::Satisfiable(start..end)
+        }
+      }
+    }
+  }
+}
+
+impl ConditionalRequest {
+  /// Respond with `body`, which must never change for a given `etag`.
+  /// Returns `304 Not Modified` if `If-None-Match` matches `etag`, and
+  /// `206 Partial Content` if a satisfiable byte range is requested and
+  /// `If-Range`, if present, matches `etag`.
+  pub(crate) fn respond(&self, etag: &str, mut headers: HeaderMap, body: Vec<u8>) -> Response {
+    let Ok(etag_value) = HeaderValue::from_str(etag) else {
+      return (headers, body).into_response();
+    };
+
+    headers.insert(header::ETAG, etag_value);
+    headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+    if let Some(if_none_match) = &self.if_none_match {
+      if if_none_match.split(',').any(|tag| {
+        let tag = tag.trim();
+        tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag
+      }) {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+      }
+    }
+
+    let Some(range) = &self.range else {
+      return (headers, body).into_response();
+    };
+
+    if let Some(if_range) = &self.if_range {
+      if if_range.trim() != etag {
+        return (headers, body).into_response();
+      }
+    }
+
+    match ByteRange::parse(range, body.len()) {
+      ByteRange::Ignored => (headers, body).into_response(),
+      ByteRange::Satisfiable(range) => {
+        headers.insert(
+          header::CONTENT_RANGE,
+          format!("bytes {}-{}/{}", range.start, range.end - 1, body.len())
+            .parse()
+            .unwrap(),
+        );
+
+        (StatusCode::PARTIAL_CONTENT, headers, body[range].to_vec()).into_response()
+      }
+      ByteRange::Unsatisfiable => (
+        StatusCode::RANGE_NOT_SATISFIABLE,
+        [(header::CONTENT_RANGE, format!("bytes */{}", body.len()))],
+      )
+        .into_response(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_byte_range() {
+    #[track_caller]
+    fn case(range: &str, len: usize, expected: ByteRange) {
+      assert_eq!(ByteRange::parse(range, len), expected);
+    }
+
+    case("bytes=0-0", 10, ByteRange::Satisfiable(0..1));
+    case("bytes=2-5", 10, ByteRange::Satisfiable(2..6));
+    case("bytes=2-", 10, ByteRange::Satisfiable(2..10));
+    case("bytes=2-100", 10, ByteRange::Satisfiable(2..10));
+    case("bytes=-3", 10, ByteRange::Satisfiable(7..10));
+    case("bytes=-100", 10, ByteRange::Satisfiable(0..10));
+    case("bytes=10-", 10, ByteRange::Unsatisfiable);
+    case("bytes=-0", 10, ByteRange::Unsatisfiable);
+    case("bytes=0-", 0, ByteRange::Unsatisfiable);
+    case("bytes=5-2", 10, ByteRange::Ignored);
+    case("bytes=0-1,3-4", 10, ByteRange::Ignored);
+    case("bytes=a-b", 10, ByteRange::Ignored);
+    case("items=0-1", 10, ByteRange::Ignored);
+  }
+
+  #[test]
+  fn not_modified() {
+    for if_none_match in ["\"foo\"", "W/\"foo\"", "\"bar\", \"foo\"", "*"] {
+      let response = ConditionalRequest {
+        if_none_match: Some(if_none_match.into()),
+        ..default()
+      }
+      .respond("\"foo\"", HeaderMap::new(), vec![1, 2, 3]);
+
+      assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+      assert_eq!(response.headers()[header::ETAG], "\"foo\"");
+    }
+
+    let response = ConditionalRequest {
+      if_none_match: Some("\"bar\"".into()),
+      ..default()
+    }
+    .respond("\"foo\"", HeaderMap::new(), vec![1, 2, 3]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+  }
+
+  #[test]
+  fn partial_content() {
+    let response = ConditionalRequest {
+      range: Some("bytes=1-".into()),
+      ..default()
+    }
+    .respond("\"foo\"", HeaderMap::new(), vec![1, 2, 3]);
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 1-2/3");
+  }
+
+  #[test]
+  fn range_is_ignored_if_if_range_does_not_match() {
+    let response = ConditionalRequest {
+      if_range: Some("\"bar\"".into()),
+      range: Some("bytes=1-".into()),
+      ..default()
+    }
+    .respond("\"foo\"", HeaderMap::new(), vec![1, 2, 3]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key(header::CONTENT_RANGE));
+  }
+}
@@ -0,0 +1,646 @@
+use {
+  self::{
+    parser::{Document, Selection, Value},
+    schema::{Context, Node, Object, Resolved},
+  },
+  super::*,
+  serde_json::Map,
+};
+
+mod parser;
+mod schema;
+
+pub(super) use schema::sdl;
+
+/// Maximum number of bytes in a query document
+const MAX_QUERY_LEN: usize = 64 * 1024;
+
+/// Maximum number of selections expanded while planning a query. Fragments
+/// may be spread more than once, so a short query can expand to exponentially
+/// many selections.
+const MAX_SELECTIONS: usize = 10_000;
+
+/// Maximum and default values of the `limit` argument of list fields
+const MAX_LIMIT: u64 = 100;
+const DEFAULT_LIMIT: u64 = 25;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Request {
+  pub(crate) query: String,
+  #[serde(default)]
+  pub(crate) operation_name: Option<String>,
+  #[serde(default)]
+  pub(crate) variables: Option<Map<String, serde_json::Value>>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Response {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) data: Option<serde_json::Value>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) errors: Vec<GraphqlError>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct GraphqlError {
+  pub(crate) message: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) path: Option<Vec<serde_json::Value>>,
+}
+
+impl IntoResponse for Response {
+  fn into_response(self) -> axum::response::Response {
+    let status = if self.data.is_some() {
+      StatusCode::OK
+    } else {
+      StatusCode::BAD_REQUEST
+    };
+
+    (status, Json(self)).into_response()
+  }
+}
+
+type Arguments = BTreeMap<&'static str, serde_json::Value>;
+
+/// A field of a validated query, with fragments inlined and variables
+/// substituted
+#[derive(Debug)]
+struct Plan {
+  arguments: Arguments,
+  field: &'static schema::Field,
+  key: String,
+  selection: Vec<Plan>,
+}
+
+impl Plan {
+  fn depth(&self) -> usize {
+    1 + self.selection.iter().map(Plan::depth).max().unwrap_or(0)
+  }
+
+  /// One for the field itself, plus the complexity of its selection,
+  /// multiplied by `limit` for list fields
+  fn complexity(&self) -> u64 {
+    let multiplier = if self.field.is_list() {
+      self
+        .arguments
+        .get("limit")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(1)
+    } else {
+      1
+    };
+
+    self
+      .selection
+      .iter()
+      .map(Plan::complexity)
+      .fold(0u64, u64::saturating_add)
+      .saturating_mul(multiplier)
+      .saturating_add(1)
+  }
+
+  /// Add `plan` to `plans`, merging it with a previously selected field with
+  /// the same response key
+  fn merge(plans: &mut Vec<Plan>, plan: Plan) -> Result {
+    let Some(existing) = plans.iter_mut().find(|existing| existing.key == plan.key) else {
+      plans.push(plan);
+      return Ok(());
+    };
+
+    ensure!(
+      existing.field.name == plan.field.name && existing.arguments == plan.arguments,
+      "fields with response key `{}` conflict",
+      plan.key,
+    );
+
+    for child in plan.selection {
+      Self::merge(&mut existing.selection, child)?;
+    }
+
+    Ok(())
+  }
+}
+
+struct Planner<'a> {
+  document: &'a Document,
+  selections: usize,
+  spreads: Vec<&'a str>,
+  variables: BTreeMap<&'a str, serde_json::Value>,
+}
+
+impl<'a> Planner<'a> {
+  fn plan(
+    &mut self,
+    object: &'static Object,
+    selection: &'a [Selection],
+    plans: &mut Vec<Plan>,
+  ) -> Result {
+    for selection in selection {
+      self.selections += 1;
+
+      ensure!(
+        self.selections <= MAX_SELECTIONS,
+        "query expands to more than {MAX_SELECTIONS} selections"
+      );
+
+      match selection {
+        Selection::Field(field) => {
+          let definition = object
+            .field(&field.name)
+            .ok_or_else(|| anyhow!("unknown field `{}` on type `{}`", field.name, object.name))?;
+
+          let arguments = self.arguments(definition, &field.arguments)?;
+
+          let mut children = Vec::new();
+
+          match schema::object(definition.base_type()) {
+            Some(child) => {
+              ensure!(
+                !field.selection.is_empty(),
+                "field `{}` of type `{}` must have a selection of subfields",
+                field.name,
+                definition.ty,
+              );
+              self.plan(child, &field.selection, &mut children)?;
+            }
+            None => ensure!(
+              field.selection.is_empty(),
+              "field `{}` of type `{}` must not have a selection of subfields",
+              field.name,
+              definition.ty,
+            ),
+          }
+
+          Plan::merge(
+            plans,
+            Plan {
+              arguments,
+              field: definition,
+              key: field.alias.clone().unwrap_or_else(|| field.name.clone()),
+              selection: children,
+            },
+          )?;
+        }
+        Selection::FragmentSpread(name) => {
+          let fragment = self
+            .document
+            .fragments
+            .get(name)
+            .ok_or_else(|| anyhow!("unknown fragment `{name}`"))?;
+
+          ensure!(
+            !self.spreads.contains(&name.as_str()),
+            "fragment `{name}` spreads itself"
+          );
+
+          ensure!(
+            fragment.type_condition == object.name,
+            "fragment `{name}` on type `{}` cannot be spread within type `{}`",
+            fragment.type_condition,
+            object.name,
+          );
+
+          self.spreads.push(name);
+          self.plan(object, &fragment.selection, plans)?;
+          self.spreads.pop();
+        }
+        Selection::InlineFragment {
+          selection,
+          type_condition,
+        } => {
+          if let Some(type_condition) = type_condition {
+            ensure!(
+              type_condition == object.name,
+              "fragment on type `{type_condition}` cannot be spread within type `{}`",
+              object.name,
+            );
+          }
+
+          self.plan(object, selection, plans)?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn arguments(
+    &self,
+    field: &'static schema::Field,
+    arguments: &[(String, Value)],
+  ) -> Result<Arguments> {
+    for (name, _) in arguments {
+      ensure!(
+        field.arguments.iter().any(|argument| argument.name == name),
+        "unknown argument `{name}` on field `{}`",
+        field.name,
+      );
+    }
+
+    let mut coerced = Arguments::new();
+
+    for argument in field.arguments {
+      let value = match arguments.iter().find(|(name, _)| name == argument.name) {
+        Some((_, value)) => self.value(value)?,
+        None => serde_json::Value::Null,
+      };
+
+      let value = match (argument.ty, value) {
+        ("String!", serde_json::Value::String(string)) => serde_json::Value::String(string),
+        ("String!", serde_json::Value::Null) => bail!(
+          "missing required argument `{}` on field `{}`",
+          argument.name,
+          field.name,
+        ),
+        ("Int", serde_json::Value::Null) => argument.default.into(),
+        ("Int", serde_json::Value::Number(number)) if argument.name == "limit" => {
+          match number.as_u64() {
+            Some(limit) if (1..=MAX_LIMIT).contains(&limit) => limit.into(),
+            _ => bail!("argument `limit` must be between 1 and {MAX_LIMIT}"),
+          }
+        }
+        (ty, _) => bail!(
+          "argument `{}` on field `{}` must be of type `{ty}`",
+          argument.name,
+          field.name,
+        ),
+      };
+
+      coerced.insert(argument.name, value);
+    }
+
+    Ok(coerced)
+  }
+
+  fn value(&self, value: &Value) -> Result<serde_json::Value> {
+    Ok(match value {
+      Value::Boolean(boolean) => (*boolean).into(),
+      Value::Enum(name) => name.as_str().into(),
+      Value::Float(float) => serde_json::Number::from_f64(*float)
+        .ok_or_else(|| anyhow!("invalid float `{float}`"))?
+        .into(),
+      Value::Int(int) => (*int).into(),
+      Value::List(list) => list
+        .iter()
+        .map(|value| self.value(value))
+        .collect::<Result<Vec<serde_json::Value>>>()?
+        .into(),
+      Value::Null => serde_json::Value::Null,
+      Value::Object(object) => object
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), self.value(value)?)))
+        .collect::<Result<Map<String, serde_json::Value>>>()?
+        .into(),
+      Value::String(string) => string.as_str().into(),
+      Value::Variable(name) => self
+        .variables
+        .get(name.as_str())
+        .cloned()
+        .ok_or_else(|| anyhow!("variable `${name}` is not defined"))?,
+    })
+  }
+}
+
+/// Parse, validate, and plan `request`, enforcing depth and complexity limits
+fn plan(request: &Request, max_depth: usize, max_complexity: u64) -> Result<Vec<Plan>> {
+  ensure!(
+    request.query.len() <= MAX_QUERY_LEN,
+    "query is longer than {MAX_QUERY_LEN} bytes"
+  );
+
+  let document = parser::parse(&request.query)?;
+
+  let operation = match &request.operation_name {
+    Some(name) => document
+      .operations
+      .iter()
+      .find(|operation| operation.name.as_ref() == Some(name))
+      .ok_or_else(|| anyhow!("unknown operation `{name}`"))?,
+    None => {
+      ensure!(
+        document.operations.len() == 1,
+        "operation name is required when query contains multiple operations"
+      );
+      &document.operations[0]
+    }
+  };
+
+  let mut planner = Planner {
+    document: &document,
+    selections: 0,
+    spreads: Vec::new(),
+    variables: BTreeMap::new(),
+  };
+
+  for (name, default) in &operation.variables {
+    let value = match request
+      .variables
+      .as_ref()
+      .and_then(|variables| variables.get(name))
+    {
+      Some(value) => value.clone(),
+      None => match default {
+        Some(default) => planner.value(default)?,
+        None => serde_json::Value::Null,
+      },
+    };
+
+    planner.variables.insert(name, value);
+  }
+
+  let mut plans = Vec::new();
+
+  planner.plan(
+    schema::object("Query").unwrap(),
+    &operation.selection,
+    &mut plans,
+  )?;
+
+  let depth = plans.iter().map(Plan::depth).max().unwrap_or(0);
+
+  ensure!(
+    depth <= max_depth,
+    "query depth {depth} exceeds maximum of {max_depth}"
+  );
+
+  let complexity = plans
+    .iter()
+    .map(Plan::complexity)
+    .fold(0u64, u64::saturating_add);
+
+  ensure!(
+    complexity <= max_complexity,
+    "query complexity {complexity} exceeds maximum of {max_complexity}"
+  );
+
+  Ok(plans)
+}
+
+struct Executor<'a> {
+  context: Context<'a>,
+  errors: Vec<GraphqlError>,
+  path: Vec<serde_json::Value>,
+}
+
+impl Executor<'_> {
+  /// Returns `None` if a non-null field resolved to null, in which case the
+  /// object itself is null
+  fn object(&mut self, node: &Node, selection: &[Plan]) -> Option<serde_json::Value> {
+    let mut object = Map::new();
+
+    for plan in selection {
+      self.path.push(plan.key.as_str().into());
+
+      let value = self.field(node, plan);
+
+      self.path.pop();
+
+      if value.is_null() && plan.field.ty.ends_with('!') {
+        return None;
+      }
+
+      object.insert(plan.key.clone(), value);
+    }
+
+    Some(object.into())
+  }
+
+  fn field(&mut self, node: &Node, plan: &Plan) -> serde_json::Value {
+    if plan.field.name == "__typename" {
+      return node.type_name().into();
+    }
+
+    match node.resolve(&self.context, plan.field.name, &plan.arguments) {
+      Ok(Resolved::Value(value)) => value,
+      Ok(Resolved::Node(None)) => serde_json::Value::Null,
+      Ok(Resolved::Node(Some(node))) => self
+        .object(&node, &plan.selection)
+        .unwrap_or(serde_json::Value::Null),
+      Ok(Resolved::Nodes(nodes)) => {
+        let mut list = Vec::new();
+
+        for (i, node) in nodes.iter().enumerate() {
+          self.path.push(i.into());
+          let item = self.object(node, &plan.selection);
+          self.path.pop();
+
+          match item {
+            Some(item) => list.push(item),
+            None => return serde_json::Value::Null,
+          }
+        }
+
+        list.into()
+      }
+      Err(err) => {
+        self.errors.push(GraphqlError {
+          message: err.to_string(),
+          path: Some(self.path.clone()),
+        });
+        serde_json::Value::Null
+      }
+    }
+  }
+}
+
+pub(super) fn execute(index: &Index, server_config: &ServerConfig, request: Request) -> Response {
+  let plans = match plan(
+    &request,
+    server_config.graphql_max_depth,
+    server_config.graphql_max_complexity,
+  ) {
+    Ok(plans) => plans,
+    Err(err) => {
+      return Response {
+        data: None,
+        errors: vec![GraphqlError {
+          message: err.to_string(),
+          path: None,
+        }],
+      }
+    }
+  };
+
+  let mut executor = Executor {
+    context: Context {
+      index,
+      server_config,
+    },
+    errors: Vec::new(),
+    path: Vec::new(),
+  };
+
+  let data = executor
+    .object(&Node::Query, &plans)
+    .unwrap_or(serde_json::Value::Null);
+
+  Response {
+    data: Some(data),
+    errors: executor.errors,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn request(query: &str) -> Request {
+    Request {
+      query: query.into(),
+      operation_name: None,
+      variables: None,
+    }
+  }
+
+  #[track_caller]
+  fn plan_err(query: &str, expected: &str) {
+    assert_eq!(
+      plan(&request(query), 10, 5000).unwrap_err().to_string(),
+      expected
+    );
+  }
+
+  #[test]
+  fn validation_errors() {
+    plan_err("{ foo }", "unknown field `foo` on type `Query`");
+    plan_err(
+      "{ blockCount { foo } }",
+      "field `blockCount` of type `Int!` must not have a selection of subfields",
+    );
+    plan_err(
+      r#"{ inscription(query: "0") }"#,
+      "field `inscription` of type `Inscription` must have a selection of subfields",
+    );
+    plan_err(
+      "{ inscription { id } }",
+      "missing required argument `query` on field `inscription`",
+    );
+    plan_err(
+      "{ inscription(query: 1) { id } }",
+      "argument `query` on field `inscription` must be of type `String!`",
+    );
+    plan_err(
+      r#"{ inscription(query: "0", foo: 1) { id } }"#,
+      "unknown argument `foo` on field `inscription`",
+    );
+    plan_err(
+      r#"{ inscription(query: "0") { children(limit: 101) { id } } }"#,
+      "argument `limit` must be between 1 and 100",
+    );
+    plan_err(
+      "{ inscription(query: $foo) { id } }",
+      "variable `$foo` is not defined",
+    );
+    plan_err("{ ...Foo }", "unknown fragment `Foo`");
+    plan_err(
+      "{ ...Foo } fragment Foo on Query { ...Foo }",
+      "fragment `Foo` spreads itself",
+    );
+    plan_err(
+      "{ ...Foo } fragment Foo on Sat { number }",
+      "fragment `Foo` on type `Sat` cannot be spread within type `Query`",
+    );
+    plan_err(
+      r#"{ a: blockCount a: sat(query: "0") { number } }"#,
+      "fields with response key `a` conflict",
+    );
+    plan_err(
+      "query A { blockCount } query B { blockCount }",
+      "operation name is required when query contains multiple operations",
+    );
+  }
+
+  #[test]
+  fn variables_and_fragments() {
+    let plans = plan(
+      &Request {
+        query: r#"
+          query Foo($sat: String!, $limit: Int = 2) {
+            sat(query: $sat) { ...SatFields }
+            sat(query: $sat) { ... on Sat { name } }
+          }
+          fragment SatFields on Sat { number inscriptions(limit: $limit) { id } }
+        "#
+        .into(),
+        operation_name: Some("Foo".into()),
+        variables: Some([("sat".into(), "1".into())].into_iter().collect()),
+      },
+      10,
+      5000,
+    )
+    .unwrap();
+
+    assert_eq!(plans.len(), 1);
+    assert_eq!(plans[0].arguments["query"], "1");
+    assert_eq!(
+      plans[0]
+        .selection
+        .iter()
+        .map(|plan| plan.key.as_str())
+        .collect::<Vec<&str>>(),
+      ["number", "inscriptions", "name"],
+    );
+    assert_eq!(plans[0].selection[1].arguments["limit"], 2);
+  }
+
+  #[test]
+  fn depth_limit() {
+    let query = r#"{ inscription(query: "0") { parents(limit: 1) { parents(limit: 1) { parents(limit: 1) { id } } } } }"#;
+
+    assert!(plan(&request(query), 5, 5000).is_ok());
+
+    assert_eq!(
+      plan(&request(query), 4, 5000).unwrap_err().to_string(),
+      "query depth 5 exceeds maximum of 4",
+    );
+  }
+
+  #[test]
+  fn complexity_limit() {
+    let query = r#"{
+      blockCount
+      inscription(query: "0") {
+        children(limit: 10) { id parents(limit: 5) { id } }
+      }
+    }"#;
+
+    // blockCount: 1
+    // inscription: 1 + children
+    // children: 1 + 10 × (id + parents)
+    // parents: 1 + 5 × id
+    assert!(plan(&request(query), 10, 1 + 1 + 1 + 10 * (1 + 1 + 5)).is_ok());
+
+    assert_eq!(
+      plan(&request(query), 10, 72).unwrap_err().to_string(),
+      "query complexity 73 exceeds maximum of 72",
+    );
+  }
+
+  #[test]
+  fn selection_limit() {
+    let mut query = "{ ...F0 }".to_string();
+
+    for i in 0..40 {
+      query.push_str(&format!(
+        " fragment F{i} on Query {{ ...F{} ...F{} }}",
+        i + 1,
+        i + 1
+      ));
+    }
+
+    query.push_str(" fragment F40 on Query { blockCount }");
+
+    assert!(query.len() < MAX_QUERY_LEN);
+
+    plan_err(&query, "query expands to more than 10000 selections");
+  }
+
+  #[test]
+  fn query_length_limit() {
+    plan_err(
+      &format!("{{ blockCount {} }}", " ".repeat(MAX_QUERY_LEN)),
+      "query is longer than 65536 bytes",
+    );
+  }
+}
@@ -0,0 +1,583 @@
+use super::*;
+
+/// Selection sets and values may not be nested more deeply than this, so that
+/// parsing a hostile query cannot overflow the stack
+const MAX_NESTING: usize = 64;
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Document {
+  pub(super) fragments: BTreeMap<String, Fragment>,
+  pub(super) operations: Vec<Operation>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Fragment {
+  pub(super) selection: Vec<Selection>,
+  pub(super) type_condition: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Operation {
+  pub(super) name: Option<String>,
+  pub(super) selection: Vec<Selection>,
+  pub(super) variables: Vec<(String, Option<Value>)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Selection {
+  Field(Field),
+  FragmentSpread(String),
+  InlineFragment {
+    selection: Vec<Selection>,
+    type_condition: Option<String>,
+  },
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Field {
+  pub(super) alias: Option<String>,
+  pub(super) arguments: Vec<(String, Value)>,
+  pub(super) name: String,
+  pub(super) selection: Vec<Selection>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Value {
+  Boolean(bool),
+  Enum(String),
+  Float(f64),
+  Int(i64),
+  List(Vec<Value>),
+  Null,
+  Object(Vec<(String, Value)>),
+  String(String),
+  Variable(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+  Float(f64),
+  Int(i64),
+  Name(String),
+  Punctuator(char),
+  Spread,
+  String(String),
+}
+
+impl Display for Token {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Float(float) => write!(f, "`{float}`"),
+      Self::Int(int) => write!(f, "`{int}`"),
+      Self::Name(name) => write!(f, "`{name}`"),
+      Self::Punctuator(punctuator) => write!(f, "`{punctuator}`"),
+      Self::Spread => write!(f, "`...`"),
+      Self::String(string) => write!(f, "{string:?}"),
+    }
+  }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+  let mut tokens = Vec::new();
+  let mut chars = source.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    match c {
+      ' ' | '\t' | '\n' | '\r' | ',' | '\u{feff}' => {}
+      '#' => while chars.next_if(|&c| c != '\n' && c != '\r').is_some() {},
+      '!' | '$' | '&' | '(' | ')' | ':' | '=' | '@' | '[' | ']' | '{' | '|' | '}' => {
+        tokens.push(Token::Punctuator(c));
+      }
+      '.' => {
+        if chars.next() != Some('.') || chars.next() != Some('.') {
+          bail!("expected `...`");
+        }
+        tokens.push(Token::Spread);
+      }
+      '"' => {
+        let mut string = String::new();
+        loop {
+          match chars.next() {
+            Some('"') => break,
+            Some('\\') => match chars.next() {
+              Some('"') => string.push('"'),
+              Some('\\') => string.push('\\'),
+              Some('/') => string.push('/'),
+              Some('b') => string.push('\u{8}'),
+              Some('f') => string.push('\u{c}'),
+              Some('n') => string.push('\n'),
+              Some('r') => string.push('\r'),
+              Some('t') => string.push('\t'),
+              Some('u') => {
+                let hex = (0..4).filter_map(|_| chars.next()).collect::<String>();
+                let c = u32::from_str_radix(&hex, 16)
+                  .ok()
+                  .and_then(char::from_u32)
+                  .ok_or_else(|| anyhow!("invalid unicode escape `\\u{hex}`"))?;
+                string.push(c);
+              }
+              Some(c) => bail!("invalid escape `\\{c}`"),
+              None => bail!("unterminated string"),
+            },
+            Some('\n' | '\r') | None => bail!("unterminated string"),
+            Some(c) => string.push(c),
+          }
+        }
+        tokens.push(Token::String(string));
+      }
+      '-' | '0'..='9' => {
+        let mut number = c.to_string();
+        let mut float = false;
+        while let Some(c) =
+          chars.next_if(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+        {
+          float |= !c.is_ascii_digit();
+          number.push(c);
+        }
+        tokens.push(if float {
+          Token::Float(
+            number
+              .parse()
+              .map_err(|_| anyhow!("invalid number `{number}`"))?,
+          )
+        } else {
+          Token::Int(
+            number
+              .parse()
+              .map_err(|_| anyhow!("invalid number `{number}`"))?,
+          )
+        });
+      }
+      'a'..='z' | 'A'..='Z' | '_' => {
+        let mut name = c.to_string();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+          name.push(c);
+        }
+        tokens.push(Token::Name(name));
+      }
+      _ => bail!("unexpected character `{c}`"),
+    }
+  }
+
+  Ok(tokens)
+}
+
+struct Parser {
+  nesting: usize,
+  position: usize,
+  tokens: Vec<Token>,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.position)
+  }
+
+  fn next(&mut self) -> Result<Token> {
+    let token = self
+      .tokens
+      .get(self.position)
+      .cloned()
+      .ok_or_else(|| anyhow!("unexpected end of query"))?;
+    self.position += 1;
+    Ok(token)
+  }
+
+  fn accept(&mut self, punctuator: char) -> bool {
+    if self.peek() == Some(&Token::Punctuator(punctuator)) {
+      self.position += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, punctuator: char) -> Result {
+    match self.next()? {
+      Token::Punctuator(c) if c == punctuator => Ok(()),
+      token => bail!("expected `{punctuator}`, found {token}"),
+    }
+  }
+
+  fn name(&mut self) -> Result<String> {
+    match self.next()? {
+      Token::Name(name) => Ok(name),
+      token => bail!("expected name, found {token}"),
+    }
+  }
+
+  fn nest(&mut self) -> Result {
+    self.nesting += 1;
+    ensure!(
+      self.nesting <= MAX_NESTING,
+      "query is nested more than {MAX_NESTING} levels deep"
+    );
+    Ok(())
+  }
+
+  fn document(&mut self) -> Result<Document> {
+    let mut fragments = BTreeMap::new();
+    let mut operations = Vec::new();
+
+    while let Some(token) = self.peek() {
+      match token {
+        Token::Punctuator('{') => operations.push(Operation {
+          name: None,
+          selection: self.selection_set()?,
+          variables: Vec::new(),
+        }),
+        Token::Name(name) if name == "query" => {
+          self.position += 1;
+          operations.push(self.operation()?);
+        }
+        Token::Name(name) if name == "mutation" || name == "subscription" => {
+          bail!("only query operations are supported")
+        }
+        Token::Name(name) if name == "fragment" => {
+          self.position += 1;
+          let name = self.name()?;
+          if self.name()? != "on" {
+            bail!("expected `on` in fragment `{name}`");
+          }
+          let type_condition = self.name()?;
+          self.directives()?;
+          let selection = self.selection_set()?;
+          if fragments
+            .insert(
+              name.clone(),
+              Fragment {
+                selection,
+                type_condition,
+              },
+            )
+            .is_some()
+          {
+            bail!("duplicate fragment `{name}`");
+          }
+        }
+        token => bail!("unexpected {token}"),
+      }
+    }
+
+    ensure!(!operations.is_empty(), "query contains no operations");
+
+    Ok(Document {
+      fragments,
+      operations,
+    })
+  }
+
+  fn operation(&mut self) -> Result<Operation> {
+    let name = match self.peek() {
+      Some(Token::Name(_)) => Some(self.name()?),
+      _ => None,
+    };
+
+    let mut variables = Vec::new();
+
+    if self.accept('(') {
+      while !self.accept(')') {
+        self.expect('$')?;
+        let name = self.name()?;
+        self.expect(':')?;
+        self.variable_type()?;
+        let default = if self.accept('=') {
+          Some(self.value()?)
+        } else {
+          None
+        };
+        variables.push((name, default));
+      }
+    }
+
+    self.directives()?;
+
+    Ok(Operation {
+      name,
+      selection: self.selection_set()?,
+      variables,
+    })
+  }
+
+  /// Parse and discard a variable type, since variables are coerced when
+  /// arguments are resolved
+  fn variable_type(&mut self) -> Result {
+    if self.accept('[') {
+      self.nest()?;
+      self.variable_type()?;
+      self.expect(']')?;
+      self.nesting -= 1;
+    } else {
+      self.name()?;
+    }
+
+    self.accept('!');
+
+    Ok(())
+  }
+
+  fn directives(&mut self) -> Result {
+    ensure!(
+      self.peek() != Some(&Token::Punctuator('@')),
+      "directives are not supported"
+    );
+    Ok(())
+  }
+
+  fn selection_set(&mut self) -> Result<Vec<Selection>> {
+    self.expect('{')?;
+    self.nest()?;
+
+    let mut selection = Vec::new();
+
+    while !self.accept('}') {
+      if self.peek() == Some(&Token::Spread) {
+        self.position += 1;
+        match self.peek() {
+          Some(Token::Name(name)) if name != "on" => {
+            let name = self.name()?;
+            self.directives()?;
+            selection.push(Selection::FragmentSpread(name));
+          }
+          _ => {
+            let type_condition = match self.peek() {
+              Some(Token::Name(_)) => {
+                self.name()?;
+                Some(self.name()?)
+              }
+              _ => None,
+            };
+            self.directives()?;
+            selection.push(Selection::InlineFragment {
+              selection: self.selection_set()?,
+              type_condition,
+            });
+          }
+        }
+      } else {
+        selection.push(Selection::Field(self.field()?));
+      }
+    }
+
+    ensure!(!selection.is_empty(), "selection set is empty");
+
+    self.nesting -= 1;
+
+    Ok(selection)
+  }
+
+  fn field(&mut self) -> Result<Field> {
+    let mut name = self.name()?;
+
+    let alias = if self.accept(':') {
+      Some(mem::replace(&mut name, self.name()?))
+    } else {
+      None
+    };
+
+    let mut arguments = Vec::new();
+
+    if self.accept('(') {
+      while !self.accept(')') {
+        let name = self.name()?;
+        self.expect(':')?;
+        arguments.push((name, self.value()?));
+      }
+    }
+
+    self.directives()?;
+
+    let selection = if self.peek() == Some(&Token::Punctuator('{')) {
+      self.selection_set()?
+    } else {
+      Vec::new()
+    };
+
+    Ok(Field {
+      alias,
+      arguments,
+      name,
+      selection,
+    })
+  }
+
+  fn value(&mut self) -> Result<Value> {
+    Ok(match self.next()? {
+      Token::Float(float) => Value::Float(float),
+      Token::Int(int) => Value::Int(int),
+      Token::String(string) => Value::String(string),
+      Token::Name(name) => match name.as_str() {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        "null" => Value::Null,
+        _ => Value::Enum(name),
+      },
+      Token::Punctuator('$') => Value::Variable(self.name()?),
+      Token::Punctuator('[') => {
+        self.nest()?;
+        let mut list = Vec::new();
+        while !self.accept(']') {
+          list.push(self.value()?);
+        }
+        self.nesting -= 1;
+        Value::List(list)
+      }
+      Token::Punctuator('{') => {
+        self.nest()?;
+        let mut object = Vec::new();
+        while !self.accept('}') {
+          let name = self.name()?;
+          self.expect(':')?;
+          object.push((name, self.value()?));
+        }
+        self.nesting -= 1;
+        Value::Object(object)
+      }
+      token => bail!("expected value, found {token}"),
+    })
+  }
+}
+
+pub(super) fn parse(source: &str) -> Result<Document> {
+  Parser {
+    nesting: 0,
+    position: 0,
+    tokens: tokenize(source)?,
+  }
+  .document()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn field(name: &str, selection: Vec<Selection>) -> Selection {
+    Selection::Field(Field {
+      alias: None,
+      arguments: Vec::new(),
+      name: name.into(),
+      selection,
+    })
+  }
+
+  #[test]
+  fn shorthand_query() {
+    assert_eq!(
+      parse("{ blockCount }").unwrap(),
+      Document {
+        fragments: BTreeMap::new(),
+        operations: vec![Operation {
+          name: None,
+          selection: vec![field("blockCount", Vec::new())],
+          variables: Vec::new(),
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn named_query_with_variables_aliases_and_arguments() {
+    assert_eq!(
+      parse(
+        r#"
+        # comment
+        query Foo($id: String! = "bar", $limit: [Int]) {
+          a: inscription(query: $id) {
+            children(limit: 5, flag: true, e: FOO, f: 1.5, l: [1, null], o: {x: "\u0041\n"}) { id }
+          }
+        }
+        "#
+      )
+      .unwrap(),
+      Document {
+        fragments: BTreeMap::new(),
+        operations: vec![Operation {
+          name: Some("Foo".into()),
+          selection: vec![Selection::Field(Field {
+            alias: Some("a".into()),
+            arguments: vec![("query".into(), Value::Variable("id".into()))],
+            name: "inscription".into(),
+            selection: vec![Selection::Field(Field {
+              alias: None,
+              arguments: vec![
+                ("limit".into(), Value::Int(5)),
+                ("flag".into(), Value::Boolean(true)),
+                ("e".into(), Value::Enum("FOO".into())),
+                ("f".into(), Value::Float(1.5)),
+                ("l".into(), Value::List(vec![Value::Int(1), Value::Null])),
+                (
+                  "o".into(),
+                  Value::Object(vec![("x".into(), Value::String("A\n".into()))])
+                ),
+              ],
+              name: "children".into(),
+              selection: vec![field("id", Vec::new())],
+            })],
+          })],
+          variables: vec![
+            ("id".into(), Some(Value::String("bar".into()))),
+            ("limit".into(), None),
+          ],
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn fragments() {
+    assert_eq!(
+      parse("{ ...Foo ... on Query { a } ... { b } } fragment Foo on Query { c }").unwrap(),
+      Document {
+        fragments: [(
+          "Foo".into(),
+          Fragment {
+            selection: vec![field("c", Vec::new())],
+            type_condition: "Query".into(),
+          }
+        )]
+        .into(),
+        operations: vec![Operation {
+          name: None,
+          selection: vec![
+            Selection::FragmentSpread("Foo".into()),
+            Selection::InlineFragment {
+              selection: vec![field("a", Vec::new())],
+              type_condition: Some("Query".into()),
+            },
+            Selection::InlineFragment {
+              selection: vec![field("b", Vec::new())],
+              type_condition: None,
+            },
+          ],
+          variables: Vec::new(),
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn errors() {
+    #[track_caller]
+    fn case(query: &str, expected: &str) {
+      assert_eq!(parse(query).unwrap_err().to_string(), expected);
+    }
+
+    case("", "query contains no operations");
+    case("{", "unexpected end of query");
+    case("{}", "selection set is empty");
+    case("{ a(b: ) }", "expected value, found `)`");
+    case("{ a @skip(if: true) }", "directives are not supported");
+    case("mutation { a }", "only query operations are supported");
+    case("{ a(b: \"c) }", "unterminated string");
+    case("{ a(b: \"\\x\") }", "invalid escape `\\x`");
+    case("{ a } ?", "unexpected character `?`");
+    case(
+      "fragment A on Query { a } fragment A on Query { b } { ...A }",
+      "duplicate fragment `A`",
+    );
+    case(
+      &format!("{}{}", "{ a ".repeat(65), "}".repeat(65)),
+      "query is nested more than 64 levels deep",
+    );
+  }
+}
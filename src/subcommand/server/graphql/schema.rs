@@ -0,0 +1,716 @@
+use {super::*, std::fmt::Write};
+
+pub(super) struct Object {
+  pub(super) fields: &'static [Field],
+  pub(super) name: &'static str,
+}
+
+impl Object {
+  pub(super) fn field(&self, name: &str) -> Option<&'static Field> {
+    if name == TYPENAME.name {
+      return Some(&TYPENAME);
+    }
+
+    self.fields.iter().find(|field| field.name == name)
+  }
+}
+
+#[derive(Debug)]
+pub(super) struct Field {
+  pub(super) arguments: &'static [Argument],
+  pub(super) name: &'static str,
+  pub(super) ty: &'static str,
+}
+
+impl Field {
+  pub(super) fn base_type(&self) -> &'static str {
+    self.ty.trim_matches(['[', ']', '!'])
+  }
+
+  pub(super) fn is_list(&self) -> bool {
+    self.ty.starts_with('[')
+  }
+}
+
+#[derive(Debug)]
+pub(super) struct Argument {
+  pub(super) default: Option<u64>,
+  pub(super) name: &'static str,
+  pub(super) ty: &'static str,
+}
+
+const LIMIT: &[Argument] = &[Argument {
+  default: Some(DEFAULT_LIMIT),
+  name: "limit",
+  ty: "Int",
+}];
+
+const fn field(name: &'static str, ty: &'static str) -> Field {
+  Field {
+    arguments: &[],
+    name,
+    ty,
+  }
+}
+
+const fn list(name: &'static str, ty: &'static str) -> Field {
+  Field {
+    arguments: LIMIT,
+    name,
+    ty,
+  }
+}
+
+const fn lookup(name: &'static str, arguments: &'static [Argument], ty: &'static str) -> Field {
+  Field {
+    arguments,
+    name,
+    ty,
+  }
+}
+
+const ADDRESS: &[Argument] = &[Argument {
+  default: None,
+  name: "address",
+  ty: "String!",
+}];
+
+const OUTPOINT: &[Argument] = &[Argument {
+  default: None,
+  name: "outpoint",
+  ty: "String!",
+}];
+
+const QUERY: &[Argument] = &[Argument {
+  default: None,
+  name: "query",
+  ty: "String!",
+}];
+
+const TYPENAME: Field = field("__typename", "String!");
+
+static OBJECTS: &[Object] = &[
+  Object {
+    name: "Query",
+    fields: &[
+      lookup("address", ADDRESS, "Address"),
+      lookup("block", QUERY, "Block"),
+      field("blockCount", "Int!"),
+      lookup("inscription", QUERY, "Inscription"),
+      lookup("output", OUTPOINT, "Output"),
+      lookup("rune", QUERY, "Rune"),
+      lookup("sat", QUERY, "Sat"),
+    ],
+  },
+  Object {
+    name: "Address",
+    fields: &[
+      field("address", "String!"),
+      list("inscriptions", "[Inscription!]"),
+      list("outputs", "[Output!]!"),
+      list("runeBalances", "[RuneBalance!]"),
+      field("satBalance", "U64!"),
+    ],
+  },
+  Object {
+    name: "Block",
+    fields: &[
+      field("hash", "String!"),
+      field("height", "Int!"),
+      list("inscriptions", "[Inscription!]!"),
+      field("previousBlockhash", "String!"),
+      list("runes", "[Rune!]!"),
+      field("timestamp", "U64!"),
+    ],
+  },
+  Object {
+    name: "Inscription",
+    fields: &[
+      field("address", "String"),
+      field("charms", "[String!]!"),
+      field("childCount", "U64!"),
+      list("children", "[Inscription!]!"),
+      field("contentLength", "Int"),
+      field("contentType", "String"),
+      field("delegate", "Inscription"),
+      field("delegatorCount", "U64"),
+      field("effectiveContentType", "String"),
+      field("fee", "U64!"),
+      field("height", "Int!"),
+      field("id", "String!"),
+      field("metaprotocol", "String"),
+      field("next", "Inscription"),
+      field("number", "Int!"),
+      field("output", "Output"),
+      list("parents", "[Inscription!]!"),
+      field("previous", "Inscription"),
+      field("rune", "Rune"),
+      field("sat", "Sat"),
+      field("satpoint", "String!"),
+      field("timestamp", "U64!"),
+      field("value", "U64"),
+    ],
+  },
+  Object {
+    name: "Output",
+    fields: &[
+      field("address", "String"),
+      field("indexed", "Boolean!"),
+      list("inscriptions", "[Inscription!]"),
+      field("outpoint", "String!"),
+      list("runes", "[RuneBalance!]"),
+      field("satRanges", "[[U64!]!]"),
+      field("scriptPubkey", "String!"),
+      field("spent", "Boolean!"),
+      field("transaction", "String!"),
+      field("value", "U64!"),
+    ],
+  },
+  Object {
+    name: "Rune",
+    fields: &[
+      field("block", "Block"),
+      field("burned", "String!"),
+      field("divisibility", "Int!"),
+      field("etching", "String!"),
+      field("holders", "U64"),
+      field("id", "String!"),
+      field("mintable", "Boolean!"),
+      field("mints", "String!"),
+      field("name", "String!"),
+      field("number", "U64!"),
+      field("parent", "Inscription"),
+      field("premine", "String!"),
+      field("supply", "String!"),
+      field("symbol", "String"),
+      field("timestamp", "U64!"),
+      field("turbo", "Boolean!"),
+    ],
+  },
+  Object {
+    name: "RuneBalance",
+    fields: &[
+      field("amount", "String!"),
+      field("name", "String!"),
+      field("rune", "Rune"),
+      field("symbol", "String"),
+    ],
+  },
+  Object {
+    name: "Sat",
+    fields: &[
+      field("block", "Block"),
+      field("charms", "[String!]!"),
+      field("cycle", "Int!"),
+      field("decimal", "String!"),
+      field("degree", "String!"),
+      field("epoch", "Int!"),
+      list("inscriptions", "[Inscription!]!"),
+      field("name", "String!"),
+      field("number", "U64!"),
+      field("offset", "U64!"),
+      field("output", "Output"),
+      field("percentile", "String!"),
+      field("period", "Int!"),
+      field("rarity", "String!"),
+      field("satpoint", "String"),
+      field("timestamp", "U64"),
+    ],
+  },
+];
+
+pub(super) fn object(name: &str) -> Option<&'static Object> {
+  OBJECTS.iter().find(|object| object.name == name)
+}
+
+/// The schema in GraphQL schema definition language
+pub(crate) fn sdl() -> String {
+  let mut sdl =
+    String::from("\"An unsigned 64-bit integer, serialized as a JSON number\"\nscalar U64\n");
+
+  for object in OBJECTS {
+    writeln!(sdl, "\ntype {} {{", object.name).unwrap();
+
+    for field in object.fields {
+      write!(sdl, "  {}", field.name).unwrap();
+
+      if !field.arguments.is_empty() {
+        let arguments = field
+          .arguments
+          .iter()
+          .map(|argument| match argument.default {
+            Some(default) => format!("{}: {} = {default}", argument.name, argument.ty),
+            None => format!("{}: {}", argument.name, argument.ty),
+          })
+          .collect::<Vec<String>>();
+
+        write!(sdl, "({})", arguments.join(", ")).unwrap();
+      }
+
+      writeln!(sdl, ": {}", field.ty).unwrap();
+    }
+
+    sdl.push_str("}\n");
+  }
+
+  sdl
+}
+
+pub(super) struct Context<'a> {
+  pub(super) index: &'a Index,
+  pub(super) server_config: &'a ServerConfig,
+}
+
+impl Context<'_> {
+  fn block(&self, height: u32) -> Result<Option<Node>> {
+    let Some(hash) = self.index.block_hash(Some(height))? else {
+      return Ok(None);
+    };
+
+    let header = self
+      .index
+      .block_header(hash)?
+      .ok_or_else(|| anyhow!("block header {hash} not found"))?;
+
+    Ok(Some(Node::Block {
+      hash,
+      header,
+      height,
+    }))
+  }
+
+  fn inscription(&self, query: query::Inscription) -> Result<Option<Node>> {
+    Ok(
+      self
+        .index
+        .inscription_info(query, None)?
+        .map(|(info, _txout, inscription)| Node::Inscription {
+          delegate: inscription.delegate(),
+          info: Box::new(info),
+        }),
+    )
+  }
+
+  fn inscriptions(
+    &self,
+    ids: impl IntoIterator<Item = InscriptionId>,
+    arguments: &Arguments,
+  ) -> Result<Vec<Node>> {
+    ids
+      .into_iter()
+      .take(limit(arguments))
+      .filter_map(|id| self.inscription(query::Inscription::Id(id)).transpose())
+      .collect()
+  }
+
+  fn output(&self, outpoint: OutPoint) -> Result<Option<Node>> {
+    if outpoint == unbound_outpoint() {
+      return Ok(None);
+    }
+
+    Ok(
+      self
+        .index
+        .get_output_info(outpoint)?
+        .map(|(info, _txout)| Node::Output(Box::new(info))),
+    )
+  }
+
+  fn outputs(&self, address: &Address) -> Result<Vec<OutPoint>> {
+    let mut outputs = self.index.get_address_info(address)?;
+    outputs.sort();
+    Ok(outputs)
+  }
+
+  fn rune(&self, rune: Rune) -> Result<Option<Node>> {
+    Ok(
+      self
+        .index
+        .rune(rune)?
+        .map(|(id, entry, parent)| Node::Rune {
+          entry: Box::new(entry),
+          id,
+          parent,
+        }),
+    )
+  }
+
+  fn sat_satpoint(&self, sat: Sat) -> Result<Option<SatPoint>> {
+    if let Some(satpoint) = self.index.rare_sat_satpoint(sat)? {
+      return Ok(Some(satpoint));
+    }
+
+    match self.index.get_inscription_ids_by_sat(sat)?.first() {
+      Some(&id) => self.index.get_inscription_satpoint_by_id(id),
+      None => Ok(None),
+    }
+  }
+}
+
+fn limit(arguments: &Arguments) -> usize {
+  arguments
+    .get("limit")
+    .and_then(serde_json::Value::as_u64)
+    .unwrap_or(DEFAULT_LIMIT)
+    .try_into()
+    .unwrap()
+}
+
+fn string<'a>(arguments: &'a Arguments, name: &str) -> Result<&'a str> {
+  arguments
+    .get(name)
+    .and_then(serde_json::Value::as_str)
+    .ok_or_else(|| anyhow!("missing argument `{name}`"))
+}
+
+fn value(value: impl Serialize) -> Result<Resolved> {
+  Ok(Resolved::Value(serde_json::to_value(value)?))
+}
+
+fn node(node: Node) -> Result<Resolved> {
+  Ok(Resolved::Node(Some(node)))
+}
+
+pub(super) enum Resolved {
+  Node(Option<Node>),
+  Nodes(Vec<Node>),
+  Value(serde_json::Value),
+}
+
+pub(super) enum Node {
+  Address(Address),
+  Block {
+    hash: BlockHash,
+    header: bitcoin::block::Header,
+    height: u32,
+  },
+  Inscription {
+    delegate: Option<InscriptionId>,
+    info: Box<api::Inscription>,
+  },
+  Output(Box<api::Output>),
+  Query,
+  Rune {
+    entry: Box<RuneEntry>,
+    id: RuneId,
+    parent: Option<InscriptionId>,
+  },
+  RuneBalance {
+    amount: Decimal,
+    rune: SpacedRune,
+    symbol: Option<char>,
+  },
+  Sat(Sat),
+}
+
+impl Node {
+  pub(super) fn type_name(&self) -> &'static str {
+    match self {
+      Self::Address(_) => "Address",
+      Self::Block { .. } => "Block",
+      Self::Inscription { .. } => "Inscription",
+      Self::Output(_) => "Output",
+      Self::Query => "Query",
+      Self::Rune { .. } => "Rune",
+      Self::RuneBalance { .. } => "RuneBalance",
+      Self::Sat(_) => "Sat",
+    }
+  }
+
+  pub(super) fn resolve(
+    &self,
+    context: &Context,
+    field: &str,
+    arguments: &Arguments,
+  ) -> Result<Resolved> {
+    let index = context.index;
+
+    match (self, field) {
+      (Self::Query, "address") => {
+        ensure!(
+          index.has_address_index(),
+          "this server has no address index"
+        );
+        let address = string(arguments, "address")?
+          .parse::<Address<NetworkUnchecked>>()?
+          .require_network(context.server_config.chain.network())?;
+        node(Self::Address(address))
+      }
+      (Self::Query, "block") => match string(arguments, "query")?.parse::<query::Block>()? {
+        query::Block::Height(height) => Ok(Resolved::Node(context.block(height)?)),
+        query::Block::Hash(hash) => match index.block_header_info(hash)? {
+          Some(info) => Ok(Resolved::Node(
+            context.block(u32::try_from(info.height).unwrap())?,
+          )),
+          None => Ok(Resolved::Node(None)),
+        },
+      },
+      (Self::Query, "blockCount") => value(index.block_count()?),
+      (Self::Query, "inscription") => Ok(Resolved::Node(
+        context.inscription(string(arguments, "query")?.parse()?)?,
+      )),
+      (Self::Query, "output") => Ok(Resolved::Node(
+        context.output(string(arguments, "outpoint")?.parse()?)?,
+      )),
+      (Self::Query, "rune") => {
+        ensure!(index.has_rune_index(), "this server has no rune index");
+        let rune = match string(arguments, "query")?.parse::<query::Rune>()? {
+          query::Rune::Spaced(spaced_rune) => Some(spaced_rune.rune),
+          query::Rune::Id(rune_id) => index.get_rune_by_id(rune_id)?,
+          query::Rune::Number(number) => index.get_rune_by_number(number.try_into()?)?,
+        };
+        match rune {
+          Some(rune) => Ok(Resolved::Node(context.rune(rune)?)),
+          None => Ok(Resolved::Node(None)),
+        }
+      }
+      (Self::Query, "sat") => node(Self::Sat(string(arguments, "query")?.parse()?)),
+
+      (Self::Address(address), "address") => value(address.to_string()),
+      (Self::Address(address), "inscriptions") => {
+        match index.get_inscriptions_for_outputs(&context.outputs(address)?)? {
+          Some(inscriptions) => Ok(Resolved::Nodes(
+            context.inscriptions(inscriptions, arguments)?,
+          )),
+          None => value(()),
+        }
+      }
+      (Self::Address(address), "outputs") => Ok(Resolved::Nodes(
+        context
+          .outputs(address)?
+          .into_iter()
+          .take(limit(arguments))
+          .filter_map(|outpoint| context.output(outpoint).transpose())
+          .collect::<Result<Vec<Node>>>()?,
+      )),
+      (Self::Address(address), "runeBalances") => {
+        match index.get_aggregated_rune_balances_for_outputs(&context.outputs(address)?)? {
+          Some(balances) => Ok(Resolved::Nodes(
+            balances
+              .into_iter()
+              .take(limit(arguments))
+              .map(|(rune, amount, symbol)| Self::RuneBalance {
+                amount,
+                rune,
+                symbol,
+              })
+              .collect(),
+          )),
+          None => value(()),
+        }
+      }
+      (Self::Address(address), "satBalance") => {
+        value(index.get_sat_balances_for_outputs(&context.outputs(address)?)?)
+      }
+
+      (Self::Block { hash, .. }, "hash") => value(hash),
+      (Self::Block { height, .. }, "height") => value(height),
+      (Self::Block { height, .. }, "inscriptions") => Ok(Resolved::Nodes(
+        context.inscriptions(index.get_inscriptions_in_block(*height)?, arguments)?,
+      )),
+      (Self::Block { header, .. }, "previousBlockhash") => value(header.prev_blockhash),
+      (Self::Block { height, .. }, "runes") => Ok(Resolved::Nodes(
+        index
+          .get_runes_in_block((*height).into())?
+          .into_iter()
+          .take(limit(arguments))
+          .filter_map(|spaced_rune| context.rune(spaced_rune.rune).transpose())
+          .collect::<Result<Vec<Node>>>()?,
+      )),
+      (Self::Block { header, .. }, "timestamp") => value(header.time),
+
+      (Self::Inscription { info, .. }, "address") => value(&info.address),
+      (Self::Inscription { info, .. }, "charms") => value(&info.charms),
+      (Self::Inscription { info, .. }, "childCount") => value(info.child_count),
+      (Self::Inscription { info, .. }, "children") => Ok(Resolved::Nodes(
+        context.inscriptions(
+          index
+            .get_children_by_inscription_id_paginated(info.id, limit(arguments), 0)?
+            .0,
+          arguments,
+        )?,
+      )),
+      (Self::Inscription { info, .. }, "contentLength") => value(info.content_length),
+      (Self::Inscription { info, .. }, "contentType") => value(&info.content_type),
+      (Self::Inscription { delegate, .. }, "delegate") => match delegate {
+        Some(delegate) => Ok(Resolved::Node(
+          context.inscription(query::Inscription::Id(*delegate))?,
+        )),
+        None => Ok(Resolved::Node(None)),
+      },
+      (Self::Inscription { info, .. }, "delegatorCount") => value(info.delegator_count),
+      (Self::Inscription { info, .. }, "effectiveContentType") => {
+        value(&info.effective_content_type)
+      }
+      (Self::Inscription { info, .. }, "fee") => value(info.fee),
+      (Self::Inscription { info, .. }, "height") => value(info.height),
+      (Self::Inscription { info, .. }, "id") => value(info.id),
+      (Self::Inscription { info, .. }, "metaprotocol") => value(&info.metaprotocol),
+      (Self::Inscription { info, .. }, "next") => match info.next {
+        Some(next) => Ok(Resolved::Node(
+          context.inscription(query::Inscription::Id(next))?,
+        )),
+        None => Ok(Resolved::Node(None)),
+      },
+      (Self::Inscription { info, .. }, "number") => value(info.number),
+      (Self::Inscription { info, .. }, "output") => {
+        Ok(Resolved::Node(context.output(info.satpoint.outpoint)?))
+      }
+      (Self::Inscription { info, .. }, "parents") => Ok(Resolved::Nodes(
+        context.inscriptions(info.parents.iter().copied(), arguments)?,
+      )),
+      (Self::Inscription { info, .. }, "previous") => match info.previous {
+        Some(previous) => Ok(Resolved::Node(
+          context.inscription(query::Inscription::Id(previous))?,
+        )),
+        None => Ok(Resolved::Node(None)),
+      },
+      (Self::Inscription { info, .. }, "rune") => match info.rune {
+        Some(spaced_rune) => Ok(Resolved::Node(context.rune(spaced_rune.rune)?)),
+        None => Ok(Resolved::Node(None)),
+      },
+      (Self::Inscription { info, .. }, "sat") => Ok(Resolved::Node(info.sat.map(Self::Sat))),
+      (Self::Inscription { info, .. }, "satpoint") => value(info.satpoint),
+      (Self::Inscription { info, .. }, "timestamp") => value(info.timestamp),
+      (Self::Inscription { info, .. }, "value") => value(info.value),
+
+      (Self::Output(info), "address") => value(&info.address),
+      (Self::Output(info), "indexed") => value(info.indexed),
+      (Self::Output(info), "inscriptions") => match &info.inscriptions {
+        Some(inscriptions) => Ok(Resolved::Nodes(
+          context.inscriptions(inscriptions.iter().copied(), arguments)?,
+        )),
+        None => value(()),
+      },
+      (Self::Output(info), "outpoint") => value(info.outpoint),
+      (Self::Output(info), "runes") => match &info.runes {
+        Some(runes) => Ok(Resolved::Nodes(
+          runes
+            .iter()
+            .take(limit(arguments))
+            .map(|(rune, pile)| Self::RuneBalance {
+              amount: Decimal {
+                value: pile.amount,
+                scale: pile.divisibility,
+              },
+              rune: *rune,
+              symbol: pile.symbol,
+            })
+            .collect(),
+        )),
+        None => value(()),
+      },
+      (Self::Output(info), "satRanges") => value(&info.sat_ranges),
+      (Self::Output(info), "scriptPubkey") => value(info.script_pubkey.to_hex_string()),
+      (Self::Output(info), "spent") => value(info.spent),
+      (Self::Output(info), "transaction") => value(info.transaction),
+      (Self::Output(info), "value") => value(info.value),
+
+      (Self::Rune { entry, .. }, "block") => {
+        Ok(Resolved::Node(context.block(entry.block.try_into()?)?))
+      }
+      (Self::Rune { entry, .. }, "burned") => value(entry.burned.to_string()),
+      (Self::Rune { entry, .. }, "divisibility") => value(entry.divisibility),
+      (Self::Rune { entry, .. }, "etching") => value(entry.etching),
+      (Self::Rune { id, .. }, "holders") => value(index.get_rune_holder_count(*id)?),
+      (Self::Rune { id, .. }, "id") => value(id.to_string()),
+      (Self::Rune { entry, .. }, "mintable") => {
+        let height = index.block_height()?.unwrap_or(Height(0));
+        value(entry.mintable((height.n() + 1).into()).is_ok())
+      }
+      (Self::Rune { entry, .. }, "mints") => value(entry.mints.to_string()),
+      (Self::Rune { entry, .. }, "name") => value(entry.spaced_rune.to_string()),
+      (Self::Rune { entry, .. }, "number") => value(entry.number),
+      (Self::Rune { parent, .. }, "parent") => match parent {
+        Some(parent) => Ok(Resolved::Node(
+          context.inscription(query::Inscription::Id(*parent))?,
+        )),
+        None => Ok(Resolved::Node(None)),
+      },
+      (Self::Rune { entry, .. }, "premine") => value(entry.premine.to_string()),
+      (Self::Rune { entry, .. }, "supply") => value(entry.supply().to_string()),
+      (Self::Rune { entry, .. }, "symbol") => value(entry.symbol),
+      (Self::Rune { entry, .. }, "timestamp") => value(entry.timestamp),
+      (Self::Rune { entry, .. }, "turbo") => value(entry.turbo),
+
+      (Self::RuneBalance { amount, .. }, "amount") => value(amount.to_string()),
+      (Self::RuneBalance { rune, .. }, "name") => value(rune.to_string()),
+      (Self::RuneBalance { rune, .. }, "rune") => Ok(Resolved::Node(context.rune(rune.rune)?)),
+      (Self::RuneBalance { symbol, .. }, "symbol") => value(symbol),
+
+      (Self::Sat(sat), "block") => Ok(Resolved::Node(context.block(sat.height().0)?)),
+      (Self::Sat(sat), "charms") => value(Charm::charms(sat.charms())),
+      (Self::Sat(sat), "cycle") => value(sat.cycle()),
+      (Self::Sat(sat), "decimal") => value(sat.decimal().to_string()),
+      (Self::Sat(sat), "degree") => value(sat.degree().to_string()),
+      (Self::Sat(sat), "epoch") => value(sat.epoch().0),
+      (Self::Sat(sat), "inscriptions") => Ok(Resolved::Nodes(
+        context.inscriptions(index.get_inscription_ids_by_sat(*sat)?, arguments)?,
+      )),
+      (Self::Sat(sat), "name") => value(sat.name()),
+      (Self::Sat(sat), "number") => value(sat.n()),
+      (Self::Sat(sat), "offset") => value(sat.third()),
+      (Self::Sat(sat), "output") => match context.sat_satpoint(*sat)? {
+        Some(satpoint) => Ok(Resolved::Node(context.output(satpoint.outpoint)?)),
+        None => Ok(Resolved::Node(None)),
+      },
+      (Self::Sat(sat), "percentile") => value(sat.percentile()),
+      (Self::Sat(sat), "period") => value(sat.period()),
+      (Self::Sat(sat), "rarity") => value(sat.rarity()),
+      (Self::Sat(sat), "satpoint") => value(context.sat_satpoint(*sat)?),
+      (Self::Sat(sat), "timestamp") => {
+        value(index.block_time(sat.height())?.timestamp().timestamp())
+      }
+
+      (node, field) => bail!(
+        "field `{field}` on type `{}` has no resolver",
+        node.type_name()
+      ),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn field_types_are_defined() {
+    for object in OBJECTS {
+      for field in object.fields {
+        let base = field.base_type();
+        assert!(
+          ["Boolean", "Int", "String", "U64"].contains(&base) || super::object(base).is_some(),
+          "{}.{} has undefined type {base}",
+          object.name,
+          field.name,
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn list_fields_take_limit() {
+    for object in OBJECTS {
+      for field in object.fields {
+        if super::object(field.base_type()).is_some() {
+          assert_eq!(
+            field.is_list(),
+            !field.arguments.is_empty() && field.arguments[0].name == "limit",
+            "{}.{}",
+            object.name,
+            field.name,
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn sdl() {
+    let sdl = super::sdl();
+    assert!(sdl.starts_with("\"An unsigned 64-bit integer, serialized as a JSON number\"\nscalar U64\n\ntype Query {\n  address(address: String!): Address\n"));
+    assert!(sdl.contains("  children(limit: Int = 25): [Inscription!]!\n"));
+    assert!(sdl.ends_with("  timestamp: U64\n}\n"));
+  }
+}
@@ -0,0 +1,235 @@
+use {
+  super::*,
+  base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
+};
+
+pub(super) const MAX_PAGE_SIZE: usize = 100;
+
+/// Index keys which identify the first item of a page. Cursors are the
+/// URL-safe base64 encoding of the big-endian key, and clients must treat them
+/// as opaque.
+pub(super) trait CursorKey: Sized {
+  fn to_bytes(&self) -> Vec<u8>;
+
+  fn from_bytes(bytes: &[u8]) -> Option<Self>;
+
+  fn encode(&self) -> String {
+    URL_SAFE_NO_PAD.encode(self.to_bytes())
+  }
+
+  fn decode(cursor: &str) -> Option<Self> {
+    Self::from_bytes(&URL_SAFE_NO_PAD.decode(cursor).ok()?)
+  }
+}
+
+impl CursorKey for u32 {
+  fn to_bytes(&self) -> Vec<u8> {
+    self.to_be_bytes().into()
+  }
+
+  fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    Some(Self::from_be_bytes(bytes.try_into().ok()?))
+  }
+}
+
+impl CursorKey for RuneId {
+  fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = self.block.to_be_bytes().to_vec();
+    bytes.extend_from_slice(&self.tx.to_be_bytes());
+    bytes
+  }
+
+  fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    let bytes: [u8; 12] = bytes.try_into().ok()?;
+    let (block, tx) = bytes.split_at(8);
+    Some(Self {
+      block: u64::from_be_bytes(block.try_into().unwrap()),
+      tx: u32::from_be_bytes(tx.try_into().unwrap()),
+    })
+  }
+}
+
+impl CursorKey for (u128, ScriptBuf) {
+  fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = self.0.to_be_bytes().to_vec();
+    bytes.extend_from_slice(self.1.as_bytes());
+    bytes
+  }
+
+  fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    if bytes.len() < 16 {
+      return None;
+    }
+
+    let (balance, script_pubkey) = bytes.split_at(16);
+
+    Some((
+      u128::from_be_bytes(balance.try_into().unwrap()),
+      ScriptBuf::from_bytes(script_pubkey.to_vec()),
+    ))
+  }
+}
+
+/// Cursor, page size, and sort order of a listing
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub(super) struct ListingQuery {
+  pub(super) cursor: Option<String>,
+  pub(super) limit: Option<usize>,
+  pub(super) sort: Option<Sort>,
+}
+
+impl ListingQuery {
+  pub(super) fn page_size(&self, default: usize) -> ServerResult<usize> {
+    match self.limit {
+      None => Ok(default),
+      Some(0) => Err(ServerError::BadRequest("limit must be at least 1".into())),
+      Some(limit) if limit > MAX_PAGE_SIZE => Err(ServerError::BadRequest(format!(
+        "limit {limit} exceeds maximum of {MAX_PAGE_SIZE}"
+      ))),
+      Some(limit) => Ok(limit),
+    }
+  }
+
+  pub(super) fn cursor<K: CursorKey>(&self) -> ServerResult<Option<K>> {
+    self
+      .cursor
+      .as_deref()
+      .map(|cursor| {
+        K::decode(cursor)
+          .ok_or_else(|| ServerError::BadRequest(format!("invalid cursor `{cursor}`")))
+      })
+      .transpose()
+  }
+
+  /// Sort order of inscription listings, which default to newest first
+  pub(super) fn inscription_sort(&self, index: &Index) -> ServerResult<Sort> {
+    let sort = self.sort.unwrap_or(Sort::Newest);
+
+    if sort == Sort::Fee && !index.has_inscription_attribute_index() {
+      return Err(ServerError::NotFound(
+        "this server has no inscription attribute index".into(),
+      ));
+    }
+
+    Ok(sort)
+  }
+
+  /// Sort order of listings which cannot be sorted by fee
+  pub(super) fn sort(&self, default: Sort, listing: &str) -> ServerResult<Sort> {
+    match self.sort.unwrap_or(default) {
+      Sort::Fee => Err(ServerError::BadRequest(format!(
+        "{listing} cannot be sorted by fee"
+      ))),
+      sort => Ok(sort),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn listing_query(cursor: Option<&str>, limit: Option<usize>) -> ListingQuery {
+    ListingQuery {
+      cursor: cursor.map(Into::into),
+      limit,
+      sort: None,
+    }
+  }
+
+  fn message(error: ServerError) -> String {
+    match error {
+      ServerError::BadRequest(message) => message,
+      error => panic!("unexpected error: {error:?}"),
+    }
+  }
+
+  #[test]
+  fn cursors_round_trip() {
+    assert_eq!(
+      listing_query(Some(&1234u32.encode()), None)
+        .cursor::<u32>()
+        .unwrap(),
+      Some(1234),
+    );
+
+    let id = RuneId {
+      block: 840000,
+      tx: 7,
+    };
+
+    assert_eq!(
+      listing_query(Some(&id.encode()), None)
+        .cursor::<RuneId>()
+        .unwrap(),
+      Some(id),
+    );
+
+    let holder = (1000u128, ScriptBuf::from_bytes(vec![0x51]));
+
+    assert_eq!(
+      listing_query(Some(&holder.encode()), None)
+        .cursor::<(u128, ScriptBuf)>()
+        .unwrap(),
+      Some(holder),
+    );
+  }
+
+  #[test]
+  fn invalid_cursors_are_rejected() {
+    for cursor in ["!", "AAAA", "AAAAAAAAAA"] {
+      assert_eq!(
+        message(
+          listing_query(Some(cursor), None)
+            .cursor::<u32>()
+            .unwrap_err()
+        ),
+        format!("invalid cursor `{cursor}`"),
+      );
+    }
+  }
+
+  #[test]
+  fn page_size_is_capped() {
+    assert_eq!(listing_query(None, None).page_size(50).unwrap(), 50);
+    assert_eq!(listing_query(None, Some(1)).page_size(50).unwrap(), 1);
+    assert_eq!(
+      listing_query(None, Some(MAX_PAGE_SIZE))
+        .page_size(50)
+        .unwrap(),
+      MAX_PAGE_SIZE
+    );
+    assert_eq!(
+      message(listing_query(None, Some(0)).page_size(50).unwrap_err()),
+      "limit must be at least 1",
+    );
+    assert_eq!(
+      message(
+        listing_query(None, Some(MAX_PAGE_SIZE + 1))
+          .page_size(50)
+          .unwrap_err()
+      ),
+      "limit 101 exceeds maximum of 100",
+    );
+  }
+
+  #[test]
+  fn fee_sort_is_rejected() {
+    let query = ListingQuery {
+      sort: Some(Sort::Fee),
+      ..default()
+    };
+
+    assert_eq!(
+      message(query.sort(Sort::Newest, "runes").unwrap_err()),
+      "runes cannot be sorted by fee",
+    );
+
+    assert_eq!(
+      ListingQuery::default()
+        .sort(Sort::Oldest, "children")
+        .unwrap(),
+      Sort::Oldest,
+    );
+  }
+}
@@ -0,0 +1,1537 @@
+use {
+  super::*,
+  crate::{index::entry::RuneEntry, inscriptions::ParsedEnvelope},
+  serde_json::{json, Map, Value},
+};
+
+/// Types which can describe their JSON serialization as an OpenAPI schema
+pub(crate) trait Schema {
+  fn schema(generator: &mut Generator) -> Value;
+}
+
+/// Structs whose fields are documented individually, so that they may be
+/// used as both response objects and query parameters
+pub(crate) trait Object {
+  fn properties(generator: &mut Generator) -> Vec<(&'static str, Value)>;
+}
+
+/// Collects named schemas into `components`, so that they are described
+/// once and referenced by `$ref` everywhere else
+#[derive(Default)]
+pub(crate) struct Generator {
+  schemas: BTreeMap<&'static str, Value>,
+}
+
+impl Generator {
+  fn component(&mut self, name: &'static str, schema: fn(&mut Self) -> Value) -> Value {
+    if !self.schemas.contains_key(name) {
+      self.schemas.insert(name, Value::Null);
+      let schema = schema(self);
+      self.schemas.insert(name, schema);
+    }
+
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+  }
+}
+
+fn is_nullable(schema: &Value) -> bool {
+  schema["anyOf"]
+    .as_array()
+    .is_some_and(|variants| variants.contains(&json!({ "type": "null" })))
+}
+
+fn object<T: Object>(generator: &mut Generator) -> Value {
+  let properties = T::properties(generator);
+
+  json!({
+    "type": "object",
+    "properties": properties
+      .iter()
+      .map(|(name, schema)| (name.to_string(), schema.clone()))
+      .collect::<Map<String, Value>>(),
+    "required": properties.iter().map(|(name, _)| *name).collect::<Vec<&str>>(),
+    "additionalProperties": false,
+  })
+}
+
+macro_rules! integers {
+  ($($ty:ty),* $(,)?) => {
+    $(
+      impl Schema for $ty {
+        fn schema(_: &mut Generator) -> Value {
+          if <$ty>::MIN == 0 {
+            json!({ "type": "integer", "minimum": 0 })
+          } else {
+            json!({ "type": "integer" })
+          }
+        }
+      }
+    )*
+  };
+}
+
+integers!(i32, i64, u8, u32, u64, u128, usize);
+
+macro_rules! strings {
+  ($($ty:ty => $description:literal),* $(,)?) => {
+    $(
+      impl Schema for $ty {
+        fn schema(_: &mut Generator) -> Value {
+          json!({ "type": "string", "description": $description })
+        }
+      }
+    )*
+  };
+}
+
+strings! {
+  Address<NetworkUnchecked> => "Bitcoin address",
+  BlockHash => "Block hash",
+  DateTime<Utc> => "RFC 3339 timestamp",
+  Decimal => "Decimal number",
+  InscriptionId => "Inscription ID, `<TXID>i<INDEX>`",
+  OutPoint => "Outpoint, `<TXID>:<VOUT>`",
+  Rune => "Rune name",
+  RuneId => "Rune ID, `<BLOCK>:<TX>`",
+  SatPoint => "Satpoint, `<TXID>:<VOUT>:<OFFSET>`",
+  ScriptBuf => "Hex-encoded script",
+  SpacedRune => "Rune name, with optional spacers",
+  String => "String",
+  TxMerkleNode => "Merkle root",
+  Txid => "Transaction ID",
+  char => "Single character",
+}
+
+impl Schema for bool {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "boolean" })
+  }
+}
+
+impl Schema for f64 {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "number" })
+  }
+}
+
+impl Schema for Value {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "description": "Any JSON value" })
+  }
+}
+
+impl Schema for Sat {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "integer", "minimum": 0, "description": "Sat number" })
+  }
+}
+
+impl Schema for Charm {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "string", "enum": Charm::ALL })
+  }
+}
+
+impl Schema for Rarity {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "string", "enum": Rarity::ALL })
+  }
+}
+
+impl Schema for Sort {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "string", "enum": Sort::ALL.map(|sort| sort.to_string()) })
+  }
+}
+
+impl Schema for Chain {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "string", "enum": <Chain as clap::ValueEnum>::value_variants() })
+  }
+}
+
+impl Schema for OutputType {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "string", "enum": ["any", "cardinal", "inscribed", "runic"] })
+  }
+}
+
+impl Schema for Duration {
+  fn schema(_: &mut Generator) -> Value {
+    json!({
+      "type": "object",
+      "properties": {
+        "secs": { "type": "integer", "minimum": 0 },
+        "nanos": { "type": "integer", "minimum": 0 },
+      },
+      "required": ["secs", "nanos"],
+      "additionalProperties": false,
+    })
+  }
+}
+
+/// Hex-encoded bytes, for fields serialized with `serde_hex`
+pub(crate) struct Hex;
+
+impl Schema for Hex {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "string", "description": "Hex-encoded bytes" })
+  }
+}
+
+macro_rules! opaque {
+  ($($ty:ty => $name:literal: $description:literal),* $(,)?) => {
+    $(
+      impl Schema for $ty {
+        fn schema(generator: &mut Generator) -> Value {
+          generator.component($name, |_| {
+            json!({ "type": "object", "description": $description })
+          })
+        }
+      }
+    )*
+  };
+}
+
+opaque! {
+  Artifact => "Artifact": "Runestone or cenotaph decoded from a transaction",
+  Event => "Event": "Index event, an object with a single key naming the event kind",
+  ParsedEnvelope => "Envelope": "Inscription envelope decoded from a transaction input",
+  Transaction => "BitcoinTransaction": "Bitcoin transaction, in the format of the `bitcoin` crate",
+}
+
+impl<T: Schema> Schema for Option<T> {
+  fn schema(generator: &mut Generator) -> Value {
+    json!({ "anyOf": [T::schema(generator), { "type": "null" }] })
+  }
+}
+
+impl<T: Schema> Schema for Vec<T> {
+  fn schema(generator: &mut Generator) -> Value {
+    json!({ "type": "array", "items": T::schema(generator) })
+  }
+}
+
+impl<T: Schema, const N: usize> Schema for [T; N] {
+  fn schema(generator: &mut Generator) -> Value {
+    json!({
+      "type": "array",
+      "items": T::schema(generator),
+      "minItems": N,
+      "maxItems": N,
+    })
+  }
+}
+
+impl<K, V: Schema> Schema for BTreeMap<K, V> {
+  fn schema(generator: &mut Generator) -> Value {
+    json!({ "type": "object", "additionalProperties": V::schema(generator) })
+  }
+}
+
+macro_rules! tuples {
+  ($(($($ty:ident),*)),* $(,)?) => {
+    $(
+      impl<$($ty: Schema),*> Schema for ($($ty,)*) {
+        fn schema(generator: &mut Generator) -> Value {
+          let items = vec![$($ty::schema(generator)),*];
+          json!({
+            "type": "array",
+            "prefixItems": items,
+            "minItems": items.len(),
+            "maxItems": items.len(),
+          })
+        }
+      }
+    )*
+  };
+}
+
+tuples!((A, B), (A, B, C));
+
+macro_rules! field_schema {
+  ($ty:ty) => {
+    <$ty as Schema>::schema
+  };
+  ($ty:ty, $schema:ty) => {
+    <$schema as Schema>::schema
+  };
+}
+
+/// Property of a field, or none if the field is not serialized
+macro_rules! property {
+  ($generator:ident, $field:ident, [skip], [$($field_ty:tt)*]) => {
+    None
+  };
+  ($generator:ident, $field:ident, [rename = $rename:literal], [$($field_ty:tt)*]) => {
+    Some(($rename, field_schema!($($field_ty)*)($generator)))
+  };
+  ($generator:ident, $field:ident, [], [$($field_ty:tt)*]) => {
+    Some((stringify!($field), field_schema!($($field_ty)*)($generator)))
+  };
+}
+
+/// Describe structs field by field. Each struct is destructured without
+/// `..`, and each field checked against its listed type, so that adding,
+/// removing, or changing a field without updating its description is a
+/// compile error.
+macro_rules! objects {
+  (
+    $(
+      $name:literal => $($segment:ident)::+ {
+        $(
+          $(#[serde($($attribute:tt)*)])?
+          $field:ident: $ty:ty $(as $schema:ty)?
+        ),* $(,)?
+      }
+    )*
+  ) => {
+    $(
+      impl Object for $($segment)::+ {
+        fn properties(generator: &mut Generator) -> Vec<(&'static str, Value)> {
+          #[allow(unused)]
+          fn exhaustive(value: $($segment)::+) {
+            let $($segment)::+ { $($field),* } = value;
+            $(let _: $ty = $field;)*
+          }
+
+          [
+            $(property!(generator, $field, [$($($attribute)*)?], [$ty $(, $schema)?]),)*
+          ]
+          .into_iter()
+          .flatten()
+          .collect()
+        }
+      }
+
+      impl Schema for $($segment)::+ {
+        fn schema(generator: &mut Generator) -> Value {
+          generator.component($name, object::<Self>)
+        }
+      }
+    )*
+  };
+}
+
+objects! {
+  "AddressInfo" => api::AddressInfo {
+    outputs: Vec<OutPoint>,
+    inscriptions: Option<Vec<InscriptionId>>,
+    sat_balance: u64,
+    runes_balances: Option<Vec<(SpacedRune, Decimal, Option<char>)>>,
+  }
+  "AddressRuneBalances" => api::AddressRuneBalances {
+    height: u32,
+    outputs: Vec<OutPoint>,
+    runes_balances: Vec<(SpacedRune, Decimal, Option<char>)>,
+  }
+  "Block" => api::Block {
+    best_height: u32,
+    hash: BlockHash,
+    height: u32,
+    inscriptions: Vec<InscriptionId>,
+    runes: Vec<SpacedRune>,
+    target: BlockHash,
+    transactions: Vec<Transaction>,
+  }
+  "BlockInfo" => api::BlockInfo {
+    average_fee: u64,
+    average_fee_rate: u64,
+    bits: u32,
+    chainwork: [u8; 32] as Hex,
+    confirmations: i32,
+    difficulty: f64,
+    hash: BlockHash,
+    feerate_percentiles: [u64; 5],
+    height: u32,
+    max_fee: u64,
+    max_fee_rate: u64,
+    max_tx_size: u32,
+    median_fee: u64,
+    median_time: Option<u64>,
+    merkle_root: TxMerkleNode,
+    min_fee: u64,
+    min_fee_rate: u64,
+    next_block: Option<BlockHash>,
+    nonce: u32,
+    previous_block: Option<BlockHash>,
+    subsidy: u64,
+    target: BlockHash,
+    timestamp: u64,
+    total_fee: u64,
+    total_size: usize,
+    total_weight: usize,
+    transaction_count: u64,
+    version: u32,
+  }
+  "Blocks" => api::Blocks {
+    last: u32,
+    blocks: Vec<BlockHash>,
+    featured_blocks: BTreeMap<BlockHash, Vec<InscriptionId>>,
+  }
+  "ChildInscriptions" => api::ChildInscriptions {
+    children: Vec<api::RelativeInscriptionRecursive>,
+    more: bool,
+    page: usize,
+  }
+  "Children" => api::Children {
+    ids: Vec<InscriptionId>,
+    more: bool,
+    next_cursor: Option<String>,
+    page: usize,
+    prev_cursor: Option<String>,
+  }
+  "Decode" => api::Decode {
+    inscriptions: Vec<ParsedEnvelope>,
+    runestone: Option<Artifact>,
+  }
+  "Delegators" => api::Delegators {
+    ids: Vec<InscriptionId>,
+    more: bool,
+    page: usize,
+  }
+  "Events" => api::Events {
+    events: Vec<api::LoggedEvent>,
+    more: bool,
+    next: u64,
+  }
+  "Health" => api::Health {
+    healthy: bool,
+    height: Option<u32>,
+    reason: Option<String>,
+  }
+  "Inscription" => api::Inscription {
+    address: Option<String>,
+    charms: Vec<Charm>,
+    child_count: u64,
+    children: Vec<InscriptionId>,
+    content_length: Option<usize>,
+    content_type: Option<String>,
+    delegator_count: Option<u64>,
+    effective_content_type: Option<String>,
+    fee: u64,
+    height: u32,
+    id: InscriptionId,
+    next: Option<InscriptionId>,
+    number: i32,
+    parents: Vec<InscriptionId>,
+    pending: Option<Txid>,
+    previous: Option<InscriptionId>,
+    rune: Option<SpacedRune>,
+    sat: Option<Sat>,
+    satpoint: SatPoint,
+    timestamp: i64,
+    value: Option<u64>,
+    metaprotocol: Option<String>,
+  }
+  "InscriptionHistory" => api::InscriptionHistory {
+    id: InscriptionId,
+    locations: Vec<api::InscriptionLocation>,
+  }
+  "InscriptionLocation" => api::InscriptionLocation {
+    address: Option<String>,
+    height: u32,
+    new_satpoint: SatPoint,
+    old_satpoint: Option<SatPoint>,
+    txid: Txid,
+  }
+  "InscriptionMetadata" => api::InscriptionMetadata {
+    id: InscriptionId,
+    metadata: Option<Value>,
+  }
+  "InscriptionRecursive" => api::InscriptionRecursive {
+    charms: Vec<Charm>,
+    content_type: Option<String>,
+    content_length: Option<usize>,
+    delegate: Option<InscriptionId>,
+    fee: u64,
+    height: u32,
+    id: InscriptionId,
+    number: i32,
+    output: OutPoint,
+    sat: Option<Sat>,
+    satpoint: SatPoint,
+    timestamp: i64,
+    value: Option<u64>,
+    address: Option<String>,
+  }
+  "Inscriptions" => api::Inscriptions {
+    ids: Vec<InscriptionId>,
+    more: bool,
+    next_cursor: Option<String>,
+    page_index: u32,
+    prev_cursor: Option<String>,
+  }
+  "LoggedEvent" => api::LoggedEvent {
+    event: Event,
+    height: u32,
+    sequence: u64,
+  }
+  "Mempool" => api::Mempool {
+    tip: Option<BlockHash>,
+    transactions: Vec<api::MempoolTransaction>,
+  }
+  "MempoolTransaction" => api::MempoolTransaction {
+    etching: Option<SpacedRune>,
+    inscriptions: Vec<InscriptionId>,
+    mint: Option<RuneId>,
+    runes: Vec<SpacedRune>,
+    transferred_inscriptions: Vec<InscriptionId>,
+    txid: Txid,
+  }
+  "Output" => api::Output {
+    address: Option<Address<NetworkUnchecked>>,
+    indexed: bool,
+    inscriptions: Option<Vec<InscriptionId>>,
+    outpoint: OutPoint,
+    pending: Option<Txid>,
+    runes: Option<BTreeMap<SpacedRune, Pile>>,
+    sat_ranges: Option<Vec<(u64, u64)>>,
+    script_pubkey: ScriptBuf,
+    spent: bool,
+    transaction: Txid,
+    value: u64,
+  }
+  "ParentInscriptions" => api::ParentInscriptions {
+    parents: Vec<api::RelativeInscriptionRecursive>,
+    more: bool,
+    page: usize,
+  }
+  "Pile" => Pile {
+    amount: u128,
+    divisibility: u8,
+    symbol: Option<char>,
+  }
+  "Readiness" => api::Readiness {
+    ready: bool,
+    height: Option<u32>,
+    lag: Option<u64>,
+    reason: Option<String>,
+  }
+  "RelativeInscriptionRecursive" => api::RelativeInscriptionRecursive {
+    charms: Vec<Charm>,
+    fee: u64,
+    height: u32,
+    id: InscriptionId,
+    number: i32,
+    output: OutPoint,
+    sat: Option<Sat>,
+    satpoint: SatPoint,
+    timestamp: i64,
+  }
+  "Rune" => api::Rune {
+    entry: RuneEntry,
+    holders: Option<u64>,
+    id: RuneId,
+    mintable: bool,
+    parent: Option<InscriptionId>,
+  }
+  "RuneEntry" => RuneEntry {
+    block: u64,
+    burned: u128,
+    divisibility: u8,
+    etching: Txid,
+    mints: u128,
+    number: u64,
+    premine: u128,
+    spaced_rune: SpacedRune,
+    symbol: Option<char>,
+    terms: Option<Terms>,
+    timestamp: u64,
+    turbo: bool,
+  }
+  "RuneHolder" => api::RuneHolder {
+    address: Option<String>,
+    amount: u128,
+    script_pubkey: ScriptBuf,
+  }
+  "RuneHolders" => api::RuneHolders {
+    count: u64,
+    divisibility: u8,
+    holders: Vec<api::RuneHolder>,
+    id: RuneId,
+    #[serde(skip)]
+    limit: Option<usize>,
+    more: bool,
+    next_cursor: Option<String>,
+    prev_cursor: Option<String>,
+    rune: SpacedRune,
+    symbol: Option<char>,
+  }
+  "Runes" => api::Runes {
+    entries: Vec<(RuneId, RuneEntry)>,
+    more: bool,
+    prev: Option<usize>,
+    next: Option<usize>,
+    prev_cursor: Option<String>,
+    next_cursor: Option<String>,
+    #[serde(skip)]
+    limit: Option<usize>,
+    #[serde(skip)]
+    sort: Option<Sort>,
+  }
+  "Sat" => api::Sat {
+    address: Option<String>,
+    block: u32,
+    charms: Vec<Charm>,
+    cycle: u32,
+    decimal: String,
+    degree: String,
+    epoch: u32,
+    inscriptions: Vec<InscriptionId>,
+    name: String,
+    number: u64,
+    offset: u64,
+    percentile: String,
+    period: u32,
+    rarity: Rarity,
+    satpoint: Option<SatPoint>,
+    timestamp: i64,
+  }
+  "SatInscription" => api::SatInscription {
+    id: Option<InscriptionId>,
+  }
+  "SatInscriptions" => api::SatInscriptions {
+    ids: Vec<InscriptionId>,
+    more: bool,
+    page: u64,
+  }
+  "Status" => api::Status {
+    address_index: bool,
+    blessed_inscriptions: u64,
+    chain: Chain,
+    cursed_inscriptions: u64,
+    height: Option<u32>,
+    initial_sync_time: Duration,
+    inscription_index: bool,
+    inscriptions: u64,
+    json_api: bool,
+    lost_sats: u64,
+    minimum_rune_for_next_block: Rune,
+    replica_lag: Option<u64>,
+    rune_index: bool,
+    runes: u64,
+    sat_index: bool,
+    started: DateTime<Utc>,
+    transaction_index: bool,
+    unrecoverably_reorged: bool,
+    uptime: Duration,
+  }
+  "Terms" => Terms {
+    amount: Option<u128>,
+    cap: Option<u128>,
+    height: (Option<u64>, Option<u64>),
+    offset: (Option<u64>, Option<u64>),
+  }
+  "Transaction" => api::Transaction {
+    chain: Chain,
+    etching: Option<SpacedRune>,
+    inscription_count: u32,
+    transaction: Transaction,
+    txid: Txid,
+  }
+  "UtxoRecursive" => api::UtxoRecursive {
+    inscriptions: Option<Vec<InscriptionId>>,
+    runes: Option<BTreeMap<SpacedRune, Pile>>,
+    sat_ranges: Option<Vec<(u64, u64)>>,
+    value: u64,
+  }
+  "AddressQuery" => AddressQuery {
+    height: Option<u32>,
+  }
+  "EventLogQuery" => EventLogQuery {
+    from_height: Option<u32>,
+  }
+  "ListingQuery" => listing::ListingQuery {
+    cursor: Option<String>,
+    limit: Option<usize>,
+    sort: Option<Sort>,
+  }
+  "InscriptionFilter" => InscriptionFilter {
+    charm: Option<Charm>,
+    content_type: Option<String>,
+    from_height: Option<u32>,
+    metaprotocol: Option<String>,
+  }
+  "MetadataQuery" => MetadataQuery {
+    key: String,
+    value: Option<String>,
+  }
+  "OutputsQuery" => OutputsQuery {
+    #[serde(rename = "type")]
+    ty: Option<OutputType>,
+  }
+}
+
+objects! {
+  "v1.Address" => api::v1::Address {
+    address: String,
+    inscriptions: Option<Vec<InscriptionId>>,
+    outputs: Vec<OutPoint>,
+    runes: Option<Vec<api::v1::RuneBalance>>,
+    sat_balance: u64,
+  }
+  "v1.Block" => api::v1::Block {
+    hash: BlockHash,
+    height: u32,
+    inscriptions: Vec<InscriptionId>,
+    previous_block: Option<BlockHash>,
+    runes: Vec<SpacedRune>,
+    timestamp: u64,
+    transaction_count: u64,
+  }
+  "v1.Error" => api::v1::Error {
+    code: api::v1::ErrorCode,
+    message: String,
+  }
+  "v1.Inscription" => api::v1::Inscription {
+    address: Option<String>,
+    charms: Vec<Charm>,
+    child_count: u64,
+    content_length: Option<u64>,
+    content_type: Option<String>,
+    fee: u64,
+    height: u32,
+    id: InscriptionId,
+    metaprotocol: Option<String>,
+    number: i32,
+    parents: Vec<InscriptionId>,
+    rune: Option<SpacedRune>,
+    sat: Option<Sat>,
+    satpoint: SatPoint,
+    timestamp: i64,
+    value: Option<u64>,
+  }
+  "v1.Output" => api::v1::Output {
+    address: Option<String>,
+    inscriptions: Option<Vec<InscriptionId>>,
+    outpoint: OutPoint,
+    runes: Option<Vec<api::v1::RuneBalance>>,
+    sat_ranges: Option<Vec<(u64, u64)>>,
+    script_pubkey: ScriptBuf,
+    spent: bool,
+    value: u64,
+  }
+  "v1.Rune" => api::v1::Rune {
+    block: u64,
+    burned: u128,
+    divisibility: u8,
+    etching: Txid,
+    holders: Option<u64>,
+    id: RuneId,
+    mintable: bool,
+    mints: u128,
+    name: SpacedRune,
+    number: u64,
+    parent: Option<InscriptionId>,
+    premine: u128,
+    symbol: Option<char>,
+    terms: Option<api::v1::RuneTerms>,
+    timestamp: u64,
+    turbo: bool,
+  }
+  "v1.RuneBalance" => api::v1::RuneBalance {
+    amount: u128,
+    divisibility: u8,
+    rune: SpacedRune,
+    symbol: Option<char>,
+  }
+  "v1.RuneTerms" => api::v1::RuneTerms {
+    amount: Option<u128>,
+    cap: Option<u128>,
+    height_end: Option<u64>,
+    height_start: Option<u64>,
+    offset_end: Option<u64>,
+    offset_start: Option<u64>,
+  }
+  "v1.Sat" => api::v1::Sat {
+    address: Option<String>,
+    block: u32,
+    charms: Vec<Charm>,
+    cycle: u32,
+    decimal: String,
+    degree: String,
+    epoch: u32,
+    inscriptions: Vec<InscriptionId>,
+    name: String,
+    number: u64,
+    offset: u64,
+    percentile: String,
+    period: u32,
+    rarity: Rarity,
+    satpoint: Option<SatPoint>,
+    timestamp: i64,
+  }
+  "v1.Status" => api::v1::Status {
+    address_index: bool,
+    chain: Chain,
+    height: Option<u32>,
+    inscription_index: bool,
+    inscriptions: u64,
+    rune_index: bool,
+    runes: u64,
+    sat_index: bool,
+    transaction_index: bool,
+    version: String,
+  }
+  "v1.PageQuery" => v1::PageQuery {
+    cursor: Option<String>,
+    limit: Option<usize>,
+    sort: Option<Sort>,
+  }
+}
+
+impl Schema for api::v1::ErrorCode {
+  fn schema(_: &mut Generator) -> Value {
+    use api::v1::ErrorCode::*;
+    json!({
+      "type": "string",
+      "enum": [BadRequest, Internal, NotAcceptable, NotFound],
+    })
+  }
+}
+
+impl<T: Schema> Schema for api::v1::Page<T> {
+  fn schema(generator: &mut Generator) -> Value {
+    #[allow(unused)]
+    fn exhaustive<T>(value: api::v1::Page<T>) {
+      let api::v1::Page { items, next } = value;
+      let _: Vec<T> = items;
+      let _: Option<String> = next;
+    }
+
+    json!({
+      "type": "object",
+      "properties": {
+        "items": Vec::<T>::schema(generator),
+        "next": Option::<String>::schema(generator),
+      },
+      "required": ["items", "next"],
+      "additionalProperties": false,
+    })
+  }
+}
+
+/// `/address/<ADDRESS>` returns rune balances at a height if `height` is
+/// given, and everything held by the address otherwise
+struct AddressResponse;
+
+impl Schema for AddressResponse {
+  fn schema(generator: &mut Generator) -> Value {
+    json!({
+      "oneOf": [
+        api::AddressInfo::schema(generator),
+        api::AddressRuneBalances::schema(generator),
+      ],
+    })
+  }
+}
+
+/// The OpenAPI description itself
+struct OpenApi;
+
+impl Schema for OpenApi {
+  fn schema(_: &mut Generator) -> Value {
+    json!({ "type": "object", "description": "OpenAPI 3.1 description" })
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Method {
+  Get,
+  Post,
+}
+
+impl Method {
+  fn name(self) -> &'static str {
+    match self {
+      Self::Get => "get",
+      Self::Post => "post",
+    }
+  }
+}
+
+pub(crate) struct Route {
+  pub(crate) accept_json: bool,
+  pub(crate) method: Method,
+  pub(crate) path: &'static str,
+  query: Vec<fn(&mut Generator) -> Vec<(&'static str, Value)>>,
+  request: Option<fn(&mut Generator) -> Value>,
+  pub(crate) response: fn(&mut Generator) -> Value,
+  summary: &'static str,
+}
+
+impl Route {
+  fn get<T: Schema>(path: &'static str, summary: &'static str) -> Self {
+    Self {
+      accept_json: false,
+      method: Method::Get,
+      path,
+      query: Vec::new(),
+      request: None,
+      response: T::schema,
+      summary,
+    }
+  }
+
+  fn post<Request: Schema, T: Schema>(path: &'static str, summary: &'static str) -> Self {
+    Self {
+      method: Method::Post,
+      request: Some(Request::schema),
+      ..Self::get::<T>(path, summary)
+    }
+  }
+
+  /// Routes which serve HTML unless `Accept: application/json` is given
+  fn accept_json(self) -> Self {
+    Self {
+      accept_json: true,
+      ..self
+    }
+  }
+
+  fn query<T: Object>(mut self) -> Self {
+    self.query.push(T::properties);
+    self
+  }
+
+  fn operation(&self, generator: &mut Generator) -> Value {
+    let mut parameters = self
+      .path
+      .split('/')
+      .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+      .map(|name| {
+        json!({
+          "name": name,
+          "in": "path",
+          "required": true,
+          "schema": { "type": "string" },
+        })
+      })
+      .collect::<Vec<Value>>();
+
+    for query in &self.query {
+      for (name, schema) in query(generator) {
+        parameters.push(json!({
+          "name": name,
+          "in": "query",
+          "required": !is_nullable(&schema),
+          "schema": schema,
+        }));
+      }
+    }
+
+    let mut operation = json!({
+      "summary": self.summary,
+      "parameters": parameters,
+      "responses": {
+        "200": {
+          "description": "Success",
+          "content": {
+            "application/json": {
+              "schema": (self.response)(generator),
+            },
+          },
+        },
+      },
+    });
+
+    // errors from the versioned API are JSON objects
+    operation["responses"]["default"] = if self.path.starts_with("/api/") {
+      json!({
+        "description": "Error",
+        "content": {
+          "application/json": {
+            "schema": api::v1::Error::schema(generator),
+          },
+        },
+      })
+    } else {
+      json!({
+        "description": "Error",
+        "content": {
+          "text/plain": {
+            "schema": { "type": "string" },
+          },
+        },
+      })
+    };
+
+    if self.accept_json {
+      operation["description"] =
+        "Returns HTML unless the `Accept: application/json` header is set.".into();
+    }
+
+    if let Some(request) = self.request {
+      operation["requestBody"] = json!({
+        "required": true,
+        "content": {
+          "application/json": {
+            "schema": request(generator),
+          },
+        },
+      });
+    }
+
+    operation
+  }
+}
+
+/// Every route which returns JSON
+pub(crate) fn routes() -> Vec<Route> {
+  vec![
+    Route::get::<AddressResponse>(
+      "/address/{address}",
+      "Outputs, inscriptions, and rune balances of an address",
+    )
+    .accept_json()
+    .query::<AddressQuery>(),
+    Route::get::<api::v1::Address>(
+      "/api/v1/addresses/{address}",
+      "Outputs, inscriptions, and rune balances of an address",
+    ),
+    Route::get::<api::v1::Block>("/api/v1/blocks/{query}", "Block by height or hash"),
+    Route::get::<api::v1::Page<api::v1::Inscription>>(
+      "/api/v1/inscriptions",
+      "Inscriptions, newest first by default",
+    )
+    .query::<v1::PageQuery>(),
+    Route::get::<api::v1::Inscription>(
+      "/api/v1/inscriptions/{query}",
+      "Inscription by ID, number, or sat name",
+    ),
+    Route::get::<api::v1::Page<api::v1::Inscription>>(
+      "/api/v1/inscriptions/{query}/children",
+      "Children of an inscription, oldest first by default",
+    )
+    .query::<v1::PageQuery>(),
+    Route::get::<api::v1::Output>("/api/v1/outputs/{outpoint}", "Output by outpoint"),
+    Route::get::<api::v1::Page<api::v1::Rune>>("/api/v1/runes", "Runes, newest first by default")
+      .query::<v1::PageQuery>(),
+    Route::get::<api::v1::Rune>("/api/v1/runes/{query}", "Rune by name, ID, or number"),
+    Route::get::<api::v1::Sat>(
+      "/api/v1/sats/{sat}",
+      "Sat by number, name, degree, or decimal",
+    ),
+    Route::get::<api::v1::Status>("/api/v1/status", "Server and index status"),
+    Route::get::<api::Block>("/block/{query}", "Block by height or hash").accept_json(),
+    Route::get::<api::Blocks>("/blocks", "Latest blocks").accept_json(),
+    Route::get::<api::Decode>(
+      "/decode/{txid}",
+      "Inscriptions and runestone decoded from a transaction",
+    )
+    .accept_json(),
+    Route::get::<api::Events>(
+      "/events/{sequence}",
+      "Index events starting at a sequence number or block height",
+    )
+    .query::<EventLogQuery>(),
+    Route::get::<api::Health>("/healthz", "Whether the server is up"),
+    Route::get::<api::Inscription>(
+      "/inscription/{inscription_query}",
+      "Inscription by ID, number, or sat name",
+    )
+    .accept_json(),
+    Route::get::<api::Delegators>(
+      "/inscription/{inscription_query}/delegators",
+      "Inscriptions delegating to an inscription",
+    )
+    .accept_json(),
+    Route::get::<api::Delegators>(
+      "/inscription/{inscription_query}/delegators/{page}",
+      "Page of inscriptions delegating to an inscription",
+    )
+    .accept_json(),
+    Route::get::<api::InscriptionHistory>(
+      "/inscription/{inscription_query}/history",
+      "Locations an inscription has been transferred to",
+    )
+    .accept_json(),
+    Route::get::<api::Inscription>(
+      "/inscription/{inscription_query}/{child}",
+      "Child of an inscription by index",
+    )
+    .accept_json(),
+    Route::get::<api::Inscriptions>("/inscriptions", "Inscriptions, newest first by default")
+      .accept_json()
+      .query::<InscriptionFilter>()
+      .query::<listing::ListingQuery>(),
+    Route::post::<Vec<InscriptionId>, Vec<api::Inscription>>("/inscriptions", "Inscriptions by ID")
+      .accept_json(),
+    Route::get::<api::Inscriptions>("/inscriptions/block/{height}", "Inscriptions in a block")
+      .accept_json(),
+    Route::get::<api::Inscriptions>(
+      "/inscriptions/block/{height}/{page}",
+      "Page of inscriptions in a block",
+    )
+    .accept_json(),
+    Route::get::<api::Inscriptions>(
+      "/inscriptions/hash/{hash}",
+      "Inscriptions with content of a SHA-256 hash",
+    )
+    .accept_json(),
+    Route::get::<api::Inscriptions>(
+      "/inscriptions/hash/{hash}/{page}",
+      "Page of inscriptions with content of a SHA-256 hash",
+    )
+    .accept_json(),
+    Route::get::<api::Inscriptions>(
+      "/inscriptions/metadata",
+      "Inscriptions with a metadata key, and optionally value",
+    )
+    .accept_json()
+    .query::<MetadataQuery>(),
+    Route::get::<api::Inscriptions>(
+      "/inscriptions/metadata/{page}",
+      "Page of inscriptions with a metadata key, and optionally value",
+    )
+    .accept_json()
+    .query::<MetadataQuery>(),
+    Route::get::<api::Inscriptions>("/inscriptions/{page}", "Page of inscriptions")
+      .accept_json()
+      .query::<InscriptionFilter>(),
+    Route::get::<api::Mempool>("/mempool", "Transactions in the mempool").accept_json(),
+    Route::get::<OpenApi>("/openapi.json", "This OpenAPI description"),
+    Route::get::<api::Output>("/output/{output}", "Output by outpoint").accept_json(),
+    Route::post::<Vec<OutPoint>, Vec<api::Output>>("/outputs", "Outputs by outpoint").accept_json(),
+    Route::get::<Vec<api::Output>>("/outputs/{address}", "Outputs of an address")
+      .accept_json()
+      .query::<OutputsQuery>(),
+    Route::get::<api::Readiness>("/readyz", "Whether the server is ready to serve requests"),
+    Route::get::<String>("/r/blockhash", "Latest block hash"),
+    Route::get::<String>("/r/blockhash/{height}", "Block hash at a height"),
+    Route::get::<api::BlockInfo>("/r/blockinfo/{query}", "Block info by height or hash"),
+    Route::get::<api::Children>(
+      "/r/children/{inscription_id}",
+      "Children of an inscription, oldest first by default",
+    )
+    .query::<listing::ListingQuery>(),
+    Route::get::<api::Children>(
+      "/r/children/{inscription_id}/{page}",
+      "Page of children of an inscription",
+    ),
+    Route::get::<api::ChildInscriptions>(
+      "/r/children/{inscription_id}/inscriptions",
+      "Details of children of an inscription",
+    ),
+    Route::get::<api::ChildInscriptions>(
+      "/r/children/{inscription_id}/inscriptions/{page}",
+      "Page of details of children of an inscription",
+    ),
+    Route::get::<api::Inscriptions>(
+      "/r/content-hash/{hash}",
+      "Inscriptions with content of a SHA-256 hash",
+    ),
+    Route::get::<api::Inscriptions>(
+      "/r/content-hash/{hash}/{page}",
+      "Page of inscriptions with content of a SHA-256 hash",
+    ),
+    Route::get::<api::Delegators>(
+      "/r/delegates/{inscription_id}",
+      "Inscriptions delegating to an inscription",
+    ),
+    Route::get::<api::Delegators>(
+      "/r/delegates/{inscription_id}/{page}",
+      "Page of inscriptions delegating to an inscription",
+    ),
+    Route::get::<api::InscriptionRecursive>("/r/inscription/{inscription_id}", "Inscription by ID"),
+    Route::get::<api::InscriptionHistory>(
+      "/r/inscription/{inscription_id}/history",
+      "Locations an inscription has been transferred to",
+    ),
+    Route::get::<api::Mempool>("/r/mempool", "Transactions in the mempool"),
+    Route::post::<Vec<InscriptionId>, Vec<Option<api::InscriptionMetadata>>>(
+      "/r/metadata",
+      "Metadata of inscriptions by ID",
+    ),
+    Route::get::<String>(
+      "/r/metadata/{inscription_id}",
+      "Hex-encoded CBOR metadata of an inscription",
+    ),
+    Route::get::<api::Inscriptions>("/r/parents/{inscription_id}", "Parents of an inscription"),
+    Route::get::<api::Inscriptions>(
+      "/r/parents/{inscription_id}/{page}",
+      "Page of parents of an inscription",
+    ),
+    Route::get::<api::ParentInscriptions>(
+      "/r/parents/{inscription_id}/inscriptions",
+      "Details of parents of an inscription",
+    ),
+    Route::get::<api::ParentInscriptions>(
+      "/r/parents/{inscription_id}/inscriptions/{page}",
+      "Page of details of parents of an inscription",
+    ),
+    Route::get::<api::SatInscriptions>("/r/sat/{sat_number}", "Inscriptions on a sat"),
+    Route::get::<api::SatInscriptions>(
+      "/r/sat/{sat_number}/{page}",
+      "Page of inscriptions on a sat",
+    ),
+    Route::get::<api::SatInscription>(
+      "/r/sat/{sat_number}/at/{index}",
+      "Inscription on a sat by index, negative indices count from the end",
+    ),
+    Route::get::<String>("/r/tx/{txid}", "Hex-encoded transaction"),
+    Route::get::<api::UtxoRecursive>("/r/utxo/{outpoint}", "Assets held by an output"),
+    Route::get::<api::Rune>("/rune/{rune}", "Rune by name, ID, or number").accept_json(),
+    Route::get::<api::RuneHolders>("/rune/{rune}/holders", "Holders of a rune, largest first")
+      .accept_json()
+      .query::<listing::ListingQuery>(),
+    Route::get::<api::Runes>("/runes", "Runes, newest first by default")
+      .accept_json()
+      .query::<listing::ListingQuery>(),
+    Route::get::<api::Runes>("/runes/{page}", "Page of runes").accept_json(),
+    Route::get::<api::Sat>("/sat/{sat}", "Sat by number, name, degree, or decimal").accept_json(),
+    Route::get::<api::Status>("/status", "Server and index status").accept_json(),
+    Route::get::<api::Transaction>("/tx/{txid}", "Transaction by ID").accept_json(),
+  ]
+}
+
+/// Generate the OpenAPI description of the JSON API
+pub(crate) fn spec() -> Value {
+  let mut generator = Generator::default();
+
+  let mut paths = Map::new();
+
+  for route in routes() {
+    let operation = route.operation(&mut generator);
+
+    paths
+      .entry(route.path)
+      .or_insert_with(|| json!({}))
+      .as_object_mut()
+      .unwrap()
+      .insert(route.method.name().into(), operation);
+  }
+
+  json!({
+    "openapi": "3.1.0",
+    "info": {
+      "title": "ord",
+      "version": env!("CARGO_PKG_VERSION"),
+      "description": "JSON API of the ord block explorer",
+    },
+    "paths": paths,
+    "components": {
+      "schemas": generator.schemas,
+    },
+  })
+}
+
+/// Check `value` against `schema`, resolving references in `spec`. Only the
+/// subset of JSON Schema generated above is supported.
+#[cfg(test)]
+pub(crate) fn validate(spec: &Value, schema: &Value, value: &Value, path: &str) -> Result {
+  if let Some(reference) = schema["$ref"].as_str() {
+    let name = reference
+      .strip_prefix("#/components/schemas/")
+      .ok_or_else(|| anyhow!("{path}: bad reference `{reference}`"))?;
+
+    let schema = &spec["components"]["schemas"][name];
+
+    ensure!(!schema.is_null(), "{path}: missing component `{name}`");
+
+    return validate(spec, schema, value, path);
+  }
+
+  if let Some(variants) = schema["anyOf"].as_array() {
+    ensure!(
+      variants
+        .iter()
+        .any(|variant| validate(spec, variant, value, path).is_ok()),
+      "{path}: {value} matches no variant of {schema}"
+    );
+    return Ok(());
+  }
+
+  if let Some(variants) = schema["oneOf"].as_array() {
+    let matches = variants
+      .iter()
+      .filter(|variant| validate(spec, variant, value, path).is_ok())
+      .count();
+    ensure!(
+      matches == 1,
+      "{path}: {value} matches {matches} variants of {schema}"
+    );
+    return Ok(());
+  }
+
+  if let Some(variants) = schema["enum"].as_array() {
+    ensure!(
+      variants.contains(value),
+      "{path}: {value} is not one of {variants:?}"
+    );
+  }
+
+  match (schema["type"].as_str(), value) {
+    (None, _)
+    | (Some("null"), Value::Null)
+    | (Some("boolean"), Value::Bool(_))
+    | (Some("number"), Value::Number(_))
+    | (Some("string"), Value::String(_)) => {}
+    (Some("integer"), Value::Number(number)) => {
+      ensure!(
+        number.is_i64() || number.is_u64() || number.as_f64().unwrap().fract() == 0.0,
+        "{path}: {number} is not an integer"
+      );
+      if schema["minimum"] == 0 {
+        ensure!(
+          !number.is_i64() || number.as_i64().unwrap() >= 0,
+          "{path}: {number} is negative"
+        );
+      }
+    }
+    (Some("array"), Value::Array(items)) => {
+      if let Some(min) = schema["minItems"].as_u64() {
+        ensure!(items.len() as u64 >= min, "{path}: fewer than {min} items");
+      }
+      if let Some(max) = schema["maxItems"].as_u64() {
+        ensure!(items.len() as u64 <= max, "{path}: more than {max} items");
+      }
+      for (i, item) in items.iter().enumerate() {
+        let item_schema = schema["prefixItems"]
+          .as_array()
+          .map(|prefix| &prefix[i])
+          .unwrap_or(&schema["items"]);
+        validate(spec, item_schema, item, &format!("{path}[{i}]"))?;
+      }
+    }
+    (Some("object"), Value::Object(object)) => {
+      if let Some(required) = schema["required"].as_array() {
+        for name in required {
+          let name = name.as_str().unwrap();
+          ensure!(
+            object.contains_key(name),
+            "{path}: missing property `{name}`"
+          );
+        }
+      }
+      for (name, value) in object {
+        let path = format!("{path}.{name}");
+        match &schema["properties"][name] {
+          Value::Null => match &schema["additionalProperties"] {
+            Value::Bool(false) => bail!("{path}: undocumented property"),
+            Value::Null | Value::Bool(true) => {}
+            additional => validate(spec, additional, value, &path)?,
+          },
+          property => validate(spec, property, value, &path)?,
+        }
+      }
+    }
+    (Some(ty), value) => bail!("{path}: {value} is not of type {ty}"),
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, regex::Regex};
+
+  /// Routes which return HTML, text, or binary content, or which have their
+  /// own schema, and so are not described
+  const UNDESCRIBED: &[&str] = &[
+    "get /",
+    "get /api/v1/{*path}",
+    "get /blockcount",
+    "get /blockhash",
+    "get /blockhash/{height}",
+    "get /blockheight",
+    "get /blocktime",
+    "get /bounties",
+    "get /children/{inscription_id}",
+    "get /children/{inscription_id}/{page}",
+    "get /clock",
+    "get /collections",
+    "get /collections/{page}",
+    "get /content/{inscription_id}",
+    "get /events",
+    "get /faq",
+    "get /favicon.ico",
+    "get /feed.xml",
+    "get /graphql",
+    "get /input/{block}/{transaction}/{input}",
+    "get /install.sh",
+    "get /metrics",
+    "get /ordinal/{sat}",
+    "get /parents/{inscription_id}",
+    "get /parents/{inscription_id}/{page}",
+    "get /preview/{inscription_id}",
+    "get /r/blockheight",
+    "get /r/blocktime",
+    "get /r/sat/{sat_number}/at/{index}/content",
+    "get /r/undelegated-content/{inscription_id}",
+    "get /rare.txt",
+    "get /satpoint/{satpoint}",
+    "get /satscard",
+    "get /search",
+    "get /search/{*query}",
+    "get /static/{*path}",
+    "get /update",
+    "post /snapshot",
+  ];
+
+  #[test]
+  fn routes_match_router() {
+    let source = include_str!("../server.rs");
+
+    let routed = Regex::new(r#"\.route\(\s*"([^"]*)",\s*(get|post)\("#)
+      .unwrap()
+      .captures_iter(source)
+      .map(|captures| format!("{} {}", &captures[2], &captures[1]))
+      .collect::<BTreeSet<String>>();
+
+    let described = routes()
+      .iter()
+      .map(|route| format!("{} {}", route.method.name(), route.path))
+      .collect::<BTreeSet<String>>();
+
+    let undescribed = UNDESCRIBED
+      .iter()
+      .map(|route| route.to_string())
+      .collect::<BTreeSet<String>>();
+
+    assert!(
+      described.is_disjoint(&undescribed),
+      "routes both described and undescribed: {:?}",
+      described
+        .intersection(&undescribed)
+        .collect::<Vec<&String>>(),
+    );
+
+    let documented = described
+      .union(&undescribed)
+      .cloned()
+      .collect::<BTreeSet<String>>();
+
+    assert_eq!(
+      routed.difference(&documented).collect::<Vec<&String>>(),
+      Vec::<&String>::new(),
+      "routes missing from OpenAPI description or UNDESCRIBED",
+    );
+
+    assert_eq!(
+      documented.difference(&routed).collect::<Vec<&String>>(),
+      Vec::<&String>::new(),
+      "routes in OpenAPI description or UNDESCRIBED which do not exist",
+    );
+  }
+
+  #[test]
+  fn references_resolve() {
+    fn check(spec: &Value, value: &Value) {
+      match value {
+        Value::Object(object) => {
+          if let Some(Value::String(reference)) = object.get("$ref") {
+            let name = reference.strip_prefix("#/components/schemas/").unwrap();
+            assert!(
+              spec["components"]["schemas"][name].is_object(),
+              "unresolved reference {reference}"
+            );
+          }
+          for value in object.values() {
+            check(spec, value);
+          }
+        }
+        Value::Array(array) => {
+          for value in array {
+            check(spec, value);
+          }
+        }
+        _ => {}
+      }
+    }
+
+    let spec = spec();
+
+    check(&spec, &spec);
+  }
+
+  #[test]
+  fn parameters() {
+    let spec = spec();
+
+    assert_eq!(
+      spec["paths"]["/r/sat/{sat_number}/at/{index}"]["get"]["parameters"],
+      json!([
+        { "name": "sat_number", "in": "path", "required": true, "schema": { "type": "string" } },
+        { "name": "index", "in": "path", "required": true, "schema": { "type": "string" } },
+      ]),
+    );
+
+    assert_eq!(
+      spec["paths"]["/outputs/{address}"]["get"]["parameters"][1],
+      json!({
+        "name": "type",
+        "in": "query",
+        "required": false,
+        "schema": {
+          "anyOf": [
+            { "type": "string", "enum": ["any", "cardinal", "inscribed", "runic"] },
+            { "type": "null" },
+          ],
+        },
+      }),
+    );
+
+    assert_eq!(
+      spec["paths"]["/inscriptions/metadata"]["get"]["parameters"][0]["required"],
+      true,
+    );
+  }
+
+  #[test]
+  fn output_types_are_deserializable() {
+    for ty in OutputType::schema(&mut Generator::default())["enum"]
+      .as_array()
+      .unwrap()
+    {
+      serde_json::from_value::<OutputType>(ty.clone()).unwrap();
+    }
+  }
+
+  #[test]
+  fn validate_rejects_mismatches() {
+    let spec = spec();
+
+    let schema = api::Health::schema(&mut Generator::default());
+
+    validate(
+      &spec,
+      &schema,
+      &json!({ "healthy": true, "height": 1, "reason": null }),
+      "",
+    )
+    .unwrap();
+
+    assert_eq!(
+      validate(&spec, &schema, &json!({ "healthy": true, "height": 1 }), "")
+        .unwrap_err()
+        .to_string(),
+      ": missing property `reason`",
+    );
+
+    assert_eq!(
+      validate(
+        &spec,
+        &schema,
+        &json!({ "healthy": true, "height": 1, "reason": null, "foo": 1 }),
+        "",
+      )
+      .unwrap_err()
+      .to_string(),
+      ".foo: undocumented property",
+    );
+
+    assert_eq!(
+      validate(
+        &spec,
+        &schema,
+        &json!({ "healthy": "yes", "height": 1, "reason": null }),
+        "",
+      )
+      .unwrap_err()
+      .to_string(),
+      ".healthy: \"yes\" is not of type boolean",
+    );
+  }
+}
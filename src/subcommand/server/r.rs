@@ -148,9 +148,33 @@ pub(super) async fn blocktime_string(
 
 pub(super) async fn children(
   Extension(index): Extension<Arc<Index>>,
-  Path(inscription_id): Path<InscriptionId>,
+  Path(parent): Path<InscriptionId>,
+  Query(listing): Query<ListingQuery>,
 ) -> ServerResult {
-  children_paginated(Extension(index), Path((inscription_id, 0))).await
+  task::block_in_place(|| {
+    let parent_sequence_number = index
+      .get_inscription_entry(parent)?
+      .ok_or_not_found(|| format!("inscription {parent}"))?
+      .sequence_number;
+
+    let children = index.get_children_by_sequence_number_from(
+      parent_sequence_number,
+      listing.sort(Sort::Oldest, "children")?,
+      listing.cursor()?,
+      listing.page_size(100)?,
+    )?;
+
+    Ok(
+      Json(api::Children {
+        ids: children.items,
+        more: children.next.is_some(),
+        next_cursor: children.next.map(|child| child.encode()),
+        page: 0,
+        prev_cursor: children.prev.map(|child| child.encode()),
+      })
+      .into_response(),
+    )
+  })
 }
 
 pub(super) async fn children_inscriptions(
@@ -206,7 +230,16 @@ pub(super) async fn children_paginated(
     let (ids, more) =
       index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
 
-    Ok(Json(api::Children { ids, more, page }).into_response())
+    Ok(
+      Json(api::Children {
+        ids,
+        more,
+        next_cursor: None,
+        page,
+        prev_cursor: None,
+      })
+      .into_response(),
+    )
   })
 }
 
@@ -216,6 +249,7 @@ pub(super) async fn content(
   Extension(server_config): Extension<Arc<ServerConfig>>,
   Path(inscription_id): Path<InscriptionId>,
   accept_encoding: AcceptEncoding,
+  conditional_request: ConditionalRequest,
 ) -> ServerResult {
   task::block_in_place(|| {
     if settings.is_hidden(inscription_id) {
@@ -228,20 +262,95 @@ pub(super) async fn content(
       )));
     };
 
+    let mut content_id = inscription_id;
+
     if let Some(delegate) = inscription.delegate() {
       inscription = index
         .get_inscription_by_id(delegate)?
-        .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+        .ok_or_not_found(|| format!("delegate {inscription_id}"))?;
+      content_id = delegate;
     }
 
-    Ok(
-      content_response(inscription, accept_encoding, &server_config)?
-        .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-        .into_response(),
-    )
+    immutable_content_response(
+      content_id,
+      inscription,
+      accept_encoding,
+      &server_config,
+      &conditional_request,
+    )?
+    .ok_or_not_found(|| format!("inscription {inscription_id} content"))
   })
 }
 
+pub(super) async fn content_hash(
+  Extension(index): Extension<Arc<Index>>,
+  Path(hash): Path<DeserializeFromStr<sha256::Hash>>,
+) -> ServerResult<Json<api::Inscriptions>> {
+  content_hash_paginated(Extension(index), Path((hash, 0))).await
+}
+
+pub(super) async fn content_hash_paginated(
+  Extension(index): Extension<Arc<Index>>,
+  Path((DeserializeFromStr(hash), page_index)): Path<(DeserializeFromStr<sha256::Hash>, u32)>,
+) -> ServerResult<Json<api::Inscriptions>> {
+  task::block_in_place(|| {
+    if !index.has_content_hash_index() {
+      return Err(ServerError::NotFound(
+        "this server has no content hash index".into(),
+      ));
+    }
+
+    let (ids, more) = index.get_inscriptions_by_content_hash_paginated(
+      hash.to_byte_array(),
+      100,
+      page_index.try_into().unwrap(),
+    )?;
+
+    Ok(Json(api::Inscriptions {
+      ids,
+      more,
+      next_cursor: None,
+      page_index,
+      prev_cursor: None,
+    }))
+  })
+}
+
+/// Respond with the content of inscription `content_id`, honoring conditional
+/// and range requests. Content never changes, so the strong ETag is derived
+/// from the inscription ID, and from whether the content was decompressed,
+/// since decompressed content is a different representation. Range responses
+/// carry `Content-Range`, which response compression skips, so byte ranges
+/// always refer to the uncompressed bytes.
+fn immutable_content_response(
+  content_id: InscriptionId,
+  inscription: Inscription,
+  accept_encoding: AcceptEncoding,
+  server_config: &ServerConfig,
+  conditional_request: &ConditionalRequest,
+) -> ServerResult<Option<Response>> {
+  let encoded = inscription.content_encoding().is_some();
+
+  let Some((mut headers, body)) = content_response(inscription, accept_encoding, server_config)?
+  else {
+    return Ok(None);
+  };
+
+  let etag = if encoded {
+    headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+
+    if headers.contains_key(header::CONTENT_ENCODING) {
+      format!("\"{content_id}\"")
+    } else {
+      format!("\"{content_id}-decompressed\"")
+    }
+  } else {
+    format!("\"{content_id}\"")
+  };
+
+  Ok(Some(conditional_request.respond(&etag, headers, body)))
+}
+
 pub(super) fn content_response(
   inscription: Inscription,
   accept_encoding: AcceptEncoding,
@@ -312,6 +421,30 @@ pub(super) fn content_response(
   Ok(Some((headers, body)))
 }
 
+pub(super) async fn delegates(
+  Extension(index): Extension<Arc<Index>>,
+  Path(inscription_id): Path<InscriptionId>,
+) -> ServerResult<Json<api::Delegators>> {
+  delegates_paginated(Extension(index), Path((inscription_id, 0))).await
+}
+
+pub(super) async fn delegates_paginated(
+  Extension(index): Extension<Arc<Index>>,
+  Path((delegate, page)): Path<(InscriptionId, usize)>,
+) -> ServerResult<Json<api::Delegators>> {
+  task::block_in_place(|| {
+    if !index.has_delegate_index() {
+      return Err(ServerError::NotFound(
+        "this server has no delegate index".into(),
+      ));
+    }
+
+    let (ids, more) = index.get_delegators_paginated(delegate, 100, page)?;
+
+    Ok(Json(api::Delegators { ids, more, page }))
+  })
+}
+
 pub(super) async fn inscription(
   Extension(index): Extension<Arc<Index>>,
   Extension(server_config): Extension<Arc<ServerConfig>>,
@@ -380,6 +513,37 @@ pub(super) async fn inscription(
   })
 }
 
+pub(super) async fn inscription_history(
+  Extension(index): Extension<Arc<Index>>,
+  Path(inscription_id): Path<InscriptionId>,
+) -> ServerResult {
+  task::block_in_place(|| {
+    if !index.has_inscription_history_index() {
+      return Err(ServerError::NotFound(
+        "this server has no inscription history index".to_string(),
+      ));
+    }
+
+    let locations = index
+      .get_inscription_history(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    Ok(
+      Json(api::InscriptionHistory {
+        id: inscription_id,
+        locations,
+      })
+      .into_response(),
+    )
+  })
+}
+
+pub(super) async fn mempool(
+  Extension(index): Extension<Arc<Index>>,
+) -> ServerResult<Json<api::Mempool>> {
+  task::block_in_place(|| Ok(Json(index.mempool())))
+}
+
 pub(super) async fn metadata(
   Extension(index): Extension<Arc<Index>>,
   Path(inscription_id): Path<InscriptionId>,
@@ -400,6 +564,37 @@ pub(super) async fn metadata(
   })
 }
 
+const MAX_METADATA_BATCH_SIZE: usize = 100;
+
+pub(super) async fn metadata_batch(
+  Extension(index): Extension<Arc<Index>>,
+  Json(inscription_ids): Json<Vec<InscriptionId>>,
+) -> ServerResult<Json<Vec<Option<api::InscriptionMetadata>>>> {
+  task::block_in_place(|| {
+    if inscription_ids.len() > MAX_METADATA_BATCH_SIZE {
+      return Err(ServerError::BadRequest(format!(
+        "batch of {} inscriptions exceeds maximum of {MAX_METADATA_BATCH_SIZE}",
+        inscription_ids.len(),
+      )));
+    }
+
+    let mut response = Vec::new();
+
+    for id in inscription_ids {
+      response.push(
+        index
+          .get_inscription_by_id(id)?
+          .map(|inscription| api::InscriptionMetadata {
+            id,
+            metadata: inscription.metadata_json(),
+          }),
+      );
+    }
+
+    Ok(Json(response))
+  })
+}
+
 pub(super) async fn parents(
   Extension(index): Extension<Arc<Index>>,
   Path(inscription_id): Path<InscriptionId>,
@@ -460,6 +655,8 @@ pub(super) async fn parents_paginated(
         ids,
         more,
         page_index,
+        next_cursor: None,
+        prev_cursor: None,
       })
       .into_response(),
     )
@@ -511,6 +708,7 @@ pub(super) async fn sat_at_index_content(
   server_config: Extension<Arc<ServerConfig>>,
   Path((DeserializeFromStr(sat), inscription_index)): Path<(DeserializeFromStr<Sat>, isize)>,
   accept_encoding: AcceptEncoding,
+  conditional_request: ConditionalRequest,
 ) -> ServerResult {
   let inscription_id = task::block_in_place(|| {
     if !index.has_sat_index() {
@@ -528,6 +726,7 @@ pub(super) async fn sat_at_index_content(
     server_config,
     Path(inscription_id),
     accept_encoding,
+    conditional_request,
   )
   .await
 }
@@ -576,6 +775,7 @@ pub(super) async fn undelegated_content(
   Extension(server_config): Extension<Arc<ServerConfig>>,
   Path(inscription_id): Path<InscriptionId>,
   accept_encoding: AcceptEncoding,
+  conditional_request: ConditionalRequest,
 ) -> ServerResult {
   task::block_in_place(|| {
     if settings.is_hidden(inscription_id) {
@@ -586,10 +786,13 @@ pub(super) async fn undelegated_content(
       .get_inscription_by_id(inscription_id)?
       .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
-    Ok(
-      r::content_response(inscription, accept_encoding, &server_config)?
-        .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-        .into_response(),
-    )
+    immutable_content_response(
+      inscription_id,
+      inscription,
+      accept_encoding,
+      &server_config,
+      &conditional_request,
+    )?
+    .ok_or_not_found(|| format!("inscription {inscription_id} content"))
   })
 }
@@ -6,8 +6,11 @@ pub struct ServerConfig {
   pub csp_origin: Option<String>,
   pub decompress: bool,
   pub domain: Option<String>,
+  pub graphql_max_complexity: u64,
+  pub graphql_max_depth: usize,
   pub index_sats: bool,
   pub json_api_enabled: bool,
+  pub max_index_lag: u64,
   pub proxy: Option<Url>,
 }
 
@@ -0,0 +1,387 @@
+use {
+  super::*,
+  axum::extract::rejection::QueryRejection,
+  listing::{CursorKey, ListingQuery},
+};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Errors returned by `/api/v1` endpoints, which are serialized as
+/// [`api::v1::Error`] objects instead of plain text
+#[derive(Debug)]
+pub(super) struct ApiError {
+  code: api::v1::ErrorCode,
+  message: String,
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+impl ApiError {
+  fn bad_request(message: impl Into<String>) -> Self {
+    Self {
+      code: api::v1::ErrorCode::BadRequest,
+      message: message.into(),
+    }
+  }
+
+  fn not_found(message: impl Into<String>) -> Self {
+    Self {
+      code: api::v1::ErrorCode::NotFound,
+      message: message.into(),
+    }
+  }
+}
+
+impl From<ServerError> for ApiError {
+  fn from(error: ServerError) -> Self {
+    match error {
+      ServerError::BadRequest(message) => Self::bad_request(message),
+      ServerError::Internal(error) => {
+        eprintln!("error serving request: {error}");
+        Self {
+          code: api::v1::ErrorCode::Internal,
+          message: StatusCode::INTERNAL_SERVER_ERROR
+            .canonical_reason()
+            .unwrap_or_default()
+            .into(),
+        }
+      }
+      ServerError::NotAcceptable {
+        content_encoding, ..
+      } => Self {
+        code: api::v1::ErrorCode::NotAcceptable,
+        message: format!(
+          "content encoding `{}` is not acceptable",
+          String::from_utf8_lossy(content_encoding.as_bytes())
+        ),
+      },
+      ServerError::NotFound(message) => Self::not_found(message),
+    }
+  }
+}
+
+impl From<Error> for ApiError {
+  fn from(error: Error) -> Self {
+    ServerError::from(error).into()
+  }
+}
+
+impl IntoResponse for ApiError {
+  fn into_response(self) -> Response {
+    let status = match self.code {
+      api::v1::ErrorCode::BadRequest => StatusCode::BAD_REQUEST,
+      api::v1::ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+      api::v1::ErrorCode::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
+      api::v1::ErrorCode::NotFound => StatusCode::NOT_FOUND,
+    };
+
+    (
+      status,
+      [(header::CACHE_CONTROL, HeaderValue::from_static("no-store"))],
+      Json(api::v1::Error {
+        code: self.code,
+        message: self.message,
+      }),
+    )
+      .into_response()
+  }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct PageQuery {
+  pub(super) cursor: Option<String>,
+  pub(super) limit: Option<usize>,
+  pub(super) sort: Option<Sort>,
+}
+
+impl PageQuery {
+  fn parse(query: Result<Query<Self>, QueryRejection>) -> Result<ListingQuery, ApiError> {
+    let Self {
+      cursor,
+      limit,
+      sort,
+    } = query
+      .map(|Query(query)| query)
+      .map_err(|rejection| ApiError::bad_request(rejection.body_text()))?;
+
+    Ok(ListingQuery {
+      cursor,
+      limit,
+      sort,
+    })
+  }
+}
+
+fn parse<T: FromStr>(kind: &str, s: &str) -> Result<T, ApiError>
+where
+  T::Err: Display,
+{
+  s.parse()
+    .map_err(|err| ApiError::bad_request(format!("invalid {kind} `{s}`: {err}")))
+}
+
+fn get_inscription(index: &Index, id: InscriptionId) -> Result<api::v1::Inscription, ApiError> {
+  Ok(
+    index
+      .inscription_info(query::Inscription::Id(id), None)?
+      .ok_or_not_found(|| format!("inscription {id}"))?
+      .0
+      .into(),
+  )
+}
+
+fn get_rune(index: &Index, rune: Rune) -> Result<api::v1::Rune, ApiError> {
+  if !index.has_rune_index() {
+    return Err(ApiError::not_found("this server has no rune index"));
+  }
+
+  let (id, entry, parent) = index
+    .rune(rune)?
+    .ok_or_not_found(|| format!("rune {rune}"))?;
+
+  let block_height = index.block_height()?.unwrap_or(Height(0));
+
+  Ok(
+    api::Rune {
+      holders: index.get_rune_holder_count(id)?,
+      id,
+      mintable: entry.mintable((block_height.n() + 1).into()).is_ok(),
+      parent,
+      entry,
+    }
+    .into(),
+  )
+}
+
+pub(super) async fn address(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+) -> ApiResult<api::v1::Address> {
+  task::block_in_place(|| {
+    let address = parse::<Address<NetworkUnchecked>>("address", &address)?
+      .require_network(server_config.chain.network())
+      .map_err(|err| ApiError::bad_request(err.to_string()))?;
+
+    let api::AddressInfo {
+      inscriptions,
+      outputs,
+      runes_balances,
+      sat_balance,
+    } = Server::address_info(&index, &address)?
+      .ok_or_else(|| ApiError::not_found("this server has no address index"))?;
+
+    Ok(Json(api::v1::Address {
+      address: address.to_string(),
+      inscriptions,
+      outputs,
+      runes: runes_balances.map(|balances| balances.into_iter().map(Into::into).collect()),
+      sat_balance,
+    }))
+  })
+}
+
+pub(super) async fn block(
+  Extension(index): Extension<Arc<Index>>,
+  Path(query): Path<String>,
+) -> ApiResult<api::v1::Block> {
+  task::block_in_place(|| {
+    let (block, height) = Server::block_from_query(&index, parse("block", &query)?)?;
+
+    Ok(Json(api::v1::Block {
+      hash: block.block_hash(),
+      height,
+      inscriptions: index.get_inscriptions_in_block(height)?,
+      previous_block: (height > 0).then_some(block.header.prev_blockhash),
+      runes: index.get_runes_in_block(height.into())?,
+      timestamp: block.header.time.into(),
+      transaction_count: block.txdata.len().try_into().unwrap(),
+    }))
+  })
+}
+
+pub(super) async fn inscription(
+  Extension(index): Extension<Arc<Index>>,
+  Path(query): Path<String>,
+) -> ApiResult<api::v1::Inscription> {
+  task::block_in_place(|| Ok(Json(inscription_from_query(&index, &query)?.into())))
+}
+
+fn inscription_from_query(index: &Index, query: &str) -> Result<api::Inscription, ApiError> {
+  let query = parse::<query::Inscription>("inscription query", query)?;
+
+  if let query::Inscription::Sat(_) = query {
+    if !index.has_sat_index() {
+      return Err(ApiError::not_found("sat index required"));
+    }
+  }
+
+  Ok(
+    index
+      .inscription_info(query, None)?
+      .ok_or_not_found(|| format!("inscription {query}"))?
+      .0,
+  )
+}
+
+pub(super) async fn inscription_children(
+  Extension(index): Extension<Arc<Index>>,
+  Path(query): Path<String>,
+  page_query: Result<Query<PageQuery>, QueryRejection>,
+) -> ApiResult<api::v1::Page<api::v1::Inscription>> {
+  task::block_in_place(|| {
+    let page_query = PageQuery::parse(page_query)?;
+
+    let id = inscription_from_query(&index, &query)?.id;
+
+    let entry = index
+      .get_inscription_entry(id)?
+      .ok_or_not_found(|| format!("inscription {id}"))?;
+
+    let children = index.get_children_by_sequence_number_from(
+      entry.sequence_number,
+      page_query.sort(Sort::Oldest, "children")?,
+      page_query.cursor()?,
+      page_query.page_size(DEFAULT_PAGE_SIZE)?,
+    )?;
+
+    Ok(Json(api::v1::Page {
+      items: children
+        .items
+        .into_iter()
+        .map(|child| get_inscription(&index, child))
+        .collect::<Result<Vec<api::v1::Inscription>, ApiError>>()?,
+      next: children
+        .next
+        .map(|sequence_number| sequence_number.encode()),
+    }))
+  })
+}
+
+pub(super) async fn inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  query: Result<Query<PageQuery>, QueryRejection>,
+) -> ApiResult<api::v1::Page<api::v1::Inscription>> {
+  task::block_in_place(|| {
+    let query = PageQuery::parse(query)?;
+
+    let inscriptions = index.get_inscriptions_from(
+      &InscriptionFilter::default(),
+      query.inscription_sort(&index)?,
+      query.cursor()?,
+      query.page_size(DEFAULT_PAGE_SIZE)?,
+    )?;
+
+    Ok(Json(api::v1::Page {
+      items: inscriptions
+        .items
+        .into_iter()
+        .map(|id| get_inscription(&index, id))
+        .collect::<Result<Vec<api::v1::Inscription>, ApiError>>()?,
+      next: inscriptions
+        .next
+        .map(|sequence_number| sequence_number.encode()),
+    }))
+  })
+}
+
+pub(super) async fn output(
+  Extension(index): Extension<Arc<Index>>,
+  Path(outpoint): Path<String>,
+) -> ApiResult<api::v1::Output> {
+  task::block_in_place(|| {
+    let outpoint = parse::<OutPoint>("outpoint", &outpoint)?;
+
+    Ok(Json(
+      index
+        .get_output_info(outpoint)?
+        .ok_or_not_found(|| format!("output {outpoint}"))?
+        .0
+        .into(),
+    ))
+  })
+}
+
+pub(super) async fn rune(
+  Extension(index): Extension<Arc<Index>>,
+  Path(query): Path<String>,
+) -> ApiResult<api::v1::Rune> {
+  task::block_in_place(|| {
+    let query = parse::<query::Rune>("rune", &query)?;
+
+    Ok(Json(get_rune(
+      &index,
+      Server::rune_from_query(&index, query)?,
+    )?))
+  })
+}
+
+pub(super) async fn runes(
+  Extension(index): Extension<Arc<Index>>,
+  query: Result<Query<PageQuery>, QueryRejection>,
+) -> ApiResult<api::v1::Page<api::v1::Rune>> {
+  task::block_in_place(|| {
+    let query = PageQuery::parse(query)?;
+
+    if !index.has_rune_index() {
+      return Err(ApiError::not_found("this server has no rune index"));
+    }
+
+    let runes = index.runes_from(
+      query.sort(Sort::Newest, "runes")?,
+      query.cursor()?,
+      query.page_size(DEFAULT_PAGE_SIZE)?,
+    )?;
+
+    Ok(Json(api::v1::Page {
+      items: runes
+        .items
+        .into_iter()
+        .map(|(_, entry)| get_rune(&index, entry.spaced_rune.rune))
+        .collect::<Result<Vec<api::v1::Rune>, ApiError>>()?,
+      next: runes.next.map(|id| id.encode()),
+    }))
+  })
+}
+
+pub(super) async fn sat(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path(sat): Path<String>,
+) -> ApiResult<api::v1::Sat> {
+  task::block_in_place(|| {
+    let sat = parse::<Sat>("sat", &sat)?;
+
+    let (inscriptions, satpoint, address) = Server::sat_location(&index, &server_config, sat)?;
+
+    Ok(Json(
+      api::Sat::new(
+        sat,
+        address,
+        index.block_time(sat.height())?,
+        inscriptions,
+        satpoint,
+      )
+      .into(),
+    ))
+  })
+}
+
+pub(super) async fn status(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+) -> ApiResult<api::v1::Status> {
+  task::block_in_place(|| Ok(Json(index.status(server_config.json_api_enabled)?.into())))
+}
+
+pub(super) async fn not_found(uri: Uri) -> ApiError {
+  ApiError::not_found(format!("{} not found", uri.path()))
+}
+
+pub(super) async fn disabled() -> ApiError {
+  ApiError {
+    code: api::v1::ErrorCode::NotAcceptable,
+    message: "JSON API disabled".into(),
+  }
+}
@@ -0,0 +1,80 @@
+use {
+  super::*,
+  std::sync::mpsc::{SyncSender, TrySendError},
+  zeromq::{Socket, SocketRecv, SubSocket},
+};
+
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait after a `rawtx` notification before updating the mempool,
+/// so that a burst of notifications causes a single update
+pub(super) const MEMPOOL_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Wake the index thread through `index` whenever Bitcoin Core publishes a
+/// `hashblock` notification to any of `urls`, and, if given, the mempool thread
+/// through `mempool` whenever it publishes a `rawtx` notification. Returns when
+/// the index thread exits.
+pub(super) async fn subscribe(
+  urls: Vec<String>,
+  index: SyncSender<()>,
+  mempool: Option<SyncSender<()>>,
+) {
+  loop {
+    match receive(&urls, &index, mempool.as_ref()).await {
+      Ok(()) => return,
+      Err(err) => log::warn!("ZMQ subscription failed, retrying in {RETRY_INTERVAL:?}: {err}"),
+    }
+
+    tokio::time::sleep(RETRY_INTERVAL).await;
+  }
+}
+
+async fn receive(
+  urls: &[String],
+  index: &SyncSender<()>,
+  mempool: Option<&SyncSender<()>>,
+) -> Result {
+  let mut socket = SubSocket::new();
+
+  for url in urls {
+    socket
+      .connect(url)
+      .await
+      .with_context(|| format!("failed to connect to ZMQ publisher at `{url}`"))?;
+  }
+
+  socket.subscribe("hashblock").await?;
+
+  if mempool.is_some() {
+    socket.subscribe("rawtx").await?;
+  }
+
+  loop {
+    let message = socket.recv().await?;
+
+    let topic = message
+      .get(0)
+      .map(|topic| topic.as_ref())
+      .unwrap_or_default();
+
+    log::debug!(
+      "received ZMQ `{}` notification",
+      String::from_utf8_lossy(topic)
+    );
+
+    let wake = match topic {
+      b"hashblock" => index,
+      b"rawtx" => match mempool {
+        Some(mempool) => mempool,
+        None => continue,
+      },
+      _ => continue,
+    };
+
+    // a full channel means that an update is already pending
+    match wake.try_send(()) {
+      Ok(()) | Err(TrySendError::Full(())) => {}
+      Err(TrySendError::Disconnected(())) => return Ok(()),
+    }
+  }
+}
@@ -3,15 +3,20 @@ use {super::*, boilerplate::Boilerplate};
 pub(crate) use {
   crate::subcommand::server::ServerConfig,
   address::AddressHtml,
+  address_rune_balances::AddressRuneBalancesHtml,
   block::BlockHtml,
   children::ChildrenHtml,
   clock::ClockSvg,
   collections::CollectionsHtml,
+  delegators::DelegatorsHtml,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
+  inscription_history::InscriptionHistoryHtml,
   inscriptions::InscriptionsHtml,
   inscriptions_block::InscriptionsBlockHtml,
+  inscriptions_hash::InscriptionsHashHtml,
+  inscriptions_metadata::InscriptionsMetadataHtml,
   metadata::MetadataHtml,
   output::OutputHtml,
   parents::ParentsHtml,
@@ -26,28 +31,36 @@ pub(crate) use {
 };
 
 pub use {
-  blocks::BlocksHtml, inscription::InscriptionHtml, rune::RuneHtml, runes::RunesHtml,
-  status::StatusHtml, transaction::TransactionHtml,
+  blocks::BlocksHtml, inscription::InscriptionHtml, mempool::MempoolHtml, rune::RuneHtml,
+  rune_holders::RuneHoldersHtml, runes::RunesHtml, status::StatusHtml,
+  transaction::TransactionHtml,
 };
 
 pub mod address;
+mod address_rune_balances;
 pub mod block;
 pub mod blocks;
 mod children;
 mod clock;
 pub mod collections;
+mod delegators;
 mod home;
 mod iframe;
 mod input;
 pub mod inscription;
+mod inscription_history;
 pub mod inscriptions;
 mod inscriptions_block;
+mod inscriptions_hash;
+mod inscriptions_metadata;
+pub mod mempool;
 mod metadata;
 pub mod output;
 mod parents;
 mod preview;
 mod rare;
 pub mod rune;
+pub mod rune_holders;
 pub mod rune_not_found;
 pub mod runes;
 pub mod sat;
@@ -0,0 +1,60 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressRuneBalancesHtml {
+  pub(crate) address: Address,
+  pub(crate) height: u32,
+  pub(crate) outputs: Vec<OutPoint>,
+  pub(crate) runes_balances: Vec<(SpacedRune, Decimal, Option<char>)>,
+}
+
+impl PageContent for AddressRuneBalancesHtml {
+  fn title(&self) -> String {
+    format!("Address {} at Height {}", self.address, self.height)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rune_balances() {
+    assert_regex_match!(
+      AddressRuneBalancesHtml {
+        address: Address::from_str(
+          "bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8"
+        )
+        .unwrap()
+        .require_network(Network::Bitcoin)
+        .unwrap(),
+        height: 840000,
+        outputs: vec![outpoint(1)],
+        runes_balances: vec![(
+          SpacedRune {
+            rune: Rune::from_str("TEEEEEEEEESTRUNE").unwrap(),
+            spacers: 0,
+          },
+          Decimal {
+            scale: 0,
+            value: 20000,
+          },
+          Some('R'),
+        )],
+      },
+      "<h1>Address bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8</h1>
+<h2>Rune Balances at Height <a href=/block/840000>840000</a></h2>
+<dl>
+  <dt>rune balances</dt>
+  <dd><a class=monospace href=/rune/TEEEEEEEEESTRUNE>TEEEEEEEEESTRUNE</a>: 20000R</dd>
+  <dt>outputs</dt>
+  <dd>
+    <ul>
+      <li><a class=collapse href=/output/1{64}:1>1{64}:1</a></li>
+    </ul>
+  </dd>
+</dl>
+"
+    );
+  }
+}
@@ -5,8 +5,26 @@ pub(crate) struct ChildrenHtml {
   pub(crate) parent: InscriptionId,
   pub(crate) parent_number: i32,
   pub(crate) children: Vec<InscriptionId>,
-  pub(crate) prev_page: Option<usize>,
-  pub(crate) next_page: Option<usize>,
+  pub(crate) limit: Option<usize>,
+  pub(crate) prev: Option<String>,
+  pub(crate) next: Option<String>,
+  pub(crate) sort: Option<Sort>,
+}
+
+impl ChildrenHtml {
+  fn query(&self, cursor: &str) -> String {
+    let mut parameters = vec![format!("cursor={cursor}")];
+
+    if let Some(limit) = self.limit {
+      parameters.push(format!("limit={limit}"));
+    }
+
+    if let Some(sort) = self.sort {
+      parameters.push(format!("sort={sort}"));
+    }
+
+    format!("?{}", parameters.join("&"))
+  }
 }
 
 impl PageContent for ChildrenHtml {
@@ -26,8 +44,10 @@ mod tests {
         parent: inscription_id(1),
         parent_number: 0,
         children: vec![inscription_id(2), inscription_id(3)],
-        prev_page: None,
-        next_page: None,
+        limit: None,
+        prev: None,
+        next: None,
+        sort: None,
       },
       "
         <h1><a href=/inscription/1{64}i1>Inscription 0</a> Children</h1>
@@ -51,8 +71,10 @@ mod tests {
         parent: inscription_id(1),
         parent_number: 0,
         children: vec![inscription_id(2), inscription_id(3)],
-        next_page: Some(3),
-        prev_page: Some(1),
+        limit: None,
+        prev: Some("AAAAAQ".into()),
+        next: Some("AAAAAw".into()),
+        sort: Some(Sort::Newest),
       },
       "
         <h1><a href=/inscription/1{64}i1>Inscription 0</a> Children</h1>
@@ -61,8 +83,8 @@ mod tests {
           <a href=/inscription/3{64}i3><iframe .* src=/preview/3{64}i3></iframe></a>
         </div>
         .*
-          <a class=prev href=/children/1{64}i1/1>prev</a>
-          <a class=next href=/children/1{64}i1/3>next</a>
+          <a class=prev href=/children/1{64}i1\\?cursor=AAAAAQ&amp;sort=newest>prev</a>
+          <a class=next href=/children/1{64}i1\\?cursor=AAAAAw&amp;sort=newest>next</a>
         .*
       "
       .unindent()
@@ -0,0 +1,89 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct DelegatorsHtml {
+  pub(crate) delegate: InscriptionId,
+  pub(crate) delegate_number: i32,
+  pub(crate) delegators: Vec<InscriptionId>,
+  pub(crate) prev_page: Option<usize>,
+  pub(crate) next_page: Option<usize>,
+}
+
+impl PageContent for DelegatorsHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Delegators", self.delegate_number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_prev_and_next() {
+    assert_regex_match!(
+      DelegatorsHtml {
+        delegate: inscription_id(1),
+        delegate_number: 0,
+        delegators: vec![inscription_id(2), inscription_id(3)],
+        prev_page: None,
+        next_page: None,
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Delegators</h1>
+        <div class=thumbnails>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+          <a href=/inscription/3{64}i3><iframe .* src=/preview/3{64}i3></iframe></a>
+        </div>
+        .*
+        prev
+        next
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      DelegatorsHtml {
+        delegate: inscription_id(1),
+        delegate_number: 0,
+        delegators: vec![inscription_id(2), inscription_id(3)],
+        prev_page: Some(0),
+        next_page: Some(2),
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Delegators</h1>
+        <div class=thumbnails>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+          <a href=/inscription/3{64}i3><iframe .* src=/preview/3{64}i3></iframe></a>
+        </div>
+        .*
+          <a class=prev href=/inscription/1{64}i1/delegators/0>prev</a>
+          <a class=next href=/inscription/1{64}i1/delegators/2>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn without_delegators() {
+    assert_regex_match!(
+      DelegatorsHtml {
+        delegate: inscription_id(1),
+        delegate_number: 0,
+        delegators: Vec::new(),
+        prev_page: None,
+        next_page: None,
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Delegators</h1>
+        <h3>No delegators</h3>
+      "
+      .unindent()
+    );
+  }
+}
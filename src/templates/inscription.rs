@@ -6,6 +6,7 @@ pub struct InscriptionHtml {
   pub charms: u16,
   pub child_count: u64,
   pub children: Vec<InscriptionId>,
+  pub delegator_count: Option<u64>,
   pub fee: u64,
   pub height: u32,
   pub inscription: Inscription,
@@ -14,6 +15,7 @@ pub struct InscriptionHtml {
   pub next: Option<InscriptionId>,
   pub output: Option<TxOut>,
   pub parents: Vec<InscriptionId>,
+  pub pending: Option<Txid>,
   pub previous: Option<InscriptionId>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<Sat>,
@@ -369,6 +371,30 @@ mod tests {
     );
   }
 
+  #[test]
+  fn with_delegators() {
+    assert_regex_match!(
+      InscriptionHtml {
+        delegator_count: Some(3),
+        fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        id: inscription_id(1),
+        number: 1,
+        satpoint: satpoint(1, 0),
+        ..default()
+      },
+      "
+        .*
+        <dl>
+          <dt>delegators</dt>
+          <dd><a href=/inscription/1{64}i1/delegators>3</a></dd>
+          <dt>id</dt>
+          .*
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn with_paginated_children() {
     assert_regex_match!(
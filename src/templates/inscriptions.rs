@@ -2,9 +2,50 @@ use super::*;
 
 #[derive(Boilerplate)]
 pub(crate) struct InscriptionsHtml {
+  pub(crate) filter: InscriptionFilter,
   pub(crate) inscriptions: Vec<InscriptionId>,
-  pub(crate) prev: Option<u32>,
-  pub(crate) next: Option<u32>,
+  pub(crate) limit: Option<usize>,
+  pub(crate) prev: Option<String>,
+  pub(crate) next: Option<String>,
+  pub(crate) sort: Option<Sort>,
+}
+
+impl InscriptionsHtml {
+  fn query(&self, cursor: &str) -> String {
+    let mut parameters = vec![format!("cursor={cursor}")];
+
+    if let Some(charm) = self.filter.charm {
+      parameters.push(format!("charm={charm}"));
+    }
+
+    if let Some(content_type) = &self.filter.content_type {
+      parameters.push(format!(
+        "content_type={}",
+        urlencoding::encode(content_type)
+      ));
+    }
+
+    if let Some(from_height) = self.filter.from_height {
+      parameters.push(format!("from_height={from_height}"));
+    }
+
+    if let Some(limit) = self.limit {
+      parameters.push(format!("limit={limit}"));
+    }
+
+    if let Some(metaprotocol) = &self.filter.metaprotocol {
+      parameters.push(format!(
+        "metaprotocol={}",
+        urlencoding::encode(metaprotocol)
+      ));
+    }
+
+    if let Some(sort) = self.sort {
+      parameters.push(format!("sort={sort}"));
+    }
+
+    format!("?{}", parameters.join("&"))
+  }
 }
 
 impl PageContent for InscriptionsHtml {
@@ -21,9 +62,12 @@ mod tests {
   fn without_prev_and_next() {
     assert_regex_match!(
       InscriptionsHtml {
+        filter: InscriptionFilter::default(),
         inscriptions: vec![inscription_id(1), inscription_id(2)],
+        limit: None,
         prev: None,
         next: None,
+        sort: None,
       },
       "
         <h1>All Inscriptions</h1>
@@ -44,9 +88,12 @@ mod tests {
   fn with_prev_and_next() {
     assert_regex_match!(
       InscriptionsHtml {
+        filter: InscriptionFilter::default(),
         inscriptions: vec![inscription_id(1), inscription_id(2)],
-        prev: Some(1),
-        next: Some(2),
+        limit: None,
+        prev: Some("AAAAAQ".into()),
+        next: Some("AAAAAg".into()),
+        sort: None,
       },
       "
         <h1>All Inscriptions</h1>
@@ -55,11 +102,51 @@ mod tests {
           <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
         </div>
         .*
-        <a class=prev href=/inscriptions/1>prev</a>
-        <a class=next href=/inscriptions/2>next</a>
+        <a class=prev href=/inscriptions\\?cursor=AAAAAQ>prev</a>
+        <a class=next href=/inscriptions\\?cursor=AAAAAg>next</a>
         .*
       "
       .unindent()
     );
   }
+
+  #[test]
+  fn with_filter() {
+    assert_regex_match!(
+      InscriptionsHtml {
+        filter: InscriptionFilter {
+          charm: Some(Charm::Cursed),
+          content_type: Some("image/svg+xml".into()),
+          from_height: Some(5),
+          metaprotocol: Some("foo bar".into()),
+        },
+        inscriptions: vec![inscription_id(1)],
+        limit: Some(10),
+        prev: Some("AAAAAQ".into()),
+        next: Some("AAAAAw".into()),
+        sort: Some(Sort::Oldest),
+      },
+      "
+        <h1>Inscriptions</h1>
+        <dl>
+          <dt>charm</dt>
+          <dd>cursed</dd>
+          <dt>content type</dt>
+          <dd>image/svg\\+xml</dd>
+          <dt>from height</dt>
+          <dd><a href=/block/5>5</a></dd>
+          <dt>metaprotocol</dt>
+          <dd>foo bar</dd>
+        </dl>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+        </div>
+        <div class=center>
+        <a class=prev href=/inscriptions\\?cursor=AAAAAQ&amp;charm=cursed&amp;content_type=image%2Fsvg%2Bxml&amp;from_height=5&amp;limit=10&amp;metaprotocol=foo%20bar&amp;sort=oldest>prev</a>
+        <a class=next href=/inscriptions\\?cursor=AAAAAw&amp;charm=cursed&amp;content_type=image%2Fsvg%2Bxml&amp;from_height=5&amp;limit=10&amp;metaprotocol=foo%20bar&amp;sort=oldest>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
 }
@@ -0,0 +1,68 @@
+use {super::*, bitcoin::hashes::sha256};
+
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionsHashHtml {
+  pub(crate) hash: sha256::Hash,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) next: Option<u32>,
+  pub(crate) prev: Option<u32>,
+}
+
+impl PageContent for InscriptionsHashHtml {
+  fn title(&self) -> String {
+    format!("Inscriptions with Content Hash {}", self.hash)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_prev_and_next() {
+    assert_regex_match!(
+      InscriptionsHashHtml {
+        hash: sha256::Hash::hash(b"foo"),
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        next: None,
+        prev: None,
+      },
+      "
+        <h1>Inscriptions with Content Hash</h1>
+        <dl>
+          <dt>hash</dt>
+          <dd class=collapse>2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae</dd>
+        </dl>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        <div class=center>
+        prev
+        next
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      InscriptionsHashHtml {
+        hash: sha256::Hash::hash(b"foo"),
+        inscriptions: vec![inscription_id(1)],
+        next: Some(2),
+        prev: Some(0),
+      },
+      "
+        <h1>Inscriptions with Content Hash</h1>
+        .*
+        <a class=prev href=/inscriptions/hash/2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae/0>prev</a>
+        <a class=next href=/inscriptions/hash/2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae/2>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionsMetadataHtml {
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) key: String,
+  pub(crate) next: Option<u32>,
+  pub(crate) prev: Option<u32>,
+  pub(crate) value: Option<String>,
+}
+
+impl InscriptionsMetadataHtml {
+  fn query(&self) -> String {
+    let mut query = format!("?key={}", urlencoding::encode(&self.key));
+
+    if let Some(value) = &self.value {
+      query.push_str(&format!("&value={}", urlencoding::encode(value)));
+    }
+
+    query
+  }
+}
+
+impl PageContent for InscriptionsMetadataHtml {
+  fn title(&self) -> String {
+    match &self.value {
+      Some(value) => format!("Inscriptions with Metadata {}={value}", self.key),
+      None => format!("Inscriptions with Metadata {}", self.key),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      InscriptionsMetadataHtml {
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        key: "eye color".into(),
+        next: Some(3),
+        prev: Some(1),
+        value: Some("blue".into()),
+      },
+      "
+        <h1>Inscriptions with Metadata</h1>
+        <dl>
+          <dt>key</dt>
+          <dd>eye color</dd>
+          <dt>value</dt>
+          <dd>blue</dd>
+        </dl>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        <div class=center>
+        <a class=prev href=/inscriptions/metadata/1\\?key=eye%20color&amp;value=blue>prev</a>
+        <a class=next href=/inscriptions/metadata/3\\?key=eye%20color&amp;value=blue>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn without_value() {
+    assert_regex_match!(
+      InscriptionsMetadataHtml {
+        inscriptions: Vec::new(),
+        key: "name".into(),
+        next: Some(1),
+        prev: None,
+        value: None,
+      },
+      "
+        <h1>Inscriptions with Metadata</h1>
+        <dl>
+          <dt>key</dt>
+          <dd>name</dd>
+        </dl>
+        .*
+        prev
+        <a class=next href=/inscriptions/metadata/1\\?key=name>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MempoolHtml {
+  pub tip: Option<BlockHash>,
+  pub transactions: Vec<api::MempoolTransaction>,
+}
+
+impl PageContent for MempoolHtml {
+  fn title(&self) -> String {
+    "Mempool".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      MempoolHtml {
+        tip: None,
+        transactions: Vec::new(),
+      },
+      "
+        <h1>Mempool</h1>
+        <p>No pending inscriptions or rune transfers.</p>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_transactions() {
+    assert_regex_match!(
+      MempoolHtml {
+        tip: None,
+        transactions: vec![api::MempoolTransaction {
+          etching: Some(SpacedRune {
+            rune: Rune(26),
+            spacers: 1,
+          }),
+          inscriptions: vec![inscription_id(1)],
+          mint: Some(RuneId { block: 1, tx: 2 }),
+          runes: vec![SpacedRune {
+            rune: Rune(27),
+            spacers: 0,
+          }],
+          transferred_inscriptions: vec![inscription_id(2)],
+          txid: txid(3),
+        }],
+      },
+      "
+        <h1>Mempool</h1>
+        <h2><a class=collapse href=/tx/3{64}>3{64}</a></h2>
+        <dl>
+          <dt>inscriptions</dt>
+          <dd class=collapse>1{64}i1</dd>
+          <dt>transferred inscriptions</dt>
+          <dd><a class=collapse href=/inscription/2{64}i2>2{64}i2</a></dd>
+          <dt>etching</dt>
+          <dd><a href=/rune/A•A>A•A</a></dd>
+          <dt>mint</dt>
+          <dd><a href=/rune/1:2>1:2</a></dd>
+          <dt>runes</dt>
+          <dd><a href=/rune/AB>AB</a></dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
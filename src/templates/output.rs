@@ -6,6 +6,7 @@ pub(crate) struct OutputHtml {
   pub(crate) inscriptions: Option<Vec<InscriptionId>>,
   pub(crate) outpoint: OutPoint,
   pub(crate) output: TxOut,
+  pub(crate) pending: Option<Txid>,
   pub(crate) runes: Option<BTreeMap<SpacedRune, Pile>>,
   pub(crate) sat_ranges: Option<Vec<(u64, u64)>>,
   pub(crate) spent: bool,
@@ -32,6 +33,7 @@ mod tests {
         inscriptions: Some(Vec::new()),
         outpoint: outpoint(1),
         output: TxOut { value: Amount::from_sat(3), script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()), },
+        pending: None,
         runes: Some(BTreeMap::new()),
         sat_ranges: Some(vec![(0, 1), (1, 3)]),
         spent: false,
@@ -66,6 +68,7 @@ mod tests {
           value: Amount::from_sat(1),
           script_pubkey: script::Builder::new().push_int(0).into_script(),
         },
+        pending: None,
         runes: None,
         sat_ranges: None,
         spent: true,
@@ -91,6 +94,7 @@ mod tests {
         inscriptions: None,
         outpoint: outpoint(1),
         output: TxOut { value: Amount::from_sat(3), script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()), },
+        pending: None,
         runes: None,
         sat_ranges: Some(vec![(0, 1), (1, 3)]),
         spent: true,
@@ -122,6 +126,7 @@ mod tests {
         inscriptions: None,
         outpoint: outpoint(1),
         output: TxOut { value: Amount::from_sat(3), script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()), },
+        pending: None,
         runes: None,
         sat_ranges: None,
         spent: false,
@@ -141,6 +146,36 @@ mod tests {
     );
   }
 
+  #[test]
+  fn pending() {
+    assert_regex_match!(
+      OutputHtml {
+        chain: Chain::Mainnet,
+        inscriptions: None,
+        outpoint: outpoint(1),
+        output: TxOut {
+          value: Amount::from_sat(1),
+          script_pubkey: script::Builder::new().push_int(0).into_script(),
+        },
+        pending: Some(txid(2)),
+        runes: None,
+        sat_ranges: None,
+        spent: false,
+      },
+      "
+        <h1>Output <span class=monospace>1{64}:1</span></h1>
+        <dl>
+          <dt>value</dt><dd>1</dd>
+          <dt>script pubkey</dt><dd class=monospace>OP_0</dd>
+          <dt>transaction</dt><dd><a class=collapse href=/tx/1{64}>1{64}</a></dd>
+          <dt>spent</dt><dd>false</dd>
+          <dt>pending</dt><dd><a class=collapse href=/tx/2{64}>2{64}</a></dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn with_inscriptions() {
     assert_regex_match!(
@@ -152,6 +187,7 @@ mod tests {
           value: Amount::from_sat(3),
           script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()),
         },
+        pending: None,
         runes: None,
         sat_ranges: None,
         spent: false,
@@ -181,6 +217,7 @@ mod tests {
           value: Amount::from_sat(3),
           script_pubkey: ScriptBuf::new_p2pkh(&PubkeyHash::all_zeros()),
         },
+        pending: None,
         runes: Some(
           vec![(
             SpacedRune {
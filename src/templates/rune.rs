@@ -3,6 +3,7 @@ use super::*;
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RuneHtml {
   pub entry: RuneEntry,
+  pub holders: Option<u64>,
   pub id: RuneId,
   pub mintable: bool,
   pub parent: Option<InscriptionId>,
@@ -67,6 +68,7 @@ mod tests {
           turbo: true,
         },
         id: RuneId { block: 10, tx: 9 },
+        holders: Some(42),
         mintable: true,
         parent: Some(InscriptionId {
           txid: Txid::all_zeros(),
@@ -115,6 +117,8 @@ mod tests {
   <dd>0.12%</dd>
   <dt>burned</dt>
   <dd>123456789.123456789\u{A0}@</dd>
+  <dt>holders</dt>
+  <dd><a href=/rune/B•CGDENLQRQWDSLRUGSNLBTMFIJAV/holders>42</a></dd>
   <dt>divisibility</dt>
   <dd>9</dd>
   <dt>symbol</dt>
@@ -152,6 +156,7 @@ mod tests {
           turbo: false,
         },
         id: RuneId { block: 10, tx: 9 },
+        holders: None,
         mintable: false,
         parent: None,
       },
@@ -186,6 +191,7 @@ mod tests {
           turbo: false,
         },
         id: RuneId { block: 10, tx: 9 },
+        holders: None,
         mintable: false,
         parent: None,
       },
@@ -225,6 +231,7 @@ mod tests {
           turbo: false,
         },
         id: RuneId { block: 10, tx: 9 },
+        holders: None,
         mintable: false,
         parent: None,
       },
@@ -281,6 +288,7 @@ mod tests {
           turbo: false,
         },
         id: RuneId { block: 0, tx: 0 },
+        holders: None,
         mintable: false,
         parent: Some(InscriptionId {
           txid: Txid::all_zeros(),
@@ -318,6 +326,7 @@ mod tests {
           turbo: false,
         },
         id: RuneId { block: 0, tx: 0 },
+        holders: None,
         mintable: true,
         parent: Some(InscriptionId {
           txid: Txid::all_zeros(),
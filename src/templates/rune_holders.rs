@@ -0,0 +1,90 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHoldersHtml {
+  pub count: u64,
+  pub divisibility: u8,
+  pub holders: Vec<api::RuneHolder>,
+  pub id: RuneId,
+  #[serde(skip)]
+  pub limit: Option<usize>,
+  pub more: bool,
+  pub next_cursor: Option<String>,
+  pub prev_cursor: Option<String>,
+  pub rune: SpacedRune,
+  pub symbol: Option<char>,
+}
+
+impl RuneHoldersHtml {
+  fn query(&self, cursor: &str) -> String {
+    match self.limit {
+      Some(limit) => format!("?cursor={cursor}&limit={limit}"),
+      None => format!("?cursor={cursor}"),
+    }
+  }
+
+  fn pile(&self, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: self.divisibility,
+      symbol: self.symbol,
+    }
+  }
+}
+
+impl PageContent for RuneHoldersHtml {
+  fn title(&self) -> String {
+    format!("Rune {} Holders", self.rune)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RuneHoldersHtml {
+        count: 2,
+        divisibility: 1,
+        holders: vec![
+          api::RuneHolder {
+            address: Some("bc1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq".into()),
+            amount: 1000,
+            script_pubkey: ScriptBuf::new(),
+          },
+          api::RuneHolder {
+            address: None,
+            amount: 5,
+            script_pubkey: ScriptBuf::from_bytes(vec![0x51]),
+          },
+        ],
+        id: RuneId { block: 1, tx: 0 },
+        limit: Some(2),
+        more: true,
+        next_cursor: Some("bar".into()),
+        prev_cursor: Some("foo".into()),
+        rune: SpacedRune {
+          rune: Rune(26),
+          spacers: 1,
+        },
+        symbol: Some('%'),
+      },
+      "<h1><a href=/rune/A•A>A•A</a> Holders</h1>
+<dl>
+  <dt>holders</dt>
+  <dd>2</dd>
+</dl>
+<ol>
+  <li><a class=collapse href=/address/bc1q[q]{58}>bc1q[q]{58}</a>: 100\u{A0}%</li>
+  <li><span class=collapse>OP_PUSHNUM_1</span>: 0.5\u{A0}%</li>
+</ol>
+<div class=center>
+  <a class=prev href=/rune/A•A/holders\\?cursor=foo&amp;limit=2>prev</a>
+  <a class=next href=/rune/A•A/holders\\?cursor=bar&amp;limit=2>next</a>
+</div>
+"
+    );
+  }
+}
@@ -6,6 +6,28 @@ pub struct RunesHtml {
   pub more: bool,
   pub prev: Option<usize>,
   pub next: Option<usize>,
+  pub prev_cursor: Option<String>,
+  pub next_cursor: Option<String>,
+  #[serde(skip)]
+  pub limit: Option<usize>,
+  #[serde(skip)]
+  pub sort: Option<Sort>,
+}
+
+impl RunesHtml {
+  fn query(&self, cursor: &str) -> String {
+    let mut parameters = vec![format!("cursor={cursor}")];
+
+    if let Some(limit) = self.limit {
+      parameters.push(format!("limit={limit}"));
+    }
+
+    if let Some(sort) = self.sort {
+      parameters.push(format!("sort={sort}"));
+    }
+
+    format!("?{}", parameters.join("&"))
+  }
 }
 
 impl PageContent for RunesHtml {
@@ -35,6 +57,10 @@ mod tests {
         more: false,
         prev: None,
         next: None,
+        prev_cursor: None,
+        next_cursor: None,
+        limit: None,
+        sort: None,
       }
       .to_string(),
       "<h1>Runes</h1>
@@ -76,6 +102,10 @@ mod tests {
         ],
         prev: Some(1),
         next: Some(2),
+        prev_cursor: Some("AAAAAAAAAAEAAAAA".into()),
+        next_cursor: Some("AAAAAAAAAAMAAAAA".into()),
+        limit: Some(2),
+        sort: Some(Sort::Oldest),
         more: true,
       }
       .to_string(),
@@ -85,8 +115,8 @@ mod tests {
   <li><a href=/rune/C>C</a></li>
 </ul>
 <div class=center>
-    <a class=prev href=/runes/1>prev</a>
-      <a class=next href=/runes/2>next</a>
+    <a class=prev href=/runes?cursor=AAAAAAAAAAEAAAAA&amp;limit=2&amp;sort=oldest>prev</a>
+      <a class=next href=/runes?cursor=AAAAAAAAAAMAAAAA&amp;limit=2&amp;sort=oldest>next</a>
   </div>"
     );
   }
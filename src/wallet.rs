@@ -3,6 +3,7 @@ use {
   batch::ParentInfo,
   bitcoin::{
     bip32::{ChildNumber, DerivationPath, Xpriv},
+    hashes::sha256,
     psbt::Psbt,
     secp256k1::Secp256k1,
   },
@@ -216,6 +217,33 @@ impl Wallet {
     Ok(inscription)
   }
 
+  /// Returns `None` if the ord server has no content hash index.
+  pub(crate) fn get_inscriptions_with_content_hash(
+    &self,
+    hash: [u8; 32],
+  ) -> Result<Option<Vec<InscriptionId>>> {
+    let response = self
+      .ord_client
+      .get(
+        self
+          .rpc_url
+          .join(&format!(
+            "/r/content-hash/{}",
+            sha256::Hash::from_byte_array(hash)
+          ))
+          .unwrap(),
+      )
+      .send()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    let inscriptions: api::Inscriptions = response.error_for_status()?.json()?;
+
+    Ok(Some(inscriptions.ids))
+  }
+
   pub(crate) fn inscription_exists(&self, inscription_id: InscriptionId) -> Result<bool> {
     Ok(
       !self
@@ -426,11 +454,9 @@ impl Wallet {
           progress.finish_with_message("Rune matured, submitting...");
           break;
         }
-        Maturity::ConfirmationsPending(remaining) => {
-          if remaining < pending_confirmations {
-            pending_confirmations = remaining;
-            progress.inc(1);
-          }
+        Maturity::ConfirmationsPending(remaining) if remaining < pending_confirmations => {
+          pending_confirmations = remaining;
+          progress.inc(1);
         }
         Maturity::CommitSpent(txid) => {
           self.clear_etching(rune)?;
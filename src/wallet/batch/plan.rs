@@ -46,6 +46,8 @@ impl Plan {
     utxos: &BTreeMap<OutPoint, TxOut>,
     wallet: &Wallet,
   ) -> SubcommandResult {
+    self.warn_about_duplicate_content(wallet);
+
     let Transactions {
       commit_tx,
       commit_vout,
@@ -252,6 +254,28 @@ impl Plan {
     }
   }
 
+  /// Warn about inscriptions whose content has already been inscribed. This
+  /// check is advisory, so failing to perform it is not an error.
+  fn warn_about_duplicate_content(&self, wallet: &Wallet) {
+    for (i, inscription) in self.inscriptions.iter().enumerate() {
+      for hash in inscription.content_hashes() {
+        let ids = match wallet.get_inscriptions_with_content_hash(hash) {
+          Ok(Some(ids)) => ids,
+          Ok(None) => return,
+          Err(err) => {
+            log::warn!("failed to check for duplicate content: {err}");
+            return;
+          }
+        };
+
+        if let Some(first) = ids.first() {
+          eprintln!("warning: content of inscription {i} has already been inscribed as {first}");
+          break;
+        }
+      }
+    }
+  }
+
   pub(crate) fn create_batch_transactions(
     &self,
     wallet_inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
@@ -462,13 +486,13 @@ impl Plan {
               Ok(ordinals::Terms {
                 cap: (terms.cap > 0).then_some(terms.cap),
                 height: (
-                  terms.height.and_then(|range| (range.start)),
-                  terms.height.and_then(|range| (range.end)),
+                  terms.height.and_then(|range| range.start),
+                  terms.height.and_then(|range| range.end),
                 ),
                 amount: Some(terms.amount.to_integer(etching.divisibility)?),
                 offset: (
-                  terms.offset.and_then(|range| (range.start)),
-                  terms.offset.and_then(|range| (range.end)),
+                  terms.offset.and_then(|range| range.start),
+                  terms.offset.and_then(|range| range.end),
                 ),
               })
             })
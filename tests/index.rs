@@ -90,3 +90,310 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn check_reports_no_violations() {
+  let core = mockcore::spawn();
+  core.mine_blocks(3);
+
+  let output = CommandBuilder::new("--index-sats index check --spot-check 2")
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::check::Output>();
+
+  assert_eq!(output.violations, Vec::new());
+}
+
+#[test]
+fn check_fails_on_violations() {
+  let core = mockcore::spawn();
+  core.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .core(&core)
+    .run_and_extract_stdout();
+
+  {
+    let database = redb::Database::open(&index_path).unwrap();
+
+    let wtx = database.begin_write().unwrap();
+
+    // blessed inscriptions
+    wtx
+      .open_table(redb::TableDefinition::<u64, u64>::new("STATISTIC_TO_COUNT"))
+      .unwrap()
+      .insert(1, 1)
+      .unwrap();
+
+    wtx.commit().unwrap();
+  }
+
+  let output = CommandBuilder::new(format!("--index {} index check", index_path.display()))
+    .core(&core)
+    .expected_exit_code(1)
+    .stderr_regex("error: index check found [0-9]+ violations?\n")
+    .run_and_deserialize_output::<ord::subcommand::index::check::Output>();
+
+  assert!(!output.violations.is_empty());
+}
+
+#[test]
+fn snapshot_can_be_restored() {
+  let core = mockcore::spawn();
+  let blocks = core.mine_blocks(3);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let snapshot_path = tempdir.path().join("snapshot");
+  let index_path = tempdir.path().join("restored.redb");
+
+  CommandBuilder::new("--index-sats index update")
+    .core(&core)
+    .temp_dir(tempdir.clone())
+    .run_and_extract_stdout();
+
+  let snapshot = CommandBuilder::new(format!(
+    "--index-sats index snapshot {}",
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .temp_dir(tempdir.clone())
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  assert_eq!(snapshot.header.chain, Chain::Mainnet);
+  assert_eq!(snapshot.header.height, Some(3));
+  assert_eq!(
+    snapshot.header.block_hash,
+    Some(blocks.last().unwrap().block_hash())
+  );
+  assert_eq!(snapshot.checksum.len(), 64);
+
+  let restore = CommandBuilder::new(format!(
+    "--index-sats --index {} index restore {}",
+    index_path.display(),
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::restore::Output>();
+
+  assert_eq!(restore.header, snapshot.header);
+
+  let output = CommandBuilder::new(format!(
+    "--index-sats --index {} index check --spot-check 2",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::check::Output>();
+
+  assert_eq!(output.violations, Vec::new());
+
+  CommandBuilder::new(format!(
+    "--index-sats --index {} index restore {}",
+    index_path.display(),
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .stderr_regex(
+    "error: index `.*restored.redb` already exists, remove it before restoring a snapshot\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn snapshot_can_be_requested_from_server() {
+  let core = mockcore::spawn();
+  let blocks = core.mine_blocks(2);
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--server-username", "foo", "--server-password", "bar"],
+    &[],
+  );
+
+  let client = reqwest::blocking::Client::new();
+
+  client
+    .get(ord.url().join("/update").unwrap())
+    .basic_auth("foo", Some("bar"))
+    .send()
+    .unwrap();
+
+  assert_eq!(
+    client
+      .post(ord.url().join("/snapshot").unwrap())
+      .send()
+      .unwrap()
+      .status(),
+    StatusCode::UNAUTHORIZED,
+  );
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot_path = tempdir.path().join("snapshot");
+
+  CommandBuilder::new(format!(
+    "index snapshot --server-url {} {}",
+    ord.url(),
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: `--server-username` and `--server-password` are required to request a snapshot\n",
+  )
+  .run_and_extract_stdout();
+
+  let snapshot = CommandBuilder::new(format!(
+    "--server-username foo --server-password bar index snapshot --server-url {} {}",
+    ord.url(),
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  assert_eq!(snapshot.header.height, Some(2));
+  assert_eq!(
+    snapshot.header.block_hash,
+    Some(blocks.last().unwrap().block_hash())
+  );
+
+  let restore = CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    tempdir.path().join("restored.redb").display(),
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::restore::Output>();
+
+  assert_eq!(restore.header, snapshot.header);
+}
+
+#[test]
+fn restore_rejects_snapshot_for_other_chain() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot_path = tempdir.path().join("snapshot");
+
+  CommandBuilder::new(format!("index snapshot {}", snapshot_path.display()))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  CommandBuilder::new(format!(
+    "--regtest --index {} index restore {}",
+    tempdir.path().join("restored.redb").display(),
+    snapshot_path.display()
+  ))
+  .expected_exit_code(1)
+  .expected_stderr("error: snapshot is for mainnet but ord is configured for regtest\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn restore_rejects_corrupted_snapshot() {
+  let core = mockcore::spawn();
+  core.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot_path = tempdir.path().join("snapshot");
+
+  CommandBuilder::new(format!("index snapshot {}", snapshot_path.display()))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::index::snapshot::Output>();
+
+  let mut snapshot = fs::read(&snapshot_path).unwrap();
+  let last = snapshot.len() - 40;
+  snapshot[last] ^= 1;
+  fs::write(&snapshot_path, snapshot).unwrap();
+
+  CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    tempdir.path().join("restored.redb").display(),
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr("error: snapshot checksum mismatch\n")
+  .run_and_extract_stdout();
+
+  assert!(!tempdir.path().join("restored.redb").exists());
+}
+
+#[test]
+fn rollback_returns_index_to_earlier_height() {
+  let core = mockcore::spawn();
+  let blocks = core.mine_blocks(5);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("index.redb");
+
+  CommandBuilder::new(format!(
+    "--undo-depth 3 --index {} index update",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--undo-depth 3 --index {} index rollback --height 1",
+    index_path.display()
+  ))
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: cannot roll back to height 1, undo records are only kept for blocks within `--undo-depth` of the tip\n",
+  )
+  .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!(
+    "--undo-depth 3 --index {} index rollback --height 3",
+    index_path.display()
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::rollback::Output>();
+
+  assert_eq!(
+    output,
+    ord::subcommand::index::rollback::Output {
+      hash: blocks[2].block_hash(),
+      height: 3,
+    }
+  );
+}
+
+#[test]
+fn read_only_replica_is_created_from_primary_snapshot() {
+  let core = mockcore::spawn();
+  core.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot_path = tempdir.path().join("index.snapshot");
+
+  CommandBuilder::new(format!(
+    "--replica-snapshot {} index update",
+    snapshot_path.display()
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+
+  assert!(snapshot_path.is_file());
+
+  let output = CommandBuilder::new(format!(
+    "--read-only --replica-snapshot {} --index {} index check --spot-check 2",
+    snapshot_path.display(),
+    tempdir.path().join("replica.redb").display(),
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<ord::subcommand::index::check::Output>();
+
+  assert_eq!(output.violations, Vec::new());
+}
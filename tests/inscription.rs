@@ -0,0 +1,150 @@
+use super::*;
+
+#[test]
+fn history_lists_every_location() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  core.mine_blocks(1);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"foo"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let transfer = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(2, 1, 0, Witness::new())],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let id = InscriptionId { txid, index: 0 };
+
+  let history = CommandBuilder::new(format!(
+    "--regtest --index-inscription-history inscription history {id}"
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<api::InscriptionHistory>();
+
+  assert_eq!(history.id, id);
+
+  pretty_assert_eq!(
+    history.locations,
+    [
+      api::InscriptionLocation {
+        address: Some(core.address(OutPoint { txid, vout: 0 }).to_string()),
+        height: 2,
+        new_satpoint: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        old_satpoint: None,
+        txid,
+      },
+      api::InscriptionLocation {
+        address: Some(
+          core
+            .address(OutPoint {
+              txid: transfer,
+              vout: 0,
+            })
+            .to_string()
+        ),
+        height: 3,
+        new_satpoint: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        old_satpoint: Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        }),
+        txid: transfer,
+      },
+    ]
+  );
+}
+
+#[test]
+fn history_requires_inscription_history_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(format!(
+    "inscription history {}",
+    InscriptionId {
+      txid: Txid::from_str(&"1".repeat(64)).unwrap(),
+      index: 0,
+    }
+  ))
+  .core(&core)
+  .expected_stderr(
+    "error: `ord inscription history` requires index created with `--index-inscription-history` flag\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn list_filters_by_metaprotocol() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  core.mine_blocks(2);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      1,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain", &[7], b"brc-20", &[], b"foo"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  core.broadcast_tx(TransactionTemplate {
+    inputs: &[(
+      2,
+      0,
+      0,
+      envelope(&[b"ord", &[1], b"text/plain", &[], b"bar"]),
+    )],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  pretty_assert_eq!(
+    CommandBuilder::new(
+      "--regtest --index-inscription-attributes inscription list --metaprotocol brc-20"
+    )
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::inscription::list::Output>(),
+    ord::subcommand::inscription::list::Output {
+      inscriptions: vec![InscriptionId { txid, index: 0 }],
+    },
+  );
+}
+
+#[test]
+fn list_by_metaprotocol_requires_inscription_attribute_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("inscription list --content-type image")
+    .core(&core)
+    .expected_stderr(
+      "error: filtering by content type or metaprotocol requires index created with `--index-inscription-attributes` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -172,6 +172,7 @@ fn get_inscription() {
       children: Vec::new(),
       content_length: Some(3),
       content_type: Some("text/plain;charset=utf-8".to_string()),
+      delegator_count: None,
       effective_content_type: Some("text/plain;charset=utf-8".to_string()),
       fee: 138,
       height: 2,
@@ -180,6 +181,7 @@ fn get_inscription() {
       next: None,
       value: Some(10000),
       parents: Vec::new(),
+      pending: None,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -228,6 +230,7 @@ fn get_inscription_with_metaprotocol() {
       children: Vec::new(),
       content_length: Some(3),
       content_type: Some("text/plain;charset=utf-8".to_string()),
+      delegator_count: None,
       effective_content_type: Some("text/plain;charset=utf-8".to_string()),
       fee: 140,
       height: 2,
@@ -236,6 +239,7 @@ fn get_inscription_with_metaprotocol() {
       next: None,
       value: Some(10000),
       parents: Vec::new(),
+      pending: None,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -412,6 +416,7 @@ fn get_output() {
         InscriptionId { txid, index: 2 },
       ]),
       indexed: true,
+      pending: None,
       runes: None,
       sat_ranges: Some(vec![
         (5000000000, 10000000000,),
@@ -574,6 +579,7 @@ fn get_status() {
       json_api: true,
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99218849511960410),
+      replica_lag: None,
       rune_index: true,
       runes: 0,
       sat_index: true,
@@ -627,6 +633,7 @@ fn get_runes() {
         turbo: false,
       },
       id: RuneId { block: 10, tx: 1 },
+      holders: None,
       mintable: false,
       parent: Some(InscriptionId {
         txid: a.output.reveal,
@@ -709,6 +716,10 @@ fn get_runes() {
       more: false,
       next: None,
       prev: None,
+      next_cursor: None,
+      prev_cursor: None,
+      limit: None,
+      sort: None,
     }
   );
 }
@@ -815,6 +826,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending: None,
       runes: Some(BTreeMap::new()),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -859,6 +871,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending: None,
       runes: Some(expected_runes),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -893,6 +906,7 @@ fn outputs_address() {
         vout: 0
       },
       indexed: true,
+      pending: None,
       runes: Some(BTreeMap::new()),
       sat_ranges: None,
       script_pubkey: ScriptBuf::from(
@@ -63,6 +63,7 @@ mod epochs;
 mod find;
 mod index;
 mod info;
+mod inscription;
 mod json_api;
 mod list;
 mod parse;
@@ -141,3 +141,58 @@ fn two_runes() {
     }
   );
 }
+
+#[test]
+fn holders() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etch = etch(&core, &ord, Rune(RUNE));
+
+  let destination = etch
+    .output
+    .rune
+    .unwrap()
+    .destination
+    .unwrap()
+    .require_network(Network::Regtest)
+    .unwrap();
+
+  pretty_assert_eq!(
+    CommandBuilder::new(format!(
+      "--index-runes --index-rune-holders --regtest runes holders {}",
+      Rune(RUNE)
+    ))
+    .core(&core)
+    .run_and_deserialize_output::<ord::subcommand::runes::holders::Output>(),
+    ord::subcommand::runes::holders::Output {
+      count: 1,
+      holders: vec![ord::subcommand::runes::holders::Holder {
+        address: Some(destination.to_string()),
+        amount: "1000".parse().unwrap(),
+        script_pubkey: destination.script_pubkey(),
+      }],
+      id: etch.id,
+      rune: SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0,
+      },
+    }
+  );
+}
+
+#[test]
+fn holders_requires_rune_holder_index() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  CommandBuilder::new(format!("--index-runes --regtest runes holders {}", Rune(RUNE)))
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: `ord runes holders` requires index created with `--index-runes` and `--index-rune-holders` flags\n",
+    )
+    .run_and_extract_stdout();
+}
@@ -254,6 +254,7 @@ fn inscription_page() {
       charms: 0,
       child_count: 0,
       children: Vec::new(),
+      delegator_count: None,
       fee: 138,
       height: 2,
       inscription: Inscription {
@@ -269,6 +270,7 @@ fn inscription_page() {
         script_pubkey: output.script_pubkey,
       }),
       parents: Vec::new(),
+      pending: None,
       previous: None,
       rune: None,
       sat: None,
@@ -816,6 +818,16 @@ fn authentication() {
 
   assert_eq!(response.status(), 200);
 
+  let response = reqwest::blocking::get(format!("http://localhost:{port}/metrics")).unwrap();
+
+  assert_eq!(response.status(), 401);
+
+  for probe in ["healthz", "readyz"] {
+    let response = reqwest::blocking::get(format!("http://localhost:{port}/{probe}")).unwrap();
+
+    assert_eq!(response.status(), 200, "{probe}");
+  }
+
   child.kill().unwrap();
   child.wait().unwrap();
 }
@@ -898,3 +910,34 @@ fn ctrl_c() {
     thread::sleep(Duration::from_millis(50));
   }
 }
+
+#[test]
+fn replica_snapshots_are_written_periodically() {
+  let core = mockcore::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot_path = tempdir.path().join("index.snapshot");
+
+  let _ord = TestServer::spawn_with_args(
+    &core,
+    &[
+      "--replica-snapshot",
+      snapshot_path.to_str().unwrap(),
+      "--replica-snapshot-interval",
+      "1",
+    ],
+  );
+
+  for attempt in 0.. {
+    if snapshot_path.is_file() {
+      break;
+    }
+
+    if attempt == 100 {
+      panic!("replica snapshot was not written");
+    }
+
+    thread::sleep(Duration::from_millis(100));
+  }
+}
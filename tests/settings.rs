@@ -19,20 +19,35 @@ fn default() {
   "config_dir": null,
   "cookie_file": ".*\.cookie",
   "data_dir": ".*",
+  "esplora_concurrency": 8,
+  "esplora_url": null,
   "height_limit": null,
   "hidden": \[\],
   "http_port": null,
   "index": ".*index\.redb",
   "index_addresses": false,
+  "index_content_hashes": false,
   "index_cache_size": \d+,
+  "index_delegates": false,
+  "index_events": false,
+  "index_inscription_attributes": false,
+  "index_inscription_history": false,
+  "index_metadata": false,
   "index_runes": false,
+  "index_rune_history": false,
+  "index_rune_holders": false,
   "index_sats": false,
   "index_transactions": false,
   "integration_test": false,
   "no_index_inscriptions": false,
+  "read_block_files": false,
+  "read_only": false,
+  "replica_snapshot": null,
+  "replica_snapshot_interval": 60,
   "server_password": null,
   "server_url": null,
-  "server_username": null
+  "server_username": null,
+  "undo_depth": null
 \}
 "#,
     )
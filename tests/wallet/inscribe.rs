@@ -199,6 +199,31 @@ fn inscribe_does_not_use_inscribed_sats_as_cardinal_utxos() {
   .run_and_extract_stdout();
 }
 
+#[test]
+fn inscribing_duplicate_content_prints_warning() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-content-hashes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  CommandBuilder::new("wallet inscribe --file foo.txt --fee-rate 1")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!(
+      "warning: content of inscription 0 has already been inscribed as {inscription}\n"
+    ))
+    .run_and_deserialize_output::<Batch>();
+
+  CommandBuilder::new("wallet inscribe --file bar.txt --fee-rate 1")
+    .write("bar.txt", "BAR")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+}
+
 #[test]
 fn refuse_to_reinscribe_sats() {
   let core = mockcore::spawn();